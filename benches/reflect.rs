@@ -0,0 +1,149 @@
+//! Benchmarks of the hot paths of reflection: building the debuginfo context
+//! of this binary, resolving the types of values, displaying values, and
+//! iterating over slices.
+//!
+//! The caches of the default provider are thread-local, so cold paths are
+//! measured on fresh threads.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::time::{Duration, Instant};
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct Leaf {
+    id: u64,
+    name: String,
+    tags: Vec<&'static str>,
+}
+
+#[allow(dead_code)]
+struct Tree {
+    label: &'static str,
+    leaves: Vec<Leaf>,
+    children: Vec<Tree>,
+}
+
+fn tree(depth: usize) -> Tree {
+    let leaves = (0..8)
+        .map(|id| Leaf {
+            id,
+            name: format!("leaf-{id}"),
+            tags: vec!["alpha", "beta"],
+        })
+        .collect();
+    let children = match depth {
+        0 => vec![],
+        _ => (0..4).map(|_| tree(depth - 1)).collect(),
+    };
+    Tree {
+        label: "node",
+        leaves,
+        children,
+    }
+}
+
+/// Measures `routine` on a fresh thread per iteration.
+fn on_fresh_threads<F>(iters: u64, routine: F) -> Duration
+where
+    F: Fn() -> Duration + Send + Copy + 'static,
+{
+    (0..iters)
+        .map(|_| {
+            std::thread::spawn(routine)
+                .join()
+                .expect("benchmark panicked")
+        })
+        .sum()
+}
+
+fn context_construction(c: &mut Criterion) {
+    c.bench_function("context construction", |b| {
+        b.iter_custom(|iters| {
+            on_fresh_threads(iters, || {
+                let context = deflect::default_provider().unwrap();
+                let erased: &dyn deflect::Reflect = &42u8;
+                let start = Instant::now();
+                black_box(erased.reflect(&context).unwrap());
+                start.elapsed()
+            })
+        })
+    });
+}
+
+fn reflect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reflect");
+    group.bench_function("first", |b| {
+        b.iter_custom(|iters| {
+            on_fresh_threads(iters, || {
+                let context = deflect::default_provider().unwrap();
+                // the context of this thread is built beforehand, so only the
+                // type of `Leaf` is resolved.
+                let erased: &dyn deflect::Reflect = &42u8;
+                erased.reflect(&context).unwrap();
+                let leaf = Leaf {
+                    id: 0,
+                    name: String::new(),
+                    tags: vec![],
+                };
+                let erased: &dyn deflect::Reflect = &leaf;
+                let start = Instant::now();
+                black_box(erased.reflect(&context).unwrap());
+                start.elapsed()
+            })
+        })
+    });
+    group.bench_function("subsequent", |b| {
+        let context = deflect::default_provider().unwrap();
+        let leaf = Leaf {
+            id: 0,
+            name: String::new(),
+            tags: vec![],
+        };
+        let erased: &dyn deflect::Reflect = &leaf;
+        erased.reflect(&context).unwrap();
+        b.iter(|| black_box(erased.reflect(&context).unwrap()))
+    });
+    group.finish();
+}
+
+fn display(c: &mut Criterion) {
+    let context = deflect::default_provider().unwrap();
+    let tree = tree(3);
+    let erased: &dyn deflect::Reflect = &tree;
+    let value = erased.reflect(&context).unwrap();
+    let mut group = c.benchmark_group("display");
+    group.bench_function("compact", |b| b.iter(|| value.to_string()));
+    group.bench_function("pretty", |b| b.iter(|| format!("{value:#}")));
+    group.finish();
+}
+
+fn slice_iteration(c: &mut Criterion) {
+    let context = deflect::default_provider().unwrap();
+    let data: Vec<u32> = (0..4096).collect();
+    let slice = data.as_slice();
+    let erased: &dyn deflect::Reflect = &slice;
+    let value: deflect::value::Slice = erased.reflect(&context).unwrap().try_into().unwrap();
+    let mut group = c.benchmark_group("slice iteration");
+    group.bench_function("sum", |b| {
+        b.iter(|| {
+            value
+                .iter()
+                .unwrap()
+                .map(|elt| u64::from(u32::try_from(elt.unwrap()).unwrap()))
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("get", |b| {
+        b.iter(|| black_box(value.get(black_box(4095)).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    context_construction,
+    reflect,
+    display,
+    slice_iteration
+);
+criterion_main!(benches);
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use deflect::Reflect;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,24 @@
+//! Parses arbitrary DWARF as schemas.
+#![no_main]
+
+use deflect::fuzz::Sections;
+use libfuzzer_sys::{arbitrary, fuzz_target};
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Input<'a> {
+    little_endian: bool,
+    debug_info: &'a [u8],
+    debug_abbrev: &'a [u8],
+    debug_str: &'a [u8],
+    debug_types: &'a [u8],
+}
+
+fuzz_target!(|input: Input<'_>| {
+    let _ = deflect::fuzz::schemas(Sections {
+        little_endian: input.little_endian,
+        debug_info: input.debug_info,
+        debug_abbrev: input.debug_abbrev,
+        debug_str: input.debug_str,
+        debug_types: input.debug_types,
+    });
+});
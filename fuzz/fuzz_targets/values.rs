@@ -0,0 +1,25 @@
+//! Reflects arbitrary bytes with schemas parsed from arbitrary DWARF.
+#![no_main]
+
+use deflect::fuzz::Sections;
+use libfuzzer_sys::{arbitrary, fuzz_target};
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Input<'a> {
+    little_endian: bool,
+    debug_info: &'a [u8],
+    debug_abbrev: &'a [u8],
+    debug_str: &'a [u8],
+    bytes: &'a [u8],
+}
+
+fuzz_target!(|input: Input<'_>| {
+    let sections = Sections {
+        little_endian: input.little_endian,
+        debug_info: input.debug_info,
+        debug_abbrev: input.debug_abbrev,
+        debug_str: input.debug_str,
+        ..Sections::default()
+    };
+    let _ = deflect::fuzz::values(sections, input.bytes);
+});
@@ -0,0 +1,497 @@
+//! Differences between the layouts of types in two programs.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use crate::schema::Type;
+use crate::{DebugInfo, DebugInfoProvider, TypeFilter};
+
+/// The differences between the layouts of the types of two programs;
+/// produced by [`abi_diff`].
+///
+/// Displayed as one line per change; e.g.:
+///
+/// ```text
+/// ~ plugin::Header: size 8 -> 12
+/// + plugin::Header.flags
+/// ~ plugin::Header.len: offset 4 -> 8
+/// ~ plugin::Kind::B: discriminant 5 -> 6
+/// - plugin::Legacy
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiDiff {
+    changes: Vec<AbiChange>,
+}
+
+impl AbiDiff {
+    /// The changes between the two programs, ordered by type name.
+    pub fn changes(&self) -> &[AbiChange] {
+        &self.changes
+    }
+
+    /// Whether the layouts of the types of the two programs are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A change of an [`AbiDiff`].
+///
+/// Members are named as in [`Value::get_path`][crate::Value::get_path]: the
+/// fields of structs by their names (e.g., `len`, or `0` for tuple fields),
+/// the fields of enum variants by their variant and name (e.g., `Some.0`),
+/// and the discriminant of an enum as `<discriminant>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbiChange {
+    /// A type present only in the second program.
+    TypeAdded {
+        /// The name of the type.
+        type_name: String,
+    },
+    /// A type present only in the first program.
+    TypeRemoved {
+        /// The name of the type.
+        type_name: String,
+    },
+    /// The size of a type, in bytes, changed.
+    Size {
+        /// The name of the type.
+        type_name: String,
+        /// The size in the first program.
+        before: u64,
+        /// The size in the second program.
+        after: u64,
+    },
+    /// The alignment of a type, in bytes, changed.
+    Align {
+        /// The name of the type.
+        type_name: String,
+        /// The alignment in the first program, if known.
+        before: Option<u64>,
+        /// The alignment in the second program, if known.
+        after: Option<u64>,
+    },
+    /// A member present only in the second program.
+    MemberAdded {
+        /// The name of the type.
+        type_name: String,
+        /// The name of the member.
+        member: String,
+    },
+    /// A member present only in the first program.
+    MemberRemoved {
+        /// The name of the type.
+        type_name: String,
+        /// The name of the member.
+        member: String,
+    },
+    /// The offset of a member, in bytes, changed.
+    Offset {
+        /// The name of the type.
+        type_name: String,
+        /// The name of the member.
+        member: String,
+        /// The offset in the first program.
+        before: u64,
+        /// The offset in the second program.
+        after: u64,
+    },
+    /// The type of a member changed.
+    MemberType {
+        /// The name of the type.
+        type_name: String,
+        /// The name of the member.
+        member: String,
+        /// The name of the member's type in the first program.
+        before: String,
+        /// The name of the member's type in the second program.
+        after: String,
+    },
+    /// A variant present only in the second program.
+    VariantAdded {
+        /// The name of the enum.
+        type_name: String,
+        /// The name of the variant.
+        variant: String,
+    },
+    /// A variant present only in the first program.
+    VariantRemoved {
+        /// The name of the enum.
+        type_name: String,
+        /// The name of the variant.
+        variant: String,
+    },
+    /// The discriminant of a variant changed.
+    Discriminant {
+        /// The name of the enum.
+        type_name: String,
+        /// The name of the variant.
+        variant: String,
+        /// The discriminant in the first program, if any.
+        before: Option<u64>,
+        /// The discriminant in the second program, if any.
+        after: Option<u64>,
+    },
+}
+
+impl fmt::Display for AbiDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AbiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Displays an optional alignment or discriminant.
+        fn opt(value: &Option<u64>) -> String {
+            value.map_or_else(|| "none".to_owned(), |value| value.to_string())
+        }
+
+        match self {
+            Self::TypeAdded { type_name } => write!(f, "+ {type_name}"),
+            Self::TypeRemoved { type_name } => write!(f, "- {type_name}"),
+            Self::Size {
+                type_name,
+                before,
+                after,
+            } => write!(f, "~ {type_name}: size {before} -> {after}"),
+            Self::Align {
+                type_name,
+                before,
+                after,
+            } => write!(f, "~ {type_name}: align {} -> {}", opt(before), opt(after)),
+            Self::MemberAdded { type_name, member } => write!(f, "+ {type_name}.{member}"),
+            Self::MemberRemoved { type_name, member } => write!(f, "- {type_name}.{member}"),
+            Self::Offset {
+                type_name,
+                member,
+                before,
+                after,
+            } => write!(f, "~ {type_name}.{member}: offset {before} -> {after}"),
+            Self::MemberType {
+                type_name,
+                member,
+                before,
+                after,
+            } => write!(f, "~ {type_name}.{member}: type {before} -> {after}"),
+            Self::VariantAdded { type_name, variant } => write!(f, "+ {type_name}::{variant}"),
+            Self::VariantRemoved { type_name, variant } => write!(f, "- {type_name}::{variant}"),
+            Self::Discriminant {
+                type_name,
+                variant,
+                before,
+                after,
+            } => {
+                let (before, after) = (opt(before), opt(after));
+                write!(
+                    f,
+                    "~ {type_name}::{variant}: discriminant {before} -> {after}"
+                )
+            }
+        }
+    }
+}
+
+/// The layout of a struct or enum, as compared by [`abi_diff`].
+struct Summary {
+    size: u64,
+    align: Option<u64>,
+    /// The members, by name, as `(offset, type name)`.
+    members: BTreeMap<String, (u64, String)>,
+    /// The variants, in declaration order, and their discriminants.
+    variants: Vec<(String, Option<u64>)>,
+}
+
+impl Summary {
+    /// The layout of a type whose members cannot be reflected.
+    fn opaque(size: u64, align: Option<u64>) -> Self {
+        Self {
+            size,
+            align,
+            members: BTreeMap::new(),
+            variants: vec![],
+        }
+    }
+}
+
+/// Produces the differences between the layouts of the structs and enums
+/// described by `a` and `b` (e.g., two builds of a plugin); see
+/// [`abi_diff_with`].
+pub fn abi_diff<A, B>(a: &A, b: &B) -> Result<AbiDiff, crate::Error>
+where
+    A: DebugInfoProvider,
+    B: DebugInfoProvider,
+{
+    abi_diff_with(a, b, &TypeFilter::new())
+}
+
+/// Produces the differences between the layouts of the structs and enums
+/// described by `a` and `b` that match `filter`; e.g., those of the crate
+/// that defines a plugin's ABI.
+///
+/// Types are matched by the fully-qualified names that their providers
+/// [enumerate][DebugInfoProvider::info_for_types] them by, and are compared
+/// by their sizes and alignments, by the offsets and type names of their
+/// fields, and by the names and discriminants of their variants. Closures and
+/// coroutines, whose names are not stable across builds, are skipped.
+pub fn abi_diff_with<A, B>(a: &A, b: &B, filter: &TypeFilter) -> Result<AbiDiff, crate::Error>
+where
+    A: DebugInfoProvider,
+    B: DebugInfoProvider,
+{
+    let before = summaries(a, filter)?;
+    let after = summaries(b, filter)?;
+    let mut changes = vec![];
+    let names: std::collections::BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    for type_name in names {
+        let (before, after) = match (before.get(type_name), after.get(type_name)) {
+            (Some(before), Some(after)) => (before, after),
+            (Some(_), None) => {
+                let type_name = type_name.clone();
+                changes.push(AbiChange::TypeRemoved { type_name });
+                continue;
+            }
+            (None, _) => {
+                let type_name = type_name.clone();
+                changes.push(AbiChange::TypeAdded { type_name });
+                continue;
+            }
+        };
+        diff_summaries(type_name, before, after, &mut changes);
+    }
+    Ok(AbiDiff { changes })
+}
+
+/// Appends the differences between `before` and `after`, the layouts of the
+/// type named `type_name`, to `changes`.
+fn diff_summaries(
+    type_name: &str,
+    before: &Summary,
+    after: &Summary,
+    changes: &mut Vec<AbiChange>,
+) {
+    let type_name = type_name.to_owned();
+    if before.size != after.size {
+        changes.push(AbiChange::Size {
+            type_name: type_name.clone(),
+            before: before.size,
+            after: after.size,
+        });
+    }
+    if before.align != after.align {
+        changes.push(AbiChange::Align {
+            type_name: type_name.clone(),
+            before: before.align,
+            after: after.align,
+        });
+    }
+
+    let members: std::collections::BTreeSet<_> =
+        before.members.keys().chain(after.members.keys()).collect();
+    for member in members {
+        let type_name = type_name.clone();
+        let ((before_offset, before_type), (after_offset, after_type)) =
+            match (before.members.get(member), after.members.get(member)) {
+                (Some(before), Some(after)) => (before, after),
+                (Some(_), None) => {
+                    let member = member.clone();
+                    changes.push(AbiChange::MemberRemoved { type_name, member });
+                    continue;
+                }
+                (None, _) => {
+                    let member = member.clone();
+                    changes.push(AbiChange::MemberAdded { type_name, member });
+                    continue;
+                }
+            };
+        if before_offset != after_offset {
+            changes.push(AbiChange::Offset {
+                type_name: type_name.clone(),
+                member: member.clone(),
+                before: *before_offset,
+                after: *after_offset,
+            });
+        }
+        if before_type != after_type {
+            changes.push(AbiChange::MemberType {
+                type_name,
+                member: member.clone(),
+                before: before_type.clone(),
+                after: after_type.clone(),
+            });
+        }
+    }
+
+    for (variant, before) in &before.variants {
+        let type_name = type_name.clone();
+        let variant = variant.clone();
+        match after.variants.iter().find(|(name, _)| *name == variant) {
+            Some((_, after)) if before != after => changes.push(AbiChange::Discriminant {
+                type_name,
+                variant,
+                before: *before,
+                after: *after,
+            }),
+            Some(_) => {}
+            None => changes.push(AbiChange::VariantRemoved { type_name, variant }),
+        }
+    }
+    for (variant, _) in &after.variants {
+        if !before.variants.iter().any(|(name, _)| name == variant) {
+            let type_name = type_name.clone();
+            let variant = variant.clone();
+            changes.push(AbiChange::VariantAdded { type_name, variant });
+        }
+    }
+}
+
+/// The layouts of the structs and enums described by `provider` that match
+/// `filter`, by name.
+fn summaries<P>(
+    provider: &P,
+    filter: &TypeFilter,
+) -> Result<BTreeMap<String, Summary>, crate::Error>
+where
+    P: DebugInfoProvider,
+{
+    let mut seen = HashSet::new();
+    let mut names = vec![];
+    let mut matches = |name: &[u8]| {
+        let name = String::from_utf8_lossy(name);
+        // closures and coroutines are named like `{closure_env#0}`.
+        let last = name.rsplit("::").next().unwrap_or_default();
+        let matches = !last.starts_with('{')
+            && filter.matches_name(name.as_bytes())
+            && seen.insert(name.clone().into_owned());
+        if matches {
+            names.push(name.into_owned());
+        }
+        matches
+    };
+    let infos = provider.info_for_types(&mut matches)?;
+
+    let mut summaries = BTreeMap::new();
+    for (name, info) in names.into_iter().zip(infos) {
+        let DebugInfo {
+            context,
+            unit,
+            entry,
+        } = info;
+        let entry = unit.entry(entry)?;
+        match entry.tag() {
+            crate::gimli::DW_TAG_structure_type
+            | crate::gimli::DW_TAG_class_type
+            | crate::gimli::DW_TAG_enumeration_type => {}
+            _ => continue,
+        }
+        let size = crate::get_size(unit, &entry)?;
+        let align = crate::get_align(&entry)?;
+        if !filter.matches_size_and_align(size, align) {
+            continue;
+        }
+        let summary = match Type::from_die(context.dwarf(), unit, entry) {
+            Ok(r#type) => match summary_of(context.dwarf(), &r#type) {
+                Ok(Some(summary)) => summary,
+                // e.g., slices and trait objects, whose layouts are fixed.
+                Ok(None) => continue,
+                Err(_) => Summary::opaque(size, align),
+            },
+            // e.g., uninhabited enums, whose layouts cannot yet be reflected.
+            Err(_) => Summary::opaque(size, align),
+        };
+        summaries.insert(name, summary);
+    }
+    Ok(summaries)
+}
+
+/// The layout of `r#type`, if it is a struct or enum.
+fn summary_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    r#type: &Type<'_, R>,
+) -> Result<Option<Summary>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut members = BTreeMap::new();
+    let mut variants = vec![];
+    match r#type {
+        Type::Struct(r#struct) => add_members(dwarf, &mut members, "", r#struct.fields()?)?,
+        Type::Enum(r#enum) => {
+            let discriminant_type = r#enum.discriminant_type()?;
+            let offset = r#enum.discriminant_location().clone().address(0)?;
+            let discriminant = (offset, member_type_name(dwarf, &discriminant_type)?);
+            members.insert("<discriminant>".to_owned(), discriminant);
+            let mut iter = r#enum.variants()?;
+            let mut iter = iter.iter()?;
+            while let Some(variant) = iter.try_next()? {
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                let prefix = format!("{name}.");
+                add_members(dwarf, &mut members, &prefix, variant.fields()?)?;
+                let discriminant = variant.discriminant_value().map(|value| value.to_u64());
+                variants.push((name, discriminant));
+            }
+        }
+        _ => return Ok(None),
+    }
+    Ok(Some(Summary {
+        size: r#type.size()?,
+        align: r#type.align()?,
+        members,
+        variants,
+    }))
+}
+
+/// Adds `fields` to `members`, with their names prefixed by `prefix`.
+fn add_members<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    members: &mut BTreeMap<String, (u64, String)>,
+    prefix: &str,
+    mut fields: crate::schema::Fields<'_, R>,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let name = field.name()?;
+        let name = name.to_string_lossy()?;
+        let name = name.strip_prefix("__").unwrap_or(&name);
+        let offset = field.offset()?.address(0)?;
+        let type_name = member_type_name(dwarf, &field.r#type()?)?;
+        members.insert(format!("{prefix}{name}"), (offset, type_name));
+    }
+    Ok(())
+}
+
+/// The name of `r#type`, as the type of a member; types are named as in their
+/// debug info (so that, e.g., closures are not located), and arrays by their
+/// elements.
+fn member_type_name<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    r#type: &Type<'_, R>,
+) -> Result<String, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    if let Type::Array(array) = r#type {
+        let elt = member_type_name(dwarf, &array.elt_type()?)?;
+        return Ok(format!("[{elt}; {}]", array.len()?));
+    }
+    let (_, unit, offset) = r#type.die();
+    let entry = unit.entry(offset)?;
+    if let Some(name) = crate::schema::Name::from_die_opt(dwarf, unit, &entry)? {
+        return Ok(name.to_string_lossy()?.into_owned());
+    }
+    match r#type {
+        // e.g., the `data_ptr` fields of slices.
+        Type::MutPtr(pointer) => {
+            let pointee = member_type_name(dwarf, &pointer.r#type()?)?;
+            Ok(format!("*mut {pointee}"))
+        }
+        r#type => crate::format::qualified_type_name(r#type),
+    }
+}
@@ -0,0 +1,346 @@
+//! Adapters that display common standard library types by their meaning,
+//! rather than by their fields; e.g., a `Duration` as `1.5s`, rather than as
+//! `Duration { secs: 1, nanos: Nanoseconds(500000000) }`, and a `PathBuf` as
+//! `"/etc/hosts"`, rather than as the `Vec<u8>` it wraps.
+//!
+//! The adapters are enabled by default, and disabled with
+//! [`FormatOptions::std_adapters`][crate::FormatOptions::std_adapters]. Each
+//! reads its type's fields by name, so that it is robust to the layouts of
+//! different versions and platforms of the standard library; if it does not
+//! recognize a layout, the value is displayed by its fields, as usual.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+use crate::Value;
+
+/// How many levels of fields are searched for the fields of a standard
+/// library type; e.g., the `tv_sec` of a `SystemTime` is in its `Timespec`,
+/// in its platform-specific `SystemTime`.
+const MAX_DEPTH: usize = 3;
+
+/// An adapter of the standard library type of the given name, which displays
+/// values of it; or produces `None`, if it does not recognize their layout.
+type Adapter<'value, 'dwarf, P> = fn(&Value<'value, 'dwarf, P>) -> Option<String>;
+
+/// The adapters of the standard library, by the names of their types.
+fn adapters<'value, 'dwarf, P>() -> [(&'static str, Adapter<'value, 'dwarf, P>); 11]
+where
+    P: crate::DebugInfoProvider,
+{
+    [
+        ("Duration", |value| Some(format!("{:?}", duration(value)?))),
+        ("SystemTime", system_time),
+        ("Ipv4Addr", |value| Some(ipv4(value)?.to_string())),
+        ("Ipv6Addr", |value| Some(ipv6(value)?.to_string())),
+        ("SocketAddrV4", |value| Some(socket_v4(value)?.to_string())),
+        ("SocketAddrV6", |value| Some(socket_v6(value)?.to_string())),
+        ("IpAddr", variant),
+        ("SocketAddr", variant),
+        ("PathBuf", os_string),
+        ("OsString", os_string),
+        ("CString", c_string),
+    ]
+}
+
+/// Displays `value` with the adapter of its type, if it is of a standard
+/// library type with one.
+pub(crate) fn display<P>(value: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    let name = std_name(value)?;
+    let (_, adapter) = adapters()
+        .into_iter()
+        .find(|(adapted, _)| adapted.as_bytes() == name)?;
+    adapter(value)
+}
+
+/// The unqualified name of the type of `value`, if it is a struct or enum of
+/// the standard library; e.g., `Duration` for a `core::time::Duration`.
+fn std_name<P>(value: &Value<'_, '_, P>) -> Option<Vec<u8>>
+where
+    P: crate::DebugInfoProvider,
+{
+    let name = match value {
+        Value::Struct(value) => value.schema().name().ok()?.to_slice().ok()?.into_owned(),
+        Value::Enum(value) => value.schema().name().to_slice().ok()?.into_owned(),
+        _ => return None,
+    };
+    let (dwarf, unit, offset) = value.r#type().die();
+    let namespaces = crate::namespace_path_of(dwarf, unit, offset).ok()?;
+    match namespaces.first().map(Vec::as_slice) {
+        Some(b"core" | b"alloc" | b"std") => Some(name),
+        _ => None,
+    }
+}
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// If this value is a `PathBuf`, `OsString` or `CString`, the bytes of
+    /// its contents; e.g., to recover a path that is not valid UTF-8.
+    ///
+    /// The bytes of a `CString` exclude its nul terminator. Those of a
+    /// `PathBuf` or `OsString` are in its platform's encoding; i.e., arbitrary
+    /// bytes on Unix-like platforms, and WTF-8 on Windows.
+    pub fn string_bytes(&self) -> Result<Option<&'value [u8]>, crate::Error> {
+        let bytes = match std_name(self).as_deref() {
+            Some(b"PathBuf" | b"OsString") => os_bytes(self, MAX_DEPTH)?,
+            Some(b"CString") => c_bytes(self)?,
+            _ => None,
+        };
+        Ok(bytes)
+    }
+}
+
+/// The bytes of the `PathBuf` or `OsString` `value`; i.e., of the `Vec<u8>`
+/// it wraps, up to `depth` levels deep.
+fn os_bytes<'value, P>(
+    value: &Value<'value, '_, P>,
+    depth: usize,
+) -> Result<Option<&'value [u8]>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Value::Struct(r#struct) = value else { return Ok(None) };
+    if r#struct.field("len")?.is_some() {
+        let Some((data, len)) = crate::value::vec_parts(value)? else { return Ok(None) };
+        // the vector's first `len` bytes are initialized.
+        return Ok(Some(unsafe { std::slice::from_raw_parts(data as *const u8, len) }));
+    }
+    let Some(depth) = depth.checked_sub(1) else { return Ok(None) };
+    // e.g., the `inner` of a `PathBuf` or a Unix `Buf`, or the `bytes` of a
+    // Windows `Wtf8Buf`.
+    for name in ["inner", "bytes"] {
+        if let Some(inner) = r#struct.field_value(name)? {
+            return os_bytes(&inner, depth);
+        }
+    }
+    Ok(None)
+}
+
+/// The bytes of the `CString` `value`, without its nul terminator.
+fn c_bytes<'value, P>(value: &Value<'value, '_, P>) -> Result<Option<&'value [u8]>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Value::Struct(value) = value else { return Ok(None) };
+    let Some(Value::BoxedSlice(inner)) = value.field_value("inner")? else { return Ok(None) };
+    let bytes = inner.as_bytes()?;
+    Ok(Some(bytes.strip_suffix(&[0]).unwrap_or(bytes)))
+}
+
+/// Displays a `PathBuf` or `OsString` as a quoted string, as would its
+/// `Debug`.
+fn os_string<P>(value: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    let bytes = os_bytes(value, MAX_DEPTH).ok()??;
+    #[cfg(unix)]
+    let os_str = <std::ffi::OsStr as std::os::unix::ffi::OsStrExt>::from_bytes(bytes);
+    // the platform's encoding is WTF-8, which is UTF-8 for valid strings.
+    #[cfg(not(unix))]
+    let os_str = String::from_utf8_lossy(bytes);
+    Some(format!("{os_str:?}"))
+}
+
+/// Displays a `CString` as a quoted string, as would its `Debug`.
+fn c_string<P>(value: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    let bytes = c_bytes(value).ok()??;
+    let c_string = std::ffi::CString::new(bytes).ok()?;
+    Some(format!("{c_string:?}"))
+}
+
+/// Displays an `IpAddr` or `SocketAddr` as the address in its variant.
+fn variant<P>(value: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Value::Enum(value) = value else { return None };
+    let mut fields = value.variant().ok()?.fields().ok()?;
+    let mut fields = fields.iter().ok()?;
+    let address = fields.try_next().ok()??.value().ok()?;
+    display(&address)
+}
+
+/// Reads a `Duration`.
+fn duration<P>(value: &Value<'_, '_, P>) -> Option<std::time::Duration>
+where
+    P: crate::DebugInfoProvider,
+{
+    let secs = integer(find(value, "secs", 0)?)?;
+    let nanos = integer(find(value, "nanos", 0)?)?;
+    Some(std::time::Duration::new(
+        secs.try_into().ok()?,
+        nanos.try_into().ok()?,
+    ))
+}
+
+/// Displays a `SystemTime` as an RFC 3339 timestamp in UTC; e.g.,
+/// `2024-05-06T07:08:09.5Z`.
+fn system_time<P>(value: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    // i.e., the `Timespec` of Unix-like platforms.
+    let secs = i64::try_from(integer(find(value, "tv_sec", MAX_DEPTH)?)?).ok()?;
+    let nanos = u32::try_from(integer(find(value, "tv_nsec", MAX_DEPTH)?)?).ok()?;
+    if nanos >= 1_000_000_000 {
+        return None;
+    }
+    let (days, time) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    let (year, month, day) = civil_from_days(days);
+    let mut timestamp = format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    );
+    if nanos != 0 {
+        let fraction = format!("{nanos:09}");
+        timestamp.push('.');
+        timestamp.push_str(fraction.trim_end_matches('0'));
+    }
+    timestamp.push('Z');
+    Some(timestamp)
+}
+
+/// The proleptic Gregorian date that is `days` after 1970-01-01; see
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+    let month = (if month < 10 { month + 3 } else { month - 9 }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Reads an `Ipv4Addr`; either its `octets`, or the `s_addr` of its
+/// `in_addr`, which is in network byte order.
+fn ipv4<P>(value: &Value<'_, '_, P>) -> Option<Ipv4Addr>
+where
+    P: crate::DebugInfoProvider,
+{
+    if let Some(octets) = find(value, "octets", 0) {
+        return Some(<[u8; 4]>::try_from(bytes(octets)?).ok()?.into());
+    }
+    let s_addr = u32::try_from(integer(find(value, "s_addr", MAX_DEPTH)?)?).ok()?;
+    Some(s_addr.to_ne_bytes().into())
+}
+
+/// Reads an `Ipv6Addr`; either its `octets`, or the `s6_addr` of its
+/// `in6_addr`.
+fn ipv6<P>(value: &Value<'_, '_, P>) -> Option<Ipv6Addr>
+where
+    P: crate::DebugInfoProvider,
+{
+    let octets = find(value, "octets", 0).or_else(|| find(value, "s6_addr", MAX_DEPTH))?;
+    Some(<[u8; 16]>::try_from(bytes(octets)?).ok()?.into())
+}
+
+/// Reads a `SocketAddrV4`; either its `ip` and `port`, or those of its
+/// `sockaddr_in`, whose port is in network byte order.
+fn socket_v4<P>(value: &Value<'_, '_, P>) -> Option<SocketAddrV4>
+where
+    P: crate::DebugInfoProvider,
+{
+    if let Some(ip) = find(value, "ip", 0) {
+        let port = integer(find(value, "port", 0)?)?.try_into().ok()?;
+        return Some(SocketAddrV4::new(ipv4(&ip)?, port));
+    }
+    let ip = ipv4(&find(value, "sin_addr", MAX_DEPTH)?)?;
+    let port = u16::try_from(integer(find(value, "sin_port", MAX_DEPTH)?)?).ok()?;
+    Some(SocketAddrV4::new(ip, u16::from_be(port)))
+}
+
+/// Reads a `SocketAddrV6`; either its `ip`, `port`, `flowinfo` and
+/// `scope_id`, or those of its `sockaddr_in6`, whose port and flow
+/// information are in network byte order.
+fn socket_v6<P>(value: &Value<'_, '_, P>) -> Option<SocketAddrV6>
+where
+    P: crate::DebugInfoProvider,
+{
+    let field = |name| find(value, name, 0).and_then(integer);
+    if let Some(ip) = find(value, "ip", 0) {
+        return Some(SocketAddrV6::new(
+            ipv6(&ip)?,
+            field("port")?.try_into().ok()?,
+            field("flowinfo")?.try_into().ok()?,
+            field("scope_id")?.try_into().ok()?,
+        ));
+    }
+    let field = |name| find(value, name, MAX_DEPTH).and_then(integer);
+    let ip = ipv6(&find(value, "sin6_addr", MAX_DEPTH)?)?;
+    let port = u16::try_from(field("sin6_port")?).ok()?;
+    let flowinfo = u32::try_from(field("sin6_flowinfo")?).ok()?;
+    let scope_id = field("sin6_scope_id")?.try_into().ok()?;
+    Some(SocketAddrV6::new(
+        ip,
+        u16::from_be(port),
+        u32::from_be(flowinfo),
+        scope_id,
+    ))
+}
+
+/// The value of the field named `name` of the struct `value`, or, up to
+/// `depth` levels deeper, of its fields.
+fn find<'value, 'dwarf, P>(
+    value: &Value<'value, 'dwarf, P>,
+    name: &str,
+    depth: usize,
+) -> Option<Value<'value, 'dwarf, P>>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Value::Struct(value) = value else { return None };
+    if let Some(found) = value.field_value(name).ok()? {
+        return Some(found);
+    }
+    let depth = depth.checked_sub(1)?;
+    let mut fields = value.fields().ok()?;
+    let mut fields = fields.iter().ok()?;
+    while let Some(field) = fields.try_next().ok()? {
+        let Ok(field) = field.value() else { continue };
+        if let Some(found) = find(&field, name, depth) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The integer `value`, or that of the newtype `value` (e.g., the
+/// `Nanoseconds` of a `Duration`).
+fn integer<P>(value: Value<'_, '_, P>) -> Option<i128>
+where
+    P: crate::DebugInfoProvider,
+{
+    if let Some(integer) = value.as_i128() {
+        return Some(integer);
+    }
+    let Value::Struct(value) = value else { return None };
+    integer(value.transparent_inner().ok()??.value().ok()?)
+}
+
+/// The bytes of the array of `u8`s `value`.
+fn bytes<P>(value: Value<'_, '_, P>) -> Option<Vec<u8>>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Value::Array(value) = value else { return None };
+    value
+        .iter()
+        .ok()?
+        .map(|byte| u8::try_from(byte.ok()?).ok())
+        .collect()
+}
@@ -0,0 +1,91 @@
+//! Assertions that values are displayed by deflect as their [`Debug`]
+//! implementations display them; for catching formatting regressions (e.g.,
+//! across compiler versions) in deflect's tests, and in those of its users.
+
+use std::fmt::Debug;
+
+/// Asserts that `value` is displayed by reflection (with the
+/// [default provider][crate::default_provider]) exactly as it is by its
+/// [`Debug`] implementation, both compactly (`{:?}`) and pretty-printed
+/// (`{:#?}`).
+///
+/// Known divergences are listed in `allowlist`, as pairs of a fragment of
+/// the reflected display and the fragment of the `Debug` display it stands
+/// for; each occurrence of the former is replaced by the latter before the
+/// displays are compared. For instance, boxes are displayed with a `box `
+/// prefix that `Debug` omits:
+///
+/// ```
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// deflect::assert_reflect_matches_debug(&Point { x: 1, y: -1 }, &[]);
+/// deflect::assert_reflect_matches_debug(&Box::new(Point { x: 1, y: -1 }), &[("box ", "")]);
+/// ```
+///
+/// See also [`assert_reflect_matches_debug!`][crate::assert_reflect_matches_debug!].
+///
+/// ## Panics
+/// Panics if `value` cannot be reflected, or if its displays differ.
+#[track_caller]
+pub fn assert_reflect_matches_debug<T: Debug>(value: &T, allowlist: &[(&str, &str)]) {
+    let context = match crate::default_provider() {
+        Ok(context) => context,
+        Err(err) => panic!("could not load debug info: {err}"),
+    };
+    let erased: &dyn crate::Reflect = value;
+    let reflected = match erased.reflect(&context) {
+        Ok(reflected) => reflected,
+        Err(err) => panic!("could not reflect `{}`: {err}", std::any::type_name::<T>()),
+    };
+    let allowed = |mut display: String| {
+        for (from, to) in allowlist {
+            display = display.replace(from, to);
+        }
+        display
+    };
+    let compact = allowed(reflected.to_string());
+    mismatch(&compact, &format!("{value:?}"));
+    let pretty = allowed(format!("{reflected:#}"));
+    mismatch(&pretty, &format!("{value:#?}"));
+}
+
+/// Panics, with the first line on which they differ, if the reflected
+/// display `reflected` differs from the `Debug` display `debug`.
+#[track_caller]
+fn mismatch(reflected: &str, debug: &str) {
+    if reflected == debug {
+        return;
+    }
+    let line = reflected
+        .lines()
+        .zip(debug.lines())
+        .position(|(reflected, debug)| reflected != debug)
+        .unwrap_or_else(|| reflected.lines().count().min(debug.lines().count()));
+    panic!(
+        "reflected display differs from `Debug` (at line {}):\n\
+         reflected: {reflected}\n    \
+         debug: {debug}",
+        line + 1
+    );
+}
+
+/// Asserts that a value is displayed by reflection exactly as it is by its
+/// [`Debug`] implementation; see
+/// [`assert_reflect_matches_debug`][fn@crate::assert_reflect_matches_debug].
+///
+/// Known divergences may follow the value, each as `reflected => debug`:
+///
+/// ```
+/// deflect::assert_reflect_matches_debug!(Some(42u8));
+/// deflect::assert_reflect_matches_debug!(Box::new(1u8), "box " => "");
+/// ```
+#[macro_export]
+macro_rules! assert_reflect_matches_debug {
+    ($val:expr $(, $from:expr => $to:expr)* $(,)?) => {
+        $crate::assert_reflect_matches_debug(&$val, &[$(($from, $to)),*])
+    };
+}
@@ -0,0 +1,321 @@
+//! Generation of Rust type definitions from schemas.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::schema::Type;
+
+impl<'dwarf, R> Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Rust source text defining a `#[repr(C)]` mirror of this type, and of
+    /// every struct and enum reachable from it through its fields; e.g., for
+    /// mirroring the types of a foreign binary over FFI.
+    ///
+    /// Fields are declared in order of offset, with explicit padding fields
+    /// (named `_pad0`, `_pad1`, etc.) between and after them, so that each
+    /// field of the mirror has the offset it has in the debug info. Each
+    /// definition is followed by `const` assertions of its size, alignment and
+    /// field offsets, which fail to compile if the mirror's layout differs.
+    ///
+    /// Definitions are named by the last path segments of their types' names
+    /// (e.g., `Option<alloc::boxed::Box<Node>>` becomes `Option_Box_Node`).
+    /// Fieldless enums are mirrored as enums of the same representation as
+    /// their discriminant. Other enums, whose layouts cannot be expressed with
+    /// `repr` attributes, are mirrored as opaque structs of bytes. References,
+    /// boxes and raw pointers are mirrored as raw pointers, and wide pointers
+    /// (e.g., to slices or trait objects) as `[*const (); 2]`. Bitfields, and
+    /// fields that overlap the preceding field, are omitted (and covered by
+    /// padding).
+    pub fn to_rust_source(&self) -> Result<String, crate::Error> {
+        let mut generator = Generator {
+            pending: VecDeque::new(),
+            idents: HashMap::new(),
+            used: HashSet::new(),
+            source: String::new(),
+        };
+        generator.field_type(self)?;
+        while let Some(r#type) = generator.pending.pop_front() {
+            match &r#type {
+                Type::Struct(r#struct) => generator.emit_struct(&r#type, r#struct)?,
+                Type::Enum(r#enum) => generator.emit_enum(&r#type, r#enum)?,
+                _ => {}
+            }
+        }
+        let source = generator.source.trim_end();
+        Ok(if source.is_empty() {
+            String::new()
+        } else {
+            format!("{source}\n")
+        })
+    }
+}
+
+/// The state of [`Type::to_rust_source`].
+struct Generator<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The structs and enums that have been named, but not yet defined.
+    pending: VecDeque<Type<'dwarf, R>>,
+    /// The identifiers of structs and enums, by their qualified names.
+    idents: HashMap<String, String>,
+    /// The identifiers given to structs and enums.
+    used: HashSet<String>,
+    source: String,
+}
+
+impl<'dwarf, R> Generator<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The identifier of the struct or enum `r#type`; the first time it is
+    /// named, its definition is scheduled.
+    fn ident(&mut self, r#type: &Type<'dwarf, R>) -> Result<String, crate::Error> {
+        let name = crate::format::qualified_type_name(r#type)?;
+        if let Some(ident) = self.idents.get(&name) {
+            return Ok(ident.clone());
+        }
+        let base = type_ident(&name);
+        let mut ident = base.clone();
+        let mut suffix = 2;
+        while !self.used.insert(ident.clone()) {
+            ident = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        self.idents.insert(name, ident.clone());
+        self.pending.push_back(r#type.clone());
+        Ok(ident)
+    }
+
+    /// The Rust type of a field of type `r#type`.
+    fn field_type(&mut self, r#type: &Type<'dwarf, R>) -> Result<String, crate::Error> {
+        Ok(match r#type {
+            Type::Struct(_) | Type::Enum(_) => self.ident(r#type)?,
+            Type::Array(r#type) => {
+                let elt = self.field_type(&r#type.elt_type()?)?;
+                format!("[{elt}; {}]", r#type.len()?)
+            }
+            Type::Vector(r#type) => {
+                let lane = self.field_type(&r#type.lane_type()?)?;
+                format!("[{lane}; {}]", r#type.lane_count()?)
+            }
+            Type::Atomic(r#type) => self.field_type(&r#type.value_type()?)?,
+            Type::NonZero(r#type) => self.field_type(&r#type.value_type()?)?,
+            Type::ManuallyDrop(r#type) => self.field_type(&r#type.value_type()?)?,
+            Type::MaybeUninit(r#type) => self.field_type(&r#type.value_type()?)?,
+            Type::NonNull(r#type) => self.field_type(&r#type.pointer().r#type()?)?,
+            Type::SharedRef(r#type) => format!("*const {}", self.pointee(&r#type.r#type()?)?),
+            Type::ConstPtr(r#type) => format!("*const {}", self.pointee(&r#type.r#type()?)?),
+            Type::UniqueRef(r#type) => format!("*mut {}", self.pointee(&r#type.r#type()?)?),
+            Type::MutPtr(r#type) => format!("*mut {}", self.pointee(&r#type.r#type()?)?),
+            Type::Box(r#type) => format!("*mut {}", self.pointee(&r#type.r#type()?)?),
+            Type::Slice(_)
+            | Type::BoxedSlice(_)
+            | Type::str(_)
+            | Type::DynRef(_)
+            | Type::BoxedDyn(_) => "[*const (); 2]".to_owned(),
+            Type::Function(_) => "()".to_owned(),
+            r#type => crate::format::type_name(r#type)?,
+        })
+    }
+
+    /// The Rust type of the pointee of a raw pointer mirroring a pointer to
+    /// `r#type`.
+    fn pointee(&mut self, r#type: &Type<'dwarf, R>) -> Result<String, crate::Error> {
+        match r#type {
+            // function pointers are pointers to subroutine types.
+            Type::Function(_) => Ok("()".to_owned()),
+            r#type => self.field_type(r#type),
+        }
+    }
+
+    /// Appends the definition of `r#type`, which is the struct `r#struct`.
+    fn emit_struct(
+        &mut self,
+        r#type: &Type<'dwarf, R>,
+        r#struct: &crate::schema::Struct<'dwarf, R>,
+    ) -> Result<(), crate::Error> {
+        let ident = self.ident(r#type)?;
+        let size = r#struct.size()?;
+        let align = r#type.align()?;
+
+        let mut members = vec![];
+        let mut fields = r#struct.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            if field.is_bitfield()? {
+                continue;
+            }
+            let field_type = field.r#type()?;
+            let name = field.name()?.to_string_lossy()?.into_owned();
+            let offset = field.offset()?.address(0)?;
+            let size = field_type.size()?;
+            let align = field_type.align()?;
+            members.push((name, offset, size, align, field_type));
+        }
+        members.sort_by_key(|&(_, offset, ..)| offset);
+        let packed = members
+            .iter()
+            .any(|&(_, offset, _, align, _)| offset % align.unwrap_or(1).max(1) != 0);
+
+        let mut body = String::new();
+        let mut asserts = vec![];
+        let mut end = 0;
+        let mut pads = 0;
+        for (name, offset, size, _, field_type) in members {
+            if offset < end {
+                writeln!(body, "    // `{name}` overlaps the preceding field.")?;
+                continue;
+            }
+            if offset > end {
+                writeln!(body, "    _pad{pads}: [u8; {}],", offset - end)?;
+                pads += 1;
+            }
+            let field_ident = field_ident(&name);
+            let field_type = self.field_type(&field_type)?;
+            writeln!(body, "    pub {field_ident}: {field_type},")?;
+            asserts.push(format!(
+                "core::mem::offset_of!({ident}, {field_ident}) == {offset}"
+            ));
+            end = offset + size;
+        }
+        if size > end {
+            writeln!(body, "    _pad{pads}: [u8; {}],", size - end)?;
+        }
+
+        let name = crate::format::qualified_type_name(r#type)?;
+        writeln!(self.source, "/// `{name}`")?;
+        match align {
+            _ if packed => writeln!(self.source, "#[repr(C, packed)]")?,
+            Some(align) if align > 1 => writeln!(self.source, "#[repr(C, align({align}))]")?,
+            _ => writeln!(self.source, "#[repr(C)]")?,
+        }
+        writeln!(self.source, "pub struct {ident} {{")?;
+        self.source.push_str(&body);
+        writeln!(self.source, "}}")?;
+        let mut layout = vec![format!("core::mem::size_of::<{ident}>() == {size}")];
+        if let (false, Some(align)) = (packed, align) {
+            layout.push(format!("core::mem::align_of::<{ident}>() == {align}"));
+        }
+        for assert in layout.into_iter().chain(asserts) {
+            writeln!(self.source, "const _: () = assert!({assert});")?;
+        }
+        writeln!(self.source)?;
+        Ok(())
+    }
+
+    /// Appends the definition of `r#type`, which is the enum `r#enum`.
+    fn emit_enum(
+        &mut self,
+        r#type: &Type<'dwarf, R>,
+        r#enum: &crate::schema::Enum<'dwarf, R>,
+    ) -> Result<(), crate::Error> {
+        let ident = self.ident(r#type)?;
+        let size = r#enum.size()?;
+        let align = r#type.align()?;
+
+        let mut fieldless = true;
+        let mut variants = vec![];
+        let mut iter = r#enum.variants()?;
+        let mut iter = iter.iter()?;
+        while let Some(variant) = iter.try_next()? {
+            let mut fields = variant.fields()?;
+            let has_fields = fields.iter()?.try_next()?.is_some();
+            let discriminant = variant.discriminant_value().map(|value| value.to_u64());
+            fieldless &= !has_fields && discriminant.is_some();
+            let name = variant.name()?.to_string_lossy()?.into_owned();
+            variants.push((name, discriminant));
+        }
+
+        let name = crate::format::qualified_type_name(r#type)?;
+        writeln!(self.source, "/// `{name}`")?;
+        let discriminant_type = r#enum.discriminant_type()?;
+        if fieldless && discriminant_type.size()? == size {
+            let repr = self.field_type(&discriminant_type)?;
+            writeln!(self.source, "#[repr({repr})]")?;
+            writeln!(self.source, "pub enum {ident} {{")?;
+            for (name, discriminant) in variants {
+                let discriminant = discriminant.unwrap_or_default();
+                writeln!(self.source, "    {} = {discriminant},", field_ident(&name))?;
+            }
+        } else {
+            let names: Vec<_> = variants
+                .iter()
+                .map(|(name, _)| format!("`{name}`"))
+                .collect();
+            writeln!(self.source, "///")?;
+            writeln!(
+                self.source,
+                "/// An opaque enum of the variants {}.",
+                names.join(", ")
+            )?;
+            match align {
+                Some(align) if align > 1 => writeln!(self.source, "#[repr(C, align({align}))]")?,
+                _ => writeln!(self.source, "#[repr(C)]")?,
+            }
+            writeln!(self.source, "pub struct {ident} {{")?;
+            writeln!(self.source, "    _opaque: [u8; {size}],")?;
+        }
+        writeln!(self.source, "}}")?;
+        writeln!(
+            self.source,
+            "const _: () = assert!(core::mem::size_of::<{ident}>() == {size});"
+        )?;
+        writeln!(self.source)?;
+        Ok(())
+    }
+}
+
+/// The identifier of the type named `name`; the last segment of each path in
+/// `name`, joined by underscores (e.g., `Option<alloc::boxed::Box<Node>>`
+/// becomes `Option_Box_Node`).
+fn type_ident(name: &str) -> String {
+    let mut segments: Vec<String> = vec![];
+    let mut segment = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else if c == ':' && chars.peek() == Some(&':') {
+            // drop the path preceding this segment.
+            chars.next();
+            segment.clear();
+        } else if !segment.is_empty() {
+            segments.push(std::mem::take(&mut segment));
+        }
+    }
+    if !segment.is_empty() {
+        segments.push(segment);
+    }
+    let ident = segments.join("_");
+    match ident.chars().next() {
+        None => "Type".to_owned(),
+        Some(c) if c.is_numeric() => format!("_{ident}"),
+        Some(_) => ident,
+    }
+}
+
+/// The identifier of the field or variant named `name`; keywords are escaped
+/// as raw identifiers.
+fn field_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    let ident: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    match ident.as_str() {
+        // these keywords cannot be raw identifiers.
+        "crate" | "self" | "Self" | "super" | "_" => format!("{ident}_"),
+        ident if KEYWORDS.contains(&ident) => format!("r#{ident}"),
+        ident if ident.starts_with(char::is_numeric) => format!("_{ident}"),
+        ident => ident.to_owned(),
+    }
+}
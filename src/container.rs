@@ -0,0 +1,193 @@
+//! Adapters that teach deflect the layouts of containers it does not know
+//! about; e.g., `smallvec::SmallVec`, `arrayvec::ArrayVec`, or a pool of an
+//! application's own.
+
+use std::fmt;
+
+use crate::value::{Iter, Struct};
+use crate::Value;
+
+/// Describes where the elements of a third-party container are, so that its
+/// values are displayed and iterated as sequences (or as the value they
+/// refer to), rather than as their raw fields; register adapters with
+/// [`DefaultProvider::with_container_adapter`][crate::DefaultProvider::with_container_adapter].
+///
+/// ```
+/// use deflect::{ContainerAdapter, Elements};
+///
+/// /// A vector whose first `len` elements are initialized.
+/// struct Stack {
+///     items: [u32; 4],
+///     len: usize,
+/// }
+///
+/// struct StackAdapter;
+///
+/// unsafe impl ContainerAdapter for StackAdapter {
+///     fn matches(&self, type_name: &str) -> bool {
+///         type_name.ends_with("::Stack")
+///     }
+///
+///     fn elements(&self, _: &str, read: &dyn Fn(&str) -> Option<u128>) -> Option<Elements> {
+///         let len = read("len")?.try_into().ok()?;
+///         Some(Elements::Inline { array: "items".to_owned(), len })
+///     }
+/// }
+///
+/// let context = deflect::default_provider()?.with_container_adapter(&StackAdapter);
+/// let stack = Stack { items: [1, 2, 0, 0], len: 2 };
+/// let erased: &dyn deflect::Reflect = &stack;
+/// assert_eq!(erased.reflect(&context)?.to_string(), "[1, 2]");
+/// # Ok::<_, deflect::anyhow::Error>(())
+/// ```
+///
+/// ## Safety
+/// The elements described by an adapter are read without further checks, so
+/// must be initialized values of the types that their paths' debuginfo
+/// describes; e.g., an adapter must not claim that more elements are
+/// initialized than there are.
+pub unsafe trait ContainerAdapter: Send + Sync {
+    /// Whether this adapter describes the struct with the given qualified
+    /// name; e.g., `smallvec::SmallVec<[u8; 4]>`.
+    fn matches(&self, type_name: &str) -> bool;
+
+    /// Where the elements of a value of the struct named `type_name` are;
+    /// or `None`, if its layout is not recognized.
+    ///
+    /// The integers at [paths][crate::Value::get_path] relative to the value
+    /// (e.g., `capacity`, or `data.heap.1`) are read with `read`, so that the
+    /// elements can depend on them; e.g., on whether a small vector has
+    /// spilled onto the heap.
+    fn elements(
+        &self,
+        type_name: &str,
+        read: &dyn Fn(&str) -> Option<u128>,
+    ) -> Option<Elements>;
+}
+
+impl fmt::Debug for dyn ContainerAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("dyn ContainerAdapter")
+    }
+}
+
+/// Where the elements of a container are, as [paths][crate::Value::get_path]
+/// relative to it; produced by [`ContainerAdapter::elements`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Elements {
+    /// The first `len` elements of the array at `array`; e.g., those of an
+    /// `ArrayVec`, or of a `SmallVec` that has not spilled.
+    Inline {
+        /// The path of the array.
+        array: String,
+        /// The number of initialized elements.
+        len: usize,
+    },
+    /// The `len` elements starting at the target of the pointer at `data`;
+    /// e.g., those of a spilled `SmallVec`.
+    Pointer {
+        /// The path of the pointer (or `NonNull`) to the first element.
+        data: String,
+        /// The number of initialized elements.
+        len: usize,
+    },
+    /// The single value that the pointer at `pointer` refers to; e.g., the
+    /// object of a handle into a pool.
+    Deref {
+        /// The path of the pointer (or `NonNull`).
+        pointer: String,
+    },
+}
+
+/// A container, reflected as described by its [`ContainerAdapter`];
+/// produced by [`Struct::adapted`].
+pub enum Adapted<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The elements of the container.
+    Elements(Iter<'value, 'dwarf, P>),
+    /// The value the container refers to.
+    Deref(Value<'value, 'dwarf, P>),
+}
+
+impl<'value, 'dwarf, P> Struct<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// This struct, as described by the first of its provider's
+    /// [container adapters][crate::DebugInfoProvider::container_adapters]
+    /// that matches it; or `None`, if none does.
+    pub fn adapted(&self) -> Result<Option<Adapted<'value, 'dwarf, P>>, crate::Error> {
+        let adapters = self.provider().container_adapters();
+        if adapters.is_empty() {
+            return Ok(None);
+        }
+        let r#type = crate::schema::Type::Struct(self.schema().clone());
+        let type_name = crate::format::qualified_type_name(&r#type)?;
+        let Some(adapter) = adapters.iter().find(|adapter| adapter.matches(&type_name)) else {
+            return Ok(None);
+        };
+        // SAFETY: the bytes of this struct were reflected as a struct.
+        let value = unsafe { Value::with_type(r#type, self.bytes(), self.provider()) }?;
+        let read = |path: &str| value.get_path(path).ok()??.as_u128();
+        let Some(elements) = adapter.elements(&type_name, &read) else { return Ok(None) };
+        let missing = |path: &str| anyhow!("`{type_name}` has no container path `{path}`");
+        Ok(Some(match elements {
+            Elements::Inline { array, len } => {
+                let Some(Value::Array(inline)) = value.get_path(&array)? else {
+                    return Err(missing(&array));
+                };
+                let elt_type = inline.schema().elt_type()?;
+                let elt_size = usize::try_from(elt_type.size()?)?;
+                // the length of the array bounds that of the container.
+                let len = len.min(inline.schema().len()?.try_into()?);
+                let bytes = inline.bytes();
+                let bytes = bytes
+                    .get(..elt_size * len)
+                    .ok_or_else(|| crate::error::size_mismatch(elt_size * len, bytes.len()))?;
+                let elements = unsafe { Iter::new(bytes, elt_size, elt_type, len, self.provider()) };
+                Adapted::Elements(elements)
+            }
+            Elements::Pointer { data, len } => {
+                let Some(pointer) = value.get_path(&data)? else { return Err(missing(&data)) };
+                let (addr, elt_type) = pointee(pointer)?;
+                // SAFETY: the adapter guarantees that `len` elements are
+                // initialized.
+                let elements =
+                    unsafe { Iter::from_raw_parts(addr, elt_type, len, self.provider()) }?;
+                Adapted::Elements(elements)
+            }
+            Elements::Deref { pointer } => {
+                let Some(target) = value.get_path(&pointer)? else {
+                    return Err(missing(&pointer));
+                };
+                let (addr, r#type) = pointee(target)?;
+                let size = usize::try_from(r#type.size()?)?;
+                // SAFETY: the adapter guarantees that the target is
+                // initialized.
+                let bytes = unsafe { &*std::ptr::slice_from_raw_parts(addr, size) };
+                Adapted::Deref(unsafe { Value::with_type(r#type, bytes, self.provider()) }?)
+            }
+        }))
+    }
+}
+
+/// The address and target type of the pointer (or `NonNull`) `pointer`.
+#[allow(clippy::type_complexity)]
+fn pointee<'value, 'dwarf, P>(
+    pointer: Value<'value, 'dwarf, P>,
+) -> Result<(*const crate::Byte, crate::schema::Type<'dwarf, P::Reader>), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    Ok(match pointer {
+        Value::SharedRef(v) => (v.deref_raw()?.as_ptr(), v.schema().r#type()?),
+        Value::UniqueRef(v) => (v.deref_raw()?.as_ptr(), v.schema().r#type()?),
+        Value::ConstPtr(v) => (v.deref_raw()?.as_ptr(), v.schema().r#type()?),
+        Value::MutPtr(v) => (v.deref_raw()?.as_ptr(), v.schema().r#type()?),
+        Value::NonNull(v) => return pointee(v.pointer()?),
+        value => bail!("expected a pointer, found `{}`", value.r#type()),
+    })
+}
@@ -0,0 +1,117 @@
+//! Snapshots of registered objects, written when the program panics.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use crate::Reflect;
+
+/// Registers `value` under `name` in the [`registry`][crate::registry], so
+/// that [`write_snapshots`] (and so, the hook installed by
+/// [`install_crash_hook`]) saves a portable snapshot of it; e.g.,
+/// `deflect::register("server_state", &STATE)`.
+///
+/// An object registered under the same name is replaced. Because registered
+/// objects may be read at any time, from any thread, they must be `'static`
+/// (e.g., `static`s, or leaked boxes) and `Sync`; and, as they are read
+/// without synchronization, objects that hold data guarded by an
+/// [`UnsafeCell`][std::cell::UnsafeCell] (e.g., by a `Mutex`) are refused, as
+/// by [`Registry::insert`][crate::Registry::insert].
+pub fn register<T>(name: impl Into<String>, value: &'static T) -> Result<(), crate::Error>
+where
+    T: Reflect + Sync,
+{
+    crate::registry().insert(name, value)
+}
+
+/// Unregisters the object registered under `name`; returns whether there was
+/// one.
+pub fn unregister(name: &str) -> bool {
+    crate::registry().remove(name)
+}
+
+/// Writes a portable snapshot of each registered object to `dir`, which is
+/// created if it does not exist, and produces the paths of the written files.
+///
+/// The snapshot of the object registered under `name` is written to
+/// `<dir>/<name>.snapshot` (with any characters of `name` other than ASCII
+/// alphanumerics, `-` and `_` replaced by `_`, and with a `-2`, `-3`, … suffix
+/// if that file name was taken by another object; e.g., of `a.b` and `a/b`),
+/// and can be loaded with
+/// [`PortableSnapshot::load`][crate::PortableSnapshot::load]. Objects whose
+/// snapshots cannot be written are skipped; the first such error is produced
+/// only if no snapshot was written.
+pub fn write_snapshots(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, crate::Error> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let provider = crate::default_provider()?;
+    let mut written = vec![];
+    let mut first_error = None;
+    let mut stems = HashSet::new();
+    for (name, registered) in crate::registry().objects() {
+        let path = dir.join(format!("{}.snapshot", file_stem(&name, &mut stems)));
+        let write = || -> Result<(), crate::Error> {
+            let object: &dyn Reflect = registered.object();
+            let value = object.reflect(&provider)?;
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            value.save_snapshot(file)
+        };
+        match write() {
+            Ok(()) => written.push(path),
+            Err(err) => {
+                let err = err.context(format!("could not snapshot `{name}`"));
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+    match first_error {
+        Some(err) if written.is_empty() => Err(err),
+        _ => Ok(written),
+    }
+}
+
+/// Installs a panic hook that writes the snapshots of the registered objects
+/// to `dir` with [`write_snapshots`], before running the previously installed
+/// hook (e.g., the one that prints the panic message and backtrace).
+///
+/// The hook also runs in programs built with `panic = "abort"`, before they
+/// abort. Aborts that are not caused by panics (e.g., by signals) are not
+/// intercepted. Only the first call installs a hook.
+pub fn install_crash_hook(dir: impl Into<PathBuf>) {
+    static INSTALLED: Once = Once::new();
+    let dir = dir.into();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            match write_snapshots(&dir) {
+                Ok(written) if !written.is_empty() => {
+                    eprintln!("deflect: wrote snapshots to `{}`", dir.display())
+                }
+                Ok(_) => {}
+                Err(err) => eprintln!("deflect: could not write snapshots: {err:#}"),
+            }
+            previous(info)
+        }));
+    });
+}
+
+/// `name`, with the characters that may not be safe in file names replaced,
+/// and suffixed to be distinct from the `taken` stems, to which it is added.
+fn file_stem(name: &str, taken: &mut HashSet<String>) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    let mut candidate = stem.clone();
+    let mut suffix = 1;
+    while !taken.insert(candidate.clone()) {
+        suffix += 1;
+        candidate = format!("{stem}-{suffix}");
+    }
+    candidate
+}
@@ -0,0 +1,211 @@
+//! The sizes of reflected values, including the heap allocations they own.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{rustc::Container, Value};
+
+/// How [`deep_size_of_with`] counts the allocations shared by reference-counted
+/// pointers (i.e., `Rc` and `Arc`).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SharedPolicy {
+    /// Each shared allocation is counted in full, by the first pointer to it
+    /// that is reached.
+    #[default]
+    Once,
+    /// Each pointer to a shared allocation counts an equal share of it; i.e.,
+    /// its size divided by its strong count.
+    DivideByStrongCount,
+    /// Shared allocations are not counted.
+    Skip,
+}
+
+/// Options controlling how [`deep_size_of_with`] measures a value.
+#[derive(Clone, Debug, Default)]
+pub struct DeepSizeOptions {
+    shared: SharedPolicy,
+}
+
+impl DeepSizeOptions {
+    /// The default options; shared allocations are counted [once][SharedPolicy::Once].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How allocations shared by `Rc` and `Arc` are counted.
+    pub fn shared(mut self, shared: SharedPolicy) -> Self {
+        self.shared = shared;
+        self
+    }
+}
+
+/// Produces the size of `value`, in bytes, including the heap allocations it
+/// owns; see [`deep_size_of_with`].
+pub fn deep_size_of<P>(value: &Value<'_, '_, P>) -> Result<usize, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    deep_size_of_with(value, &DeepSizeOptions::new())
+}
+
+/// Produces the size of `value`, in bytes, including the heap allocations it
+/// owns, per `options`.
+///
+/// The allocations of `Box`es, boxed slices and trait objects, `Vec`s (by their
+/// capacity), `String`s, and `Rc`s and `Arc`s are followed; references, raw
+/// pointers and `Weak`s are not, nor are the allocations of other collections
+/// (e.g., `HashMap`), which count only their inline size. Each allocation is
+/// counted once, even if it is reached through a cycle.
+pub fn deep_size_of_with<P>(
+    value: &Value<'_, '_, P>,
+    options: &DeepSizeOptions,
+) -> Result<usize, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut measure = Measure {
+        options,
+        owned: HashSet::new(),
+        shared: HashMap::new(),
+    };
+    let size = usize::try_from(value.r#type().size()?)?;
+    Ok(size + measure.heap_of(value)?)
+}
+
+/// The state of a [`deep_size_of_with`] measurement.
+struct Measure<'options> {
+    options: &'options DeepSizeOptions,
+    /// The addresses of the uniquely-owned allocations counted so far.
+    owned: HashSet<usize>,
+    /// The sizes of the shared allocations reached so far, by address; zero
+    /// while an allocation is being measured, so that cycles count nothing.
+    shared: HashMap<usize, usize>,
+}
+
+impl Measure<'_> {
+    /// The size of the heap allocations owned by `value`.
+    fn heap_of<P>(&mut self, value: &Value<'_, '_, P>) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        match value {
+            Value::Box(value) => self.owned_referent(&value.deref()?),
+            Value::BoxedDyn(value) => self.owned_referent(&value.deref()?),
+            Value::BoxedSlice(value) => {
+                let addr = value.data_ptr()?.as_ptr() as usize;
+                let elt_size = usize::try_from(value.schema().elt()?.size()?)?;
+                let size = elt_size
+                    .checked_mul(value.length()?)
+                    .ok_or_else(crate::error::arithmetic_overflow)?;
+                if size == 0 || !self.owned.insert(addr) {
+                    return Ok(0);
+                }
+                self.heap_of_elements(value.iter()?).map(|heap| size + heap)
+            }
+            Value::Array(value) => self.heap_of_elements(value.iter()?),
+            Value::ManuallyDrop(value) => self.heap_of(&value.value()?),
+            Value::Struct(value) => self.heap_of_struct(value),
+            Value::Enum(value) => self.heap_of_fields(value.variant()?.fields()?),
+            _ => Ok(0),
+        }
+    }
+
+    /// The size of the allocation holding `referent`, if it is not yet
+    /// counted, and of the heap allocations it owns.
+    fn owned_referent<P>(&mut self, referent: &Value<'_, '_, P>) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let size = usize::try_from(referent.r#type().size()?)?;
+        // zero-sized values are not allocated.
+        if size == 0 || !self.owned.insert(referent.bytes().as_ptr() as usize) {
+            return Ok(0);
+        }
+        Ok(size + self.heap_of(referent)?)
+    }
+
+    fn heap_of_elements<P>(
+        &mut self,
+        elements: crate::value::Iter<'_, '_, P>,
+    ) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let mut heap = 0;
+        for element in elements {
+            heap += self.heap_of(&element?)?;
+        }
+        Ok(heap)
+    }
+
+    fn heap_of_fields<P>(
+        &mut self,
+        fields: crate::value::Fields<'_, '_, P>,
+    ) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let mut fields = fields;
+        let mut fields = fields.iter()?;
+        let mut heap = 0;
+        while let Some(field) = fields.try_next()? {
+            heap += self.heap_of(&field.value()?)?;
+        }
+        Ok(heap)
+    }
+
+    fn heap_of_struct<P>(
+        &mut self,
+        value: &crate::value::Struct<'_, '_, P>,
+    ) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        match crate::rustc::container_of(value.schema())? {
+            Some(Container::Vec) => {
+                let buffer = crate::rustc::vec_buffer(value)?;
+                // zero-sized elements, and empty `Vec`s, are not allocated.
+                if buffer.size == 0 || !self.owned.insert(buffer.addr) {
+                    return Ok(0);
+                }
+                let heap = self.heap_of_elements(buffer.elements)?;
+                Ok(buffer.size + heap)
+            }
+            Some(Container::Shared) => self.heap_of_shared(value),
+            Some(Container::String) => self.heap_of_struct(&crate::rustc::string_vec(value)?),
+            Some(Container::Mutex) => self.heap_of(&crate::rustc::mutex_data(value)?),
+            Some(Container::Weak) => Ok(0),
+            Some(Container::HashMap) | None => self.heap_of_fields(value.fields()?),
+        }
+    }
+
+    /// The share of the allocation of the `Rc` or `Arc` `value` that it
+    /// accounts for, per the [`SharedPolicy`].
+    fn heap_of_shared<P>(
+        &mut self,
+        value: &crate::value::Struct<'_, '_, P>,
+    ) -> Result<usize, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        if self.options.shared == SharedPolicy::Skip {
+            return Ok(0);
+        }
+        let (addr, inner) = crate::rustc::shared_allocation(value)?;
+        let total = match self.shared.get(&addr) {
+            Some(_) if self.options.shared == SharedPolicy::Once => return Ok(0),
+            Some(total) => *total,
+            None => {
+                self.shared.insert(addr, 0);
+                let size = usize::try_from(inner.r#type().size()?)?;
+                let total = size + self.heap_of(&inner)?;
+                self.shared.insert(addr, total);
+                total
+            }
+        };
+        if self.options.shared == SharedPolicy::Once {
+            return Ok(total);
+        }
+        Ok(total / crate::rustc::strong_count(&inner)?)
+    }
+}
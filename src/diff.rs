@@ -0,0 +1,265 @@
+//! Differences between reflected values.
+
+use std::fmt;
+
+use crate::{Snapshot, Value};
+
+/// The differences between two reflected values; produced by [`diff`].
+///
+/// Displayed as a report in the style of a unified diff.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    /// The changes between the two values, in schema order.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Whether the two values are [structurally
+    /// equal][crate::Value::structural_eq].
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A changed leaf of a [`Diff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    path: String,
+    before: Option<Snapshot>,
+    after: Option<Snapshot>,
+}
+
+impl Change {
+    fn new(path: String, before: Option<Snapshot>, after: Option<Snapshot>) -> Self {
+        Self {
+            path,
+            before,
+            after,
+        }
+    }
+
+    /// The path of the changed value, relative to the diffed values, in the
+    /// syntax of [`Value::get_path`]; empty (displayed as `.`) if the diffed
+    /// values themselves differ.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The value before the change; `None` if the value was added (e.g., a
+    /// slice grew).
+    pub fn before(&self) -> Option<&Snapshot> {
+        self.before.as_ref()
+    }
+
+    /// The value after the change; `None` if the value was removed (e.g., a
+    /// slice shrank).
+    pub fn after(&self) -> Option<&Snapshot> {
+        self.after.as_ref()
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("--- a\n+++ b\n")?;
+        for change in &self.changes {
+            change.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "."
+        } else {
+            &self.path
+        };
+        writeln!(f, "@@ {path} @@")?;
+        if let Some(before) = &self.before {
+            writeln!(f, "-{before}")?;
+        }
+        if let Some(after) = &self.after {
+            writeln!(f, "+{after}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Produces the differences between `a` and `b`.
+///
+/// Structs, the fields of enums with the same active variant, arrays, slices,
+/// and the referents of references and boxes are compared piecewise; all
+/// other values are compared [structurally][crate::Value::structural_eq], and
+/// reported as changed leaves.
+pub fn diff<'dwarf, P>(
+    a: &Value<'_, 'dwarf, P>,
+    b: &Value<'_, 'dwarf, P>,
+) -> Result<Diff, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut changes = vec![];
+    diff_at(String::new(), a, b, &mut changes)?;
+    Ok(Diff { changes })
+}
+
+/// Appends the differences between `a` and `b`, which are at `path`, to
+/// `changes`.
+fn diff_at<'dwarf, P>(
+    path: String,
+    a: &Value<'_, 'dwarf, P>,
+    b: &Value<'_, 'dwarf, P>,
+    changes: &mut Vec<Change>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    // overridden values are leaves, which differ as a whole.
+    let overridden = a.overridden()?.is_some() || b.overridden()?.is_some();
+    match (a, b) {
+        (a, b) if overridden => {
+            if !a.structural_eq(b)? {
+                let (before, after) = (a.to_owned_snapshot()?, b.to_owned_snapshot()?);
+                changes.push(Change::new(path, Some(before), Some(after)));
+            }
+            Ok(())
+        }
+        (Value::Array(a), Value::Array(b)) => diff_elements(path, a.iter()?, b.iter()?, changes),
+        (Value::Slice(a), Value::Slice(b)) => diff_elements(path, a.iter()?, b.iter()?, changes),
+        (Value::BoxedSlice(a), Value::BoxedSlice(b)) => {
+            diff_elements(path, a.iter()?, b.iter()?, changes)
+        }
+        (Value::Box(a), Value::Box(b)) => diff_at(deref(path), &a.deref()?, &b.deref()?, changes),
+        (Value::BoxedDyn(a), Value::BoxedDyn(b)) => {
+            diff_at(deref(path), &a.deref()?, &b.deref()?, changes)
+        }
+        (Value::SharedRef(a), Value::SharedRef(b)) => {
+            diff_at(deref(path), &a.deref()?, &b.deref()?, changes)
+        }
+        (Value::UniqueRef(a), Value::UniqueRef(b)) => {
+            diff_at(deref(path), &a.deref()?, &b.deref()?, changes)
+        }
+        (Value::Struct(a), Value::Struct(b))
+            if a.schema().name()?.to_slice()? == b.schema().name()?.to_slice()? =>
+        {
+            diff_fields(path, a.fields()?, b.fields()?, changes)
+        }
+        (Value::Enum(a_enum), Value::Enum(b_enum))
+            if a_enum.as_cow()?.is_none()
+                && a_enum.schema().name().to_slice()? == b_enum.schema().name().to_slice()? =>
+        {
+            let (a_variant, b_variant) = (a_enum.variant()?, b_enum.variant()?);
+            if a_variant.name()?.to_slice()? != b_variant.name()?.to_slice()? {
+                let (before, after) = (a.to_owned_snapshot()?, b.to_owned_snapshot()?);
+                changes.push(Change::new(path, Some(before), Some(after)));
+                return Ok(());
+            }
+            diff_fields(path, a_variant.fields()?, b_variant.fields()?, changes)
+        }
+        (a, b) => {
+            if !a.structural_eq(b)? {
+                let (before, after) = (a.to_owned_snapshot()?, b.to_owned_snapshot()?);
+                changes.push(Change::new(path, Some(before), Some(after)));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Appends the differences between the elements produced by `a` and `b`,
+/// which are at `path`, to `changes`.
+fn diff_elements<'dwarf, P>(
+    path: String,
+    mut a: crate::value::Iter<'_, 'dwarf, P>,
+    mut b: crate::value::Iter<'_, 'dwarf, P>,
+    changes: &mut Vec<Change>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    for index in 0.. {
+        let path = format!("{path}[{index}]");
+        match (a.next().transpose()?, b.next().transpose()?) {
+            (Some(a), Some(b)) => diff_at(path, &a, &b, changes)?,
+            (Some(a), None) => {
+                changes.push(Change::new(path, Some(a.to_owned_snapshot()?), None));
+            }
+            (None, Some(b)) => {
+                changes.push(Change::new(path, None, Some(b.to_owned_snapshot()?)));
+            }
+            (None, None) => break,
+        }
+    }
+    Ok(())
+}
+
+/// Appends the differences between the fields `a` and `b`, which are at
+/// `path`, to `changes`.
+fn diff_fields<'dwarf, P>(
+    path: String,
+    mut a: crate::value::Fields<'_, 'dwarf, P>,
+    mut b: crate::value::Fields<'_, 'dwarf, P>,
+    changes: &mut Vec<Change>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut a = a.iter()?;
+    let mut b = b.iter()?;
+    loop {
+        let (a, b) = match (a.try_next()?, b.try_next()?) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Ok(()),
+        };
+        let name = a.schema().name()?;
+        let name = name.to_string_lossy()?;
+        // the fields of tuples are named `__0`, `__1`, etc., but are accessed
+        // as `.0`, `.1`, etc.
+        let name = match name.strip_prefix("__") {
+            Some(index) if index.bytes().all(|b| b.is_ascii_digit()) => index,
+            _ => &name,
+        };
+        let path = if path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{path}.{name}")
+        };
+        if a.is_redacted()? {
+            // redacted fields are compared, but not displayed.
+            let equal = match (a.bits()?, b.bits()?) {
+                (None, None) => a.value()?.structural_eq(&b.value()?)?,
+                (a, b) => a == b,
+            };
+            if !equal {
+                let before = Snapshot::Opaque(a.display_value().to_string());
+                let after = Snapshot::Opaque(b.display_value().to_string());
+                changes.push(Change::new(path, Some(before), Some(after)));
+            }
+            continue;
+        }
+        match (a.bits()?, b.bits()?) {
+            (None, None) => diff_at(path, &a.value()?, &b.value()?, changes)?,
+            (a_bits, b_bits) => {
+                if a_bits != b_bits {
+                    let before = Snapshot::Opaque(a.display_value().to_string());
+                    let after = Snapshot::Opaque(b.display_value().to_string());
+                    changes.push(Change::new(path, Some(before), Some(after)));
+                }
+            }
+        }
+    }
+}
+
+/// The path of the referent of the reference at `path`.
+fn deref(path: String) -> String {
+    if path.is_empty() {
+        "*".to_owned()
+    } else {
+        format!("{path}.*")
+    }
+}
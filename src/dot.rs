@@ -0,0 +1,195 @@
+//! Rendering of the object graphs of reflected values in the DOT language of
+//! [Graphviz](https://graphviz.org).
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{rustc::Container, value::Path, Value};
+
+/// The maximum length of the summary of a value in its node's label, in bytes.
+const SUMMARY_BYTES: usize = 64;
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Renders the graph of the values reachable from this value, by
+    /// following its pointers, as a DOT `digraph`; e.g., for rendering with
+    /// `dot -Tsvg`.
+    ///
+    /// The nodes of the graph are this value and the referents of the boxes,
+    /// references, `Rc`s and `Arc`s reachable from it, each labeled with its
+    /// type and a summary of its contents. The edges of the graph are those
+    /// pointers, each labeled with its path from the value that holds it
+    /// (e.g., `children[2]` or `next::Some.0`); the elements of `Vec`s and
+    /// boxed slices belong to the value that holds them. Raw pointers and
+    /// `Weak`s are not followed. Each referent is rendered once, so shared and
+    /// cyclic references converge on the same node.
+    pub fn to_dot(&self) -> Result<String, crate::Error> {
+        let mut graph = Graph {
+            labels: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+            pending: vec![],
+        };
+        let root = graph.node(self)?;
+        graph.walk(root, &Path::default(), self)?;
+        while let Some((id, value)) = graph.pending.pop() {
+            graph.walk(id, &Path::default(), &value)?;
+        }
+
+        let mut dot = String::from("digraph {\n    node [shape=box];\n");
+        for (id, label) in graph.labels.iter().enumerate() {
+            writeln!(dot, "    n{id} [label=\"{}\"];", escape(label))?;
+        }
+        for (from, to, label) in &graph.edges {
+            writeln!(dot, "    n{from} -> n{to} [label=\"{}\"];", escape(label))?;
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+}
+
+/// The object graph of a value, as it is built by [`Value::to_dot`].
+struct Graph<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The label of each node, by id.
+    labels: Vec<String>,
+    /// The id of each node, by its address and type.
+    ids: HashMap<(usize, String), usize>,
+    /// The ids of the endpoints of each edge, and its label.
+    edges: Vec<(usize, usize, String)>,
+    /// The nodes whose pointers are yet to be walked, and their values.
+    pending: Vec<(usize, Value<'value, 'dwarf, P>)>,
+}
+
+impl<'value, 'dwarf, P> Graph<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The id of the node of `value`, which is added to the graph if it is not
+    /// yet in it.
+    fn node(&mut self, value: &Value<'value, 'dwarf, P>) -> Result<usize, crate::Error> {
+        let r#type = crate::format::type_name(&value.r#type())?;
+        let key = (value.bytes().as_ptr() as usize, r#type);
+        if let Some(id) = self.ids.get(&key) {
+            return Ok(*id);
+        }
+        let options = crate::FormatOptions::new()
+            .max_depth(1)
+            .max_elements(4)
+            .max_bytes(SUMMARY_BYTES);
+        let label = format!("{}\n{}", key.1, value.display_with(options));
+        let id = self.labels.len();
+        self.labels.push(label);
+        self.ids.insert(key, id);
+        Ok(id)
+    }
+
+    /// Adds an edge, labeled `path`, from the node `from` to the node of
+    /// `referent`.
+    fn edge(
+        &mut self,
+        from: usize,
+        path: &Path,
+        referent: Value<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        let known = self.labels.len();
+        let to = self.node(&referent)?;
+        if to == known {
+            self.pending.push((to, referent));
+        }
+        self.edges.push((from, to, path.to_string()));
+        Ok(())
+    }
+
+    /// Adds the edges of the pointers within `value`, which is at `path`
+    /// within the node `from`.
+    fn walk(
+        &mut self,
+        from: usize,
+        path: &Path,
+        value: &Value<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        match value {
+            Value::Box(v) => self.edge(from, path, v.deref()?),
+            Value::BoxedDyn(v) => self.edge(from, path, v.deref()?),
+            Value::DynRef(v) => self.edge(from, path, v.downcast_value()?),
+            Value::SharedRef(v) => self.edge(from, path, v.deref()?),
+            Value::UniqueRef(v) => self.edge(from, path, v.deref()?),
+            Value::Array(v) => self.walk_elements(from, path, v.iter()?),
+            Value::Slice(v) => self.walk_elements(from, path, v.iter()?),
+            Value::BoxedSlice(v) => self.walk_elements(from, path, v.iter()?),
+            Value::NonZero(v) => self.walk(from, path, &v.value()?),
+            Value::ManuallyDrop(v) => self.walk(from, path, &v.value()?),
+            Value::Struct(v) => match crate::rustc::container_of(v.schema())? {
+                Some(Container::Vec) => {
+                    let buffer = crate::rustc::vec_buffer(v)?;
+                    self.walk_elements(from, path, buffer.elements)
+                }
+                Some(Container::Shared) => {
+                    let (_, inner) = crate::rustc::shared_allocation(v)?;
+                    self.edge(from, path, inner)
+                }
+                Some(Container::Weak) => Ok(()),
+                Some(Container::String | Container::HashMap | Container::Mutex) | None => {
+                    self.walk_fields(from, path, v.fields()?)
+                }
+            },
+            Value::Enum(v) => {
+                let variant = v.variant()?;
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                self.walk_fields(from, &path.variant(name), variant.fields()?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds the edges of the pointers within `elements`, which are at `path`
+    /// within the node `from`.
+    fn walk_elements(
+        &mut self,
+        from: usize,
+        path: &Path,
+        elements: crate::value::Iter<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        for (index, element) in elements.enumerate() {
+            self.walk(from, &path.index(index), &element?)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the edges of the pointers within `fields`, which are at `path`
+    /// within the node `from`; bitfields hold no pointers, and are skipped.
+    fn walk_fields(
+        &mut self,
+        from: usize,
+        path: &Path,
+        mut fields: crate::value::Fields<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            if field.schema().is_bitfield()? {
+                continue;
+            }
+            let name = field.schema().name()?.to_string_lossy()?.into_owned();
+            self.walk(from, &path.field(name), &field.value()?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes `label` for use within a quoted DOT string; lines are centered.
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
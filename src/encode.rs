@@ -0,0 +1,806 @@
+//! Encoding of values as the bytes of types described by debuginfo; the
+//! inverse of reflection, with which values are written into the memory of
+//! another process, or passed to foreign functions, whose types are known only
+//! by their debuginfo.
+
+use std::fmt;
+
+use ::serde::ser::{self, Impossible, Serialize, Serializer};
+
+use crate::schema::Type;
+
+/// An error produced while encoding a value; converted to a [`crate::Error`]
+/// by [`Type::encode`].
+#[derive(Debug)]
+struct EncodeError(crate::Error);
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+impl ser::Error for EncodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(anyhow!("{msg}"))
+    }
+}
+
+impl From<crate::Error> for EncodeError {
+    fn from(error: crate::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl<'dwarf, R> Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The bytes of `value`, laid out as a value of this type; e.g., to write
+    /// into the memory of another process, or to pass to a foreign function.
+    ///
+    /// `value` is encoded with [`serde`], as reflected values are
+    /// [deserialized][crate::DeserializeError]: structs are encoded from
+    /// structs (by the names of their fields), tuples, tuple structs and
+    /// arrays from sequences, newtypes from their inner value, and enums from
+    /// enums (by the names of their variants), with their discriminants where
+    /// their debuginfo places them, including in niches. `Option`s are encoded
+    /// from options, integers from any integer that fits, and raw pointers
+    /// and `NonNull`s from their addresses; zero cannot be encoded as a
+    /// `NonZero` or `NonNull`. Values are laid out in the byte order, and
+    /// with the address size, of the target that this type's debuginfo
+    /// describes.
+    ///
+    /// Every field must be encoded, except those of zero-sized types; padding
+    /// is zeroed. Types that refer to other memory (e.g., references, boxes,
+    /// `str`s and `Vec`s) cannot be encoded, nor can bitfields.
+    ///
+    /// Requires the `serde` feature.
+    pub fn encode<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut bytes = vec![0; usize::try_from(self.size()?)?];
+        let encoder = Encoder::new(self.clone(), &mut bytes)?;
+        value.serialize(encoder).map_err(|EncodeError(error)| error)?;
+        Ok(bytes)
+    }
+}
+
+/// Encodes a value as the value of `r#type` in `bytes`.
+struct Encoder<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    r#type: Type<'dwarf, R>,
+    bytes: &'bytes mut [u8],
+    /// Whether the value is wrapped in a `NonZero` or `NonNull`, and so must
+    /// not be zero.
+    non_zero: bool,
+}
+
+/// An integer to encode.
+#[derive(Clone, Copy)]
+enum Integer {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Integer::Signed(value) => value.fmt(f),
+            Integer::Unsigned(value) => value.fmt(f),
+        }
+    }
+}
+
+impl<'bytes, 'dwarf, R> Encoder<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// An encoder of a value of `r#type` at the start of `bytes`.
+    fn new(r#type: Type<'dwarf, R>, bytes: &'bytes mut [u8]) -> Result<Self, crate::Error> {
+        let size = usize::try_from(r#type.size()?)?;
+        let len = bytes.len();
+        let bytes = bytes
+            .get_mut(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, len))?;
+        Ok(Self {
+            r#type,
+            bytes,
+            non_zero: false,
+        })
+    }
+
+    /// This encoder, retargeted at the value that its type wraps (e.g., the
+    /// value of a `ManuallyDrop`, or of an atomic) and, if `newtypes`, at the
+    /// fields of newtypes.
+    ///
+    /// The values of `NonZero`s and `NonNull`s must not be zero; their zero is
+    /// the niche of the `None` of an `Option`.
+    fn unwrapped(self, newtypes: bool) -> Result<Self, crate::Error> {
+        let non_zero = self.non_zero || matches!(self.r#type, Type::NonZero(_) | Type::NonNull(_));
+        let (offset, r#type) = match &self.r#type {
+            Type::Atomic(schema) => (schema.cell().offset()?.address(0)?, schema.value_type()?),
+            Type::ManuallyDrop(schema) => {
+                (schema.value().offset()?.address(0)?, schema.value_type()?)
+            }
+            Type::MaybeUninit(schema) => {
+                (schema.value().offset()?.address(0)?, schema.value_type()?)
+            }
+            Type::NonZero(schema) => (0, schema.value_type()?),
+            Type::NonNull(schema) => {
+                (schema.pointer().offset()?.address(0)?, schema.pointer().r#type()?)
+            }
+            Type::Struct(schema) if newtypes => match schema.transparent_inner()? {
+                Some(inner) => (inner.offset()?.address(0)?, inner.r#type()?),
+                None => return Ok(self),
+            },
+            _ => return Ok(self),
+        };
+        let offset = usize::try_from(offset)?;
+        let bytes = self.bytes;
+        let len = bytes.len();
+        let bytes = bytes
+            .get_mut(offset..)
+            .ok_or_else(|| crate::error::size_mismatch(offset, len))?;
+        let encoder = Self {
+            non_zero,
+            ..Self::new(r#type, bytes)?
+        };
+        encoder.unwrapped(newtypes)
+    }
+
+    /// The error of encoding `input` as a value of this encoder's type.
+    fn mismatch(&self, input: &str) -> crate::Error {
+        anyhow!("cannot encode {input} as `{}`", self.r#type)
+    }
+
+    /// Whether the target that this encoder's debuginfo describes is
+    /// big-endian.
+    fn is_big_endian(&self) -> bool {
+        use crate::gimli::{Endianity, Section};
+        let (dwarf, _, _) = self.r#type.die();
+        dwarf.debug_info.reader().endian().is_big_endian()
+    }
+
+    /// The low `size` bytes of `value`, in the byte order of this encoder's
+    /// target; fails if `value` does not fit in them, zero- or sign-extended.
+    fn truncated(&self, value: u64, size: u64) -> Result<Vec<u8>, crate::Error> {
+        let size = usize::try_from(size)?;
+        if !matches!(size, 1 | 2 | 4 | 8) {
+            bail!("unsupported integer of {size} bytes");
+        }
+        let unused = 64 - 8 * size as u32;
+        let zero_extended = value << unused >> unused;
+        let sign_extended = ((value << unused) as i64 >> unused) as u64;
+        if value != zero_extended && value != sign_extended {
+            bail!("{value:#x} does not fit in {size} bytes");
+        }
+        Ok(match self.is_big_endian() {
+            true => value.to_be_bytes()[8 - size..].to_vec(),
+            false => value.to_le_bytes()[..size].to_vec(),
+        })
+    }
+
+    /// Writes `bytes` as the value of this encoder's type.
+    fn write(self, bytes: &[u8]) -> Result<(), crate::Error> {
+        if self.bytes.len() != bytes.len() {
+            return Err(crate::error::size_mismatch(self.bytes.len(), bytes.len()));
+        }
+        self.bytes.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Encodes the integer `value`, if it fits in this encoder's type.
+    fn integer(self, value: Integer) -> Result<(), crate::Error> {
+        let encoder = self.unwrapped(true)?;
+        macro_rules! encode {
+            ($t:ty) => {{
+                let converted = match value {
+                    Integer::Signed(value) => <$t>::try_from(value).ok(),
+                    Integer::Unsigned(value) => <$t>::try_from(value).ok(),
+                };
+                let Some(converted) = converted else {
+                    bail!("{value} does not fit in `{}`", encoder.r#type);
+                };
+                if encoder.non_zero && converted == 0 {
+                    bail!("cannot encode zero as the non-zero `{}`", encoder.r#type);
+                }
+                let bytes = match encoder.is_big_endian() {
+                    true => converted.to_be_bytes(),
+                    false => converted.to_le_bytes(),
+                };
+                encoder.write(&bytes)
+            }};
+        }
+        match &encoder.r#type {
+            Type::i8(_) => encode!(i8),
+            Type::i16(_) => encode!(i16),
+            Type::i32(_) => encode!(i32),
+            Type::i64(_) => encode!(i64),
+            Type::i128(_) => encode!(i128),
+            Type::isize(_) => encode!(isize),
+            Type::u8(_) => encode!(u8),
+            Type::u16(_) => encode!(u16),
+            Type::u32(_) => encode!(u32),
+            Type::u64(_) => encode!(u64),
+            Type::u128(_) => encode!(u128),
+            Type::usize(_) => encode!(usize),
+            // only thin pointers are the size of an address; of the target's,
+            // which may differ from this host's.
+            Type::ConstPtr(_) | Type::MutPtr(_) => {
+                let (_, unit, _) = encoder.r#type.die();
+                let size = crate::address_size(unit);
+                let addr = match value {
+                    Integer::Signed(value) => u64::try_from(value).ok(),
+                    Integer::Unsigned(value) => u64::try_from(value).ok(),
+                };
+                let Some(addr) = addr.filter(|addr| size >= 8 || addr >> (8 * size) == 0) else {
+                    bail!("{value} does not fit in `{}`", encoder.r#type);
+                };
+                if encoder.non_zero && addr == 0 {
+                    bail!("cannot encode zero as the non-zero `{}`", encoder.r#type);
+                }
+                let bytes = encoder.truncated(addr, size)?;
+                encoder.write(&bytes)
+            }
+            _ => Err(encoder.mismatch("an integer")),
+        }
+    }
+
+    /// Whether this encoder's type is an `Option`.
+    fn is_option(&self) -> Result<bool, crate::Error> {
+        Ok(match &self.r#type {
+            Type::Enum(schema) => schema.is_instance_of("core::option::Option")?,
+            _ => false,
+        })
+    }
+
+    /// The fields of the variant named `name` of this encoder's enum, which
+    /// are followed by its discriminant.
+    fn variant(self, name: &str) -> Result<Compound<'bytes, 'dwarf, R>, crate::Error> {
+        let encoder = self.unwrapped(true)?;
+        let Type::Enum(schema) = &encoder.r#type else {
+            return Err(encoder.mismatch(&format!("the variant `{name}`")));
+        };
+        let Some(variant) = schema.variant_by_name(name)? else {
+            bail!("`{}` has no variant named `{name}`", encoder.r#type);
+        };
+        // the dataful variant of a niche-optimized enum has no discriminant;
+        // it is implied by the values of its fields.
+        let discriminant = match variant.discriminant_value() {
+            Some(value) => {
+                let offset = schema.discriminant_location().clone().address(0)?;
+                // signed discriminants are the two's-complement bits of their
+                // value, which may be zero- or sign-extended to a `u64`.
+                let size = schema.discriminant_type()?.size()?;
+                let bytes = encoder.truncated(value.to_u64(), size)?;
+                Some((usize::try_from(offset)?, bytes))
+            }
+            None => None,
+        };
+        let slots = slots(variant.fields()?)?;
+        Ok(Compound {
+            r#type: encoder.r#type,
+            bytes: encoder.bytes,
+            slots,
+            discriminant,
+        })
+    }
+
+    /// The elements of this encoder's array or vector, or the fields of its
+    /// tuple or tuple struct.
+    fn sequence(self) -> Result<Compound<'bytes, 'dwarf, R>, crate::Error> {
+        let encoder = self.unwrapped(false)?;
+        let slots = match &encoder.r#type {
+            Type::Array(schema) => elements(schema.elt_type()?, schema.len()?, 0)?,
+            Type::Vector(schema) => {
+                let offset = schema.lanes().offset()?.address(0)?;
+                let lanes = schema.as_array()?;
+                elements(lanes.elt_type()?, lanes.len()?, offset)?
+            }
+            Type::Struct(schema) => slots(schema.fields()?)?,
+            _ => return Err(encoder.mismatch("a sequence")),
+        };
+        Ok(Compound::new(encoder, slots))
+    }
+
+    /// The fields of this encoder's struct.
+    fn structure(self) -> Result<Compound<'bytes, 'dwarf, R>, crate::Error> {
+        let encoder = self.unwrapped(false)?;
+        let Type::Struct(schema) = &encoder.r#type else {
+            return Err(encoder.mismatch("a struct"));
+        };
+        let slots = slots(schema.fields()?)?;
+        Ok(Compound::new(encoder, slots))
+    }
+}
+
+/// A field or element of a [`Compound`].
+struct Slot<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The name of the field; `None`, if this is an element.
+    name: Option<String>,
+    r#type: Type<'dwarf, R>,
+    offset: usize,
+    filled: bool,
+}
+
+/// The slots of `fields`.
+fn slots<R>(mut fields: crate::schema::Fields<'_, R>) -> Result<Vec<Slot<'_, R>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut slots = vec![];
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let name = field.name()?.to_string_lossy()?.into_owned();
+        if field.is_bitfield()? {
+            bail!("the bitfield `{name}` cannot be encoded");
+        }
+        slots.push(Slot {
+            name: Some(name),
+            r#type: field.r#type()?,
+            offset: usize::try_from(field.offset()?.address(0)?)?,
+            filled: false,
+        });
+    }
+    Ok(slots)
+}
+
+/// The slots of `len` elements of `elt_type`, starting at `offset`.
+fn elements<R>(elt_type: Type<'_, R>, len: u64, offset: u64) -> Result<Vec<Slot<'_, R>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let elt_size = elt_type.size()?;
+    (0..len)
+        .map(|index| -> Result<_, crate::Error> {
+            let offset = elt_size
+                .checked_mul(index)
+                .and_then(|start| start.checked_add(offset))
+                .ok_or_else(crate::error::arithmetic_overflow)?;
+            Ok(Slot {
+                name: None,
+                r#type: elt_type.clone(),
+                offset: usize::try_from(offset)?,
+                filled: false,
+            })
+        })
+        .collect()
+}
+
+/// Encodes the fields of a struct or variant, or the elements of an array, in
+/// order or by name.
+struct Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    r#type: Type<'dwarf, R>,
+    bytes: &'bytes mut [u8],
+    slots: Vec<Slot<'dwarf, R>>,
+    /// The offset and bytes of the discriminant of a variant.
+    discriminant: Option<(usize, Vec<u8>)>,
+}
+
+impl<'bytes, 'dwarf, R> Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The slots of the value of `encoder`.
+    fn new(encoder: Encoder<'bytes, 'dwarf, R>, slots: Vec<Slot<'dwarf, R>>) -> Self {
+        Self {
+            r#type: encoder.r#type,
+            bytes: encoder.bytes,
+            slots,
+            discriminant: None,
+        }
+    }
+
+    /// An encoder of the next slot that has not been filled.
+    fn next(&mut self) -> Result<Encoder<'_, 'dwarf, R>, crate::Error> {
+        let Some(slot) = self.slots.iter_mut().find(|slot| !slot.filled) else {
+            bail!("too many elements for `{}`", self.r#type);
+        };
+        slot.filled = true;
+        let len = self.bytes.len();
+        let bytes = self
+            .bytes
+            .get_mut(slot.offset..)
+            .ok_or_else(|| crate::error::size_mismatch(slot.offset, len))?;
+        Encoder::new(slot.r#type.clone(), bytes)
+    }
+
+    /// An encoder of the field named `name`.
+    fn named(&mut self, name: &str) -> Result<Encoder<'_, 'dwarf, R>, crate::Error> {
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.name.as_deref() == Some(name));
+        let Some(slot) = slot else {
+            bail!("`{}` has no field named `{name}`", self.r#type);
+        };
+        slot.filled = true;
+        let len = self.bytes.len();
+        let bytes = self
+            .bytes
+            .get_mut(slot.offset..)
+            .ok_or_else(|| crate::error::size_mismatch(slot.offset, len))?;
+        Encoder::new(slot.r#type.clone(), bytes)
+    }
+
+    /// Checks that every slot is filled, and writes the discriminant, if any.
+    fn finish(self) -> Result<(), crate::Error> {
+        for slot in &self.slots {
+            if slot.filled || slot.r#type.size()? == 0 {
+                continue;
+            }
+            match &slot.name {
+                Some(name) => bail!("the field `{name}` of `{}` is missing", self.r#type),
+                None => bail!("too few elements for `{}`", self.r#type),
+            }
+        }
+        if let Some((offset, discriminant)) = self.discriminant {
+            let len = self.bytes.len();
+            let end = offset + discriminant.len();
+            self.bytes
+                .get_mut(offset..end)
+                .ok_or_else(|| crate::error::size_mismatch(end, len))?
+                .copy_from_slice(&discriminant);
+        }
+        Ok(())
+    }
+}
+
+impl<'bytes, 'dwarf, R> Serializer for Encoder<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+    type SerializeSeq = Compound<'bytes, 'dwarf, R>;
+    type SerializeTuple = Compound<'bytes, 'dwarf, R>;
+    type SerializeTupleStruct = Compound<'bytes, 'dwarf, R>;
+    type SerializeTupleVariant = Compound<'bytes, 'dwarf, R>;
+    type SerializeMap = Impossible<(), EncodeError>;
+    type SerializeStruct = Compound<'bytes, 'dwarf, R>;
+    type SerializeStructVariant = Compound<'bytes, 'dwarf, R>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        match &encoder.r#type {
+            Type::bool(_) => Ok(encoder.write(&[u8::from(v)])?),
+            _ => Err(encoder.mismatch("a `bool`").into()),
+        }
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Signed(v.into()))?)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Signed(v.into()))?)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Signed(v.into()))?)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Signed(v.into()))?)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Signed(v))?)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Unsigned(v.into()))?)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Unsigned(v.into()))?)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Unsigned(v.into()))?)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Unsigned(v.into()))?)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), EncodeError> {
+        Ok(self.integer(Integer::Unsigned(v))?)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), EncodeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        let big_endian = encoder.is_big_endian();
+        let bytes = match &encoder.r#type {
+            Type::f32(_) if big_endian => (v as f32).to_be_bytes().to_vec(),
+            Type::f32(_) => (v as f32).to_le_bytes().to_vec(),
+            Type::f64(_) if big_endian => v.to_be_bytes().to_vec(),
+            Type::f64(_) => v.to_le_bytes().to_vec(),
+            _ => return Err(encoder.mismatch("a float").into()),
+        };
+        Ok(encoder.write(&bytes)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        match &encoder.r#type {
+            Type::char(_) => {
+                let bytes = encoder.truncated(u32::from(v).into(), 4)?;
+                Ok(encoder.write(&bytes)?)
+            }
+            _ => Err(encoder.mismatch("a `char`").into()),
+        }
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        let mut chars = v.chars();
+        match (&encoder.r#type, chars.next(), chars.next()) {
+            (Type::char(_), Some(c), None) => encoder.serialize_char(c),
+            // e.g., the names of unit variants, from self-describing formats.
+            (Type::Enum(_), _, _) => Ok(encoder.variant(v)?.finish()?),
+            _ => Err(encoder.mismatch("a string").into()),
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        match &encoder.r#type {
+            Type::Array(schema) if matches!(schema.elt_type()?, Type::u8(_)) => {
+                Ok(encoder.write(v)?)
+            }
+            _ => Err(encoder.mismatch("bytes").into()),
+        }
+    }
+
+    fn serialize_none(self) -> Result<(), EncodeError> {
+        let encoder = self.unwrapped(true)?;
+        if !encoder.is_option()? {
+            return Err(encoder.mismatch("`None`").into());
+        }
+        Ok(encoder.variant("None")?.finish()?)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let encoder = self.unwrapped(true)?;
+        if !encoder.is_option()? {
+            return value.serialize(encoder);
+        }
+        let mut some = encoder.variant("Some")?;
+        value.serialize(some.next()?)?;
+        Ok(some.finish()?)
+    }
+
+    fn serialize_unit(self) -> Result<(), EncodeError> {
+        match self.r#type.size()? {
+            0 => Ok(()),
+            _ => Err(self.mismatch("`()`").into()),
+        }
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), EncodeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), EncodeError> {
+        Ok(self.variant(variant)?.finish()?)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let encoder = self.unwrapped(false)?;
+        let is_newtype = match &encoder.r#type {
+            Type::Struct(schema) => schema.transparent_inner()?.is_some(),
+            _ => false,
+        };
+        if !is_newtype {
+            return value.serialize(encoder);
+        }
+        let mut newtype = encoder.sequence()?;
+        value.serialize(newtype.next()?)?;
+        Ok(newtype.finish()?)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut variant = self.variant(variant)?;
+        value.serialize(variant.next()?)?;
+        Ok(variant.finish()?)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, EncodeError> {
+        Ok(self.sequence()?)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, EncodeError> {
+        Ok(self.sequence()?)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, EncodeError> {
+        Ok(self.sequence()?)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, EncodeError> {
+        Ok(self.variant(variant)?)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, EncodeError> {
+        Err(self.mismatch("a map").into())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, EncodeError> {
+        Ok(self.structure()?)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, EncodeError> {
+        Ok(self.variant(variant)?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeSeq for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.next()?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeTuple for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.next()?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeTupleStruct for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.next()?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeTupleVariant for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.next()?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeStruct for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.named(key)?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
+
+impl<'bytes, 'dwarf, R> ser::SerializeStructVariant for Compound<'bytes, 'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self.named(key)?)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(self.finish()?)
+    }
+}
@@ -37,6 +37,10 @@ pub(crate) fn name_mismatch(expected: &'static str, actual: String) -> crate::Er
     anyhow!("name mismatch; expected {expected} bytes, found {actual}.")
 }
 
+pub(crate) fn unrecognized_pointer(name: String) -> crate::Error {
+    anyhow!("pointer type `{name}` is not a raw pointer, reference, `fn` or `Box`")
+}
+
 pub(crate) fn file_indexing() -> crate::Error {
     anyhow!("could not map file index to a file name")
 }
@@ -45,10 +49,218 @@ pub(crate) fn arithmetic_overflow() -> crate::Error {
     anyhow!("arithmetic operation overflowed")
 }
 
+pub(crate) fn bitfield() -> crate::Error {
+    anyhow!("bitfields cannot be reflected as values; read their bits instead")
+}
+
 pub(crate) fn enum_destructure() -> crate::Error {
     anyhow!("could not destructure enum into variant")
 }
 
+pub(crate) fn type_mismatch(expected: &'static str, actual: String) -> crate::Error {
+    anyhow!("type mismatch; expected `{expected}`, found `{actual}`")
+}
+
+pub(crate) fn type_not_found(name: &str) -> crate::Error {
+    anyhow!("could not find a type named `{name}`")
+}
+
+pub(crate) fn accessor_mismatch(expected: String, actual: String) -> crate::Error {
+    anyhow!("accessor of `{expected}` applied to a value of `{actual}`")
+}
+
+pub(crate) fn invalid_path(path: &str, reason: &str) -> crate::Error {
+    anyhow!("invalid path {path:?}: {reason}")
+}
+
+pub(crate) fn dangling_reference(target: crate::gimli::UnitSectionOffset) -> crate::Error {
+    anyhow!("reference to {target:?} is not within any unit")
+}
+
+pub(crate) fn unmemoized_reference(target: crate::gimli::UnitSectionOffset) -> crate::Error {
+    anyhow!("cannot follow a reference into the unit at {target:?} of sections not memoized")
+}
+
+pub(crate) fn missing_type_unit(signature: crate::gimli::DebugTypeSignature) -> crate::Error {
+    anyhow!("no type unit has the signature 0x{:016x}", signature.0)
+}
+
+pub(crate) fn foreign_address(size: u64) -> crate::Error {
+    let host = std::mem::size_of::<usize>();
+    anyhow!("cannot dereference a {size}-byte address on a target with {host}-byte addresses")
+}
+
+pub(crate) fn virtual_base() -> crate::Error {
+    anyhow!("virtual base classes cannot be reflected; their offsets are only known at run time")
+}
+
+pub(crate) fn invalid_snapshot(reason: &str) -> crate::Error {
+    anyhow!("invalid snapshot: {reason}")
+}
+
+/// Describes the type at `offset`, as context for an error that occurred while
+/// reading it; e.g., ``while reading `Server` (DIE 0x1f3a)``.
+pub(crate) fn reading_type<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    offset: crate::gimli::UnitOffset,
+) -> String
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    format!(
+        "while reading `{}` ({})",
+        name_of(dwarf, unit, offset),
+        die(unit, offset)
+    )
+}
+
+/// Describes the field at `offset`, as context for an error that occurred
+/// while reading it; e.g., ``while reading field `retries` of `Server` (DIE
+/// 0x1f3a)``.
+pub(crate) fn reading_field<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    offset: crate::gimli::UnitOffset,
+) -> String
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    // the parent is the innermost named ancestor; e.g., an enum, rather than
+    // its variant part.
+    let parent = crate::ancestors_of(unit, offset)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .map(|ancestor| name_of(dwarf, unit, ancestor))
+        .find(|name| name != ANONYMOUS);
+    let field = name_of(dwarf, unit, offset);
+    let die = die(unit, offset);
+    match parent {
+        Some(parent) => format!("while reading field `{field}` of `{parent}` ({die})"),
+        None => format!("while reading field `{field}` ({die})"),
+    }
+}
+
+/// The name of entries without one.
+const ANONYMOUS: &str = "<anonymous>";
+
+/// The name of the entry at `offset`, for error messages.
+fn name_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    offset: crate::gimli::UnitOffset,
+) -> String
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let name = unit.entry(offset).ok().and_then(|entry| {
+        let name = crate::schema::Name::from_die_opt(dwarf, unit, &entry).ok()??;
+        Some(name.to_string_lossy().ok()?.into_owned())
+    });
+    name.unwrap_or_else(|| ANONYMOUS.to_owned())
+}
+
+/// Identifies the entry at `offset` by its offset in `.debug_info`, as would
+/// `dwarfdump`; e.g., `DIE 0x1f3a`.
+fn die<R>(unit: &crate::gimli::Unit<R>, offset: crate::gimli::UnitOffset) -> String
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    match offset.to_debug_info_offset(&unit.header) {
+        Some(offset) => format!("DIE 0x{:x}", offset.0),
+        None => format!("DIE 0x{:x} of its unit", offset.0),
+    }
+}
+
+/// How to build code that was built without debuginfo.
+pub(crate) const MISSING_DEBUG_INFO: &str = "the reflected code was built without debuginfo; \
+    set `debug = true` in the Cargo profile it is built with (e.g., `[profile.release]`), \
+    and do not strip the binary";
+
+/// How to build code that was built with debuginfo that does not describe
+/// types.
+pub(crate) const LIMITED_DEBUG_INFO: &str = "the reflected code was built with limited \
+    debuginfo (e.g., `debug = \"line-tables-only\"` or `debug = 1`), which does not describe \
+    types; set `debug = true` in the Cargo profile it is built with";
+
+pub(crate) fn symbol_not_found(addr: usize) -> crate::Error {
+    ErrorKind::SymbolNotFound { addr: addr as u64 }.into()
+}
+
+pub(crate) fn no_debug_info(suggestion: &'static str) -> crate::Error {
+    ErrorKind::NoDebugInfo { suggestion }.into()
+}
+
+pub(crate) fn missing_template_parameter() -> crate::Error {
+    ErrorKind::MissingTemplateParameter.into()
+}
+
+pub(crate) fn unsupported_layout(tag: crate::gimli::DwTag) -> crate::Error {
+    ErrorKind::UnsupportedLayout { tag }.into()
+}
+
+pub(crate) fn unsupported_std_layout(r#type: String, producer: Option<String>) -> crate::Error {
+    let producer = producer.unwrap_or_else(|| "an unknown compiler".to_owned());
+    ErrorKind::UnsupportedStdLayout { r#type, producer }.into()
+}
+
+pub(crate) fn invalid_bit_pattern(r#type: &'static str, bytes: Vec<u8>) -> crate::Error {
+    ErrorKind::InvalidBitPattern { r#type, bytes }.into()
+}
+
+/// A failure mode of reflection that callers may match on.
+///
+/// Errors of these kinds can be recovered from a [`crate::Error`] with
+/// [`downcast_ref`][anyhow::Error::downcast_ref].
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// No function or symbol with debuginfo is at the given address.
+    #[error("could not find a function with debuginfo at the address 0x{addr:x}")]
+    SymbolNotFound {
+        /// The address that could not be resolved.
+        addr: u64,
+    },
+    /// The program does not have the debuginfo needed for reflection.
+    #[error("insufficient debuginfo for reflection; {suggestion}")]
+    NoDebugInfo {
+        /// Why the debuginfo is insufficient, and how to build the program
+        /// with sufficient debuginfo.
+        suggestion: &'static str,
+    },
+    /// The debuginfo of a [`Reflect::local_type_id`][crate::Reflect::local_type_id]
+    /// function does not describe the type it is generic over.
+    #[error("the debuginfo of a reflected function does not describe its type parameter")]
+    MissingTemplateParameter,
+    /// The debuginfo describes a type in a way that cannot be reflected.
+    #[error("cannot reflect debuginfo entries of tag {tag}")]
+    UnsupportedLayout {
+        /// The tag of the unsupported entry.
+        tag: crate::gimli::DwTag,
+    },
+    /// The layout of a standard library type is not one that this crate knows
+    /// how to read for the compiler that produced it.
+    #[error("unsupported layout of `{r#type}` in the standard library of {producer}")]
+    UnsupportedStdLayout {
+        /// The name of the type.
+        r#type: String,
+        /// The `DW_AT_producer` of the unit declaring the type; e.g., `clang
+        /// LLVM (rustc version 1.84.0)`.
+        producer: String,
+    },
+    /// The bytes of a reflected value are not a valid instance of its type;
+    /// e.g., a `bool` of `7`. Only reported by providers that
+    /// [validate bit patterns][crate::DebugInfoProvider::validates_bit_patterns].
+    #[error("invalid bit pattern {bytes:02x?} for `{r#type}`")]
+    InvalidBitPattern {
+        /// The name of the type; e.g., `char`.
+        r#type: &'static str,
+        /// The invalid bytes.
+        bytes: Vec<u8>,
+    },
+}
+
 /// Could not downcast the value into the given type.
 #[derive(thiserror::Error, Debug)]
 #[error("Could not downcast into {src}, received {dst}")]
@@ -0,0 +1,132 @@
+//! Filters for searching the types described by debug info.
+
+use std::ops::{Bound, RangeBounds};
+
+/// A filter on the types found by [`find_types`][crate::find_types].
+///
+/// By default, all types match.
+#[derive(Clone, Debug)]
+pub struct TypeFilter {
+    name: Option<String>,
+    krate: Option<String>,
+    size: (Bound<u64>, Bound<u64>),
+    align: (Bound<u64>, Bound<u64>),
+}
+
+impl Default for TypeFilter {
+    fn default() -> Self {
+        Self {
+            name: None,
+            krate: None,
+            size: (Bound::Unbounded, Bound::Unbounded),
+            align: (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+}
+
+impl TypeFilter {
+    /// The default filter, which all types match.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only types whose fully-qualified names match the glob `pattern` (e.g.,
+    /// `tokio::sync::*`); `*` matches any sequence of characters, including
+    /// `::`.
+    pub fn name(mut self, pattern: impl Into<String>) -> Self {
+        self.name = Some(pattern.into());
+        self
+    }
+
+    /// Only types defined in the crate named `krate`; i.e., whose outermost
+    /// namespace is `krate`.
+    pub fn in_crate(mut self, krate: impl Into<String>) -> Self {
+        self.krate = Some(krate.into());
+        self
+    }
+
+    /// Only types whose size, in bytes, is within `range`.
+    pub fn size(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.size = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Only types whose alignment, in bytes, is within `range`. Types whose
+    /// alignment is not described by their debug info do not match.
+    pub fn align(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.align = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Whether the fully-qualified `name` of a type matches this filter.
+    pub(crate) fn matches_name(&self, name: &[u8]) -> bool {
+        if let Some(krate) = &self.krate {
+            let Some(rest) = name.strip_prefix(krate.as_bytes()) else {
+                return false;
+            };
+            if !rest.starts_with(b"::") {
+                return false;
+            }
+        }
+        match &self.name {
+            Some(pattern) => glob(pattern.as_bytes(), name),
+            None => true,
+        }
+    }
+
+    /// Whether `r#type` matches the layout predicates of this filter.
+    pub(crate) fn matches_layout<R>(
+        &self,
+        r#type: &crate::schema::Type<'_, R>,
+    ) -> Result<bool, crate::Error>
+    where
+        R: crate::gimli::Reader<Offset = usize>,
+    {
+        let align = if self.align == (Bound::Unbounded, Bound::Unbounded) {
+            None
+        } else {
+            r#type.align()?
+        };
+        Ok(self.matches_size_and_align(r#type.size()?, align))
+    }
+
+    /// Whether a type of `size` and `align` bytes matches the layout
+    /// predicates of this filter.
+    pub(crate) fn matches_size_and_align(&self, size: u64, align: Option<u64>) -> bool {
+        if !self.size.contains(&size) {
+            return false;
+        }
+        if self.align == (Bound::Unbounded, Bound::Unbounded) {
+            return true;
+        }
+        match align {
+            Some(align) => self.align.contains(&align),
+            None => false,
+        }
+    }
+}
+
+/// Whether `name` matches the glob `pattern`, in which `*` matches any
+/// sequence of bytes.
+pub(crate) fn glob(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // the position of the last `*` in `pattern`, and of the byte in `name`
+    // that it was matched up to.
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == name[n] {
+            p += 1;
+            n += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
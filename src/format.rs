@@ -0,0 +1,636 @@
+//! Options controlling how reflected values are displayed.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+};
+
+/// Options controlling how a reflected [`Value`][crate::Value] is displayed;
+/// apply them with [`Value::display_with`][crate::Value::display_with].
+///
+/// The limits are useful for bounding the output of values logged in
+/// production; by default, values are displayed in full.
+#[derive(Clone, Debug, Default)]
+pub struct FormatOptions {
+    transparent_newtypes: bool,
+    max_depth: Option<usize>,
+    max_elements: Option<usize>,
+    max_bytes: Option<usize>,
+    hex_integers: bool,
+    field_types: bool,
+    lossy_strings: bool,
+    bytes: ByteFormat,
+    raw_std_types: bool,
+    addresses: AddressFormat,
+}
+
+/// How slices of bytes (e.g., `&[u8]`) are displayed; see
+/// [`FormatOptions::bytes`].
+///
+/// Like other slices, at most [`FormatOptions::max_elements`] bytes are
+/// displayed, followed by a count of the rest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ByteFormat {
+    /// As a list of integers; e.g., `[1, 2, 104, 105]`.
+    #[default]
+    List,
+    /// As a byte string literal; e.g., `b"\x01\x02hi"`.
+    Escaped,
+    /// As hexadecimal; e.g., `hex:01026869`.
+    Hex,
+    /// As padded, standard base64; e.g., `base64:AQJoaQ==`.
+    Base64,
+}
+
+/// How the addresses of raw pointers, functions and cycles are displayed;
+/// see [`FormatOptions::addresses`].
+///
+/// Null pointers are always displayed as `0x0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AddressFormat {
+    /// In hexadecimal; e.g., `0x7ffd5f0c as *const u8`.
+    #[default]
+    Raw,
+    /// As a placeholder; e.g., `<ptr> as *const u8`.
+    Hidden,
+    /// As ids numbered in the order in which their addresses are first
+    /// displayed, so that equal addresses have equal ids; e.g.,
+    /// `<ptr#1> as *const u8`.
+    Stable,
+}
+
+impl FormatOptions {
+    /// The default options; values are displayed as they are with
+    /// [`Display`][fmt::Display].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether single-field tuple structs (e.g., `UserId(u64)`) are displayed
+    /// as just their [inner value][crate::schema::Struct::transparent_inner].
+    pub fn transparent_newtypes(mut self, transparent_newtypes: bool) -> Self {
+        self.transparent_newtypes = transparent_newtypes;
+        self
+    }
+
+    /// Values nested more than `depth` levels deep (e.g., the fields of a
+    /// struct are one level deeper than the struct) are displayed as `…`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Only the first `len` elements of arrays and slices are displayed,
+    /// followed by a count of the rest; e.g., `[1, 2, … 990 more]`.
+    pub fn max_elements(mut self, len: usize) -> Self {
+        self.max_elements = Some(len);
+        self
+    }
+
+    /// At most `len` bytes of output are displayed; if the output is longer,
+    /// it is truncated (at a character boundary) and followed by `…`.
+    pub fn max_bytes(mut self, len: usize) -> Self {
+        self.max_bytes = Some(len);
+        self
+    }
+
+    /// Whether integers are displayed in hexadecimal (e.g., `0x2a`), rather
+    /// than decimal.
+    pub fn hex_integers(mut self, hex_integers: bool) -> Self {
+        self.hex_integers = hex_integers;
+        self
+    }
+
+    /// Whether the values of fields are displayed with their types; e.g.,
+    /// `Foo { a: u8 = 42 }`.
+    pub fn field_types(mut self, field_types: bool) -> Self {
+        self.field_types = field_types;
+        self
+    }
+
+    /// Whether strings that are not valid UTF-8 (e.g., because they were read
+    /// from corrupt memory) are displayed with their invalid bytes replaced
+    /// by `�`, rather than failing to display.
+    pub fn lossy_strings(mut self, lossy_strings: bool) -> Self {
+        self.lossy_strings = lossy_strings;
+        self
+    }
+
+    /// How slices of bytes are displayed; e.g., binary payloads are more
+    /// legible in logs as [`ByteFormat::Base64`] than as a list of integers.
+    pub fn bytes(mut self, bytes: ByteFormat) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    /// Whether common standard library types are displayed by their meaning,
+    /// rather than by their fields; e.g., a `Duration` as `1.5s`, a
+    /// `SystemTime` as `2024-05-06T07:08:09Z`, an `IpAddr` as `127.0.0.1`,
+    /// and a `Box<dyn Error>` as `box dyn Error(app::ConfigError { … })`,
+    /// with the qualified name of its concrete type. Enabled by default.
+    pub fn std_adapters(mut self, std_adapters: bool) -> Self {
+        self.raw_std_types = !std_adapters;
+        self
+    }
+
+    /// How addresses are displayed; e.g., so that the output of snapshot
+    /// tests does not vary between runs, as addresses do with ASLR.
+    pub fn addresses(mut self, addresses: AddressFormat) -> Self {
+        self.addresses = addresses;
+        self
+    }
+
+    /// Calls `f` with these options installed, as they are while a value is
+    /// displayed by [`display_with`][crate::Value::display_with]; values
+    /// displayed, [snapshotted][crate::Value::to_owned_snapshot],
+    /// [diffed][crate::diff] or converted to JSON within `f` are rendered with
+    /// these options (except for [`max_bytes`][Self::max_bytes], which only
+    /// applies to `display_with`).
+    ///
+    /// With [`AddressFormat::Stable`], ids are numbered across all of `f`; so,
+    /// e.g., the pointers of two diffed values to the same address have the
+    /// same id.
+    ///
+    /// ```
+    /// use deflect::{AddressFormat, FormatOptions};
+    ///
+    /// let context = deflect::default_provider()?;
+    /// let ptr: *const u8 = &42;
+    /// let erased: &dyn deflect::Reflect = &ptr;
+    /// let value = erased.reflect(&context)?;
+    /// let displayed = FormatOptions::new()
+    ///     .addresses(AddressFormat::Stable)
+    ///     .scope(|| value.to_string());
+    /// assert_eq!(displayed, "<ptr#1> as *const u8");
+    /// # Ok::<_, deflect::anyhow::Error>(())
+    /// ```
+    pub fn scope<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let outer = install(self.clone());
+        let result = f();
+        uninstall(outer);
+        result
+    }
+}
+
+/// The state of the formatting being done outside an [`install`]ation.
+struct Installed {
+    options: FormatOptions,
+    depth: usize,
+    addresses: Vec<usize>,
+}
+
+/// Installs `options` for the values displayed until the returned state is
+/// [`uninstall`]ed.
+fn install(options: FormatOptions) -> Installed {
+    Installed {
+        options: OPTIONS.with(|cell| cell.replace(options)),
+        depth: DEPTH.with(|depth| depth.replace(0)),
+        addresses: ADDRESSES.with(|addresses| addresses.replace(Vec::new())),
+    }
+}
+
+/// Restores the state of the formatting being done outside an
+/// [`install`]ation.
+fn uninstall(outer: Installed) {
+    ADDRESSES.with(|addresses| addresses.replace(outer.addresses));
+    DEPTH.with(|depth| depth.set(outer.depth));
+    OPTIONS.with(|options| options.replace(outer.options));
+}
+
+thread_local! {
+    /// The options of the innermost `display_with` being formatted.
+    static OPTIONS: RefCell<FormatOptions> = RefCell::new(FormatOptions::default());
+
+    /// The depth of the value being formatted, within the innermost
+    /// `display_with`.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// The addresses and sizes of the outermost value and the referents being
+    /// displayed, outermost first.
+    static VISITING: RefCell<Vec<(usize, usize)>> = const { RefCell::new(Vec::new()) };
+
+    /// The addresses displayed as stable ids, in order of their ids.
+    static ADDRESSES: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+
+    /// The error that caused the current formatting failure, if any.
+    static ERROR: RefCell<Option<crate::Error>> = const { RefCell::new(None) };
+
+    /// Whether errors are propagated (by `try_format`), rather than displayed
+    /// as placeholders.
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Records `err` as the cause of the current formatting failure.
+pub(crate) fn record_error(err: crate::Error) {
+    ERROR.with(|error| *error.borrow_mut() = Some(err));
+}
+
+/// Displays a value with `fmt`; if that fails because of an error reading the
+/// value or its debuginfo, the error and its causes are displayed as a
+/// placeholder (e.g., `<error: arithmetic operation overflowed>`) in place of
+/// the rest of the value, unless errors are being propagated by
+/// [`Value::try_format`][crate::Value::try_format].
+///
+/// Failures of the underlying writer are always propagated.
+pub(crate) fn recover<F>(f: &mut fmt::Formatter<'_>, fmt: F) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    // discard any error that was recorded, but not displayed, by a failure
+    // outside of `recover`.
+    ERROR.with(|error| error.borrow_mut().take());
+    let Err(err) = fmt(f) else { return Ok(()) };
+    if STRICT.with(Cell::get) {
+        return Err(err);
+    }
+    match ERROR.with(|error| error.borrow_mut().take()) {
+        Some(error) => write!(f, "<error: {error:#}>"),
+        None => Err(err),
+    }
+}
+
+/// Produces whether newtypes are currently displayed transparently.
+pub(crate) fn transparent_newtypes() -> bool {
+    OPTIONS.with(|options| options.borrow().transparent_newtypes)
+}
+
+/// Produces whether integers are currently displayed in hexadecimal.
+pub(crate) fn hex_integers() -> bool {
+    OPTIONS.with(|options| options.borrow().hex_integers)
+}
+
+/// Produces whether fields are currently displayed with their types.
+pub(crate) fn field_types() -> bool {
+    OPTIONS.with(|options| options.borrow().field_types)
+}
+
+/// Produces whether standard library types are currently displayed by their
+/// adapters.
+pub(crate) fn std_adapters() -> bool {
+    OPTIONS.with(|options| !options.borrow().raw_std_types)
+}
+
+/// Produces whether invalid UTF-8 in strings is currently replaced.
+pub(crate) fn lossy_strings() -> bool {
+    OPTIONS.with(|options| options.borrow().lossy_strings)
+}
+
+/// Displays `bytes` in the current [`ByteFormat`]; or, if that is
+/// [`ByteFormat::List`], produces `None`.
+pub(crate) fn bytes(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> Option<fmt::Result> {
+    let format = OPTIONS.with(|options| options.borrow().bytes);
+    let max_elements = OPTIONS.with(|options| options.borrow().max_elements);
+    let shown = max_elements.map_or(bytes.len(), |max_elements| max_elements.min(bytes.len()));
+    let result = match format {
+        ByteFormat::List => return None,
+        ByteFormat::Escaped => f.write_str("b\"").and_then(|()| {
+            for &byte in &bytes[..shown] {
+                fmt::Display::fmt(&std::ascii::escape_default(byte), f)?;
+            }
+            f.write_str("\"")
+        }),
+        ByteFormat::Hex => f.write_str("hex:").and_then(|()| {
+            for byte in &bytes[..shown] {
+                write!(f, "{byte:02x}")?;
+            }
+            Ok(())
+        }),
+        ByteFormat::Base64 => f
+            .write_str("base64:")
+            .and_then(|()| base64(f, &bytes[..shown])),
+    };
+    if shown < bytes.len() {
+        return Some(result.and_then(|()| write!(f, "… {} more", bytes.len() - shown)));
+    }
+    Some(result)
+}
+
+/// Writes `bytes` to `f` as padded, standard base64.
+fn base64(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in bytes.chunks(3) {
+        let mut group = [0; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for sextet in 0..4 {
+            if sextet <= chunk.len() {
+                let index = (group >> (18 - 6 * sextet)) & 0x3f;
+                fmt::Write::write_char(f, ALPHABET[index as usize] as char)?;
+            } else {
+                f.write_str("=")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Displays an address in the current [`AddressFormat`].
+pub(crate) struct Address(pub(crate) usize);
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(addr) = *self;
+        match OPTIONS.with(|options| options.borrow().addresses) {
+            AddressFormat::Raw => write!(f, "{addr:#x}"),
+            _ if addr == 0 => write!(f, "{addr:#x}"),
+            AddressFormat::Hidden => f.write_str("<ptr>"),
+            AddressFormat::Stable => {
+                let id = ADDRESSES.with(|addresses| {
+                    let mut addresses = addresses.borrow_mut();
+                    match addresses.iter().position(|&seen| seen == addr) {
+                        Some(index) => index + 1,
+                        None => {
+                            addresses.push(addr);
+                            addresses.len()
+                        }
+                    }
+                });
+                write!(f, "<ptr#{id}>")
+            }
+        }
+    }
+}
+
+/// Displays a value with `fmt`, one level deeper than the value being
+/// displayed; or, if that exceeds the current maximum depth, as `…`.
+pub(crate) fn nested<F>(f: &mut fmt::Formatter<'_>, fmt: F) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    let depth = DEPTH.with(Cell::get);
+    let max_depth = OPTIONS.with(|options| options.borrow().max_depth);
+    if matches!(max_depth, Some(max_depth) if depth > max_depth) {
+        return f.write_str("…");
+    }
+    DEPTH.with(|cell| cell.set(depth + 1));
+    let result = fmt(f);
+    DEPTH.with(|cell| cell.set(depth));
+    result
+}
+
+/// Displays the value `root` with `fmt`; if it is the outermost value being
+/// displayed, pointers back to it are displayed as closing a cycle, as those
+/// to [referents][follow] being displayed are.
+pub(crate) fn root<F>(f: &mut fmt::Formatter<'_>, root: crate::Bytes<'_>, fmt: F) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    let outermost = VISITING.with(|visiting| visiting.borrow().is_empty());
+    // zero-sized values cannot contain pointers, so cannot be in cycles.
+    if !outermost || root.is_empty() {
+        return fmt(f);
+    }
+    let key = (root.as_ptr() as usize, root.len());
+    VISITING.with(|visiting| visiting.borrow_mut().push(key));
+    let result = fmt(f);
+    VISITING.with(|visiting| visiting.borrow_mut().pop());
+    result
+}
+
+/// Displays the value `referent`, which is behind a pointer, with `fmt`; or,
+/// if `referent` is already being displayed (i.e., the pointer closes a
+/// cycle), as a marker like `<cycle -> 0x7ffd5f0c>`.
+pub(crate) fn follow<F>(
+    f: &mut fmt::Formatter<'_>,
+    referent: crate::Bytes<'_>,
+    fmt: F,
+) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    // zero-sized referents cannot contain pointers, so cannot close cycles.
+    if referent.is_empty() {
+        return fmt(f);
+    }
+    let key = (referent.as_ptr() as usize, referent.len());
+    if VISITING.with(|visiting| visiting.borrow().contains(&key)) {
+        return write!(f, "<cycle -> {}>", Address(key.0));
+    }
+    VISITING.with(|visiting| visiting.borrow_mut().push(key));
+    let result = fmt(f);
+    VISITING.with(|visiting| visiting.borrow_mut().pop());
+    result
+}
+
+/// Adds the `elements` of a list to `list`, up to the current maximum number
+/// of elements.
+pub(crate) fn entries<I, T>(list: &mut fmt::DebugList<'_, '_>, elements: I) -> fmt::Result
+where
+    I: ExactSizeIterator<Item = Result<T, crate::Error>>,
+    T: fmt::Display,
+{
+    let len = elements.len();
+    let max_elements = OPTIONS.with(|options| options.borrow().max_elements);
+    let shown = max_elements.map_or(len, |max_elements| max_elements.min(len));
+    for elt in elements.take(shown) {
+        list.entry(&crate::DebugDisplay(elt.map_err(crate::fmt_err)?));
+    }
+    if shown < len {
+        list.entry(&crate::DebugDisplay(format_args!("… {} more", len - shown)));
+    }
+    Ok(())
+}
+
+/// The name of `r#type`, as displayed alongside field values; unlike its
+/// [`Display`][fmt::Display], this omits the fields of structs and enums.
+pub(crate) fn type_name<R>(r#type: &crate::schema::Type<'_, R>) -> Result<String, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    use crate::schema::Type;
+    Ok(match r#type {
+        Type::Struct(r#type) => r#type.display_name()?,
+        Type::Enum(r#type) => r#type.display_name()?,
+        // rustc does not name array types.
+        Type::Array(r#type) => {
+            let elt = type_name(&r#type.elt_type()?)?;
+            format!("[{elt}; {}]", r#type.len()?)
+        }
+        r#type => canonical(&r#type.to_string()),
+    })
+}
+
+/// The type name `name`, as `rustc` would display it; default allocators are
+/// elided (e.g., `Vec<u8, alloc::alloc::Global>` becomes `Vec<u8>`), and
+/// closure and coroutine environments are named like their bodies (e.g.,
+/// `{closure_env#0}` becomes `{closure#0}`).
+pub(crate) fn canonical(name: &str) -> String {
+    const ALLOCATOR: &str = ", alloc::alloc::Global";
+    let mut canonical = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(index) = rest.find(ALLOCATOR) {
+        canonical.push_str(&rest[..index]);
+        rest = &rest[index + ALLOCATOR.len()..];
+        // e.g., `GlobalAlloc` is not the default allocator.
+        if !rest.starts_with(['>', ',']) {
+            canonical.push_str(ALLOCATOR);
+        }
+    }
+    canonical.push_str(rest);
+    // identifiers cannot contain `#`, so this only matches environments.
+    canonical.replace("_env#", "#")
+}
+
+/// The name of `r#type`, qualified by its namespaces if it is a struct or enum
+/// (e.g., `core::option::Option<u8>`), so that it is unique.
+pub(crate) fn qualified_type_name<R>(
+    r#type: &crate::schema::Type<'_, R>,
+) -> Result<String, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    use crate::schema::Type;
+    match r#type {
+        Type::Struct(_) | Type::Enum(_) => {
+            let (dwarf, unit, offset) = r#type.die();
+            let mut name = String::new();
+            for namespace in crate::namespace_path_of(dwarf, unit, offset)? {
+                name.push_str(&String::from_utf8_lossy(&namespace));
+                name.push_str("::");
+            }
+            name.push_str(&type_name(r#type)?);
+            Ok(name)
+        }
+        // rustc does not name array types.
+        Type::Array(array) => {
+            let elt = qualified_type_name(&array.elt_type()?)?;
+            Ok(format!("[{elt}; {}]", array.len()?))
+        }
+        r#type => type_name(r#type),
+    }
+}
+
+impl<'value, 'dwarf, P> crate::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Displays this value into `writer`, like [`Display`][fmt::Display], but
+    /// fails on the first error encountered while reading this value or its
+    /// debuginfo.
+    ///
+    /// By contrast, [`Display`][fmt::Display] renders such errors as
+    /// placeholders (e.g., `Foo { a: <error: …> }`), so that displaying a
+    /// malformed value cannot panic.
+    pub fn try_format<W: fmt::Write>(&self, writer: &mut W) -> Result<(), crate::Error> {
+        let outer = STRICT.with(|strict| strict.replace(true));
+        let result = write!(writer, "{self}");
+        STRICT.with(|strict| strict.set(outer));
+        let error = ERROR.with(|error| error.borrow_mut().take());
+        match (result, error) {
+            (Ok(()), _) => Ok(()),
+            (Err(_), Some(error)) => Err(error),
+            (Err(_), None) => bail!("the writer failed"),
+        }
+    }
+
+    /// If this value is an integer, displays it in hexadecimal.
+    pub(crate) fn fmt_hex(&self, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+        use crate::Value;
+        Some(match self {
+            Value::i8(v) => write!(f, "{:#x}", v.value()),
+            Value::i16(v) => write!(f, "{:#x}", v.value()),
+            Value::i32(v) => write!(f, "{:#x}", v.value()),
+            Value::i64(v) => write!(f, "{:#x}", v.value()),
+            Value::i128(v) => write!(f, "{:#x}", v.value()),
+            Value::isize(v) => write!(f, "{:#x}", v.value()),
+            Value::u8(v) => write!(f, "{:#x}", v.value()),
+            Value::u16(v) => write!(f, "{:#x}", v.value()),
+            Value::u32(v) => write!(f, "{:#x}", v.value()),
+            Value::u64(v) => write!(f, "{:#x}", v.value()),
+            Value::u128(v) => write!(f, "{:#x}", v.value()),
+            Value::usize(v) => write!(f, "{:#x}", v.value()),
+            _ => return None,
+        })
+    }
+}
+
+/// The name of the type `name` as `derive(Debug)` displays it; i.e., without
+/// its generic arguments. Closures and coroutines, whose names are not
+/// paths, and `PhantomData`, whose `Debug` includes its argument, are left
+/// as-is.
+pub(crate) fn debug_type_name(name: &str) -> &str {
+    if name.starts_with('{') || name.starts_with("PhantomData<") {
+        return name;
+    }
+    match name.find('<') {
+        Some(generics) => &name[..generics],
+        None => name,
+    }
+}
+
+/// Displays a value with the given [`FormatOptions`]; produced by
+/// [`Value::display_with`][crate::Value::display_with].
+pub struct DisplayWith<T> {
+    value: T,
+    options: FormatOptions,
+}
+
+impl<T> DisplayWith<T> {
+    pub(crate) fn new(value: T, options: FormatOptions) -> Self {
+        Self { value, options }
+    }
+}
+
+impl<T> fmt::Display for DisplayWith<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // nested values are displayed by their own `Display` impls, which
+        // consult the options installed here.
+        let outer = install(self.options.clone());
+        let result = match self.options.max_bytes {
+            Some(max_bytes) => {
+                let mut limited = Limited {
+                    f,
+                    remaining: max_bytes,
+                    truncated: false,
+                };
+                let result = if limited.f.alternate() {
+                    fmt::write(&mut limited, format_args!("{:#}", self.value))
+                } else {
+                    fmt::write(&mut limited, format_args!("{}", self.value))
+                };
+                if limited.truncated {
+                    limited.f.write_str("…")
+                } else {
+                    result
+                }
+            }
+            None => self.value.fmt(f),
+        };
+        uninstall(outer);
+        result
+    }
+}
+
+/// Writes at most `remaining` bytes to `f`.
+struct Limited<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl<'a, 'b> fmt::Write for Limited<'a, 'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.f.write_str(s);
+        }
+        let mut end = self.remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.f.write_str(&s[..end])?;
+        self.remaining = 0;
+        self.truncated = true;
+        // stop formatting the rest of the value.
+        Err(fmt::Error)
+    }
+}
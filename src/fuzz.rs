@@ -0,0 +1,277 @@
+//! Entry points for fuzzing the parsing of DWARF into schemas, and the
+//! reflection of values with those schemas; see the `fuzz` directory of this
+//! crate's source.
+//!
+//! The entry points accept arbitrary sections, and should return errors, but
+//! never panic or read out of bounds, however malformed those sections are.
+
+use crate::gimli::{self, EndianSlice, RunTimeEndian, SectionId, Unit, UnitOffset};
+use crate::schema::Type;
+use std::marker::PhantomData;
+
+/// The reader of fuzzed sections.
+pub type Reader<'input> = EndianSlice<'input, RunTimeEndian>;
+
+/// How many levels of fields, variants and elements are walked into; fuzzed
+/// types may contain themselves.
+const MAX_DEPTH: usize = 8;
+
+/// The largest slice that is reflected; fuzzed types may be arbitrarily large.
+const MAX_SIZE: u64 = 4096;
+
+/// The sections of fuzzed debug info; missing sections are empty.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sections<'input> {
+    /// Whether the sections are little-endian.
+    pub little_endian: bool,
+    /// The `.debug_info` section.
+    pub debug_info: &'input [u8],
+    /// The `.debug_abbrev` section.
+    pub debug_abbrev: &'input [u8],
+    /// The `.debug_str` section.
+    pub debug_str: &'input [u8],
+    /// The `.debug_types` section.
+    pub debug_types: &'input [u8],
+}
+
+/// A provider of no debug info, with which values of fuzzed types are
+/// reflected; those values contain no trait objects or functions, and their
+/// `bool`s and `char`s are validated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FuzzProvider<'input>(PhantomData<&'input [u8]>);
+
+unsafe impl<'input> crate::DebugInfoProvider for FuzzProvider<'input> {
+    type Reader = Reader<'input>;
+
+    fn info_for(&self, fn_addr: u64) -> Result<crate::DebugInfo<'_, Self::Reader>, crate::Error> {
+        Err(crate::error::symbol_not_found(fn_addr as usize))
+    }
+
+    fn validates_bit_patterns(&self) -> bool {
+        true
+    }
+}
+
+/// Parses every entry of `sections` as a [`Type`], and walks the fields,
+/// variants and discriminants of those that are types.
+pub fn schemas(sections: Sections<'_>) -> Result<(), crate::Error> {
+    let dwarf = load(sections)?;
+    for (unit, offset) in entries(dwarf)? {
+        let Ok(r#type) = Type::from_die(dwarf, unit, unit.entry(offset)?) else {
+            continue;
+        };
+        let _ = walk_type(&r#type, 0);
+    }
+    Ok(())
+}
+
+/// Parses every entry of `sections` as a [`Type`], and reflects `bytes` as a
+/// value of each of those types; slices are reflected as pointing to `bytes`.
+///
+/// Values are walked without dereferencing their pointers, whose addresses
+/// are arbitrary.
+pub fn values(sections: Sections<'_>, bytes: &[u8]) -> Result<(), crate::Error> {
+    let dwarf = load(sections)?;
+    let provider = &FuzzProvider(PhantomData);
+    // values are reflected from aligned copies of `bytes`.
+    let mut aligned = vec![0u128; (bytes.len() + 15) / 16];
+    let into = aligned.as_mut_ptr() as *mut u8;
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), into, bytes.len()) };
+    let bytes = std::ptr::slice_from_raw_parts(aligned.as_ptr() as *const crate::Byte, bytes.len());
+    let bytes = unsafe { &*bytes };
+
+    for (unit, offset) in entries(dwarf)? {
+        let Ok(r#type) = Type::from_die(dwarf, unit, unit.entry(offset)?) else {
+            continue;
+        };
+        let _ = match r#type {
+            Type::Slice(schema) => walk_slice(schema, bytes, provider),
+            r#type if matches!(r#type.size(), Ok(size) if size <= bytes.len() as u64) => {
+                // SAFETY: `bytes` are initialized, and values are walked
+                // without dereferencing their pointers.
+                unsafe { crate::Value::with_type(r#type, bytes, provider) }
+                    .and_then(|value| walk_value(&value, 0))
+            }
+            _ => continue,
+        };
+    }
+    Ok(())
+}
+
+/// Loads the DWARF of `sections`.
+fn load(sections: Sections<'_>) -> Result<&gimli::Dwarf<Reader<'_>>, crate::Error> {
+    let endian = match sections.little_endian {
+        true => RunTimeEndian::Little,
+        false => RunTimeEndian::Big,
+    };
+    let dwarf = gimli::Dwarf::load(|id| {
+        let data = match id {
+            SectionId::DebugInfo => sections.debug_info,
+            SectionId::DebugAbbrev => sections.debug_abbrev,
+            SectionId::DebugStr => sections.debug_str,
+            SectionId::DebugTypes => sections.debug_types,
+            _ => &[],
+        };
+        Ok::<_, crate::Error>(EndianSlice::new(data, endian))
+    })?;
+    // what is reflected of the input may outlive this call; so the sections
+    // are leaked, rather than retired and freed.
+    Ok(Box::leak(Box::new(dwarf)))
+}
+
+/// Produces the offsets of all entries of all units of `dwarf`.
+fn entries<'dwarf, 'input>(
+    dwarf: &'dwarf gimli::Dwarf<Reader<'input>>,
+) -> Result<Vec<(&'dwarf Unit<Reader<'input>>, UnitOffset)>, crate::Error> {
+    let mut units = vec![];
+    let mut headers = dwarf.units();
+    while let Some(header) = headers.next()? {
+        units.push(dwarf.unit(header)?);
+    }
+    // units are leaked for the same reason as their sections.
+    let units: &'dwarf [_] = Vec::leak(units);
+    let mut offsets = vec![];
+    for unit in units {
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs()? {
+            offsets.push((unit, entry.offset()));
+        }
+    }
+    Ok(offsets)
+}
+
+/// Walks the fields, variants, discriminants and elements of `r#type`.
+fn walk_type<R>(r#type: &Type<'_, R>, depth: usize) -> Result<(), crate::Error>
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    if depth > MAX_DEPTH {
+        return Ok(());
+    }
+    r#type.size()?;
+    r#type.align()?;
+    match r#type {
+        Type::Struct(schema) => walk_fields(schema.fields()?, depth)?,
+        Type::Enum(schema) => {
+            schema.discriminant_type()?;
+            schema.discriminant_location().clone().address(0)?;
+            let mut variants = schema.variants()?;
+            let mut variants = variants.iter()?;
+            while let Some(variant) = variants.try_next()? {
+                variant.name()?;
+                if let Some(discriminant) = variant.discriminant_value() {
+                    schema.variant_for_discriminant(discriminant.to_u64())?;
+                }
+                walk_fields(variant.fields()?, depth)?;
+            }
+        }
+        Type::Array(schema) => {
+            schema.len()?;
+            walk_type(&schema.elt_type()?, depth + 1)?;
+        }
+        Type::Slice(schema) => walk_type(&schema.elt()?, depth + 1)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Walks the offsets and types of `fields`.
+fn walk_fields<R>(
+    mut fields: crate::schema::Fields<'_, R>,
+    depth: usize,
+) -> Result<(), crate::Error>
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        field.name()?;
+        if !field.is_bitfield()? {
+            field.offset()?.address(0)?;
+        }
+        walk_type(&field.r#type()?, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// Walks the fields, variants and elements of `value`.
+fn walk_value<P>(value: &crate::Value<'_, '_, P>, depth: usize) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    if depth > MAX_DEPTH {
+        return Ok(());
+    }
+    match value {
+        crate::Value::Struct(value) => walk_field_values(value.fields()?, depth)?,
+        crate::Value::Enum(value) => walk_field_values(value.variant()?.fields()?, depth)?,
+        crate::Value::Array(value) => {
+            for element in value.iter()? {
+                walk_value(&element?, depth + 1)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Walks the values of `fields`.
+fn walk_field_values<P>(
+    mut fields: crate::value::Fields<'_, '_, P>,
+    depth: usize,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        if field.schema().is_bitfield()? {
+            field.bits()?;
+        } else {
+            walk_value(&field.value()?, depth + 1)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reflects a slice of the elements in `bytes`, and walks those elements.
+fn walk_slice<'dwarf, 'input>(
+    schema: crate::schema::Slice<'dwarf, Reader<'input>>,
+    bytes: crate::Bytes<'_>,
+    provider: &'dwarf FuzzProvider<'input>,
+) -> Result<(), crate::Error> {
+    let size = schema.size()?;
+    if size > MAX_SIZE {
+        return Ok(());
+    }
+    let elt_size = usize::try_from(schema.elt()?.size()?)?;
+    let length = bytes.len().checked_div(elt_size).unwrap_or(bytes.len());
+
+    // the slice is written at the offsets of its fields in its schema.
+    let mut slice = vec![0u128; (usize::try_from(size)? + 15) / 16];
+    let slice_bytes = slice.len() * 16;
+    let into = slice.as_mut_ptr() as *mut u8;
+    let words = [
+        (schema.data_ptr().offset()?, bytes.as_ptr() as usize),
+        (schema.length().offset()?, length),
+    ];
+    for (offset, word) in words {
+        let offset = usize::try_from(offset.address(0)?)?;
+        if offset.saturating_add(std::mem::size_of::<usize>()) > slice_bytes {
+            return Ok(());
+        }
+        unsafe { (into.add(offset) as *mut usize).write_unaligned(word) };
+    }
+
+    let slice = std::ptr::slice_from_raw_parts(slice.as_ptr() as *const crate::Byte, slice_bytes);
+    // SAFETY: the slice points to `length` initialized elements; its elements
+    // are walked without dereferencing their pointers.
+    let value = unsafe { crate::Value::with_type(Type::Slice(schema), &*slice, provider) }?;
+    let crate::Value::Slice(value) = value else {
+        return Ok(());
+    };
+    for element in value.iter()? {
+        walk_value(&element?, 1)?;
+    }
+    Ok(())
+}
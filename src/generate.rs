@@ -0,0 +1,190 @@
+//! Generation of random values of reflected types; e.g., for property-testing
+//! serializers and formatters of reflected values.
+
+use crate::schema::Type;
+
+impl<'dwarf, R> Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Generates the bytes of a random, valid value of this type, drawing
+    /// randomness from `next_u64`; e.g., from a seeded RNG, or from
+    /// `quickcheck`'s or `proptest`'s generators.
+    ///
+    /// The generated values respect the validity of their leaves: `bool`s
+    /// are `0` or `1`, `char`s are Unicode scalar values, `NonZero`s are
+    /// non-zero, and each enum is of a uniformly chosen variant, with its
+    /// discriminant; so that niche-optimized enums are valid, too. Padding and
+    /// the contents of `MaybeUninit`s are random.
+    ///
+    /// Fails for types containing references, boxes, slices, trait objects,
+    /// `NonNull`s or functions, whose values cannot be generated.
+    pub fn generate_bytes(
+        &self,
+        next_u64: &mut dyn FnMut() -> u64,
+    ) -> Result<Vec<u8>, crate::Error> {
+        let size = usize::try_from(self.size()?)?;
+        let mut bytes = vec![0; size];
+        fill_random(&mut bytes, next_u64);
+        generate(self, &mut bytes, next_u64)?;
+        Ok(bytes)
+    }
+}
+
+/// Fills `bytes` with random bytes.
+fn fill_random(bytes: &mut [u8], next_u64: &mut dyn FnMut() -> u64) {
+    for chunk in bytes.chunks_mut(8) {
+        let random = next_u64().to_le_bytes();
+        chunk.copy_from_slice(&random[..chunk.len()]);
+    }
+}
+
+/// Overwrites the leaves of the random `bytes` of a value of `r#type` with
+/// valid values.
+fn generate<R>(
+    r#type: &Type<'_, R>,
+    bytes: &mut [u8],
+    next_u64: &mut dyn FnMut() -> u64,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let size = usize::try_from(r#type.size()?)?;
+    if bytes.len() < size {
+        return Err(crate::error::size_mismatch(size, bytes.len()));
+    }
+    let bytes = &mut bytes[..size];
+    match r#type {
+        Type::bool(_) => bytes[0] = (next_u64() & 1) as u8,
+        Type::char(_) => {
+            let c = loop {
+                if let Some(c) = char::from_u32((next_u64() % 0x11_0000) as u32) {
+                    break c;
+                }
+            };
+            bytes.copy_from_slice(&(c as u32).to_ne_bytes());
+        }
+        Type::f32(_)
+        | Type::f64(_)
+        | Type::i8(_)
+        | Type::i16(_)
+        | Type::i32(_)
+        | Type::i64(_)
+        | Type::i128(_)
+        | Type::isize(_)
+        | Type::u8(_)
+        | Type::u16(_)
+        | Type::u32(_)
+        | Type::u64(_)
+        | Type::u128(_)
+        | Type::usize(_)
+        | Type::unit(_)
+        | Type::ConstPtr(_)
+        | Type::MutPtr(_)
+        | Type::MaybeUninit(_) => {}
+        Type::Array(schema) => {
+            generate_elements(&schema.elt_type()?, schema.len()?, bytes, next_u64)?
+        }
+        Type::Vector(schema) => {
+            generate_elements(&schema.lane_type()?, schema.lane_count()?, bytes, next_u64)?
+        }
+        Type::NonZero(schema) => {
+            generate(&schema.value_type()?, bytes, next_u64)?;
+            if bytes.iter().all(|byte| *byte == 0) {
+                bytes[0] = 1;
+            }
+        }
+        Type::ManuallyDrop(schema) => generate(&schema.value_type()?, bytes, next_u64)?,
+        Type::Atomic(schema) if schema.is_bool()? => bytes[0] = (next_u64() & 1) as u8,
+        Type::Atomic(_) => {}
+        Type::Struct(schema) => generate_fields(schema.fields()?, bytes, next_u64)?,
+        Type::Enum(schema) => {
+            let count = schema.variant_count()?;
+            if count == 0 {
+                bail!("`{}` has no variants, and so no values", r#type);
+            }
+            let chosen = (next_u64() % count as u64) as usize;
+            let mut variants = schema.variants()?;
+            let mut variants = variants.iter()?;
+            let mut index = 0;
+            while let Some(variant) = variants.try_next()? {
+                if index != chosen {
+                    index += 1;
+                    continue;
+                }
+                generate_fields(variant.fields()?, bytes, next_u64)?;
+                // the discriminant of a tagged variant does not overlap its
+                // fields, and that of a niche variant overwrites its niche.
+                if let Some(discriminant) = variant.discriminant_value() {
+                    let offset = schema.discriminant_location().clone().address(0)?;
+                    let offset = usize::try_from(offset)?;
+                    let len = usize::try_from(schema.discriminant_type()?.size()?)?;
+                    let discriminant = discriminant.to_u64().to_ne_bytes();
+                    let discriminant = if cfg!(target_endian = "little") {
+                        &discriminant[..len]
+                    } else {
+                        &discriminant[discriminant.len() - len..]
+                    };
+                    bytes[offset..][..len].copy_from_slice(discriminant);
+                }
+                break;
+            }
+        }
+        Type::Box(_)
+        | Type::BoxedDyn(_)
+        | Type::BoxedSlice(_)
+        | Type::DynRef(_)
+        | Type::SharedRef(_)
+        | Type::UniqueRef(_)
+        | Type::Slice(_)
+        | Type::str(_)
+        | Type::NonNull(_)
+        | Type::Function(_) => bail!("cannot generate values of `{}`", r#type),
+    }
+    Ok(())
+}
+
+/// Overwrites the random `bytes` of `len` elements of `elt_type` with valid
+/// values.
+fn generate_elements<R>(
+    elt_type: &Type<'_, R>,
+    len: u64,
+    bytes: &mut [u8],
+    next_u64: &mut dyn FnMut() -> u64,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let elt_size = usize::try_from(elt_type.size()?)?;
+    if elt_size == 0 {
+        return Ok(());
+    }
+    for element in bytes.chunks_mut(elt_size).take(usize::try_from(len)?) {
+        generate(elt_type, element, next_u64)?;
+    }
+    Ok(())
+}
+
+/// Overwrites the random `bytes` of `fields` with valid values; bitfields
+/// are left random.
+fn generate_fields<R>(
+    mut fields: crate::schema::Fields<'_, R>,
+    bytes: &mut [u8],
+    next_u64: &mut dyn FnMut() -> u64,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        if field.is_bitfield()? {
+            continue;
+        }
+        let offset = usize::try_from(field.offset()?.address(0)?)?;
+        if offset > bytes.len() {
+            return Err(crate::error::size_mismatch(offset, bytes.len()));
+        }
+        generate(&field.r#type()?, &mut bytes[offset..], next_u64)?;
+    }
+    Ok(())
+}
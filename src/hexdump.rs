@@ -0,0 +1,248 @@
+//! Annotated hex dumps of the memory of reflected values.
+
+use std::fmt;
+
+use crate::{value::Path, Value};
+
+/// The number of bytes displayed per line of a [`Hexdump`].
+const BYTES_PER_LINE: usize = 8;
+
+/// The bytes of a value, each annotated with the path of the field it belongs
+/// to, or as padding; produced by [`Value::hexdump`].
+///
+/// Displayed as a hex view, with each run of bytes that belong to the same
+/// field on lines of its own; e.g.:
+///
+/// ```text
+/// Header (16 bytes)
+/// 0000  2a 00 00 00 00 00 00 00  id
+/// 0008  01 00                    flags
+/// 000a  .. .. .. .. .. ..        <padding>
+/// ```
+///
+/// Padding may be uninitialized, and so is not read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hexdump {
+    name: String,
+    /// The bytes of the value; `None`, if the byte is padding, or may be
+    /// uninitialized.
+    bytes: Vec<Option<u8>>,
+    /// The index in `paths` of the annotation of each byte; `None`, if the
+    /// byte is padding.
+    annotations: Vec<Option<usize>>,
+    paths: Vec<String>,
+}
+
+impl Hexdump {
+    /// The name of the type of the dumped value.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The size of the dumped value, in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the dumped value is zero-sized.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The byte at `offset`; `None`, if it is padding or the contents of a
+    /// `MaybeUninit` (either of which may be uninitialized), or if `offset` is
+    /// out of bounds.
+    pub fn byte(&self, offset: usize) -> Option<u8> {
+        *self.bytes.get(offset)?
+    }
+
+    /// The path of the field that the byte at `offset` belongs to (e.g.,
+    /// `header.flags`, or `state.<discriminant>`); `None`, if the byte is
+    /// padding, or `offset` is out of bounds.
+    ///
+    /// Fields that are not structs, enums or arrays (e.g., references, or
+    /// integers) are not further subdivided; a value that is not itself
+    /// subdivided is annotated with the name of its type.
+    pub fn path_of(&self, offset: usize) -> Option<&str> {
+        let index = (*self.annotations.get(offset)?)?;
+        Some(&self.paths[index])
+    }
+
+    /// Whether the byte at `offset` is padding; i.e., belongs to no field.
+    pub fn is_padding(&self, offset: usize) -> bool {
+        matches!(self.annotations.get(offset), Some(None))
+    }
+
+    /// Annotates the `len` bytes from `offset` that are not yet annotated
+    /// with `path`; and, if they are `init`ialized, reads them from `raw`.
+    fn annotate(
+        &mut self,
+        raw: crate::Bytes<'_>,
+        offset: usize,
+        len: usize,
+        path: &Path,
+        init: bool,
+    ) {
+        let end = offset.saturating_add(len).min(self.annotations.len());
+        if offset >= end {
+            return;
+        }
+        let path = if path.is_root() {
+            self.name.clone()
+        } else {
+            path.to_string()
+        };
+        let index = self.paths.len();
+        let mut used = false;
+        let bytes = self.bytes[offset..end].iter_mut().zip(&raw[offset..end]);
+        for (annotation, (byte, raw)) in self.annotations[offset..end].iter_mut().zip(bytes) {
+            if annotation.is_none() {
+                *annotation = Some(index);
+                if init {
+                    // SAFETY: the byte belongs to a field, rather than to
+                    // padding, and so is initialized.
+                    *byte = Some(unsafe { raw.assume_init() });
+                }
+                used = true;
+            }
+        }
+        if used {
+            self.paths.push(path);
+        }
+    }
+
+    /// Annotates the bytes of `value`, which is at `path` within the dumped
+    /// value of the bytes `raw`.
+    fn walk<P>(
+        &mut self,
+        raw: crate::Bytes<'_>,
+        path: &Path,
+        value: &Value<'_, '_, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let start = value.bytes().as_ptr() as usize;
+        let offset = start.wrapping_sub(raw.as_ptr() as usize);
+        match value {
+            Value::Array(v) => {
+                for (index, element) in v.iter()?.enumerate() {
+                    self.walk(raw, &path.index(index), &element?)?;
+                }
+            }
+            Value::Vector(v) => {
+                for (index, element) in v.iter()?.enumerate() {
+                    self.walk(raw, &path.index(index), &element?)?;
+                }
+            }
+            Value::NonZero(v) => self.walk(raw, path, &v.value()?)?,
+            Value::ManuallyDrop(v) => self.walk(raw, path, &v.value()?)?,
+            Value::Struct(v) => self.walk_fields(raw, start, path, v.fields()?)?,
+            Value::Enum(v) => {
+                let variant = v.variant()?;
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                let fields = variant.fields()?;
+                self.walk_fields(raw, start, &path.variant(name), fields)?;
+                // niche-encoded discriminants overlap a field, and so are
+                // annotated only where they do not.
+                let schema = v.schema();
+                let discriminant = schema.discriminant_location().clone().address(0)?;
+                let discriminant = usize::try_from(discriminant)?;
+                let size = usize::try_from(schema.discriminant_type()?.size()?)?;
+                let discriminant_path = path.field("<discriminant>".to_owned());
+                self.annotate(raw, offset + discriminant, size, &discriminant_path, true);
+            }
+            _ => {
+                let size = usize::try_from(value.r#type().size()?)?;
+                let init = !matches!(value, Value::MaybeUninit(_));
+                self.annotate(raw, offset, size, path, init);
+            }
+        }
+        Ok(())
+    }
+
+    /// Annotates the bytes of `fields`, which are at `path` within the dumped
+    /// value of the bytes `raw`, and belong to a value at the address `start`.
+    fn walk_fields<P>(
+        &mut self,
+        raw: crate::Bytes<'_>,
+        start: usize,
+        path: &Path,
+        mut fields: crate::value::Fields<'_, '_, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            let schema = field.schema();
+            let name = schema.name()?.to_string_lossy()?.into_owned();
+            let path = path.field(name);
+            // bitfields are annotated by the bytes their bits occupy.
+            if let (Some(bit_offset), Some(bit_size)) = (schema.bit_offset()?, schema.bit_size()?) {
+                let first = usize::try_from(bit_offset / 8)?;
+                let last = usize::try_from((bit_offset + bit_size + 7) / 8)?;
+                let offset = start.wrapping_sub(raw.as_ptr() as usize) + first;
+                self.annotate(raw, offset, last - first, &path, true);
+                continue;
+            }
+            self.walk(raw, &path, &field.value()?)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Hexdump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} bytes)", self.name, self.bytes.len())?;
+        let mut offset = 0;
+        while offset < self.bytes.len() {
+            let annotation = self.annotations[offset];
+            let run = self.annotations[offset..]
+                .iter()
+                .take_while(|other| **other == annotation)
+                .count();
+            for line in (offset..offset + run).step_by(BYTES_PER_LINE) {
+                let end = (line + BYTES_PER_LINE).min(offset + run);
+                write!(f, "{line:04x} ")?;
+                for byte in &self.bytes[line..end] {
+                    match byte {
+                        Some(byte) => write!(f, " {byte:02x}")?,
+                        None => f.write_str(" ..")?,
+                    }
+                }
+                let fill = 3 * (BYTES_PER_LINE - (end - line));
+                match annotation {
+                    Some(index) => writeln!(f, "{:fill$}  {}", "", self.paths[index])?,
+                    None => writeln!(f, "{:fill$}  <padding>", "")?,
+                }
+            }
+            offset += run;
+        }
+        Ok(())
+    }
+}
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// A hex dump of the bytes of this value, with each byte annotated by the
+    /// path of the field it belongs to (e.g., `header.flags`), or as padding.
+    ///
+    /// Structs, enums and arrays are subdivided into their fields, active
+    /// variant and elements; the discriminant of an enum is annotated as
+    /// `<discriminant>`. Pointers are not followed; the dump covers only the
+    /// bytes of this value.
+    pub fn hexdump(&self) -> Result<Hexdump, crate::Error> {
+        let raw = self.bytes();
+        let mut hexdump = Hexdump {
+            name: crate::format::type_name(&self.r#type())?,
+            bytes: vec![None; raw.len()],
+            annotations: vec![None; raw.len()],
+            paths: vec![],
+        };
+        hexdump.walk(raw, &Path::default(), self)?;
+        Ok(hexdump)
+    }
+}
@@ -0,0 +1,149 @@
+//! An HTTP endpoint that serves the [registry][crate::registry] of live
+//! objects as JSON.
+
+use std::fmt;
+
+use ::http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use serde_json::json;
+
+use crate::Reflect;
+
+/// The path under which [`handle`] serves the registry.
+const PREFIX: &str = "/debug/values";
+
+/// Serves the [registry][crate::registry] of live objects as JSON; e.g., for
+/// introspecting a live service with `curl`.
+///
+/// - `GET /debug/values` lists the registered objects, as an array of their
+///   names and types (e.g., `[{"name": "state", "type": "State"}]`); the
+///   types of objects that cannot be reflected are listed as `<error: …>`.
+/// - `GET /debug/values/{name}` produces the [JSON
+///   conversion][crate::Value::to_json] of the object registered under
+///   `name`.
+/// - `GET /debug/values/{name}?path=a.b[0]` produces that of the value at the
+///   given [path][crate::Value::get_path] within it.
+///
+/// Errors are reported with the appropriate status code, and a body like
+/// `{"error": "…"}`. The handler is independent of any server; e.g., with
+/// `axum`, it can be routed as
+/// `any(|request: Request<Body>| async move { deflect::http::handle(&request) })`,
+/// and with `hyper`, its response's body converted with
+/// `response.map(Full::from)`.
+pub fn handle<B>(request: &Request<B>) -> Response<String> {
+    match route(request) {
+        Ok(body) => respond(StatusCode::OK, body),
+        Err((status, message)) => respond(status, json!({ "error": message })),
+    }
+}
+
+/// The JSON response to `request`, or the status and message of its error.
+fn route<B>(request: &Request<B>) -> Result<serde_json::Value, (StatusCode, String)> {
+    let not_found = || (StatusCode::NOT_FOUND, "not found".to_owned());
+    let rest = request
+        .uri()
+        .path()
+        .strip_prefix(PREFIX)
+        .ok_or_else(not_found)?;
+    if request.method() != Method::GET {
+        let message = format!("method {} is not allowed", request.method());
+        return Err((StatusCode::METHOD_NOT_ALLOWED, message));
+    }
+    let internal = |err: crate::Error| (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"));
+    let provider = crate::default_provider().map_err(internal)?;
+
+    let name = match rest.strip_prefix('/') {
+        None if !rest.is_empty() => return Err(not_found()),
+        None | Some("") => {
+            let mut entries = vec![];
+            for (name, registered) in crate::registry().objects() {
+                let r#type = TypeName(registered.object(), &provider).to_string();
+                entries.push(json!({ "name": name, "type": r#type }));
+            }
+            return Ok(entries.into());
+        }
+        Some(name) => percent_decode(name)?,
+    };
+
+    let registered = crate::registry().get(&name).ok_or_else(|| {
+        let message = format!("no object is registered under {name:?}");
+        (StatusCode::NOT_FOUND, message)
+    })?;
+    let object: &dyn Reflect = registered.object();
+    let value = object.reflect(&provider).map_err(internal)?;
+    let query = request.uri().query().unwrap_or_default();
+    let path = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("path="))
+        .map(|path| percent_decode(&path.replace('+', " ")))
+        .transpose()?;
+    match path {
+        Some(path) if !path.is_empty() => {
+            let value = value
+                .get_path(&path)
+                .map_err(|err| (StatusCode::BAD_REQUEST, format!("{err:#}")))?
+                .ok_or_else(|| {
+                    let message = format!("the path {path:?} does not resolve in {name:?}");
+                    (StatusCode::NOT_FOUND, message)
+                })?;
+            value.to_json().map_err(internal)
+        }
+        _ => value.to_json().map_err(internal),
+    }
+}
+
+/// Displays the type of a registered object; or, if it cannot be reflected,
+/// the error of reflecting it.
+struct TypeName<'a, P>(&'a dyn Reflect, &'a P);
+
+impl<P> fmt::Display for TypeName<'_, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::format::recover(f, |f| {
+            let value = self.0.reflect(self.1).map_err(crate::fmt_err)?;
+            let r#type = crate::format::type_name(&value.r#type()).map_err(crate::fmt_err)?;
+            f.write_str(&r#type)
+        })
+    }
+}
+
+/// A JSON response of `body`, with `status`.
+fn respond(status: StatusCode, body: serde_json::Value) -> Response<String> {
+    let mut response = Response::new(body.to_string());
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// Decodes the percent-encoded `encoded`; e.g., `a.b%5B0%5D` as `a.b[0]`.
+///
+/// In queries, where `+` encodes a space, `+`s must be replaced beforehand.
+fn percent_decode(encoded: &str) -> Result<String, (StatusCode, String)> {
+    let invalid = || {
+        let message = format!("invalid percent-encoding in {encoded:?}");
+        (StatusCode::BAD_REQUEST, message)
+    };
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+        let hex = [
+            bytes.next().ok_or_else(invalid)?,
+            bytes.next().ok_or_else(invalid)?,
+        ];
+        // `from_str_radix` also accepts a leading sign; e.g., `%+1`.
+        if !hex.iter().all(u8::is_ascii_hexdigit) {
+            return Err(invalid());
+        }
+        let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+        decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+    }
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
@@ -0,0 +1,495 @@
+//! Accelerated lookups of debuginfo entries by name, with the `.debug_names`
+//! and `.debug_pubtypes` indexes of a binary.
+//!
+//! Units that are not described by an index (e.g., those of a precompiled
+//! standard library built without them) must still be scanned. With the
+//! `rayon` feature, the names of the types of every unit are instead indexed
+//! across threads, in the background. Either way, the names of all types may
+//! be persisted across runs; see [`crate::index_cache`].
+
+use crate::gimli::{
+    DebugInfoOffset, DebugStr, DebugStrOffset, DwForm, DwTag, EndianSlice, Format, Reader,
+    RunTimeEndian, UnitOffset,
+};
+use anyhow::bail;
+use std::collections::{HashMap, HashSet};
+
+type Slice = EndianSlice<'static, RunTimeEndian>;
+
+type Object = crate::object::File<'static, &'static [u8]>;
+
+type SymbolMap = crate::object::SymbolMap<crate::object::SymbolMapName<'static>>;
+
+/// The fully-qualified names of the types defined by a binary, with the
+/// offsets of the first unit and entry defining each.
+pub(crate) type TypeNames = HashMap<Vec<u8>, (DebugInfoOffset, UnitOffset)>;
+
+/// The [`TypeNames`] of a binary, once they have been built.
+#[cfg(feature = "rayon")]
+type PendingTypeNames = once_cell::sync::OnceCell<Result<TypeNames, crate::Error>>;
+
+/// `DW_IDX_compile_unit`; the index of the entry's unit in the CU list.
+const DW_IDX_COMPILE_UNIT: u64 = 1;
+/// `DW_IDX_type_unit`; the index of the entry's type unit.
+const DW_IDX_TYPE_UNIT: u64 = 2;
+/// `DW_IDX_die_offset`; the offset of the entry, within its unit.
+const DW_IDX_DIE_OFFSET: u64 = 3;
+
+/// A debuginfo entry found in an index.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Entry {
+    /// The offset of the entry's unit, within `.debug_info`.
+    pub(crate) unit: DebugInfoOffset,
+    /// The offset of the entry, within its unit.
+    pub(crate) offset: UnitOffset,
+    /// The tag of the entry, if recorded by the index.
+    pub(crate) tag: Option<DwTag>,
+}
+
+/// The name indexes of a binary.
+pub(crate) struct Indexes {
+    names: Vec<NameIndex>,
+    pubtypes: Option<crate::gimli::DebugPubTypes<Slice>>,
+    debug_str: DebugStr<Slice>,
+    /// The units described by `names` or `pubtypes`.
+    covered: HashSet<DebugInfoOffset>,
+    /// The symbols of `object`, read once they are first needed.
+    symbols: once_cell::sync::OnceCell<SymbolMap>,
+    object: &'static Object,
+    /// The [`TypeNames`] of `object`, loaded from or saved to the index cache.
+    persisted: once_cell::sync::OnceCell<TypeNames>,
+    #[cfg(feature = "rayon")]
+    type_names: &'static PendingTypeNames,
+}
+
+impl Indexes {
+    /// Reads the name indexes of `object`, if any.
+    pub(crate) fn new(object: &'static Object) -> Result<Self, crate::Error> {
+        let section = |name| section(object, name);
+
+        let mut names = vec![];
+        let mut covered = HashSet::new();
+        let mut debug_names = section(".debug_names")?;
+        while !debug_names.is_empty() {
+            let index = NameIndex::parse(&mut debug_names)?;
+            for i in 0..index.comp_unit_count {
+                covered.insert(index.comp_unit(i)?);
+            }
+            names.push(index);
+        }
+
+        let debug_pubtypes = section(".debug_pubtypes")?;
+        let pubtypes = if debug_pubtypes.is_empty() {
+            None
+        } else {
+            let pubtypes = crate::gimli::DebugPubTypes::from(debug_pubtypes);
+            let mut items = pubtypes.items();
+            while let Some(item) = items.next()? {
+                covered.insert(item.unit_header_offset());
+            }
+            Some(pubtypes)
+        };
+
+        Ok(Self {
+            names,
+            pubtypes,
+            debug_str: DebugStr::from(section(".debug_str")?),
+            covered,
+            symbols: once_cell::sync::OnceCell::new(),
+            object,
+            persisted: once_cell::sync::OnceCell::new(),
+            #[cfg(feature = "rayon")]
+            type_names: type_names_of(object),
+        })
+    }
+
+    /// The [`TypeNames`] of the binary, read from the index cache if they
+    /// were saved by a previous run; otherwise, they are built and saved.
+    pub(crate) fn persisted_type_names(&self) -> Result<&TypeNames, crate::Error> {
+        self.persisted.get_or_try_init(|| {
+            let path = crate::index_cache::path_of(self.object)?;
+            if let Some(names) = path.as_deref().and_then(crate::index_cache::read) {
+                return Ok(names);
+            }
+            #[cfg(feature = "rayon")]
+            let names = match self.type_names.wait() {
+                Ok(names) => names.clone(),
+                Err(_) => build_type_names(self.object)?,
+            };
+            #[cfg(not(feature = "rayon"))]
+            let names = build_type_names(self.object)?;
+            if let Some(path) = path {
+                // the cache is an optimization; failing to save it is benign.
+                let _ = crate::index_cache::write(&path, &names);
+            }
+            Ok(names)
+        })
+    }
+
+    /// The [`TypeNames`] of the binary, unless they could not be built;
+    /// blocks until they have been.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn type_names(&self) -> Option<&TypeNames> {
+        self.type_names.wait().as_ref().ok()
+    }
+
+    /// Whether the binary has a `.debug_names` index.
+    pub(crate) fn has_names(&self) -> bool {
+        !self.names.is_empty()
+    }
+
+    /// Whether the types of the unit at `unit` are indexed.
+    pub(crate) fn covers(&self, unit: DebugInfoOffset) -> bool {
+        self.covered.contains(&unit)
+    }
+
+    /// The name of the symbol at the static address `addr`, if any.
+    pub(crate) fn symbol_at(&self, addr: u64) -> Option<&'static str> {
+        self.symbols
+            .get_or_init(|| crate::object::Object::symbol_map(self.object))
+            .get(addr)
+            .filter(|symbol| symbol.address() == addr)
+            .map(|symbol| symbol.name())
+    }
+
+    /// The indexed entries that may be the type with the fully-qualified
+    /// `name`; `.debug_names` indexes types by their unqualified names, so
+    /// candidates must be checked against `name`.
+    pub(crate) fn types_named(&self, name: &str) -> Result<Vec<Entry>, crate::Error> {
+        let mut entries = vec![];
+        let unqualified = unqualified(name);
+        for index in &self.names {
+            index.find(&self.debug_str, unqualified.as_bytes(), &mut entries)?;
+        }
+        if let Some(pubtypes) = &self.pubtypes {
+            let mut items = pubtypes.items();
+            while let Some(item) = items.next()? {
+                if item.name().slice() == name.as_bytes() {
+                    entries.push(Entry {
+                        unit: item.unit_header_offset(),
+                        offset: item.die_offset(),
+                        tag: None,
+                    });
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// The indexed subprograms with the linkage name `name`.
+    pub(crate) fn subprograms_named(&self, name: &str) -> Result<Vec<Entry>, crate::Error> {
+        let mut entries = vec![];
+        for index in &self.names {
+            index.find(&self.debug_str, name.as_bytes(), &mut entries)?;
+        }
+        entries.retain(|entry| entry.tag == Some(crate::gimli::DW_TAG_subprogram));
+        Ok(entries)
+    }
+}
+
+/// The data of the section of `object` named `name`; empty, if there is no
+/// such section.
+pub(crate) fn section(object: &'static Object, name: &str) -> Result<Slice, crate::Error> {
+    use crate::object::{Object, ObjectSection};
+
+    let endian = if object.is_little_endian() {
+        RunTimeEndian::Little
+    } else {
+        RunTimeEndian::Big
+    };
+    let data = match object.section_by_name(name) {
+        Some(section) => match section.uncompressed_data()? {
+            std::borrow::Cow::Borrowed(data) => data,
+            // sections are leaked so that, like the object, they live for the
+            // rest of the program.
+            std::borrow::Cow::Owned(data) => Vec::leak(data),
+        },
+        None => &[],
+    };
+    Ok(EndianSlice::new(data, endian))
+}
+
+/// Starts building the [`TypeNames`] of `object` across threads, unless they
+/// are already being built.
+#[cfg(feature = "rayon")]
+fn type_names_of(object: &'static Object) -> &'static PendingTypeNames {
+    static PENDING: once_cell::sync::Lazy<dashmap::DashMap<usize, &'static PendingTypeNames>> =
+        once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+    let key = object as *const Object as usize;
+    *PENDING.entry(key).or_insert_with(|| {
+        let pending: &'static PendingTypeNames = Box::leak(Box::default());
+        rayon::spawn(move || {
+            let _ = pending.set(build_type_names(object));
+        });
+        pending
+    })
+}
+
+/// Builds the [`TypeNames`] of `object`, scanning its units (in parallel,
+/// with `rayon`).
+fn build_type_names(object: &'static Object) -> Result<TypeNames, crate::Error> {
+    #[cfg(feature = "rayon")]
+    use rayon::prelude::*;
+
+    let dwarf = crate::gimli::Dwarf::load(|id| section(object, id.name()))?;
+    let mut headers = vec![];
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        headers.push(header);
+    }
+    #[cfg(feature = "rayon")]
+    let headers = headers.into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let headers = headers.into_iter();
+    let types: Result<Vec<_>, crate::Error> = headers
+        .map(|header| {
+            let mut types = vec![];
+            let Some(unit_offset) = header.offset().as_debug_info_offset() else {
+                return Ok(types);
+            };
+            let unit = dwarf.unit(header)?;
+            crate::types_in(&dwarf, &unit, |name, offset| {
+                types.push((name.to_owned(), (unit_offset, offset)));
+                std::ops::ControlFlow::Continue(())
+            })?;
+            Ok(types)
+        })
+        .collect();
+    // as when scanning, the first unit defining a type is found.
+    let mut names = TypeNames::new();
+    for (name, found) in types?.into_iter().flatten() {
+        names.entry(name).or_insert(found);
+    }
+    Ok(names)
+}
+
+/// The last segment of the path `name`; e.g., `Vec<alloc::string::String>`
+/// of `alloc::vec::Vec<alloc::string::String>`.
+fn unqualified(name: &str) -> &str {
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut prev = None;
+    for (i, c) in name.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            // the `>` of `->` does not close a bracket.
+            '>' if prev == Some('-') => {}
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 && prev == Some(':') => start = i + 1,
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    &name[start..]
+}
+
+/// An abbreviation of the entries of a [`NameIndex`].
+struct Abbreviation {
+    tag: DwTag,
+    attributes: Vec<(u64, DwForm)>,
+}
+
+/// A name index of the `.debug_names` section.
+struct NameIndex {
+    format: Format,
+    comp_unit_count: usize,
+    bucket_count: usize,
+    name_count: usize,
+    comp_units: Slice,
+    buckets: Slice,
+    hashes: Slice,
+    string_offsets: Slice,
+    entry_offsets: Slice,
+    abbreviations: HashMap<u64, Abbreviation>,
+    entry_pool: Slice,
+}
+
+impl NameIndex {
+    /// Parses the name index at the start of `input`.
+    fn parse(input: &mut Slice) -> Result<Self, crate::Error> {
+        let (length, format) = input.read_initial_length()?;
+        let mut input = input.split(length)?;
+        let version = input.read_u16()?;
+        if version != 5 {
+            bail!("unsupported `.debug_names` version {version}");
+        }
+        let _padding = input.read_u16()?;
+        let comp_unit_count = input.read_u32()? as usize;
+        let local_type_unit_count = input.read_u32()? as usize;
+        let foreign_type_unit_count = input.read_u32()? as usize;
+        let bucket_count = input.read_u32()? as usize;
+        let name_count = input.read_u32()? as usize;
+        let abbreviation_table_size = input.read_u32()? as usize;
+        let augmentation_string_size = input.read_u32()? as usize;
+        input.skip(augmentation_string_size)?;
+
+        let offset_size = format.word_size() as usize;
+        let comp_units = input.split(comp_unit_count * offset_size)?;
+        input.skip(local_type_unit_count * offset_size + foreign_type_unit_count * 8)?;
+        let buckets = input.split(bucket_count * 4)?;
+        let hashes = input.split(if bucket_count == 0 { 0 } else { name_count * 4 })?;
+        let string_offsets = input.split(name_count * offset_size)?;
+        let entry_offsets = input.split(name_count * offset_size)?;
+
+        let mut table = input.split(abbreviation_table_size)?;
+        let mut abbreviations = HashMap::new();
+        loop {
+            let code = table.read_uleb128()?;
+            if code == 0 {
+                break;
+            }
+            let tag = DwTag(table.read_uleb128_u16()?);
+            let mut attributes = vec![];
+            loop {
+                let index = table.read_uleb128()?;
+                let form = DwForm(table.read_uleb128_u16()?);
+                if index == 0 && form.0 == 0 {
+                    break;
+                }
+                attributes.push((index, form));
+            }
+            abbreviations.insert(code, Abbreviation { tag, attributes });
+        }
+
+        Ok(Self {
+            format,
+            comp_unit_count,
+            bucket_count,
+            name_count,
+            comp_units,
+            buckets,
+            hashes,
+            string_offsets,
+            entry_offsets,
+            abbreviations,
+            entry_pool: input,
+        })
+    }
+
+    /// The offset of the `i`th unit of this index.
+    fn comp_unit(&self, i: usize) -> Result<DebugInfoOffset, crate::Error> {
+        let mut comp_units = self.comp_units;
+        comp_units.skip(i * self.format.word_size() as usize)?;
+        Ok(DebugInfoOffset(comp_units.read_offset(self.format)?))
+    }
+
+    /// Appends the entries named `name` to `entries`.
+    fn find(
+        &self,
+        debug_str: &DebugStr<Slice>,
+        name: &[u8],
+        entries: &mut Vec<Entry>,
+    ) -> Result<(), crate::Error> {
+        let offset_size = self.format.word_size() as usize;
+        let mut visit = |i: usize| -> Result<(), crate::Error> {
+            let mut string_offset = self.string_offsets;
+            string_offset.skip(i * offset_size)?;
+            let string_offset = DebugStrOffset(string_offset.read_offset(self.format)?);
+            if debug_str.get_str(string_offset)?.slice() != name {
+                return Ok(());
+            }
+            let mut entry_offset = self.entry_offsets;
+            entry_offset.skip(i * offset_size)?;
+            let entry_offset = entry_offset.read_offset(self.format)?;
+            self.read_entries(entry_offset, entries)
+        };
+
+        // names are hashed as lowercase; names that are not ASCII are folded
+        // by Unicode rules, so those are found by comparing every name.
+        if self.bucket_count == 0 || !name.is_ascii() {
+            for i in 0..self.name_count {
+                visit(i)?;
+            }
+            return Ok(());
+        }
+        let hash = name.iter().fold(5381u32, |hash, byte| {
+            hash.wrapping_mul(33)
+                .wrapping_add(byte.to_ascii_lowercase() as u32)
+        });
+        let bucket = hash as usize % self.bucket_count;
+        let mut buckets = self.buckets;
+        buckets.skip(bucket * 4)?;
+        let start = buckets.read_u32()? as usize;
+        if start == 0 {
+            return Ok(());
+        }
+        let mut hashes = self.hashes;
+        hashes.skip((start - 1) * 4)?;
+        for i in start - 1..self.name_count {
+            let other = hashes.read_u32()?;
+            if other as usize % self.bucket_count != bucket {
+                break;
+            }
+            if other == hash {
+                visit(i)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the series of entries at `offset` in the entry pool to
+    /// `entries`.
+    fn read_entries(&self, offset: usize, entries: &mut Vec<Entry>) -> Result<(), crate::Error> {
+        let mut pool = self.entry_pool;
+        pool.skip(offset)?;
+        loop {
+            let code = pool.read_uleb128()?;
+            if code == 0 {
+                return Ok(());
+            }
+            let Some(abbreviation) = self.abbreviations.get(&code) else {
+                bail!("unknown `.debug_names` abbreviation {code}")
+            };
+            let mut comp_unit = None;
+            let mut type_unit = None;
+            let mut die_offset = None;
+            for &(index, form) in &abbreviation.attributes {
+                let value = read_form(&mut pool, form)?;
+                match index {
+                    DW_IDX_COMPILE_UNIT => comp_unit = value,
+                    DW_IDX_TYPE_UNIT => type_unit = value,
+                    DW_IDX_DIE_OFFSET => die_offset = value,
+                    _ => {}
+                }
+            }
+            // entries of type units are not supported.
+            if type_unit.is_some() {
+                continue;
+            }
+            // an index of a single unit may omit `DW_IDX_compile_unit`.
+            let comp_unit = match comp_unit {
+                Some(comp_unit) => comp_unit as usize,
+                None if self.comp_unit_count == 1 => 0,
+                None => continue,
+            };
+            let Some(die_offset) = die_offset else { continue };
+            if comp_unit >= self.comp_unit_count {
+                bail!("`.debug_names` entry refers to unit {comp_unit}, which is not indexed");
+            }
+            entries.push(Entry {
+                unit: self.comp_unit(comp_unit)?,
+                offset: UnitOffset(die_offset as usize),
+                tag: Some(abbreviation.tag),
+            });
+        }
+    }
+}
+
+/// Reads an attribute of `form` from `input`; flags are valueless.
+fn read_form(input: &mut Slice, form: DwForm) -> Result<Option<u64>, crate::Error> {
+    Ok(Some(match form {
+        crate::gimli::DW_FORM_data1 | crate::gimli::DW_FORM_ref1 => input.read_u8()? as u64,
+        crate::gimli::DW_FORM_data2 | crate::gimli::DW_FORM_ref2 => input.read_u16()? as u64,
+        crate::gimli::DW_FORM_data4 | crate::gimli::DW_FORM_ref4 => input.read_u32()? as u64,
+        crate::gimli::DW_FORM_data8 | crate::gimli::DW_FORM_ref8 => input.read_u64()?,
+        crate::gimli::DW_FORM_udata | crate::gimli::DW_FORM_ref_udata => input.read_uleb128()?,
+        crate::gimli::DW_FORM_sdata => input.read_sleb128()? as u64,
+        crate::gimli::DW_FORM_flag_present => return Ok(None),
+        crate::gimli::DW_FORM_data16 => {
+            input.skip(16)?;
+            return Ok(None);
+        }
+        form => bail!("unsupported `.debug_names` form {form}"),
+    }))
+}
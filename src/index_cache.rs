@@ -0,0 +1,94 @@
+//! An on-disk cache of the [`TypeNames`] of binaries, so that short-lived
+//! programs need not scan their debuginfo on every run.
+//!
+//! Indexes are saved to `$XDG_CACHE_HOME/deflect/<build-id>` (by default,
+//! `~/.cache/deflect/<build-id>`). Binaries without a build ID are never
+//! cached, as nothing would tell a stale index from a current one.
+
+use crate::gimli::{DebugInfoOffset, UnitOffset};
+use crate::index::TypeNames;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Identifies an index file, and the version of its format.
+const MAGIC: &[u8; 8] = b"DFLXIDX1";
+
+/// The path of the cached index of `object`, if it has a build ID and a
+/// cache directory can be determined.
+pub(crate) fn path_of(
+    object: &crate::object::File<'static, &'static [u8]>,
+) -> Result<Option<PathBuf>, crate::Error> {
+    use crate::object::Object;
+
+    let Some(build_id) = object.build_id()? else { return Ok(None) };
+    if build_id.is_empty() {
+        return Ok(None);
+    }
+    let Some(dir) = cache_dir() else { return Ok(None) };
+    let name: String = build_id.iter().map(|b| format!("{b:02x}")).collect();
+    Ok(Some(dir.join("deflect").join(name)))
+}
+
+/// The per-user cache directory, per the XDG base directory specification.
+fn cache_dir() -> Option<PathBuf> {
+    match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if Path::new(&dir).is_absolute() => Some(dir.into()),
+        _ => std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")),
+    }
+}
+
+/// Reads the index at `path`; `None` if it is missing or malformed.
+pub(crate) fn read(path: &Path) -> Option<TypeNames> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut rest = bytes.strip_prefix(&MAGIC[..])?;
+    let count = read_usize(&mut rest)?;
+    let mut names = TypeNames::new();
+    for _ in 0..count {
+        let len = read_usize(&mut rest)?;
+        let name = take(&mut rest, len)?.to_vec();
+        let unit = DebugInfoOffset(read_usize(&mut rest)?);
+        let offset = UnitOffset(read_usize(&mut rest)?);
+        names.insert(name, (unit, offset));
+    }
+    rest.is_empty().then_some(names)
+}
+
+/// Splits the first `len` bytes off of `rest`.
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if rest.len() < len {
+        return None;
+    }
+    let (taken, remaining) = rest.split_at(len);
+    *rest = remaining;
+    Some(taken)
+}
+
+/// Splits a little-endian `u64` off of `rest`.
+fn read_usize(rest: &mut &[u8]) -> Option<usize> {
+    let bytes = take(rest, 8)?.try_into().ok()?;
+    usize::try_from(u64::from_le_bytes(bytes)).ok()
+}
+
+/// Writes `names` to the index at `path`, replacing it atomically.
+pub(crate) fn write(path: &Path, names: &TypeNames) -> Result<(), crate::Error> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&(names.len() as u64).to_le_bytes());
+    for (name, (unit, offset)) in names {
+        bytes.extend_from_slice(&(name.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(name);
+        bytes.extend_from_slice(&(unit.0 as u64).to_le_bytes());
+        bytes.extend_from_slice(&(offset.0 as u64).to_le_bytes());
+    }
+
+    let Some(dir) = path.parent() else { return Ok(()) };
+    std::fs::create_dir_all(dir)?;
+    // concurrent runs each write their own file, and the last rename wins.
+    let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+    let mut file = std::fs::File::create(&temp)?;
+    file.write_all(&bytes)?;
+    drop(file);
+    std::fs::rename(&temp, path).map_err(|err| {
+        let _ = std::fs::remove_file(&temp);
+        err.into()
+    })
+}
@@ -0,0 +1,492 @@
+//! Conversion of reflected values and their schemas into
+//! [`serde_json::Value`]s.
+
+use serde_json::{Map, Value as Json};
+
+use crate::format::Address;
+use crate::Value;
+
+/// Options controlling how a reflected [`Value`] is converted to JSON by
+/// [`Value::to_json_with`].
+///
+/// Requires the `json` feature.
+#[derive(Clone, Debug)]
+pub struct JsonOptions {
+    follow_references: bool,
+    enums: JsonEnums,
+    hex_bytes_above: Option<usize>,
+}
+
+/// How enums are represented in JSON; see [`JsonOptions::enums`].
+///
+/// Regardless of representation, `Option`s are represented as their contents
+/// (or `null`), and `Cow`s are represented as their contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonEnums {
+    /// As `serde` represents enums by default; unit variants are represented
+    /// as their name (e.g., `"Idle"`), and other variants as an object mapping
+    /// their name to their contents (e.g., `{"Active": {"since": 10}}`).
+    External,
+    /// Variants are represented as an object of their name and contents (e.g.,
+    /// `{"variant": "Active", "fields": {"since": 10}}`).
+    Adjacent,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            follow_references: true,
+            enums: JsonEnums::External,
+            hex_bytes_above: None,
+        }
+    }
+}
+
+impl JsonOptions {
+    /// The default options; references and boxes are followed, enums are
+    /// represented [externally][JsonEnums::External], and byte arrays are
+    /// represented as arrays of numbers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether references and boxes are represented as their referents, or as
+    /// their addresses (e.g., `"0x7ffd5f0c"`). Raw pointers are always
+    /// represented as their addresses.
+    pub fn follow_references(mut self, follow_references: bool) -> Self {
+        self.follow_references = follow_references;
+        self
+    }
+
+    /// How enums are represented.
+    pub fn enums(mut self, enums: JsonEnums) -> Self {
+        self.enums = enums;
+        self
+    }
+
+    /// Arrays and slices of more than `len` bytes are represented as strings
+    /// of hexadecimal digits, rather than as arrays of numbers.
+    pub fn hex_bytes_above(mut self, len: usize) -> Self {
+        self.hex_bytes_above = Some(len);
+        self
+    }
+}
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Converts this value to JSON with the default [`JsonOptions`].
+    ///
+    /// Requires the `json` feature.
+    pub fn to_json(&self) -> Result<Json, crate::Error> {
+        self.to_json_with(&JsonOptions::default())
+    }
+
+    /// Converts this value to JSON with the given `options`.
+    ///
+    /// Structs are represented as objects of their fields; tuple structs as
+    /// arrays of their fields, and newtypes as their inner value. Integers
+    /// that do not fit in a JSON number are represented as strings, and
+    /// non-finite floats as `null`.
+    ///
+    /// Requires the `json` feature.
+    pub fn to_json_with(&self, options: &JsonOptions) -> Result<Json, crate::Error> {
+        if let Some(overridden) = self.overridden()? {
+            return Ok(Json::String(overridden.render()?));
+        }
+        Ok(match self {
+            Value::bool(v) => Json::from(*v.value()),
+            Value::char(v) => Json::from(v.value().to_string()),
+            Value::f32(v) => Json::from(*v.value()),
+            Value::f64(v) => Json::from(*v.value()),
+            Value::i8(v) => Json::from(*v.value()),
+            Value::i16(v) => Json::from(*v.value()),
+            Value::i32(v) => Json::from(*v.value()),
+            Value::i64(v) => Json::from(*v.value()),
+            Value::i128(v) => match i64::try_from(*v.value()) {
+                Ok(v) => Json::from(v),
+                Err(_) => Json::from(v.value().to_string()),
+            },
+            Value::isize(v) => Json::from(*v.value()),
+            Value::u8(v) => Json::from(*v.value()),
+            Value::u16(v) => Json::from(*v.value()),
+            Value::u32(v) => Json::from(*v.value()),
+            Value::u64(v) => Json::from(*v.value()),
+            Value::u128(v) => match u64::try_from(*v.value()) {
+                Ok(v) => Json::from(v),
+                Err(_) => Json::from(v.value().to_string()),
+            },
+            Value::usize(v) => Json::from(*v.value()),
+            Value::unit(_) => Json::Null,
+            Value::str(v) => Json::from(v.value()?),
+            Value::Array(v) => elements(v.iter()?, options)?,
+            Value::Slice(v) => elements(v.iter()?, options)?,
+            Value::BoxedSlice(v) => elements(v.iter()?, options)?,
+            Value::Vector(v) => elements(v.iter()?, options)?,
+            Value::Box(v) if options.follow_references => v.deref()?.to_json_with(options)?,
+            Value::BoxedDyn(v) if options.follow_references => v.deref()?.to_json_with(options)?,
+            Value::DynRef(v) if options.follow_references => {
+                v.downcast_value()?.to_json_with(options)?
+            }
+            Value::SharedRef(v) if options.follow_references => v.deref()?.to_json_with(options)?,
+            Value::UniqueRef(v) if options.follow_references => v.deref()?.to_json_with(options)?,
+            Value::Box(_)
+            | Value::BoxedDyn(_)
+            | Value::DynRef(_)
+            | Value::SharedRef(_)
+            | Value::UniqueRef(_) => address(self)?,
+            Value::NonNull(v) => Json::from(Address(v.addr()?).to_string()),
+            Value::ConstPtr(v) => Json::from(Address(v.addr()).to_string()),
+            Value::MutPtr(v) => Json::from(Address(v.addr()).to_string()),
+            Value::Function(v) => Json::from(Address(v.addr()).to_string()),
+            Value::NonZero(v) => v.value()?.to_json_with(options)?,
+            Value::ManuallyDrop(v) => v.value()?.to_json_with(options)?,
+            Value::MaybeUninit(_) => Json::Null,
+            Value::Atomic(v) => displayed(v.to_string()),
+            Value::Struct(v) => {
+                if let Some(inner) = v.transparent_inner()? {
+                    return field(&inner, options);
+                }
+                fields(v.fields()?, options)?
+            }
+            Value::Enum(v) => {
+                if let Some(cow) = v.as_cow()? {
+                    return Ok(match cow {
+                        crate::value::CowData::Str(value) => Json::from(value),
+                        crate::value::CowData::Slice(iter) => elements(iter, options)?,
+                    });
+                }
+                if let Some(option) = v.as_option()? {
+                    return match option {
+                        Some(value) => value.to_json_with(options),
+                        None => Ok(Json::Null),
+                    };
+                }
+                let variant = v.variant()?;
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                let mut variant_fields = variant.fields()?;
+                let is_unit = variant_fields.iter()?.try_next()?.is_none();
+                match options.enums {
+                    JsonEnums::External if is_unit => Json::from(name),
+                    JsonEnums::External => {
+                        let mut object = Map::new();
+                        object.insert(name, fields(variant_fields, options)?);
+                        Json::Object(object)
+                    }
+                    JsonEnums::Adjacent => {
+                        let mut object = Map::new();
+                        object.insert("variant".to_owned(), Json::from(name));
+                        if !is_unit {
+                            object.insert("fields".to_owned(), fields(variant_fields, options)?);
+                        }
+                        Json::Object(object)
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Converts the elements produced by `iter` to JSON.
+fn elements<P>(
+    iter: crate::value::Iter<'_, '_, P>,
+    options: &JsonOptions,
+) -> Result<Json, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let elements: Vec<_> = iter.collect::<Result<_, _>>()?;
+    if matches!(options.hex_bytes_above, Some(len) if elements.len() > len) {
+        let bytes: Option<String> = elements
+            .iter()
+            .map(|elt| match elt {
+                Value::u8(byte) => Some(format!("{:02x}", byte.value())),
+                _ => None,
+            })
+            .collect();
+        if let Some(bytes) = bytes {
+            return Ok(Json::from(bytes));
+        }
+    }
+    elements
+        .iter()
+        .map(|elt| elt.to_json_with(options))
+        .collect()
+}
+
+/// Converts `fields` to JSON; as an array if they are the fields of a tuple
+/// (named `__0`, `__1`, etc.), and otherwise as an object.
+fn fields<P>(
+    mut fields: crate::value::Fields<'_, '_, P>,
+    options: &JsonOptions,
+) -> Result<Json, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut entries = vec![];
+    for value in fields.iter()? {
+        let value = value?;
+        let name = value.schema().name()?.to_string_lossy()?.into_owned();
+        entries.push((name, field(&value, options)?));
+    }
+    if !entries.is_empty() && entries.iter().all(|(name, _)| name.starts_with("__")) {
+        return Ok(entries.into_iter().map(|(_, value)| value).collect());
+    }
+    Ok(Json::Object(entries.into_iter().collect()))
+}
+
+/// Converts the value of `field` to JSON.
+fn field<P>(
+    field: &crate::value::Field<'_, '_, P>,
+    options: &JsonOptions,
+) -> Result<Json, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    if field.is_redacted()? {
+        return Ok(Json::String(crate::redact::REDACTED.to_owned()));
+    }
+    Ok(match field.typed_bits()? {
+        Some(crate::value::field::Bits::Bool(bits)) => Json::Bool(bits),
+        Some(crate::value::field::Bits::Signed(bits)) => Json::from(bits),
+        Some(crate::value::field::Bits::Unsigned(bits)) => Json::from(bits),
+        None => field.value()?.to_json_with(options)?,
+    })
+}
+
+/// Converts a value, as it was displayed, to JSON; e.g., `true` or `-3`
+/// become a boolean or number, and anything else a string.
+fn displayed(display: String) -> Json {
+    serde_json::from_str(&display).unwrap_or(Json::String(display))
+}
+
+/// The address stored at the start of the pointer `value`, as a JSON string.
+fn address<P>(value: &Value<'_, '_, P>) -> Result<Json, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (dwarf, unit, _) = value.r#type().die();
+    let addr = crate::read_address(dwarf, unit, value.bytes())?;
+    Ok(Json::from(Address(usize::try_from(addr)?).to_string()))
+}
+
+impl<'dwarf, R> crate::schema::Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// A JSON schema document describing this type and every type reachable
+    /// from it (through fields, variants, elements, pointees, etc.).
+    ///
+    /// The document is an object of the form `{"root": name, "types": {...}}`,
+    /// in which `types` maps the name of each type to its description. Types
+    /// refer to one another by name, so recursive types are described once.
+    /// Each description has a `kind` (e.g., `"struct"`, `"enum"`, `"array"`,
+    /// `"ref"`), a `name`, a `size` and an `align` (or `null`, if unknown),
+    /// and:
+    ///
+    /// - structs, their `fields`, as `[{"name", "type", "offset"}]`;
+    /// - enums, their `discriminant`, as `{"type", "offset"}`, and their
+    ///   `variants`, as `[{"name", "discriminant", "fields"}]`;
+    /// - arrays, their `element` type and `len`; vectors, their `element` type
+    ///   and `lanes`; slices and `str`s, their `element` type;
+    /// - boxes, references and pointers, their `pointee`;
+    /// - atomics, `NonZero`s, `NonNull`s, `ManuallyDrop`s and `MaybeUninit`s,
+    ///   their `inner` type;
+    /// - functions, their `parameters` and `return` type.
+    ///
+    /// Objects are keyed in sorted order, so that the document is stable
+    /// across runs.
+    ///
+    /// Requires the `json` feature.
+    pub fn to_schema_json(&self) -> Result<Json, crate::Error> {
+        let root = crate::format::qualified_type_name(self)?;
+        let mut types = Map::new();
+        let mut pending = vec![self.clone()];
+        while let Some(r#type) = pending.pop() {
+            let name = crate::format::qualified_type_name(&r#type)?;
+            if types.contains_key(&name) {
+                continue;
+            }
+            let description = describe(&r#type, &mut pending)?;
+            types.insert(name, description);
+        }
+        let mut document = Map::new();
+        document.insert("root".to_owned(), Json::from(root));
+        document.insert("types".to_owned(), Json::Object(types));
+        Ok(Json::Object(document))
+    }
+}
+
+/// Describes `r#type` for [`to_schema_json`][crate::schema::Type::to_schema_json],
+/// and pushes the types it refers to onto `pending`.
+fn describe<'dwarf, R>(
+    r#type: &crate::schema::Type<'dwarf, R>,
+    pending: &mut Vec<crate::schema::Type<'dwarf, R>>,
+) -> Result<Json, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    use crate::schema::Type;
+    let mut refer = |r#type: Type<'dwarf, R>| -> Result<Json, crate::Error> {
+        let name = crate::format::qualified_type_name(&r#type)?;
+        pending.push(r#type);
+        Ok(Json::from(name))
+    };
+    let mut description = Map::new();
+    let kind = match r#type {
+        Type::bool(_)
+        | Type::char(_)
+        | Type::f32(_)
+        | Type::f64(_)
+        | Type::i8(_)
+        | Type::i16(_)
+        | Type::i32(_)
+        | Type::i64(_)
+        | Type::i128(_)
+        | Type::isize(_)
+        | Type::u8(_)
+        | Type::u16(_)
+        | Type::u32(_)
+        | Type::u64(_)
+        | Type::u128(_)
+        | Type::usize(_)
+        | Type::unit(_) => "primitive",
+        Type::Struct(r#type) => {
+            let fields = schema_fields(r#type.fields()?, &mut refer)?;
+            description.insert("fields".to_owned(), fields);
+            "struct"
+        }
+        Type::Enum(r#type) => {
+            let mut discriminant = Map::new();
+            let discriminant_type = refer(r#type.discriminant_type()?)?;
+            let offset = r#type.discriminant_location().clone().address(0)?;
+            discriminant.insert("type".to_owned(), discriminant_type);
+            discriminant.insert("offset".to_owned(), Json::from(offset));
+            description.insert("discriminant".to_owned(), Json::Object(discriminant));
+            let mut variants = vec![];
+            let mut iter = r#type.variants()?;
+            let mut iter = iter.iter()?;
+            while let Some(variant) = iter.try_next()? {
+                let mut entry = Map::new();
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                let value = variant.discriminant_value().map(|value| value.to_u64());
+                entry.insert("name".to_owned(), Json::from(name));
+                entry.insert("discriminant".to_owned(), Json::from(value));
+                let fields = schema_fields(variant.fields()?, &mut refer)?;
+                entry.insert("fields".to_owned(), fields);
+                variants.push(Json::Object(entry));
+            }
+            description.insert("variants".to_owned(), Json::Array(variants));
+            "enum"
+        }
+        Type::Array(r#type) => {
+            description.insert("element".to_owned(), refer(r#type.elt_type()?)?);
+            description.insert("len".to_owned(), Json::from(r#type.len()?));
+            "array"
+        }
+        Type::Vector(r#type) => {
+            description.insert("element".to_owned(), refer(r#type.lane_type()?)?);
+            description.insert("lanes".to_owned(), Json::from(r#type.lane_count()?));
+            "vector"
+        }
+        Type::Slice(r#type) => {
+            description.insert("element".to_owned(), refer(r#type.elt()?)?);
+            "slice"
+        }
+        Type::BoxedSlice(r#type) => {
+            description.insert("element".to_owned(), refer(r#type.elt()?)?);
+            "boxed_slice"
+        }
+        Type::str(r#type) => {
+            description.insert("element".to_owned(), refer(r#type.elt()?)?);
+            "str"
+        }
+        Type::Box(r#type) => {
+            description.insert("pointee".to_owned(), refer(r#type.r#type()?)?);
+            "box"
+        }
+        Type::SharedRef(r#type) => {
+            description.insert("pointee".to_owned(), refer(r#type.r#type()?)?);
+            "ref"
+        }
+        Type::UniqueRef(r#type) => {
+            description.insert("pointee".to_owned(), refer(r#type.r#type()?)?);
+            "ref"
+        }
+        Type::ConstPtr(r#type) => {
+            description.insert("pointee".to_owned(), refer(r#type.r#type()?)?);
+            "ptr"
+        }
+        Type::MutPtr(r#type) => {
+            description.insert("pointee".to_owned(), refer(r#type.r#type()?)?);
+            "ptr"
+        }
+        Type::BoxedDyn(_) => "boxed_dyn",
+        Type::DynRef(_) => "dyn_ref",
+        Type::Atomic(r#type) => {
+            description.insert("inner".to_owned(), refer(r#type.value_type()?)?);
+            "atomic"
+        }
+        Type::NonZero(r#type) => {
+            description.insert("inner".to_owned(), refer(r#type.value_type()?)?);
+            "non_zero"
+        }
+        Type::NonNull(r#type) => {
+            description.insert("inner".to_owned(), refer(r#type.pointer().r#type()?)?);
+            "non_null"
+        }
+        Type::ManuallyDrop(r#type) => {
+            description.insert("inner".to_owned(), refer(r#type.value_type()?)?);
+            "manually_drop"
+        }
+        Type::MaybeUninit(r#type) => {
+            description.insert("inner".to_owned(), refer(r#type.value_type()?)?);
+            "maybe_uninit"
+        }
+        Type::Function(r#type) => {
+            let parameters = r#type
+                .parameters()?
+                .into_iter()
+                .map(&mut refer)
+                .collect::<Result<_, _>>()?;
+            description.insert("parameters".to_owned(), Json::Array(parameters));
+            let r#return = match r#type.return_type()? {
+                Some(r#type) => refer(r#type)?,
+                None => Json::Null,
+            };
+            description.insert("return".to_owned(), r#return);
+            "function"
+        }
+    };
+    description.insert("kind".to_owned(), Json::from(kind));
+    let name = crate::format::qualified_type_name(r#type)?;
+    description.insert("name".to_owned(), Json::from(name));
+    description.insert("size".to_owned(), Json::from(r#type.size()?));
+    description.insert("align".to_owned(), Json::from(r#type.align()?));
+    Ok(Json::Object(description))
+}
+
+/// Describes `fields` as `[{"name", "type", "offset"}]`, referring to their
+/// types by name with `refer`.
+fn schema_fields<'dwarf, R>(
+    mut fields: crate::schema::Fields<'dwarf, R>,
+    refer: &mut impl FnMut(crate::schema::Type<'dwarf, R>) -> Result<Json, crate::Error>,
+) -> Result<Json, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut descriptions = vec![];
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let mut description = Map::new();
+        let name = field.name()?.to_string_lossy()?.into_owned();
+        description.insert("name".to_owned(), Json::from(name));
+        description.insert("type".to_owned(), refer(field.r#type()?)?);
+        let offset = field.offset()?.address(0)?;
+        description.insert("offset".to_owned(), Json::from(offset));
+        descriptions.push(Json::Object(description));
+    }
+    Ok(Json::Array(descriptions))
+}
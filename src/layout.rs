@@ -0,0 +1,341 @@
+//! `pahole`-style reports of the memory layouts of types.
+
+use std::fmt;
+
+use crate::schema::Type;
+
+/// The memory layout of a type: the offsets and sizes of its members, and the
+/// padding between and after them; produced by
+/// [`Type::layout_report`][crate::schema::Type::layout_report].
+///
+/// Displayed in the style of `pahole`; e.g.:
+///
+/// ```text
+/// struct Foo {
+///     /*    0    4 */ b: u32,
+///     /*    4    1 */ a: u8,
+///     /* XXX 3 bytes tail padding */
+/// } /* size: 8, align: 4, holes: 0, padding: 3 */
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    keyword: Option<&'static str>,
+    name: String,
+    size: u64,
+    align: Option<u64>,
+    members: Members,
+    variants: Vec<VariantLayout>,
+}
+
+/// The layout of a variant of an enum, within a [`Layout`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantLayout {
+    name: String,
+    members: Members,
+}
+
+/// A member of a [`Layout`]; i.e., a field or an enum's discriminant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    name: String,
+    type_name: String,
+    offset: u64,
+    size: u64,
+    hole: u64,
+    layout: Option<Box<Layout>>,
+}
+
+/// The members of a struct or variant, in order of offset, and the padding
+/// after them.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Members {
+    members: Vec<Member>,
+    tail_padding: u64,
+}
+
+impl Layout {
+    /// The name of the type.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The size of the type, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The alignment of the type, in bytes, if known.
+    pub fn align(&self) -> Option<u64> {
+        self.align
+    }
+
+    /// The members of this struct, in order of offset; empty if the type is
+    /// not a struct.
+    pub fn members(&self) -> &[Member] {
+        &self.members.members
+    }
+
+    /// The layouts of the variants of this enum; empty if the type is not an
+    /// enum.
+    pub fn variants(&self) -> &[VariantLayout] {
+        &self.variants
+    }
+
+    /// The number of unused bytes after the last member of this struct.
+    pub fn tail_padding(&self) -> u64 {
+        self.members.tail_padding
+    }
+
+    /// The number of holes (i.e., unused bytes) between the members of this
+    /// struct.
+    pub fn holes(&self) -> usize {
+        self.members.holes()
+    }
+
+    /// The total number of unused bytes in this struct, between and after its
+    /// members; excludes the padding within its members.
+    pub fn padding(&self) -> u64 {
+        self.members.padding()
+    }
+}
+
+impl VariantLayout {
+    /// The name of the variant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The members of this variant (including the discriminant, if it does not
+    /// overlap a field), in order of offset.
+    pub fn members(&self) -> &[Member] {
+        &self.members.members
+    }
+
+    /// The number of unused bytes after the last member of this variant.
+    pub fn tail_padding(&self) -> u64 {
+        self.members.tail_padding
+    }
+
+    /// The number of holes (i.e., unused bytes) between the members of this
+    /// variant.
+    pub fn holes(&self) -> usize {
+        self.members.holes()
+    }
+
+    /// The total number of unused bytes in this variant, between and after its
+    /// members.
+    pub fn padding(&self) -> u64 {
+        self.members.padding()
+    }
+}
+
+impl Member {
+    /// The name of the member; the discriminant of an enum is named
+    /// `<discriminant>`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the type of the member.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The offset of the member, in bytes, from the start of its parent.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The size of the member, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The number of unused bytes between this member and the next.
+    pub fn hole(&self) -> u64 {
+        self.hole
+    }
+
+    /// The layout of the member's type, if it is a struct or enum.
+    pub fn layout(&self) -> Option<&Layout> {
+        self.layout.as_deref()
+    }
+}
+
+impl Members {
+    /// Sorts `members` by offset, and computes the holes between them and the
+    /// padding after them, within a parent of `size` bytes.
+    fn new(mut members: Vec<Member>, size: u64) -> Self {
+        members.sort_by_key(|member| member.offset);
+        let mut end = 0;
+        for i in (0..members.len()).rev() {
+            let member_end = members[i].offset + members[i].size;
+            if let Some(next) = members.get(i + 1) {
+                // overlapping members (e.g., of unions) have no hole.
+                members[i].hole = next.offset.saturating_sub(member_end);
+            }
+            end = end.max(member_end);
+        }
+        Self {
+            members,
+            tail_padding: size.saturating_sub(end),
+        }
+    }
+
+    fn holes(&self) -> usize {
+        self.members.iter().filter(|member| member.hole > 0).count()
+    }
+
+    fn padding(&self) -> u64 {
+        let holes: u64 = self.members.iter().map(|member| member.hole).sum();
+        holes + self.tail_padding
+    }
+
+    /// Displays these members, one per line, indented by `depth` levels.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "    ".repeat(depth);
+        for member in &self.members {
+            write!(
+                f,
+                "{indent}/* {:4} {:4} */ {}: ",
+                member.offset, member.size, member.name
+            )?;
+            match &member.layout {
+                Some(layout) if layout.has_body() => layout.fmt_body(f, depth)?,
+                _ => f.write_str(&member.type_name)?,
+            }
+            writeln!(f, ",")?;
+            if member.hole > 0 {
+                writeln!(f, "{indent}/* XXX {} bytes hole */", member.hole)?;
+            }
+        }
+        if self.tail_padding > 0 {
+            let padding = self.tail_padding;
+            writeln!(f, "{indent}/* XXX {padding} bytes tail padding */")?;
+        }
+        Ok(())
+    }
+}
+
+impl Layout {
+    /// Whether this layout has members or variants to display.
+    fn has_body(&self) -> bool {
+        self.keyword.is_some()
+    }
+
+    /// Displays this layout, without its summary, nested `depth` levels deep.
+    fn fmt_body(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let Some(keyword) = self.keyword else {
+            return f.write_str(&self.name);
+        };
+        writeln!(f, "{keyword} {} {{", self.name)?;
+        self.members.fmt(f, depth + 1)?;
+        let indent = "    ".repeat(depth + 1);
+        for variant in &self.variants {
+            writeln!(f, "{indent}{} {{", variant.name)?;
+            variant.members.fmt(f, depth + 2)?;
+            writeln!(f, "{indent}}},")?;
+        }
+        write!(f, "{}}}", "    ".repeat(depth))
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_body(f, 0)?;
+        write!(f, " /* size: {}", self.size)?;
+        if let Some(align) = self.align {
+            write!(f, ", align: {align}")?;
+        }
+        if self.keyword == Some("struct") {
+            write!(f, ", holes: {}, padding: {}", self.holes(), self.padding())?;
+        }
+        f.write_str(" */")
+    }
+}
+
+impl<'dwarf, R> Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// A report of the memory layout of this type, in the style of `pahole`:
+    /// the offsets and sizes of its members (recursively, for members that are
+    /// structs or enums), the holes between them, and its tail padding.
+    ///
+    /// Members are reported in order of offset, which, as `rustc` reorders
+    /// fields, may differ from their declaration order.
+    pub fn layout_report(&self) -> Result<Layout, crate::Error> {
+        let size = self.size()?;
+        let mut layout = Layout {
+            keyword: None,
+            name: crate::format::type_name(self)?,
+            size,
+            align: self.align()?,
+            members: Members::default(),
+            variants: vec![],
+        };
+        match self {
+            Type::Struct(r#type) => {
+                layout.keyword = Some("struct");
+                layout.members = Members::new(members(r#type.fields()?)?, size);
+            }
+            Type::Enum(r#type) => {
+                layout.keyword = Some("enum");
+                let discriminant_type = r#type.discriminant_type()?;
+                let discriminant = Member {
+                    name: "<discriminant>".to_owned(),
+                    type_name: crate::format::type_name(&discriminant_type)?,
+                    offset: r#type.discriminant_location().clone().address(0)?,
+                    size: discriminant_type.size()?,
+                    hole: 0,
+                    layout: None,
+                };
+                let mut variants = r#type.variants()?;
+                let mut variants = variants.iter()?;
+                while let Some(variant) = variants.try_next()? {
+                    let mut members = members(variant.fields()?)?;
+                    // niche-encoded discriminants overlap a field.
+                    let overlaps = members.iter().any(|member| {
+                        member.offset < discriminant.offset + discriminant.size
+                            && discriminant.offset < member.offset + member.size
+                    });
+                    if !overlaps {
+                        members.push(discriminant.clone());
+                    }
+                    layout.variants.push(VariantLayout {
+                        name: variant.name()?.to_string_lossy()?.into_owned(),
+                        members: Members::new(members, size),
+                    });
+                }
+            }
+            _ => {}
+        }
+        Ok(layout)
+    }
+}
+
+/// The members of a struct or variant with the given `fields`, in declaration
+/// order.
+fn members<R>(mut fields: crate::schema::Fields<'_, R>) -> Result<Vec<Member>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut members = vec![];
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let r#type = field.r#type()?;
+        let layout = match r#type {
+            Type::Struct(_) | Type::Enum(_) => Some(Box::new(r#type.layout_report()?)),
+            _ => None,
+        };
+        members.push(Member {
+            name: field.name()?.to_string_lossy()?.into_owned(),
+            type_name: crate::format::type_name(&r#type)?,
+            offset: field.offset()?.address(0)?,
+            size: r#type.size()?,
+            hole: 0,
+            layout,
+        });
+    }
+    Ok(members)
+}
@@ -31,6 +31,9 @@
 //! // pretty-print the reflected value
 //! assert_eq!(value.to_string(), "box Foo { a: 42 }");
 //!
+//! // or pretty-print it across multiple lines, as with `{:#?}`
+//! assert_eq!(format!("{value:#}"), "box Foo {\n    a: 42,\n}");
+//!
 //! // downcast into a `BoxedDyn` value
 //! let value: deflect::value::BoxedDyn = value.try_into()?;
 //!
@@ -55,7 +58,8 @@
 //! assert_eq!(value, 42);
 //!
 //! // reflect a type
-//! let type_: deflect::Type = deflect::reflect_type::<Foo>(&context)?;
+//! let type_ = deflect::reflect_type::<Foo, _>(&context)?;
+//! assert_eq!(type_.to_string(), "Foo { a: u8 }");
 //!
 //! # Ok::<_, Box<dyn std::error::Error>>(())
 //! ```
@@ -64,7 +68,9 @@
 //! ## Limitations
 //! The current implementation of [`default_provider`] only works when DWARF
 //! debuginfo is stored in the program's binary. It will not work if DWARF
-//! debug info is split into other files. Pull requests are welcome.
+//! debug info is split into other files. Pull requests are welcome. Reflecting
+//! code built without full debuginfo (e.g., with `debug = "line-tables-only"`)
+//! fails with [`ErrorKind::NoDebugInfo`], which explains how to build it.
 //!
 //! This crate is highly experimental. It is not suitable as a critical
 //! component of any system. The initial releases of this crate require
@@ -96,20 +102,91 @@ use std::{
     mem::{self, MaybeUninit},
     path::Path,
     ptr::slice_from_raw_parts,
-    rc::Rc,
+    sync::Arc,
 };
 
+mod abi;
+mod adapters;
+mod assert;
+mod codegen;
+mod container;
+mod crash;
 mod debug;
+mod deep_size;
+mod diff;
+mod dot;
+#[cfg(feature = "serde")]
+mod encode;
 mod error;
-pub use error::DowncastErr;
-
+mod filter;
+mod format;
+mod generate;
+mod hexdump;
+mod index;
+mod index_cache;
+#[cfg(feature = "json")]
+mod json;
+mod layout;
+mod memo;
+mod metrics;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod overrides;
+mod portable;
+mod pretty_printers;
+mod redact;
+mod referrers;
+mod refs;
+mod registry;
+mod rustc;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
+#[cfg(feature = "valuable")]
+mod valuable;
+pub use abi::{abi_diff, abi_diff_with, AbiChange, AbiDiff};
+pub use assert::assert_reflect_matches_debug;
+pub use container::{Adapted, ContainerAdapter, Elements};
+pub use crash::{install_crash_hook, register, unregister, write_snapshots};
+pub use deep_size::{deep_size_of, deep_size_of_with, DeepSizeOptions, SharedPolicy};
+pub use diff::{diff, Change, Diff};
+pub use error::{DowncastErr, ErrorKind};
+pub use filter::TypeFilter;
+pub use format::{AddressFormat, ByteFormat, DisplayWith, FormatOptions};
+pub use hexdump::Hexdump;
+#[cfg(feature = "json")]
+pub use json::{JsonEnums, JsonOptions};
+pub use layout::{Layout, Member, VariantLayout};
+pub use memo::MemoContext;
+pub use metrics::render_metrics;
+pub use overrides::{FormatHook, FormatOverride};
+pub use portable::{PortableSnapshot, SnapshotProvider};
+pub use pretty_printers::{pretty_printers, Debugger};
+pub use redact::Redaction;
+pub use referrers::{find_references, Referrer};
+pub use registry::{registry, Registered, Registry};
+#[cfg(feature = "msgpack")]
+pub use rmp;
+#[cfg(feature = "erased-serde")]
+pub use erased_serde;
+#[cfg(feature = "serde")]
+pub use self::serde::DeserializeError;
+#[cfg(feature = "json")]
+pub use serde_json;
+pub use snapshot::Snapshot;
+
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzz;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod schema;
 pub mod value;
 
 type Byte = MaybeUninit<u8>;
 type Bytes<'value> = &'value [Byte];
 
-type Addr2LineReader = EndianReader<RunTimeEndian, Rc<[u8]>>;
+type Addr2LineReader = EndianReader<RunTimeEndian, Arc<[u8]>>;
 type Context = addr2line::Context<Addr2LineReader>;
 
 /// Raw debug info for a function.
@@ -143,6 +220,10 @@ where
 /// A source of debug info that can be trusted to correspond to the current
 /// executable.
 ///
+/// What is read from the DWARF sections of a provider (e.g., types, and units
+/// referred to across units) is memoized only if its debug info is produced
+/// from a [`MemoContext`].
+///
 /// ## Safety
 /// Implementers of this trait must provide accurate debug info for this
 /// program.
@@ -152,6 +233,63 @@ pub unsafe trait DebugInfoProvider: Clone {
 
     /// Produces debug info for a given function.
     fn info_for(&self, fn_addr: u64) -> Result<DebugInfo<'_, Self::Reader>, crate::Error>;
+
+    /// Produces debug info for the type implementing the given vtable.
+    fn info_for_vtable(
+        &self,
+        vtable_addr: u64,
+    ) -> Result<DebugInfo<'_, Self::Reader>, crate::Error> {
+        bail!("this provider cannot resolve the vtable at 0x{vtable_addr:x}")
+    }
+
+    /// Produces debug info for the type with the given fully-qualified
+    /// `name`; e.g., `my_crate::Config`.
+    fn info_for_type(&self, name: &str) -> Result<DebugInfo<'_, Self::Reader>, crate::Error> {
+        bail!("this provider cannot look up types by name, such as `{name}`")
+    }
+
+    /// Produces debug info for each type whose fully-qualified name satisfies
+    /// `matches`, in the order in which `matches` accepted their names.
+    fn info_for_types(
+        &self,
+        matches: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<Vec<DebugInfo<'_, Self::Reader>>, crate::Error> {
+        let _ = matches;
+        bail!("this provider cannot enumerate types")
+    }
+
+    /// Whether reflected `bool`s and `char`s are checked to hold valid bit
+    /// patterns, failing with [`ErrorKind::InvalidBitPattern`] otherwise;
+    /// e.g., when reflecting possibly-corrupt memory, such as that of a core
+    /// dump or another process.
+    ///
+    /// Without validation, reflecting an invalid `bool` or `char` is
+    /// undefined behavior.
+    fn validates_bit_patterns(&self) -> bool {
+        false
+    }
+
+    /// The adapters of third-party containers, with which values of their
+    /// types are displayed and iterated; see [`ContainerAdapter`].
+    fn container_adapters(&self) -> &[&'static dyn ContainerAdapter] {
+        &[]
+    }
+
+    /// The overrides of the display of values of particular types (e.g.,
+    /// `uuid::Uuid`), in all of deflect's output: their [`Display`][fmt::Display],
+    /// JSON, snapshots and diffs.
+    ///
+    /// Each overridden type must have no padding, as its hook is passed all of
+    /// the bytes of its values.
+    fn format_overrides(&self) -> &[FormatOverride] {
+        &[]
+    }
+
+    /// The policy of which values are redacted in all of deflect's output;
+    /// see [`Redaction`].
+    fn redaction(&self) -> Option<&Redaction> {
+        None
+    }
 }
 
 mod dbginfo_provider {
@@ -159,7 +297,7 @@ mod dbginfo_provider {
 
     struct Map {
         path: std::path::PathBuf,
-        static_addr: usize,
+        file_offset: u64,
     }
 
     fn map_of(dynamic_addr: usize) -> Result<Map, crate::Error> {
@@ -170,22 +308,40 @@ mod dbginfo_provider {
             if (map.base..=map.ceiling).contains(&dynamic_addr) {
                 if let procmaps::Path::MappedFile(file) = &map.pathname {
                     return Ok(Map {
-                        static_addr: (dynamic_addr - map.base) + map.offset,
+                        file_offset: ((dynamic_addr - map.base) + map.offset) as u64,
                         path: file.into(),
                     });
                 }
             }
         }
-        bail!("could not map the dynamic address 0x{dynamic_addr:x} to a static address in the binary");
+        Err(crate::error::symbol_not_found(dynamic_addr))
+    }
+
+    /// Translates an offset into the object file into the virtual address it
+    /// is loaded at, per the object's segments.
+    fn static_addr_of(
+        object: &object::File<'static, &'static [u8]>,
+        file_offset: u64,
+    ) -> Result<usize, crate::Error> {
+        use object::{Object, ObjectSegment};
+        for segment in object.segments() {
+            let (start, size) = segment.file_range();
+            if (start..start + size).contains(&file_offset) {
+                return Ok((segment.address() + (file_offset - start)) as usize);
+            }
+        }
+        bail!("could not map the file offset 0x{file_offset:x} to a static address in the binary");
     }
 
     pub fn context_of(dynamic_addr: usize) -> Result<(&'static Context, usize), crate::Error> {
-        let Map { path, static_addr } = map_of(dynamic_addr)?;
+        let Map { path, file_offset } = map_of(dynamic_addr)?;
+        let object = read_object(path.as_path())?;
+        let static_addr = static_addr_of(object, file_offset)?;
         let context = read_context(path)?;
         Ok((context, static_addr))
     }
 
-    pub fn read_context<P>(path: P) -> Result<&'static Context, crate::Error>
+    fn read_object<P>(path: P) -> Result<&'static object::File<'static, &'static [u8]>, crate::Error>
     where
         P: Borrow<Path>,
     {
@@ -205,6 +361,46 @@ mod dbginfo_provider {
             Ok::<_, crate::Error>(object)
         })?;
 
+        Ok(*object)
+    }
+
+    /// Loads the DWARF sections of `object`.
+    ///
+    /// The contexts of `addr2line` cannot be shared across threads, so each
+    /// thread reads its own; their sections are shared, and so are only
+    /// decompressed and copied once.
+    fn load_dwarf(
+        object: &'static object::File<'static, &'static [u8]>,
+    ) -> Result<gimli::Dwarf<Addr2LineReader>, crate::Error> {
+        type Key = (usize, &'static str);
+
+        static SECTION_CACHE: Lazy<DashMap<Key, Arc<[u8]>>> = Lazy::new(DashMap::new);
+
+        let endian = if object::Object::is_little_endian(object) {
+            RunTimeEndian::Little
+        } else {
+            RunTimeEndian::Big
+        };
+        gimli::Dwarf::load(|id| {
+            let key = (object as *const _ as usize, id.name());
+            let data = SECTION_CACHE.entry(key).or_try_insert_with(|| {
+                let data = crate::index::section(object, id.name())?;
+                Ok::<_, crate::Error>(Arc::from(data.slice()))
+            })?;
+            Ok(EndianReader::new(data.clone(), endian))
+        })
+    }
+
+    pub fn read_context<P>(path: P) -> Result<&'static Context, crate::Error>
+    where
+        P: Borrow<Path>,
+    {
+        let path = path.borrow().to_owned();
+        let object = read_object(path.as_path())?;
+        if crate::index::section(object, ".debug_info")?.is_empty() {
+            return Err(crate::error::no_debug_info(crate::error::MISSING_DEBUG_INFO));
+        }
+
         thread_local! {
             pub static CONTEXT_CACHE: RefCell<HashMap<std::path::PathBuf, &'static Context>> =
                 RefCell::new(HashMap::new());
@@ -215,39 +411,600 @@ mod dbginfo_provider {
             if let Some(context) = context_cache.get(&path) {
                 Ok(*context)
             } else {
-                let context = addr2line::Context::new(*object)?;
-                let context: &'static _ = Box::leak(Box::new(context));
+                let context = addr2line::Context::from_dwarf(load_dwarf(object)?)?;
+                let context = Box::leak(Box::new(crate::MemoContext::new(context)));
+                let context: &'static Context = context;
+                let indexes: &'static _ = Box::leak(Box::new(crate::index::Indexes::new(object)?));
+                INDEXES_CACHE.with(|cache| {
+                    let key = context as *const Context as usize;
+                    cache.borrow_mut().insert(key, indexes)
+                });
                 context_cache.insert(path, context);
                 Ok(context)
             }
         })
     }
+
+    thread_local! {
+        static INDEXES_CACHE: RefCell<HashMap<usize, &'static crate::index::Indexes>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// Produces the name indexes of the binary described by `context`.
+    fn indexes_of(
+        context: &'static Context,
+    ) -> Result<&'static crate::index::Indexes, crate::Error> {
+        let key = context as *const Context as usize;
+        match INDEXES_CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            Some(indexes) => Ok(indexes),
+            None => bail!("no name indexes were read for this context"),
+        }
+    }
+
+    /// Produces the unit at `offset` in `.debug_info`, parsing only that unit.
+    fn unit_at(
+        context: &'static Context,
+        offset: crate::gimli::DebugInfoOffset,
+    ) -> Result<&'static crate::gimli::Unit<Addr2LineReader>, crate::Error> {
+        type Key = (usize, crate::gimli::DebugInfoOffset);
+        type Cached = &'static crate::gimli::Unit<Addr2LineReader>;
+
+        thread_local! {
+            pub static UNIT_CACHE: RefCell<HashMap<Key, Cached>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let key = (context as *const Context as usize, offset);
+        if let Some(cached) = UNIT_CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let dwarf = context.dwarf();
+        let header = dwarf.debug_info.header_from_offset(offset)?;
+        // units are leaked so that, like those of `addr2line`, they live as
+        // long as their `Context`.
+        let unit: &'static _ = Box::leak(Box::new(dwarf.unit(header)?));
+        UNIT_CACHE.with(|cache| cache.borrow_mut().insert(key, unit));
+        Ok(unit)
+    }
+
+    /// Produces the context, DWARF unit and entry offset of the type whose
+    /// [`Reflect::local_type_id`] is at the dynamic address `fn_addr`.
+    ///
+    /// Like a `TypeId`, that address uniquely identifies its type (even if the
+    /// type is not `'static`), so the debuginfo of a type that is reflected
+    /// repeatedly is only resolved once.
+    pub fn unit_and_die_of_fn(
+        fn_addr: usize,
+    ) -> Result<
+        (
+            &'static Context,
+            &'static crate::gimli::Unit<Addr2LineReader>,
+            UnitOffset,
+        ),
+        crate::Error,
+    > {
+        type Cached = (
+            &'static Context,
+            &'static crate::gimli::Unit<Addr2LineReader>,
+            UnitOffset,
+        );
+
+        thread_local! {
+            pub static FN_CACHE: RefCell<HashMap<usize, Cached>> =
+                RefCell::new(HashMap::new());
+        }
+
+        if let Some(cached) = FN_CACHE.with(|cache| cache.borrow().get(&fn_addr).copied()) {
+            return Ok(cached);
+        }
+
+        let (context, static_addr) = context_of(fn_addr)?;
+        let (unit, entry) = match indexed_unit_and_die_of_fn(context, static_addr)? {
+            Some(found) => found,
+            None => match crate::dw_unit_and_die_of_addr(context, static_addr) {
+                Ok(found) => found,
+                // in optimized builds, the debuginfo of a function may not
+                // describe its address.
+                Err(err) => symbol_unit_and_die_of_fn(context, static_addr)?.ok_or(err)?,
+            },
+        };
+        FN_CACHE.with(|cache| cache.borrow_mut().insert(fn_addr, (context, unit, entry)));
+        Ok((context, unit, entry))
+    }
+
+    /// Produces the DWARF unit and entry offset of the type whose
+    /// [`Reflect::local_type_id`] is the function at `static_addr`, if that
+    /// function can be found by its symbol in the `.debug_names` index.
+    fn indexed_unit_and_die_of_fn(
+        context: &'static Context,
+        static_addr: usize,
+    ) -> Result<Option<(&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset)>, crate::Error>
+    {
+        let indexes = indexes_of(context)?;
+        if !indexes.has_names() {
+            return Ok(None);
+        }
+        let Some(symbol) = indexes.symbol_at(static_addr as u64) else { return Ok(None) };
+        match indexes.subprograms_named(symbol)?.into_iter().next() {
+            Some(entry) => {
+                let unit = unit_at(context, entry.unit)?;
+                Ok(Some(crate::type_param_of(
+                    context.dwarf(),
+                    unit,
+                    entry.offset,
+                )?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Produces the DWARF unit and entry offset of the type whose
+    /// [`Reflect::local_type_id`] is the function at `static_addr`, if that
+    /// function can be found by the linkage name of its symbol.
+    fn symbol_unit_and_die_of_fn(
+        context: &'static Context,
+        static_addr: usize,
+    ) -> Result<Option<(&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset)>, crate::Error>
+    {
+        let Some(symbol) = indexes_of(context)?.symbol_at(static_addr as u64) else {
+            return Ok(None);
+        };
+        let dwarf = context.dwarf();
+        for unit in units_of(context)? {
+            let mut entries = unit.entries();
+            while let Some((_, entry)) = entries.next_dfs()? {
+                if entry.tag() != crate::gimli::DW_TAG_subprogram {
+                    continue;
+                }
+                let Some(name) = entry.attr_value(crate::gimli::DW_AT_linkage_name)? else {
+                    continue;
+                };
+                let name = dwarf.attr_string(unit, name)?;
+                if crate::gimli::Reader::to_slice(&name)? == symbol.as_bytes() {
+                    return Ok(Some(crate::type_param_of(dwarf, unit, entry.offset())?));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Produces the DWARF unit and entry offset of the type implementing the
+    /// vtable at `static_addr`.
+    pub fn unit_and_die_of_vtable(
+        context: &'static Context,
+        static_addr: usize,
+    ) -> Result<(&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset), crate::Error> {
+        type Key = (usize, usize);
+        type Cached = (&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset);
+
+        thread_local! {
+            pub static VTABLE_CACHE: RefCell<HashMap<Key, Cached>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let key = (context as *const Context as usize, static_addr);
+        if let Some(cached) = VTABLE_CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let dwarf = context.dwarf();
+        let mut headers = dwarf.units();
+        while let Some(header) = headers.next()? {
+            let unit = dwarf.unit(header)?;
+            let Some(offset) = crate::vtable_impl_of(dwarf, &unit, static_addr)? else {
+                continue;
+            };
+            // units are leaked so that, like those of `addr2line`, they live as
+            // long as their `Context`.
+            let unit: &'static _ = Box::leak(Box::new(unit));
+            VTABLE_CACHE.with(|cache| cache.borrow_mut().insert(key, (unit, offset)));
+            return Ok((unit, offset));
+        }
+
+        bail!("could not find the DWARF vtable at static address 0x{static_addr:x}")
+    }
+
+    /// Produces the DWARF unit and entry offset of the type with the given
+    /// fully-qualified `name`; with the `index_cache`, if `persisted`.
+    pub fn unit_and_die_of_type(
+        context: &'static Context,
+        name: &str,
+        persisted: bool,
+    ) -> Result<(&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset), crate::Error> {
+        type Key = (usize, String);
+        type Cached = (&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset);
+
+        thread_local! {
+            pub static TYPE_CACHE: RefCell<HashMap<Key, Cached>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let key = (context as *const Context as usize, name.to_owned());
+        if let Some(cached) = TYPE_CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let dwarf = context.dwarf();
+        let indexes = indexes_of(context)?;
+        if persisted {
+            let type_names = indexes.persisted_type_names()?;
+            let Some(&(unit, offset)) = type_names.get(name.as_bytes()) else {
+                return Err(crate::error::type_not_found(name));
+            };
+            let unit = unit_at(context, unit)?;
+            TYPE_CACHE.with(|cache| cache.borrow_mut().insert(key, (unit, offset)));
+            return Ok((unit, offset));
+        }
+
+        for entry in indexes.types_named(name)? {
+            let unit = unit_at(context, entry.unit)?;
+            let qualified = crate::qualified_name_of(dwarf, unit, entry.offset)?;
+            if qualified.as_deref() == Some(name.as_bytes()) {
+                TYPE_CACHE.with(|cache| cache.borrow_mut().insert(key, (unit, entry.offset)));
+                return Ok((unit, entry.offset));
+            }
+        }
+
+        // with `rayon`, the types of all units are indexed in the background.
+        #[cfg(feature = "rayon")]
+        if let Some(type_names) = indexes.type_names() {
+            let Some(&(unit, offset)) = type_names.get(name.as_bytes()) else {
+                return Err(crate::error::type_not_found(name));
+            };
+            let unit = unit_at(context, unit)?;
+            TYPE_CACHE.with(|cache| cache.borrow_mut().insert(key, (unit, offset)));
+            return Ok((unit, offset));
+        }
+
+        // units without indexes are scanned.
+        for unit in units_of(context)? {
+            if let crate::gimli::UnitSectionOffset::DebugInfoOffset(offset) = unit.header.offset() {
+                if indexes.covers(offset) {
+                    continue;
+                }
+            }
+            if let Some(offset) = crate::type_named(dwarf, unit, name)? {
+                TYPE_CACHE.with(|cache| cache.borrow_mut().insert(key, (unit, offset)));
+                return Ok((unit, offset));
+            }
+        }
+
+        Err(crate::error::type_not_found(name))
+    }
+
+    /// Produces the DWARF units and entry offsets of the types whose
+    /// fully-qualified names satisfy `matches`; with the `index_cache`, if
+    /// `persisted`.
+    pub fn units_and_dies_of_types(
+        context: &'static Context,
+        matches: &mut dyn FnMut(&[u8]) -> bool,
+        persisted: bool,
+    ) -> Result<Vec<(&'static crate::gimli::Unit<Addr2LineReader>, UnitOffset)>, crate::Error> {
+        let dwarf = context.dwarf();
+        let mut types = vec![];
+        if persisted {
+            let type_names = indexes_of(context)?.persisted_type_names()?;
+            // types are matched in the order of their debuginfo.
+            let mut found: Vec<_> = type_names.iter().collect();
+            found.sort_unstable_by_key(|(_, &(unit, offset))| (unit, offset));
+            for (name, &(unit, offset)) in found {
+                if matches(name) {
+                    types.push((unit_at(context, unit)?, offset));
+                }
+            }
+            return Ok(types);
+        }
+        for unit in units_of(context)? {
+            crate::types_in(dwarf, unit, |name, offset| {
+                if matches(name) {
+                    types.push((unit, offset));
+                }
+                std::ops::ControlFlow::Continue(())
+            })?;
+        }
+        Ok(types)
+    }
+
+    /// Produces all of the DWARF units of `context`.
+    fn units_of(
+        context: &'static Context,
+    ) -> Result<&'static [crate::gimli::Unit<Addr2LineReader>], crate::Error> {
+        type Cached = &'static [crate::gimli::Unit<Addr2LineReader>];
+
+        thread_local! {
+            pub static UNITS_CACHE: RefCell<HashMap<usize, Cached>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let key = context as *const Context as usize;
+        if let Some(cached) = UNITS_CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let dwarf = context.dwarf();
+        let mut units = vec![];
+        let mut headers = dwarf.units();
+        while let Some(header) = headers.next()? {
+            units.push(dwarf.unit(header)?);
+        }
+        // units are leaked so that, like those of `addr2line`, they live as
+        // long as their `Context`.
+        let units: &'static [_] = Vec::leak(units);
+        UNITS_CACHE.with(|cache| cache.borrow_mut().insert(key, units));
+        Ok(units)
+    }
 }
 
 pub(crate) mod private {
-    #[derive(Copy, Clone, Debug)]
-    pub struct DefaultProvider {}
+    #[derive(Clone, Debug)]
+    pub struct DefaultProvider {
+        pub(crate) index_cache: bool,
+        pub(crate) validation: bool,
+        pub(crate) container_adapters: &'static [&'static dyn crate::ContainerAdapter],
+        pub(crate) format_overrides: Option<std::sync::Arc<[crate::FormatOverride]>>,
+        pub(crate) redaction: Option<&'static crate::Redaction>,
+    }
 }
 
 pub(crate) use private::DefaultProvider;
 
-/// The default provider of DWARF debug info.
-pub fn default_provider() -> Result<DefaultProvider, crate::Error> {
-    unsafe impl DebugInfoProvider for DefaultProvider {
-        type Reader = Addr2LineReader;
+unsafe impl DebugInfoProvider for DefaultProvider {
+    type Reader = Addr2LineReader;
+
+    fn info_for(&self, fn_addr: u64) -> Result<DebugInfo<'static, Self::Reader>, crate::Error> {
+        let (context, unit, entry) = crate::dbginfo_provider::unit_and_die_of_fn(fn_addr as _)?;
+        Ok(DebugInfo {
+            context,
+            unit,
+            entry,
+        })
+    }
+
+    fn info_for_vtable(
+        &self,
+        vtable_addr: u64,
+    ) -> Result<DebugInfo<'static, Self::Reader>, crate::Error> {
+        let (context, static_addr) = crate::dbginfo_provider::context_of(vtable_addr as _)?;
+        let (unit, entry) = crate::dbginfo_provider::unit_and_die_of_vtable(context, static_addr)?;
+        Ok(DebugInfo {
+            context,
+            unit,
+            entry,
+        })
+    }
+
+    fn info_for_type(&self, name: &str) -> Result<DebugInfo<'static, Self::Reader>, crate::Error> {
+        // types are looked up in the binary containing deflect itself.
+        let anchor = default_provider as *const () as usize;
+        let (context, _) = crate::dbginfo_provider::context_of(anchor)?;
+        let (unit, entry) =
+            crate::dbginfo_provider::unit_and_die_of_type(context, name, self.index_cache)?;
+        Ok(DebugInfo {
+            context,
+            unit,
+            entry,
+        })
+    }
 
-        fn info_for(&self, fn_addr: u64) -> Result<DebugInfo<'static, Self::Reader>, crate::Error> {
-            let (context, static_addr) = crate::dbginfo_provider::context_of(fn_addr as _)?;
-            let (unit, entry) = crate::dw_unit_and_die_of_addr(context, static_addr)?;
-            Ok(DebugInfo {
+    fn info_for_types(
+        &self,
+        matches: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<Vec<DebugInfo<'static, Self::Reader>>, crate::Error> {
+        let anchor = default_provider as *const () as usize;
+        let (context, _) = crate::dbginfo_provider::context_of(anchor)?;
+        let types =
+            crate::dbginfo_provider::units_and_dies_of_types(context, matches, self.index_cache)?;
+        Ok(types
+            .into_iter()
+            .map(|(unit, entry)| DebugInfo {
                 context,
                 unit,
                 entry,
             })
+            .collect())
+    }
+
+    fn validates_bit_patterns(&self) -> bool {
+        self.validation
+    }
+
+    fn container_adapters(&self) -> &[&'static dyn ContainerAdapter] {
+        self.container_adapters
+    }
+
+    fn format_overrides(&self) -> &[FormatOverride] {
+        self.format_overrides.as_deref().unwrap_or_default()
+    }
+
+    fn redaction(&self) -> Option<&Redaction> {
+        self.redaction
+    }
+}
+
+/// The default provider of DWARF debug info.
+pub fn default_provider() -> Result<DefaultProvider, crate::Error> {
+    Ok(DefaultProvider {
+        index_cache: false,
+        validation: false,
+        container_adapters: &[],
+        format_overrides: None,
+        redaction: None,
+    })
+}
+
+impl DefaultProvider {
+    /// Persists the index of type names built by this provider to
+    /// `~/.cache/deflect/<build-id>` (or under `$XDG_CACHE_HOME`), and reloads
+    /// it on later runs of the same binary; e.g., so that short-lived programs
+    /// that [find types][find_type] at startup need not scan their debuginfo
+    /// each time.
+    ///
+    /// Binaries without a build ID are not cached.
+    pub fn with_index_cache(self) -> Self {
+        Self {
+            index_cache: true,
+            ..self
+        }
+    }
+
+    /// Checks that reflected `bool`s and `char`s hold valid bit patterns; see
+    /// [`DebugInfoProvider::validates_bit_patterns`].
+    pub fn with_validation(self) -> Self {
+        Self {
+            validation: true,
+            ..self
+        }
+    }
+
+    /// Displays and iterates the containers that `adapter` matches as it
+    /// describes; adapters are tried in the order in which they were added.
+    ///
+    /// Adapters are registered once per program (e.g., at startup); each
+    /// registration leaks a copy of this provider's list of adapters.
+    pub fn with_container_adapter(self, adapter: &'static dyn ContainerAdapter) -> Self {
+        let mut container_adapters = self.container_adapters.to_vec();
+        container_adapters.push(adapter);
+        Self {
+            container_adapters: Vec::leak(container_adapters),
+            ..self
+        }
+    }
+
+    /// Displays the values of the type with the canonical name `type_name`
+    /// (e.g., `uuid::Uuid`) with `hook`, which is passed their bytes; in
+    /// their [`Display`][fmt::Display], JSON, snapshots and diffs. The first
+    /// override registered for a type is used.
+    ///
+    /// ```
+    /// struct Uuid([u8; 16]);
+    ///
+    /// fn main() -> Result<(), deflect::anyhow::Error> {
+    ///     let type_name = std::any::type_name::<Uuid>();
+    ///     let context = unsafe {
+    ///         deflect::default_provider()?.with_format_override(type_name, |bytes, f| {
+    ///             bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    ///         })
+    ///     };
+    ///     let uuid = Uuid([0xab; 16]);
+    ///     let erased: &dyn deflect::Reflect = &uuid;
+    ///     assert_eq!(erased.reflect(&context)?.to_string(), "ab".repeat(16));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// ## Safety
+    /// The named type must have no padding, nor any other uninitialized
+    /// bytes, as `hook` is passed all of the bytes of its values.
+    pub unsafe fn with_format_override<F>(self, type_name: &str, hook: F) -> Self
+    where
+        F: Fn(&[u8], &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+    {
+        let mut format_overrides = self.format_overrides().to_vec();
+        format_overrides.push(FormatOverride {
+            type_name: crate::format::canonical(type_name).into(),
+            hook: Arc::new(hook),
+        });
+        Self {
+            format_overrides: Some(format_overrides.into()),
+            ..self
+        }
+    }
+
+    /// Redacts the values that `redaction` describes; see [`Redaction`].
+    ///
+    /// Policies are registered once per program (e.g., at startup); each
+    /// registration leaks `redaction`, which replaces any policy registered
+    /// before.
+    pub fn with_redaction(self, redaction: Redaction) -> Self {
+        Self {
+            redaction: Some(Box::leak(Box::new(redaction))),
+            ..self
         }
     }
 
-    Ok(DefaultProvider {})
+    /// Produces a `'static` reference to this provider.
+    ///
+    /// The debuginfo read by the default provider lives for the rest of the
+    /// program, so values and types reflected with a `'static` provider only
+    /// borrow the reflected data. They can then be returned from helper
+    /// functions, without also keeping a local provider borrowed:
+    ///
+    /// ```
+    /// fn reflect<T>(value: &T) -> Result<deflect::Value<'_, 'static>, deflect::anyhow::Error> {
+    ///     let context = deflect::default_provider()?.into_static();
+    ///     let erased: &dyn deflect::Reflect = value;
+    ///     erased.reflect(context)
+    /// }
+    ///
+    /// assert_eq!(reflect(&Some(42u8))?.to_string(), "Some(42)");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// A provider configured with container adapters, format overrides or a
+    /// redaction policy is leaked the first time it is made `'static`; the
+    /// same reference is produced for it (and its clones) thereafter.
+    pub fn into_static(self) -> &'static Self {
+        static PROVIDERS: [[DefaultProvider; 2]; 2] = [
+            [
+                DefaultProvider {
+                    index_cache: false,
+                    validation: false,
+                    container_adapters: &[],
+                    format_overrides: None,
+                    redaction: None,
+                },
+                DefaultProvider {
+                    index_cache: false,
+                    validation: true,
+                    container_adapters: &[],
+                    format_overrides: None,
+                    redaction: None,
+                },
+            ],
+            [
+                DefaultProvider {
+                    index_cache: true,
+                    validation: false,
+                    container_adapters: &[],
+                    format_overrides: None,
+                    redaction: None,
+                },
+                DefaultProvider {
+                    index_cache: true,
+                    validation: true,
+                    container_adapters: &[],
+                    format_overrides: None,
+                    redaction: None,
+                },
+            ],
+        ];
+
+        // providers that are configured with container adapters, format
+        // overrides or a redaction policy are leaked once per configuration.
+        type Key = (bool, bool, usize, usize, usize, usize, usize);
+        static INTERNED: Lazy<DashMap<Key, &'static DefaultProvider>> = Lazy::new(DashMap::new);
+
+        if self.container_adapters.is_empty()
+            && self.format_overrides().is_empty()
+            && self.redaction.is_none()
+        {
+            return &PROVIDERS[self.index_cache as usize][self.validation as usize];
+        }
+        let key = (
+            self.index_cache,
+            self.validation,
+            self.container_adapters.as_ptr() as usize,
+            self.container_adapters.len(),
+            self.format_overrides().as_ptr() as usize,
+            self.format_overrides().len(),
+            self.redaction
+                .map_or(0, |redaction| redaction as *const Redaction as usize),
+        );
+        *INTERNED
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(self)))
+    }
 }
 
 /// A reflectable type.
@@ -256,7 +1013,9 @@ pub trait Reflect {
     /// unit.
     #[inline(never)]
     fn local_type_id(&self) -> usize {
-        <Self as Reflect>::local_type_id as usize
+        // the address must be of this out-of-line function, whose debuginfo
+        // describes `Self`, even in optimized builds.
+        std::hint::black_box(<Self as Reflect>::local_type_id as *const () as usize)
     }
 }
 
@@ -273,6 +1032,20 @@ impl dyn Reflect + '_ {
             slice_from_raw_parts(self as *const Self as *const Byte, mem::size_of_val(self));
         unsafe { value::Value::with_type(r#type, &*value, provider) }
     }
+
+    /// Produces a reflected [`Place`][value::Place] of `&mut self`, through
+    /// which its fields may be overwritten.
+    pub fn reflect_mut<'value, 'dwarf, P: DebugInfoProvider>(
+        &'value mut self,
+        provider: &'dwarf P,
+    ) -> Result<value::Place<'value, 'dwarf, P>, crate::Error> {
+        let r#type = do_reflect_type(self.local_type_id(), provider)?;
+        let len = mem::size_of_val(self);
+        let ptr = self as *mut Self as *mut Byte;
+        // SAFETY: `ptr` is derived from the exclusive borrow of `self`, which
+        // the place holds for `'value`.
+        Ok(unsafe { value::Place::new(r#type, ptr, len, provider) })
+    }
 }
 
 /// Produces a reflected [`Type`].
@@ -280,7 +1053,59 @@ impl dyn Reflect + '_ {
 pub fn reflect_type<'dwarf, T, P: DebugInfoProvider>(
     provider: &'dwarf P,
 ) -> Result<Type<'dwarf, P::Reader>, crate::Error> {
-    do_reflect_type(<T as Reflect>::local_type_id as usize, provider)
+    do_reflect_type(
+        <T as Reflect>::local_type_id as *const () as usize,
+        provider,
+    )
+}
+
+/// Produces the reflected [`Type`] with the given fully-qualified `name`
+/// (e.g., `my_crate::Config`), without a value or static knowledge of it.
+///
+/// Names are matched exactly against the namespaced names of types in the
+/// debug info, which for most types are those produced by
+/// [`std::any::type_name`]. Only types with debug info in this program (e.g.,
+/// those it uses) can be found.
+pub fn find_type<'dwarf, P: DebugInfoProvider>(
+    provider: &'dwarf P,
+    name: &str,
+) -> Result<Type<'dwarf, P::Reader>, crate::Error> {
+    let DebugInfo {
+        context,
+        unit,
+        entry,
+    } = provider.info_for_type(name)?;
+    let entry = unit.entry(entry)?;
+    Type::from_die(context.dwarf(), unit, entry)
+}
+
+/// Produces the reflected [`Type`]s that match `filter`; e.g., all types in
+/// the `tokio::sync` module larger than a cache line.
+///
+/// As with [`find_type`], only types with debug info in this program are
+/// found. Types are produced in the order of their debug info; a type
+/// described by several compilation units (e.g., a generic type instantiated
+/// by several crates) is produced once.
+pub fn find_types<'dwarf, P: DebugInfoProvider>(
+    provider: &'dwarf P,
+    filter: &TypeFilter,
+) -> Result<Vec<Type<'dwarf, P::Reader>>, crate::Error> {
+    let mut seen = std::collections::HashSet::new();
+    let mut matches = |name: &[u8]| filter.matches_name(name) && seen.insert(name.to_owned());
+    let mut types = vec![];
+    for info in provider.info_for_types(&mut matches)? {
+        let DebugInfo {
+            context,
+            unit,
+            entry,
+        } = info;
+        let entry = unit.entry(entry)?;
+        let r#type = Type::from_die(context.dwarf(), unit, entry)?;
+        if filter.matches_layout(&r#type)? {
+            types.push(r#type);
+        }
+    }
+    Ok(types)
 }
 
 fn do_reflect_type<'dwarf, P: DebugInfoProvider>(
@@ -304,49 +1129,366 @@ fn dw_unit_and_die_of_addr<'ctx, R>(
 where
     R: crate::gimli::Reader<Offset = usize>,
 {
+    let unit = ctx.find_dwarf_unit(static_addr as u64);
     let Some(frame) = ctx
         .find_frames(static_addr as u64)?
         .next()? else {
-            bail!("could not find a DWARF frame for the address 0x{static_addr:x}")
+            return Err(diagnose(unit, error::symbol_not_found(static_addr)))
         };
 
     let Some(dw_die_offset) = frame.dw_die_offset else {
-        bail!("could not find the DWARF unit offset corresponding to the DIE of the function at static address 0x{static_addr:x}")
+        return Err(diagnose(unit, error::symbol_not_found(static_addr)))
     };
 
-    let Some(unit) = ctx.find_dwarf_unit(static_addr as u64) else {
-        bail!("could not find the DWARF unit containing debug info for the function at static address 0x{static_addr:x}")
+    let Some(unit) = unit else {
+        return Err(error::no_debug_info(error::MISSING_DEBUG_INFO));
     };
 
+    type_param_of(ctx.dwarf(), unit, dw_die_offset)
+}
+
+/// Produces the unit and entry offset of the type parameter of the function at
+/// `dw_die_offset`; i.e., of the `T` of a `<T as Reflect>::local_type_id`.
+fn type_param_of<'dwarf, R>(
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R>,
+    dw_die_offset: UnitOffset,
+) -> Result<(&'dwarf crate::gimli::Unit<R>, UnitOffset), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
     let mut ty = None;
     let mut tree = unit.entries_tree(Some(dw_die_offset))?;
     let mut children = tree.root()?.children();
 
     while let Some(child) = children.next()? {
         if ty.is_none() && child.entry().tag() == crate::gimli::DW_TAG_template_type_parameter {
-            ty = Some(get_type(child.entry())?);
+            ty = Some(get_type(dwarf, unit, child.entry())?);
             break;
         }
     }
 
     let Some(ty) = ty else {
-        return Err(error::missing_child(crate::gimli::DW_TAG_template_type_parameter))
+        return Err(diagnose(Some(unit), error::missing_template_parameter()));
     };
 
-    Ok((unit, ty))
+    Ok(ty)
 }
 
-impl fmt::Debug for dyn Reflect {
+/// Explains why the debuginfo of a function in `unit` (if there is any) could
+/// not be reflected, as well as `err` does; e.g., because it was built with
+/// debuginfo that does not describe types.
+fn diagnose<R>(unit: Option<&crate::gimli::Unit<R>>, err: crate::Error) -> crate::Error
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let Some(unit) = unit else {
+        return error::no_debug_info(error::MISSING_DEBUG_INFO);
+    };
+    match describes_types(unit) {
+        Ok(false) => error::no_debug_info(error::LIMITED_DEBUG_INFO),
+        _ => err,
+    }
+}
+
+/// Whether `unit` describes any types; those built with limited debuginfo
+/// only describe functions.
+fn describes_types<R>(unit: &crate::gimli::Unit<R>) -> Result<bool, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut entries = unit.entries();
+    while let Some((_, entry)) = entries.next_dfs()? {
+        if matches!(
+            entry.tag(),
+            crate::gimli::DW_TAG_base_type
+                | crate::gimli::DW_TAG_structure_type
+                | crate::gimli::DW_TAG_enumeration_type
+                | crate::gimli::DW_TAG_union_type
+                | crate::gimli::DW_TAG_pointer_type
+        ) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Produces the offsets of the ancestors of the entry at `offset`, outermost
+/// first.
+fn ancestors_of<R>(
+    unit: &crate::gimli::Unit<R>,
+    offset: UnitOffset,
+) -> Result<Vec<UnitOffset>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut ancestors: Vec<UnitOffset> = vec![];
+    let mut entries = unit.entries();
+    while let Some((delta_depth, entry)) = entries.next_dfs()? {
+        // after each step, `ancestors` holds the entry and its ancestors, so
+        // its length is the depth of the entry.
+        let depth = (ancestors.len() as isize + delta_depth).max(1) as usize;
+        ancestors.truncate(depth - 1);
+        if entry.offset() == offset {
+            return Ok(ancestors);
+        }
+        ancestors.push(entry.offset());
+    }
+    Ok(vec![])
+}
+
+/// Produces the names of the namespaces enclosing the entry at `offset`,
+/// outermost first.
+fn namespace_path_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    offset: UnitOffset,
+) -> Result<Vec<Vec<u8>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut path = vec![];
+    for ancestor in ancestors_of(unit, offset)? {
+        let ancestor = unit.entry(ancestor)?;
+        if ancestor.tag() != crate::gimli::DW_TAG_namespace {
+            continue;
+        }
+        if let Some(name) = ancestor.attr_value(crate::gimli::DW_AT_name)? {
+            path.push(dwarf.attr_string(unit, name)?.to_slice()?.into_owned());
+        }
+    }
+    Ok(path)
+}
+
+/// Produces the fully-qualified name of the type defined at `offset`, if it
+/// is a type definition that [`types_in`] would visit.
+fn qualified_name_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    offset: UnitOffset,
+) -> Result<Option<Vec<u8>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let entry = unit.entry(offset)?;
+    let is_type = matches!(
+        entry.tag(),
+        crate::gimli::DW_TAG_base_type
+            | crate::gimli::DW_TAG_structure_type
+            | crate::gimli::DW_TAG_enumeration_type
+            | crate::gimli::DW_TAG_union_type
+    );
+    if !is_type || entry.attr_value(crate::gimli::DW_AT_declaration)?.is_some() {
+        return Ok(None);
+    }
+    let Some(name) = entry.attr_value(crate::gimli::DW_AT_name)? else { return Ok(None) };
+    let mut qualified = vec![];
+    // the first ancestor is the unit itself.
+    for ancestor in ancestors_of(unit, offset)?.into_iter().skip(1) {
+        let ancestor = unit.entry(ancestor)?;
+        if ancestor.tag() != crate::gimli::DW_TAG_namespace {
+            return Ok(None);
+        }
+        let Some(namespace) = ancestor.attr_value(crate::gimli::DW_AT_name)? else {
+            return Ok(None);
+        };
+        qualified.extend_from_slice(&dwarf.attr_string(unit, namespace)?.to_slice()?);
+        qualified.extend_from_slice(b"::");
+    }
+    qualified.extend_from_slice(&dwarf.attr_string(unit, name)?.to_slice()?);
+    Ok(Some(qualified))
+}
+
+/// Produces the entry offset of the definition of the type named `name`, if
+/// it is in `unit`.
+fn type_named<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    name: &str,
+) -> Result<Option<UnitOffset>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut found = None;
+    types_in(dwarf, unit, |qualified, offset| {
+        if qualified == name.as_bytes() {
+            found = Some(offset);
+            return std::ops::ControlFlow::Break(());
+        }
+        std::ops::ControlFlow::Continue(())
+    })?;
+    Ok(found)
+}
+
+/// Calls `visit` with the fully-qualified name and entry offset of each type
+/// defined in `unit`, until it breaks.
+fn types_in<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    mut visit: impl FnMut(&[u8], UnitOffset) -> std::ops::ControlFlow<()>,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    // the namespace names of the ancestors of the current entry; `None` for
+    // ancestors that are not namespaces.
+    let mut scopes: Vec<Option<Vec<u8>>> = vec![];
+    let mut entries = unit.entries();
+    while let Some((delta_depth, entry)) = entries.next_dfs()? {
+        let depth = (scopes.len() as isize + delta_depth).max(1) as usize;
+        scopes.truncate(depth - 1);
+        let tag = entry.tag();
+        let is_type = matches!(
+            tag,
+            crate::gimli::DW_TAG_base_type
+                | crate::gimli::DW_TAG_structure_type
+                | crate::gimli::DW_TAG_enumeration_type
+                | crate::gimli::DW_TAG_union_type
+        );
+        if !is_type && tag != crate::gimli::DW_TAG_namespace {
+            scopes.push(None);
+            continue;
+        }
+        let entry_name = match entry.attr_value(crate::gimli::DW_AT_name)? {
+            Some(attr) => Some(dwarf.attr_string(unit, attr)?.to_slice()?.into_owned()),
+            None => None,
+        };
+        if is_type && entry.attr_value(crate::gimli::DW_AT_declaration)?.is_none() {
+            if let Some(entry_name) = &entry_name {
+                // the first scope is that of the unit itself.
+                let namespaces: Option<Vec<&[u8]>> =
+                    scopes.iter().skip(1).map(Option::as_deref).collect();
+                if let Some(namespaces) = namespaces {
+                    let mut qualified = namespaces.join(&b"::"[..]);
+                    if !qualified.is_empty() {
+                        qualified.extend_from_slice(b"::");
+                    }
+                    qualified.extend_from_slice(entry_name);
+                    if visit(&qualified, entry.offset()).is_break() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        scopes.push(if tag == crate::gimli::DW_TAG_namespace {
+            entry_name
+        } else {
+            None
+        });
+    }
+    Ok(())
+}
+
+/// Produces the entry offset of the type implementing the vtable at
+/// `static_addr`, if that vtable is described by a `{vtable}` variable in
+/// `unit`.
+fn vtable_impl_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    static_addr: usize,
+) -> Result<Option<UnitOffset>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut entries = unit.entries();
+    while let Some((_, entry)) = entries.next_dfs()? {
+        if entry.tag() != crate::gimli::DW_TAG_variable {
+            continue;
+        }
+        let Some(AttributeValue::Exprloc(location)) = entry.attr_value(crate::gimli::DW_AT_location)?
+        else {
+            continue;
+        };
+        let mut operations = location.operations(unit.encoding());
+        let address = match operations.next()? {
+            Some(crate::gimli::Operation::Address { address }) => address,
+            // DWARF 5 locates variables by their index in `.debug_addr`.
+            Some(crate::gimli::Operation::AddressIndex { index }) => dwarf.address(unit, index)?,
+            _ => continue,
+        };
+        if address != static_addr as u64 {
+            continue;
+        }
+        let Some(name) = entry.attr_value(crate::gimli::DW_AT_name)? else {
+            continue;
+        };
+        if !dwarf.attr_string(unit, name)?.to_slice()?.ends_with(b"::{vtable}") {
+            continue;
+        }
+        // vtables are described in the unit that emits them, along with the
+        // type of their `{vtable}` variable.
+        let vtable_type = get_attr_ref(entry, crate::gimli::DW_AT_type)?
+            .ok_or_else(|| error::invalid_attr(crate::gimli::DW_AT_type))?;
+        let vtable_type = unit.entry(vtable_type)?;
+        return match get(&vtable_type, crate::gimli::DW_AT_containing_type)? {
+            AttributeValue::UnitRef(offset) => Ok(Some(offset)),
+            _ => Err(error::invalid_attr(crate::gimli::DW_AT_containing_type)),
+        };
+    }
+    Ok(None)
+}
+
+impl fmt::Debug for dyn Reflect + '_ {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let context = default_provider().map_err(crate::fmt_err)?;
-        let value = self.reflect(&context).map_err(crate::fmt_err)?;
-        fmt::Display::fmt(&value, f)
+        crate::format::recover(f, |f| {
+            let context = default_provider().map_err(crate::fmt_err)?;
+            let value = self.reflect(&context).map_err(crate::fmt_err)?;
+            fmt::Display::fmt(&value, f)
+        })
     }
 }
 
+/// Prints and returns the value of a given expression, like [`std::dbg!`], but
+/// with the expression's reflected value, rather than its [`Debug`] output.
+///
+/// The value is pretty-printed to stderr (as with `{:#}`), prefixed with the
+/// file, line and column of the invocation, and the expression's source text.
+/// Its type need not implement [`Debug`]:
+///
+/// ```
+/// # #[allow(dead_code)]
+/// struct Config {
+///     retries: u8,
+/// }
+///
+/// let config = deflect::dbg!(Config { retries: 3 });
+/// assert_eq!(config.retries, 3);
+/// ```
+///
+/// [`Debug`]: std::fmt::Debug
+#[macro_export]
+macro_rules! dbg {
+    () => {
+        ::std::eprintln!("[{}:{}:{}]", ::std::file!(), ::std::line!(), ::std::column!())
+    };
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                ::std::eprintln!(
+                    "[{}:{}:{}] {} = {:#?}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::column!(),
+                    ::std::stringify!($val),
+                    &tmp as &dyn $crate::Reflect
+                );
+                tmp
+            }
+        }
+    };
+    ($($val:expr),+ $(,)?) => {
+        ($($crate::dbg!($val)),+,)
+    };
+}
+
 macro_rules! generate_type_and_value {
     ($($(#[$attr:meta])* $t:ident,)*) => {
         /// A reflected type.
+        ///
+        /// Types are [`Send`], as are [values][Value], if their reader is
+        /// [`Send`] and [`Sync`]; but not [`Sync`], as the debuginfo entries
+        /// they hold cache the extent of their attributes in a
+        /// [`Cell`][std::cell::Cell].
         #[allow(non_camel_case_types)]
         #[derive(Debug, Clone)]
         #[non_exhaustive]
@@ -365,9 +1507,9 @@ macro_rules! generate_type_and_value {
             R: crate::gimli::Reader<Offset = usize>,
         {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                match self {
+                crate::format::recover(f, |f| match self {
                     $(Self::$t(v) => v.fmt(f),)*
-                }
+                })
             }
         }
 
@@ -439,9 +1581,16 @@ macro_rules! generate_type_and_value {
         )*
 
         /// A reflected value.
+        ///
+        /// This enum is exhaustive, so that reflected values may be matched on
+        /// by kind, rather than by successive [`TryFrom`] conversions.
+        ///
+        /// Values are [`Send`], if their provider is, and may be formatted on
+        /// other threads, following references into other units through the
+        /// memos of their [`MemoContext`]; but, like [types][Type], they are
+        /// not [`Sync`].
         #[allow(non_camel_case_types)]
         #[derive(Debug)]
-        #[non_exhaustive]
         pub enum Value<'value, 'dwarf, P = crate::DefaultProvider>
         where
             P: crate::DebugInfoProvider,
@@ -456,6 +1605,28 @@ macro_rules! generate_type_and_value {
         where
             P: crate::DebugInfoProvider,
         {
+            /// Displays this value with the given `options`.
+            pub fn display_with(
+                &self,
+                options: crate::FormatOptions,
+            ) -> crate::DisplayWith<&Self> {
+                crate::DisplayWith::new(self, options)
+            }
+
+            /// The type of this value.
+            pub(crate) fn r#type(&self) -> crate::schema::Type<'dwarf, P::Reader> {
+                match self {
+                    $(Self::$t(v) => crate::schema::Type::$t(v.schema().clone()),)*
+                }
+            }
+
+            /// The bytes of this value.
+            pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+                match self {
+                    $(Self::$t(v) => v.bytes(),)*
+                }
+            }
+
             /// Safety: `value` absolutely must have the correct `type`.
             pub(crate) unsafe fn with_type(
                 r#type: crate::schema::Type<'dwarf, P::Reader>,
@@ -473,9 +1644,26 @@ macro_rules! generate_type_and_value {
             P: crate::DebugInfoProvider,
         {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                match self {
-                    $(Self::$t(v) => v.fmt(f),)*
-                }
+                crate::format::root(f, self.bytes(), |f| crate::format::nested(f, |f| {
+                    crate::format::recover(f, |f| {
+                        if let Some(overridden) = self.overridden().map_err(crate::fmt_err)? {
+                            return overridden.fmt(f);
+                        }
+                        if crate::format::hex_integers() {
+                            if let Some(result) = self.fmt_hex(f) {
+                                return result;
+                            }
+                        }
+                        if crate::format::std_adapters() {
+                            if let Some(display) = crate::adapters::display(self) {
+                                return f.write_str(&display);
+                            }
+                        }
+                        match self {
+                            $(Self::$t(v) => v.fmt(f),)*
+                        }
+                    })
+                }))
             }
         }
 
@@ -606,6 +1794,24 @@ generate_type_and_value! {
     /// A reflected [`array`][prim@array].
     Array,
 
+    /// A reflected atomic (e.g., [`AtomicUsize`][std::sync::atomic::AtomicUsize]).
+    Atomic,
+
+    /// A reflected non-zero integer (e.g., [`NonZeroU32`][std::num::NonZeroU32]).
+    NonZero,
+
+    /// A reflected [`NonNull`][std::ptr::NonNull].
+    NonNull,
+
+    /// A reflected SIMD vector (e.g., [`__m128`][std::arch::x86_64::__m128]).
+    Vector,
+
+    /// A reflected [`ManuallyDrop`][std::mem::ManuallyDrop].
+    ManuallyDrop,
+
+    /// A reflected [`MaybeUninit`][std::mem::MaybeUninit].
+    MaybeUninit,
+
     /// A reflected [`Box`].
     Box,
 
@@ -615,6 +1821,9 @@ generate_type_and_value! {
     /// A reflected [`Box`]'d dyn.
     BoxedDyn,
 
+    /// A reflected `&dyn Trait` or `&mut dyn Trait`.
+    DynRef,
+
     /// A reflected slice.
     Slice,
 
@@ -662,25 +1871,54 @@ fn get<R: crate::gimli::Reader<Offset = usize>>(
         .ok_or_else(|| crate::error::missing_attr(attr))
 }
 
-fn get_opt<R: crate::gimli::Reader<Offset = usize>>(
+/// Produces the value of the attribute `attr` of `entry` or, if `entry` does
+/// not have it, of the entry it completes or is an instance of (per its
+/// `DW_AT_specification` or `DW_AT_abstract_origin`), if any.
+fn get_origin_opt<R: crate::gimli::Reader<Offset = usize>>(
+    unit: &crate::gimli::Unit<R>,
     entry: &crate::gimli::DebuggingInformationEntry<R>,
     attr: crate::gimli::DwAt,
 ) -> Result<Option<AttributeValue<R>>, crate::Error> {
-    Ok(entry.attr_value(attr)?)
+    if let Some(value) = entry.attr_value(attr)? {
+        return Ok(Some(value));
+    }
+    let mut origin = get_origin_of(entry)?;
+    // origins may chain (e.g., a concrete instance of the definition of a
+    // declaration), but never cycle; the bound guards malformed debuginfo.
+    for _ in 0..8 {
+        let Some(offset) = origin else { break };
+        let entry = unit.entry(offset)?;
+        if let Some(value) = entry.attr_value(attr)? {
+            return Ok(Some(value));
+        }
+        origin = get_origin_of(&entry)?;
+    }
+    Ok(None)
+}
+
+/// The entry that `entry` completes or is an instance of, if any.
+fn get_origin_of<R: crate::gimli::Reader<Offset = usize>>(
+    entry: &crate::gimli::DebuggingInformationEntry<R>,
+) -> Result<Option<UnitOffset>, crate::Error> {
+    match get_attr_ref(entry, crate::gimli::DW_AT_specification)? {
+        Some(offset) => Ok(Some(offset)),
+        None => get_attr_ref(entry, crate::gimli::DW_AT_abstract_origin),
+    }
 }
 
 fn get_size<R: crate::gimli::Reader<Offset = usize>>(
+    unit: &crate::gimli::Unit<R>,
     entry: &crate::gimli::DebuggingInformationEntry<R>,
 ) -> Result<u64, crate::Error> {
-    let size = get(entry, crate::gimli::DW_AT_byte_size)?;
-    size.udata_value()
-        .ok_or_else(|| crate::error::invalid_attr(crate::gimli::DW_AT_byte_size))
+    get_size_opt(unit, entry)?
+        .ok_or_else(|| crate::error::missing_attr(crate::gimli::DW_AT_byte_size))
 }
 
 fn get_size_opt<R: crate::gimli::Reader<Offset = usize>>(
+    unit: &crate::gimli::Unit<R>,
     entry: &crate::gimli::DebuggingInformationEntry<R>,
 ) -> Result<Option<u64>, crate::Error> {
-    let maybe_size = entry.attr_value(crate::gimli::DW_AT_byte_size)?;
+    let maybe_size = get_origin_opt(unit, entry, crate::gimli::DW_AT_byte_size)?;
     if let Some(size) = maybe_size {
         Ok(Some(size.udata_value().ok_or_else(|| {
             crate::error::invalid_attr(crate::gimli::DW_AT_byte_size)
@@ -690,6 +1928,63 @@ fn get_size_opt<R: crate::gimli::Reader<Offset = usize>>(
     }
 }
 
+/// The size of the addresses (e.g., of pointers, `usize`s and the lengths of
+/// slices) of the target that `unit` was compiled for; which may differ from
+/// this host's, if the reflected values were captured from another target.
+fn address_size<R: crate::gimli::Reader<Offset = usize>>(unit: &crate::gimli::Unit<R>) -> u64 {
+    unit.encoding().address_size.into()
+}
+
+/// Reads the address (or other address-sized integer) of `unit` at the start
+/// of `bytes`, in the byte order of `dwarf`.
+fn read_address<R: crate::gimli::Reader<Offset = usize>>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+    bytes: Bytes<'_>,
+) -> Result<u64, crate::Error> {
+    use crate::gimli::{Endianity, Section};
+    let len = usize::try_from(address_size(unit))?;
+    let Some(bytes) = bytes.get(..len) else {
+        return Err(crate::error::size_mismatch(len, bytes.len()));
+    };
+    // SAFETY: the bytes of addresses are initialized.
+    let bytes = unsafe { &*(bytes as *const [Byte] as *const [u8]) };
+    let endian = dwarf.debug_info.reader().endian();
+    Ok(match len {
+        2 => endian.read_u16(bytes).into(),
+        4 => endian.read_u32(bytes).into(),
+        8 => endian.read_u64(bytes),
+        _ => bail!("unsupported address size of {len} bytes"),
+    })
+}
+
+/// Reads the address-sized integer of `field` (e.g., the length of a slice)
+/// from the bytes of the value that contains it.
+fn read_address_field<R: crate::gimli::Reader<Offset = usize>>(
+    field: &crate::schema::Field<'_, R>,
+    value: Bytes<'_>,
+) -> Result<u64, crate::Error> {
+    let offset = usize::try_from(field.offset()?.address(0)?)?;
+    let bytes = value
+        .get(offset..)
+        .ok_or_else(|| crate::error::size_mismatch(offset, value.len()))?;
+    read_address(field.dwarf(), field.unit(), bytes)
+}
+
+/// Converts `addr`, an address of `unit`, to a pointer that may be
+/// dereferenced; fails if `unit` describes a target whose addresses are not as
+/// wide as this one's, as `addr` cannot then point into this program.
+fn host_address<R: crate::gimli::Reader<Offset = usize>>(
+    unit: &crate::gimli::Unit<R>,
+    addr: u64,
+) -> Result<*const Byte, crate::Error> {
+    let size = address_size(unit);
+    if size != std::mem::size_of::<usize>() as u64 {
+        return Err(crate::error::foreign_address(size));
+    }
+    Ok(usize::try_from(addr)? as *const Byte)
+}
+
 fn get_align<R: crate::gimli::Reader<Offset = usize>>(
     entry: &crate::gimli::DebuggingInformationEntry<R>,
 ) -> Result<Option<u64>, crate::Error> {
@@ -703,37 +1998,31 @@ fn get_align<R: crate::gimli::Reader<Offset = usize>>(
     }
 }
 
-fn get_type_ref<'entry, 'dwarf, R: crate::gimli::Reader<Offset = usize>>(
-    entry: &'entry crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
-) -> Result<UnitOffset, crate::Error> {
-    if let AttributeValue::UnitRef(offset) = get(entry, crate::gimli::DW_AT_type)? {
-        Ok(offset)
-    } else {
-        Err(crate::error::invalid_attr(crate::gimli::DW_AT_type))
-    }
-}
-
-fn get_type_res<'entry, 'dwarf, R: crate::gimli::Reader<Offset = usize>>(
+fn get_type_res<'dwarf, R: crate::gimli::Reader<Offset = usize>>(
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
     unit: &'dwarf crate::gimli::Unit<R, usize>,
-    entry: &'entry crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
-) -> Result<crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>, crate::Error> {
-    if let AttributeValue::UnitRef(offset) = get(entry, crate::gimli::DW_AT_type)? {
-        Ok(unit.entry(offset)?)
-    } else {
-        Err(crate::error::invalid_attr(crate::gimli::DW_AT_type))
-    }
+    entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+) -> Result<
+    (
+        &'dwarf crate::gimli::Unit<R, usize>,
+        crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ),
+    crate::Error,
+> {
+    let (unit, offset) = get_type(dwarf, unit, entry)?;
+    Ok((unit, unit.entry(offset)?))
 }
 
+/// Produces the unit and offset of the type of `entry`; possibly in another
+/// unit than `entry` (e.g., a type unit).
 fn get_type<'dwarf, R: crate::gimli::Reader<Offset = usize>>(
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R>,
     entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
-) -> Result<UnitOffset, crate::Error> {
+) -> Result<(&'dwarf crate::gimli::Unit<R>, UnitOffset), crate::Error> {
     let attr = crate::gimli::DW_AT_type;
-    let value = get(entry, attr)?;
-    if let AttributeValue::UnitRef(offset) = value {
-        Ok(offset)
-    } else {
-        Err(error::invalid_attr(attr))
-    }
+    let value = get_origin_opt(unit, entry, attr)?.ok_or_else(|| error::missing_attr(attr))?;
+    refs::resolve(dwarf, unit, value)?.ok_or_else(|| error::invalid_attr(attr))
 }
 
 fn get_file<'a, R: crate::gimli::Reader<Offset = usize> + 'a>(
@@ -760,6 +2049,17 @@ fn get_file<'a, R: crate::gimli::Reader<Offset = usize> + 'a>(
     Ok(Some(filename.into_owned().into()))
 }
 
+/// The file and line `entry` was declared at, if both are available; see
+/// [`schema::DeclLocation`].
+fn get_decl_location<R: crate::gimli::Reader<Offset = usize>>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R, usize>,
+    entry: &crate::gimli::DebuggingInformationEntry<R>,
+) -> Result<Option<(String, u64)>, crate::Error> {
+    let location = schema::DeclLocation::from_die(dwarf, unit, entry)?;
+    Ok(location.and_then(|location| Some((location.file().to_owned(), location.line()?))))
+}
+
 fn get_attr_ref<R: crate::gimli::Reader<Offset = usize>>(
     entry: &crate::gimli::DebuggingInformationEntry<R>,
     name: crate::gimli::DwAt,
@@ -810,7 +2110,9 @@ where
     }
 }
 
-fn fmt_err<E: fmt::Display>(err: E) -> fmt::Error {
-    eprintln!("ERROR: {err}");
+/// Records `err` as the cause of a formatting failure; see
+/// [`format::recover`].
+fn fmt_err<E: Into<crate::Error>>(err: E) -> fmt::Error {
+    format::record_error(err.into());
     fmt::Error
 }
@@ -0,0 +1,179 @@
+//! Memoization of what is read from [DWARF sections](crate::gimli::Dwarf).
+//!
+//! What is read of sections (e.g., their types, and the units referred to
+//! across units) is memoized by the [`MemoContext`] that owns them, and freed
+//! with it. Memos are found by the address of their sections, on any thread
+//! to which values read from them were sent; what is read of sections that
+//! are not memoized (e.g., those of providers that do not use a
+//! [`MemoContext`]) is read anew each time.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError, Weak},
+};
+
+use once_cell::sync::Lazy;
+
+/// The memos of all threads, by the address of their sections.
+static MEMOS: Lazy<Mutex<HashMap<usize, Weak<Store>>>> = Lazy::new(Default::default);
+
+/// An [`addr2line::Context`] that memoizes what is read of its DWARF
+/// sections, for as long as it lives.
+///
+/// Memos are shared by all threads, so that values sent to other threads
+/// (e.g., to be formatted there) may still follow references into other
+/// units; so the sections must be read by a [`Send`] and [`Sync`] reader.
+///
+/// Providers should produce their [`DebugInfo`][crate::DebugInfo] from a
+/// `MemoContext`, to which it dereferences; otherwise, types are read anew
+/// each time they are reflected, and references into other units than the
+/// one a type is described by cannot be followed.
+pub struct MemoContext<R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    // declared first, so that what was memoized is freed before the sections
+    // it borrows.
+    #[allow(dead_code)]
+    memos: Memos,
+    context: addr2line::Context<R>,
+}
+
+impl<R> MemoContext<R>
+where
+    R: crate::gimli::Reader<Offset = usize> + Send + Sync,
+{
+    /// Memoizes what is read of the sections of `context`.
+    pub fn new(context: addr2line::Context<R>) -> Self {
+        // SAFETY: `addr2line` keeps the sections behind an `Arc`, so they do
+        // not move with `context`; and `memos` is dropped before them.
+        let memos = unsafe { Memos::new(context.dwarf()) };
+        Self { memos, context }
+    }
+}
+
+impl<R> std::ops::Deref for MemoContext<R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Target = addr2line::Context<R>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.context
+    }
+}
+
+/// The memos of some DWARF sections; what was memoized is freed when they are
+/// dropped.
+pub(crate) struct Memos(#[allow(dead_code)] Arc<Store>);
+
+/// Memos by name; e.g., `"types"`.
+#[derive(Default)]
+struct Store(Mutex<HashMap<&'static str, Box<dyn Any>>>);
+
+// SAFETY: memos are only kept of sections read by `Send` and `Sync` readers
+// (see `Memos::new`), and hold only what was read of them: units, which are
+// `Send` and `Sync` with their readers, and values like types, which are
+// `Send` with their readers, and are only used with the lock held.
+unsafe impl Send for Store {}
+unsafe impl Sync for Store {}
+
+impl Store {
+    /// Locks these memos; they hold no invariants that a panic could break.
+    fn lock(&self) -> MutexGuard<'_, HashMap<&'static str, Box<dyn Any>>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Memos {
+    /// Memoizes what is read of `dwarf`, for all threads, until dropped.
+    ///
+    /// ## Safety
+    /// The memos must be dropped before `dwarf` is moved or freed, and nothing
+    /// read from `dwarf` may be used after they are dropped.
+    pub(crate) unsafe fn new<R>(dwarf: &crate::gimli::Dwarf<R>) -> Self
+    where
+        R: crate::gimli::Reader<Offset = usize> + Send + Sync,
+    {
+        let store = Arc::new(Store::default());
+        let mut memos = MEMOS.lock().unwrap_or_else(PoisonError::into_inner);
+        memos.retain(|_, store| store.strong_count() > 0);
+        memos.insert(address_of(dwarf), Arc::downgrade(&store));
+        Self(store)
+    }
+}
+
+/// Applies `f` to the memo named `name` of `dwarf`, if its sections are
+/// memoized; a memo is created by `Default` when first used.
+///
+/// The memo is locked while `f` is applied to it; so `f` may not read
+/// anything from `dwarf`.
+pub(crate) fn with_memo<R, M, T>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    name: &'static str,
+    f: impl FnOnce(&mut M) -> T,
+) -> Option<T>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+    M: Any + Default,
+{
+    let addr = address_of(dwarf);
+    let store = MEMOS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&addr)
+        .and_then(Weak::upgrade)?;
+    let mut memos = store.lock();
+    let memo = memos
+        .entry(name)
+        .or_insert_with(|| Box::new(M::default()))
+        .downcast_mut::<M>()
+        .expect("memos of the same name have the same type");
+    Some(f(memo))
+}
+
+/// The address of `dwarf`, by which its memos are found.
+fn address_of<R>(dwarf: &crate::gimli::Dwarf<R>) -> usize
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    dwarf as *const crate::gimli::Dwarf<R> as usize
+}
+
+/// A boxed value that may borrow the sections it was read from (and so is not
+/// `'static`), which is memoized with the memos of those sections.
+pub(crate) struct Erased {
+    ptr: *mut (),
+    drop: unsafe fn(*mut ()),
+}
+
+impl Erased {
+    /// Boxes `value`.
+    pub(crate) fn new<T>(value: T) -> Self {
+        unsafe fn drop<T>(ptr: *mut ()) {
+            std::mem::drop(Box::from_raw(ptr as *mut T));
+        }
+        Self {
+            ptr: Box::into_raw(Box::new(value)) as *mut (),
+            drop: drop::<T>,
+        }
+    }
+
+    /// The boxed value.
+    ///
+    /// ## Safety
+    /// The value must have been boxed as a `T`; and the borrow must not
+    /// outlive the borrow of the sections it was read from, through which its
+    /// memo was found.
+    pub(crate) unsafe fn get<'a, T>(&self) -> &'a T {
+        &*(self.ptr as *const T)
+    }
+}
+
+impl Drop for Erased {
+    fn drop(&mut self) {
+        // SAFETY: `drop` was instantiated with the type `ptr` was boxed as.
+        unsafe { (self.drop)(self.ptr) }
+    }
+}
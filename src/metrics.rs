@@ -0,0 +1,116 @@
+//! Rendering of the numeric leaves of reflected values as metrics, in the
+//! [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+
+use std::fmt::Write;
+
+use crate::{Reflect, Value};
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Renders each numeric [leaf][Self::leaves] of this value as a sample
+    /// in the Prometheus text exposition format, named by `prefix` and the
+    /// path of the leaf; e.g., `server_stats_requests_total 42`.
+    ///
+    /// Integers, floats, `bool`s (as `0` or `1`) and atomics are rendered;
+    /// other leaves (e.g., `char`s and `str`s) are skipped. Paths are
+    /// converted to metric names by replacing each run of characters other
+    /// than ASCII alphanumerics with `_`; e.g., the path `servers[2]::Some.0`
+    /// of the value prefixed `pool` becomes `pool_servers_2_Some_0`.
+    pub fn to_metrics(self, prefix: &str) -> Result<String, crate::Error> {
+        let mut metrics = String::new();
+        for leaf in self.leaves() {
+            let (path, value) = leaf?;
+            let Some(sample) = sample(&value) else {
+                continue;
+            };
+            let name = metric_name(prefix, &path.to_string());
+            writeln!(metrics, "{name} {sample}")?;
+        }
+        Ok(metrics)
+    }
+}
+
+/// Renders the numeric leaves of each object in the
+/// [registry][crate::registry] as metrics, prefixed by the name it is
+/// registered under, with [`Value::to_metrics`]; e.g., for serving from a
+/// `/metrics` endpoint.
+///
+/// Objects are rendered in the order of their names.
+pub fn render_metrics() -> Result<String, crate::Error> {
+    let provider = crate::default_provider()?;
+    let mut metrics = String::new();
+    for (name, registered) in crate::registry().objects() {
+        let object: &dyn Reflect = registered.object();
+        metrics.push_str(&object.reflect(&provider)?.to_metrics(&name)?);
+    }
+    Ok(metrics)
+}
+
+/// The value of the sample of `leaf`; `None`, if it is not numeric.
+fn sample<P>(leaf: &Value<'_, '_, P>) -> Option<String>
+where
+    P: crate::DebugInfoProvider,
+{
+    let sample = match leaf {
+        Value::bool(v) => u8::from(*v.value()).to_string(),
+        Value::f32(v) => float(f64::from(*v.value())),
+        Value::f64(v) => float(*v.value()),
+        Value::i8(_)
+        | Value::i16(_)
+        | Value::i32(_)
+        | Value::i64(_)
+        | Value::i128(_)
+        | Value::isize(_)
+        | Value::u8(_)
+        | Value::u16(_)
+        | Value::u32(_)
+        | Value::u64(_)
+        | Value::u128(_)
+        | Value::usize(_) => leaf.to_string(),
+        Value::Atomic(v) => match v.to_string().as_str() {
+            "true" => "1".to_owned(),
+            "false" => "0".to_owned(),
+            // atomic pointers are not numeric.
+            displayed => {
+                displayed.parse::<i128>().ok()?;
+                displayed.to_owned()
+            }
+        },
+        _ => return None,
+    };
+    Some(sample)
+}
+
+/// Renders `value` as a Prometheus float.
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        let sign = if value.is_sign_negative() { "-" } else { "+" };
+        format!("{sign}Inf")
+    } else {
+        value.to_string()
+    }
+}
+
+/// The metric name of the leaf at `path` of the value prefixed `prefix`.
+fn metric_name(prefix: &str, path: &str) -> String {
+    let mut name = String::with_capacity(prefix.len() + path.len() + 1);
+    for c in prefix.chars().chain(Some('_')).chain(path.chars()) {
+        if c.is_ascii_alphanumeric() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    while name.ends_with('_') {
+        name.pop();
+    }
+    // metric names may not begin with a digit.
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
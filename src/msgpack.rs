@@ -0,0 +1,213 @@
+//! Encoding of reflected values as [MessagePack](https://msgpack.org).
+
+use std::io;
+
+use rmp::encode;
+
+use crate::Value;
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Encodes this value as MessagePack.
+    ///
+    /// Requires the `msgpack` feature.
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, crate::Error> {
+        let mut buf = vec![];
+        self.write_msgpack(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Encodes this value as MessagePack into `writer`.
+    ///
+    /// Values are encoded as they are converted to JSON by default (with the
+    /// `json` feature): structs are encoded as maps from their field names to
+    /// their values, tuple structs as arrays, and newtypes as their inner
+    /// value; unit variants are encoded as their name, and other
+    /// variants as a map from their name to their fields. References and boxes
+    /// are followed, and raw pointers are encoded as their addresses.
+    ///
+    /// Unlike JSON, arrays and slices of bytes are encoded compactly as
+    /// binary, and 128-bit integers are encoded as 16 bytes of little-endian
+    /// binary.
+    ///
+    /// Requires the `msgpack` feature.
+    pub fn write_msgpack<W: io::Write>(&self, writer: &mut W) -> Result<(), crate::Error> {
+        match self {
+            Value::bool(v) => encode::write_bool(writer, *v.value())?,
+            Value::char(v) => encode::write_str(writer, v.value().encode_utf8(&mut [0; 4]))?,
+            Value::f32(v) => encode::write_f32(writer, *v.value())?,
+            Value::f64(v) => encode::write_f64(writer, *v.value())?,
+            Value::i8(v) => write_sint(writer, *v.value() as i64)?,
+            Value::i16(v) => write_sint(writer, *v.value() as i64)?,
+            Value::i32(v) => write_sint(writer, *v.value() as i64)?,
+            Value::i64(v) => write_sint(writer, *v.value())?,
+            Value::i128(v) => encode::write_bin(writer, &v.value().to_le_bytes())?,
+            Value::isize(v) => write_sint(writer, *v.value() as i64)?,
+            Value::u8(v) => write_uint(writer, *v.value() as u64)?,
+            Value::u16(v) => write_uint(writer, *v.value() as u64)?,
+            Value::u32(v) => write_uint(writer, *v.value() as u64)?,
+            Value::u64(v) => write_uint(writer, *v.value())?,
+            Value::u128(v) => encode::write_bin(writer, &v.value().to_le_bytes())?,
+            Value::usize(v) => write_uint(writer, *v.value() as u64)?,
+            Value::unit(_) => encode::write_nil(writer)?,
+            Value::str(v) => encode::write_str(writer, v.value()?)?,
+            Value::Array(v) => write_elements(writer, v.iter()?)?,
+            Value::Slice(v) => write_elements(writer, v.iter()?)?,
+            Value::BoxedSlice(v) => write_elements(writer, v.iter()?)?,
+            Value::Vector(v) => write_elements(writer, v.iter()?)?,
+            Value::Box(v) => v.deref()?.write_msgpack(writer)?,
+            Value::BoxedDyn(v) => v.deref()?.write_msgpack(writer)?,
+            Value::DynRef(v) => v.downcast_value()?.write_msgpack(writer)?,
+            Value::SharedRef(v) => v.deref()?.write_msgpack(writer)?,
+            Value::UniqueRef(v) => v.deref()?.write_msgpack(writer)?,
+            Value::NonNull(v) => write_uint(writer, v.addr()? as u64)?,
+            Value::ConstPtr(v) => write_uint(writer, v.addr() as u64)?,
+            Value::MutPtr(v) => write_uint(writer, v.addr() as u64)?,
+            Value::Function(v) => write_uint(writer, v.addr() as u64)?,
+            Value::NonZero(v) => v.value()?.write_msgpack(writer)?,
+            Value::ManuallyDrop(v) => v.value()?.write_msgpack(writer)?,
+            Value::MaybeUninit(_) => encode::write_nil(writer)?,
+            Value::Atomic(v) => {
+                use crate::schema::Type;
+                let bits = v.load_bits()?;
+                if v.schema().is_bool()? {
+                    encode::write_bool(writer, bits != 0)?;
+                    return Ok(());
+                }
+                match v.schema().value_type()? {
+                    Type::i8(_) => write_sint(writer, bits as i8 as i64)?,
+                    Type::i16(_) => write_sint(writer, bits as i16 as i64)?,
+                    Type::i32(_) => write_sint(writer, bits as i32 as i64)?,
+                    Type::i64(_) | Type::isize(_) => write_sint(writer, bits as i64)?,
+                    _ => write_uint(writer, bits)?,
+                }
+            }
+            Value::Struct(v) => {
+                if let Some(inner) = v.transparent_inner()? {
+                    return write_field(writer, &inner);
+                }
+                write_fields(writer, v.fields()?)?
+            }
+            Value::Enum(v) => {
+                if let Some(cow) = v.as_cow()? {
+                    match cow {
+                        crate::value::CowData::Str(value) => encode::write_str(writer, value)?,
+                        crate::value::CowData::Slice(iter) => write_elements(writer, iter)?,
+                    }
+                    return Ok(());
+                }
+                if let Some(option) = v.as_option()? {
+                    return match option {
+                        Some(value) => value.write_msgpack(writer),
+                        None => Ok(encode::write_nil(writer)?),
+                    };
+                }
+                let variant = v.variant()?;
+                let name = variant.name()?;
+                let name = name.to_string_lossy()?;
+                let mut fields = variant.fields()?;
+                if fields.iter()?.try_next()?.is_none() {
+                    encode::write_str(writer, &name)?;
+                } else {
+                    encode::write_map_len(writer, 1)?;
+                    encode::write_str(writer, &name)?;
+                    write_fields(writer, fields)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the elements produced by `iter`; as binary, if they are bytes.
+fn write_elements<W, P>(
+    writer: &mut W,
+    iter: crate::value::Iter<'_, '_, P>,
+) -> Result<(), crate::Error>
+where
+    W: io::Write,
+    P: crate::DebugInfoProvider,
+{
+    let elements: Vec<_> = iter.collect::<Result<_, _>>()?;
+    let bytes: Option<Vec<u8>> = elements
+        .iter()
+        .map(|elt| match elt {
+            Value::u8(byte) => Some(*byte.value()),
+            _ => None,
+        })
+        .collect();
+    match bytes {
+        Some(bytes) if !bytes.is_empty() => encode::write_bin(writer, &bytes)?,
+        _ => {
+            encode::write_array_len(writer, elements.len().try_into()?)?;
+            for elt in &elements {
+                elt.write_msgpack(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encodes `fields`; as an array if they are the fields of a tuple (named
+/// `__0`, `__1`, etc.), and otherwise as a map from their names.
+fn write_fields<W, P>(
+    writer: &mut W,
+    mut fields: crate::value::Fields<'_, '_, P>,
+) -> Result<(), crate::Error>
+where
+    W: io::Write,
+    P: crate::DebugInfoProvider,
+{
+    let mut entries = vec![];
+    for field in fields.iter()? {
+        let field = field?;
+        let name = field.schema().name()?.to_string_lossy()?.into_owned();
+        entries.push((name, field));
+    }
+    let len = entries.len().try_into()?;
+    if !entries.is_empty() && entries.iter().all(|(name, _)| name.starts_with("__")) {
+        encode::write_array_len(writer, len)?;
+        for (_, field) in &entries {
+            write_field(writer, field)?;
+        }
+    } else {
+        encode::write_map_len(writer, len)?;
+        for (name, field) in &entries {
+            encode::write_str(writer, name)?;
+            write_field(writer, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Encodes the value of `field`; as an unsigned integer, if it is a bitfield.
+fn write_field<W, P>(
+    writer: &mut W,
+    field: &crate::value::Field<'_, '_, P>,
+) -> Result<(), crate::Error>
+where
+    W: io::Write,
+    P: crate::DebugInfoProvider,
+{
+    match field.typed_bits()? {
+        Some(crate::value::field::Bits::Bool(bits)) => return Ok(encode::write_bool(writer, bits)?),
+        Some(crate::value::field::Bits::Signed(bits)) => return write_sint(writer, bits),
+        Some(crate::value::field::Bits::Unsigned(bits)) => return write_uint(writer, bits),
+        None => {}
+    }
+    field.value()?.write_msgpack(writer)
+}
+
+/// Encodes `value` in the fewest bytes.
+fn write_sint<W: io::Write>(writer: &mut W, value: i64) -> Result<(), crate::Error> {
+    encode::write_sint(writer, value)?;
+    Ok(())
+}
+
+/// Encodes `value` in the fewest bytes.
+fn write_uint<W: io::Write>(writer: &mut W, value: u64) -> Result<(), crate::Error> {
+    encode::write_uint(writer, value)?;
+    Ok(())
+}
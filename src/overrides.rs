@@ -0,0 +1,126 @@
+//! Per-type formatting overrides, with which domain types (e.g.,
+//! `uuid::Uuid`) are displayed meaningfully without changing their crates.
+
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use once_cell::sync::Lazy;
+
+use crate::Value;
+
+/// Displays the bytes of a value of an overridden type; see
+/// [`DefaultProvider::with_format_override`][crate::DefaultProvider::with_format_override].
+pub type FormatHook = dyn Fn(&[u8], &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync;
+
+/// A [`FormatHook`], and the canonical name of the type whose values it
+/// displays; see [`DebugInfoProvider::format_overrides`][crate::DebugInfoProvider::format_overrides].
+#[derive(Clone)]
+pub struct FormatOverride {
+    pub(crate) type_name: Cow<'static, str>,
+    pub(crate) hook: Arc<FormatHook>,
+}
+
+impl FormatOverride {
+    /// An override of the display of the type named `type_name`; e.g.,
+    /// `uuid::Uuid`.
+    ///
+    /// ## Safety
+    /// The named type must have no padding, nor any other uninitialized
+    /// bytes, as `hook` is passed all of the bytes of its values.
+    pub unsafe fn new(type_name: &'static str, hook: &'static FormatHook) -> Self {
+        let hook: Arc<FormatHook> = Arc::new(hook);
+        Self {
+            type_name: Cow::Borrowed(type_name),
+            hook,
+        }
+    }
+
+    /// The canonical name of the overridden type.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+impl fmt::Debug for FormatOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormatOverride")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A value, as displayed by the [`FormatHook`] of its type.
+pub(crate) struct Overridden<'value> {
+    hook: Arc<FormatHook>,
+    bytes: &'value [u8],
+}
+
+impl Overridden<'_> {
+    /// This value, as displayed by its hook.
+    pub(crate) fn render(&self) -> Result<String, crate::Error> {
+        use fmt::Write;
+        let mut rendered = String::new();
+        write!(rendered, "{self}").map_err(|_| anyhow!("a format override failed"))?;
+        Ok(rendered)
+    }
+}
+
+impl fmt::Display for Overridden<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.hook)(self.bytes, f)
+    }
+}
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// This value, as displayed by the first of its provider's
+    /// [format overrides][crate::DebugInfoProvider::format_overrides] for its
+    /// type; or `None`, if its type is not overridden.
+    ///
+    /// Values of [redacted][crate::Redaction] types are displayed as
+    /// `<redacted>`. Only structs and enums are overridden.
+    pub(crate) fn overridden(&self) -> Result<Option<Overridden<'value>>, crate::Error> {
+        let (provider, bytes) = match self {
+            Value::Struct(value) => (value.provider(), value.bytes()),
+            Value::Enum(value) => (value.provider(), value.bytes()),
+            _ => return Ok(None),
+        };
+        let overrides = provider.format_overrides();
+        let redaction = provider.redaction();
+        if overrides.is_empty() && redaction.is_none() {
+            return Ok(None);
+        }
+        let type_name = crate::format::qualified_type_name(&self.r#type())?;
+        let type_name = crate::format::canonical(&type_name);
+        if redaction.map_or(false, |redaction| redaction.redacts_type(&type_name)) {
+            return Ok(Some(Overridden {
+                hook: REDACTED.clone(),
+                bytes: &[],
+            }));
+        }
+        let Some(r#override) = overrides.iter().find(|o| o.type_name == type_name) else {
+            return Ok(None);
+        };
+        // the bytes of a field extend to the end of its parent.
+        let size = usize::try_from(self.r#type().size()?)?;
+        let bytes = bytes
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, bytes.len()))?;
+        // SAFETY: overrides are only registered for types without padding,
+        // whose bytes are all initialized.
+        let bytes = unsafe { &*(bytes as *const [crate::Byte] as *const [u8]) };
+        Ok(Some(Overridden {
+            hook: r#override.hook.clone(),
+            bytes,
+        }))
+    }
+}
+
+/// The hook of redacted values.
+static REDACTED: Lazy<Arc<FormatHook>> = Lazy::new(|| Arc::new(redacted) as Arc<FormatHook>);
+
+/// Displays a redacted value, whose bytes are not read.
+fn redacted(_: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(crate::redact::REDACTED)
+}
@@ -0,0 +1,1116 @@
+//! Portable snapshots of reflected values, which can be inspected without the
+//! program they were taken from.
+//!
+//! A snapshot bundles the bytes of a value, the heap blocks reachable from it
+//! (through its references, boxes, `Vec`s, `HashMap`s, `Rc`s and `Arc`s), and
+//! the subset of the debuginfo that describes their types. When a snapshot is
+//! loaded, its blocks are copied into fresh allocations, and the pointers
+//! between them are relocated to those copies.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use crate::gimli::{
+    self, EndianReader, Endianity as _, RunTimeEndian, Section as _, SectionId, UnitOffset,
+    UnitSectionOffset,
+};
+use crate::{rustc::Container, Value};
+
+/// Identifies the format of snapshots.
+const MAGIC: &[u8; 8] = b"DEFLECT\0";
+
+/// The version of the format of snapshots.
+const VERSION: u32 = 1;
+
+/// The sections, besides `.debug_info`, that snapshots include in full.
+const SECTIONS: [SectionId; 9] = [
+    SectionId::DebugAbbrev,
+    SectionId::DebugAddr,
+    SectionId::DebugLine,
+    SectionId::DebugLineStr,
+    SectionId::DebugStr,
+    SectionId::DebugStrOffsets,
+    SectionId::DebugTypes,
+    SectionId::DebugRanges,
+    SectionId::DebugRngLists,
+];
+
+/// The size of the header of a DWARF 4 unit in the 32-bit format.
+const UNIT_HEADER_SIZE: usize = 11;
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Writes a portable snapshot of this value to `writer`, which
+    /// [`PortableSnapshot::load`] re-creates as a fully inspectable `Value`;
+    /// e.g., on another machine, without this program or its binary.
+    ///
+    /// The snapshot includes the bytes of this value, and of the values
+    /// reachable from it through references, boxes, `Vec`s, `HashMap`s, `Rc`s
+    /// and `Arc`s, and the units of debuginfo that describe their types. Raw pointers
+    /// (including [`NonNull`][std::ptr::NonNull]) and `Weak`s are not
+    /// followed, and so cannot be dereferenced in the loaded snapshot. Padding
+    /// is not read, and is saved as zeros.
+    pub fn save_snapshot<W: Write>(&self, mut writer: W) -> Result<(), crate::Error> {
+        let (dwarf, unit, offset) = self.r#type().die();
+        let mut saver = Saver {
+            dwarf,
+            units: vec![unit],
+            vtables: vec![],
+            blocks: vec![],
+            starts: BTreeMap::new(),
+            relocations: vec![],
+        };
+        let mut bytes = vec![0; self.bytes().len()];
+        initialized_bytes(self, &mut bytes)?;
+        let addr = self.bytes().as_ptr() as usize;
+        saver.blocks.push(Block { addr, bytes });
+        saver.starts.insert(addr, 0);
+        saver.walk(0, self)?;
+        saver.write(&mut writer, (unit.header.offset(), offset))
+    }
+}
+
+/// A block of memory in a snapshot; e.g., the referent of a `Box`.
+struct Block {
+    /// The address of the block, when it was saved.
+    addr: usize,
+    bytes: Vec<u8>,
+}
+
+/// A pointer within a block of a snapshot, to a location within a block.
+struct Relocation {
+    block: usize,
+    offset: usize,
+    target: usize,
+    target_offset: usize,
+}
+
+/// The state of [`Value::save_snapshot`].
+struct Saver<'dwarf, R>
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    dwarf: &'dwarf gimli::Dwarf<R>,
+    /// The units of the saved types whose debuginfo is not referred to from
+    /// that of other saved types; i.e., of the root and of trait objects.
+    units: Vec<&'dwarf gimli::Unit<R>>,
+    /// The address of each vtable of a saved trait object, and the unit and
+    /// offset of the type that implements it.
+    vtables: Vec<(u64, UnitSectionOffset, UnitOffset)>,
+    blocks: Vec<Block>,
+    /// The index of each block, by its address.
+    starts: BTreeMap<usize, usize>,
+    relocations: Vec<Relocation>,
+}
+
+impl<'dwarf, R> Saver<'dwarf, R>
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    /// Saves the pointers within `value`, which is within `block`.
+    fn walk<P>(&mut self, block: usize, value: &Value<'_, 'dwarf, P>) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let addr = value.bytes().as_ptr() as usize;
+        match value {
+            Value::Box(v) => self.follow(block, addr, v.deref()?),
+            Value::SharedRef(v) => self.follow(block, addr, v.deref()?),
+            Value::UniqueRef(v) => self.follow(block, addr, v.deref()?),
+            Value::BoxedDyn(v) => {
+                let schema = v.schema();
+                let referent = v.deref()?;
+                self.vtable(v.bytes(), schema.vtable(), &referent)?;
+                let word = addr + usize::try_from(schema.pointer().offset()?.address(0)?)?;
+                self.follow(block, word, referent)
+            }
+            Value::DynRef(v) => {
+                let schema = v.schema();
+                let referent = v.downcast_value()?;
+                self.vtable(v.bytes(), schema.vtable(), &referent)?;
+                let word = addr + usize::try_from(schema.pointer().offset()?.address(0)?)?;
+                self.follow(block, word, referent)
+            }
+            Value::Slice(v) => {
+                let word = addr + usize::try_from(v.schema().data_ptr().offset()?.address(0)?)?;
+                let data = v.data_ptr()?.as_ptr() as usize;
+                self.follow_elements(block, word, data, v.iter()?)
+            }
+            Value::BoxedSlice(v) => {
+                let word = addr + usize::try_from(v.schema().data_ptr().offset()?.address(0)?)?;
+                let data = v.data_ptr()?.as_ptr() as usize;
+                self.follow_elements(block, word, data, v.iter()?)
+            }
+            Value::str(v) => {
+                let word = addr + usize::try_from(v.schema().data_ptr().offset()?.address(0)?)?;
+                let data = v.as_bytes();
+                let target =
+                    self.block::<P>(data.as_ptr() as usize, data.len(), vec![], Some((0, data)))?;
+                self.relocate(block, word, target)
+            }
+            Value::Array(v) => {
+                for element in v.iter()? {
+                    self.walk(block, &element?)?;
+                }
+                Ok(())
+            }
+            Value::NonZero(v) => self.walk(block, &v.value()?),
+            Value::ManuallyDrop(v) => self.walk(block, &v.value()?),
+            Value::Struct(v) => match crate::rustc::container_of(v.schema())? {
+                Some(Container::Vec) => {
+                    let buffer = crate::rustc::vec_buffer(v)?;
+                    let word = pointer_word(value, buffer.addr)?;
+                    self.follow_elements(block, word, buffer.addr, buffer.elements)
+                }
+                Some(Container::Shared) => {
+                    let (shared, inner) = crate::rustc::shared_allocation(v)?;
+                    let word = pointer_word(value, shared)?;
+                    self.follow(block, word, inner)
+                }
+                Some(Container::HashMap) => {
+                    self.walk_fields(block, v.fields()?)?;
+                    let table = crate::rustc::hash_table(v)?;
+                    // the buckets precede their control bytes.
+                    let buckets_size = table
+                        .buckets
+                        .checked_mul(table.bucket_size)
+                        .ok_or_else(crate::error::arithmetic_overflow)?;
+                    let start = table.ctrl.wrapping_sub(buckets_size);
+                    let control = unsafe {
+                        std::slice::from_raw_parts(table.ctrl as *const u8, table.buckets)
+                    };
+                    let size = buckets_size + table.buckets;
+                    let raw = Some((buckets_size, control));
+                    let target = self.block(start, size, table.entries, raw)?;
+                    let target = target.map(|(index, offset)| (index, offset + buckets_size));
+                    let word = pointer_word(value, table.ctrl)?;
+                    self.relocate(block, word, target)
+                }
+                Some(Container::String) => {
+                    self.walk(block, &Value::Struct(crate::rustc::string_vec(v)?))
+                }
+                Some(Container::Mutex) => self.walk(block, &crate::rustc::mutex_data(v)?),
+                Some(Container::Weak) => Ok(()),
+                None => self.walk_fields(block, v.fields()?),
+            },
+            Value::Enum(v) => self.walk_fields(block, v.variant()?.fields()?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Saves the pointers within `fields`, which are within `block`.
+    fn walk_fields<P>(
+        &mut self,
+        block: usize,
+        mut fields: crate::value::Fields<'_, 'dwarf, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            if field.schema().is_bitfield()? {
+                continue;
+            }
+            self.walk(block, &field.value()?)?;
+        }
+        Ok(())
+    }
+
+    /// Saves `referent`, and the pointer to it at the address `word`, within
+    /// `block`.
+    fn follow<P>(
+        &mut self,
+        block: usize,
+        word: usize,
+        referent: Value<'_, 'dwarf, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let addr = referent.bytes().as_ptr() as usize;
+        let size = referent.bytes().len();
+        let target = self.block(addr, size, vec![referent], None)?;
+        self.relocate(block, word, target)
+    }
+
+    /// Saves `elements`, which begin at `addr`, and the pointer to them at the
+    /// address `word`, within `block`.
+    fn follow_elements<P>(
+        &mut self,
+        block: usize,
+        word: usize,
+        addr: usize,
+        elements: crate::value::Iter<'_, 'dwarf, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let elements = elements.collect::<Result<Vec<_>, _>>()?;
+        let size = match elements.last() {
+            Some(last) => last.bytes().as_ptr() as usize + last.bytes().len() - addr,
+            None => 0,
+        };
+        let target = self.block(addr, size, elements, None)?;
+        self.relocate(block, word, target)
+    }
+
+    /// Saves the vtable of the trait object of `bytes`, which refers to
+    /// `referent`.
+    fn vtable<P>(
+        &mut self,
+        bytes: crate::Bytes<'_>,
+        vtable: &crate::schema::Field<'dwarf, R>,
+        referent: &Value<'_, 'dwarf, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let addr = crate::read_address_field(vtable, bytes)?;
+        let (_, unit, entry) = referent.r#type().die();
+        self.vtables.push((addr, unit.header.offset(), entry));
+        self.units.push(unit);
+        Ok(())
+    }
+
+    /// Produces the block of the `size` bytes at `addr`, and the offset of
+    /// those bytes within it; `None`, if `size` is zero. If those bytes are
+    /// not yet saved, a block of `values` (and of the initialized bytes
+    /// `raw`, at their offset) is saved, with the values reachable from them.
+    fn block<P>(
+        &mut self,
+        addr: usize,
+        size: usize,
+        values: Vec<Value<'_, 'dwarf, P>>,
+        raw: Option<(usize, &[u8])>,
+    ) -> Result<Option<(usize, usize)>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        // zero-sized referents are not allocated, and need not be saved.
+        if size == 0 {
+            return Ok(None);
+        }
+        if let Some((&start, &index)) = self.starts.range(..=addr).next_back() {
+            if addr + size <= start + self.blocks[index].bytes.len() {
+                return Ok(Some((index, addr - start)));
+            }
+        }
+        let mut bytes = vec![0; size];
+        for value in &values {
+            let offset = value.bytes().as_ptr() as usize - addr;
+            initialized_bytes(value, &mut bytes[offset..])?;
+        }
+        if let Some((offset, raw)) = raw {
+            bytes[offset..][..raw.len()].copy_from_slice(raw);
+        }
+        let index = self.blocks.len();
+        self.blocks.push(Block { addr, bytes });
+        self.starts.insert(addr, index);
+        for value in &values {
+            self.walk(index, value)?;
+        }
+        Ok(Some((index, 0)))
+    }
+
+    /// Saves the pointer at the address `word`, within `block`, to `target`.
+    fn relocate(
+        &mut self,
+        block: usize,
+        word: usize,
+        target: Option<(usize, usize)>,
+    ) -> Result<(), crate::Error> {
+        let Some((target, target_offset)) = target else {
+            return Ok(());
+        };
+        let Block { addr, bytes } = &self.blocks[block];
+        let offset = word.wrapping_sub(*addr);
+        if offset + std::mem::size_of::<usize>() > bytes.len() {
+            bail!("the pointer at 0x{word:x} is not within the value that contains it");
+        }
+        self.relocations.push(Relocation {
+            block,
+            offset,
+            target,
+            target_offset,
+        });
+        Ok(())
+    }
+
+    /// The offsets of the units describing the saved types; i.e., of the
+    /// units of the root and of trait objects, and the units they refer to.
+    fn closure(&self) -> Result<BTreeSet<UnitSectionOffset>, crate::Error> {
+        let mut units = BTreeSet::new();
+        let mut pending = self.units.clone();
+        while let Some(unit) = pending.pop() {
+            if !units.insert(unit.header.offset()) {
+                continue;
+            }
+            let mut entries = unit.entries();
+            while let Some((_, entry)) = entries.next_dfs()? {
+                let mut attrs = entry.attrs();
+                while let Some(attr) = attrs.next()? {
+                    if let Some((target, _)) = crate::refs::resolve(self.dwarf, unit, attr.value())?
+                    {
+                        if !units.contains(&target.header.offset()) {
+                            pending.push(target);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(units)
+    }
+
+    /// Writes the snapshot of the value of the type at `root`.
+    fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        root: (UnitSectionOffset, UnitOffset),
+    ) -> Result<(), crate::Error> {
+        writer.write_all(MAGIC)?;
+        write_u32(writer, VERSION)?;
+        let little_endian = self.dwarf.debug_info.reader().endian().is_little_endian();
+        writer.write_all(&[little_endian as u8, std::mem::size_of::<usize>() as u8])?;
+        write_unit_offset(writer, root.0)?;
+        write_u64(writer, root.1 .0 as u64)?;
+
+        write_count(writer, SECTIONS.len())?;
+        for id in SECTIONS {
+            write_bytes(writer, id.name().as_bytes())?;
+            write_bytes(writer, &section_of(self.dwarf, id).to_slice()?)?;
+        }
+
+        // only the units that describe the saved types are saved; the others
+        // are elided, and replaced by placeholders when the snapshot is
+        // loaded, so that the offsets of the saved units are unchanged.
+        let debug_info = self.dwarf.debug_info.reader().to_slice()?;
+        let units: Vec<_> = self
+            .closure()?
+            .into_iter()
+            .filter_map(|offset| offset.as_debug_info_offset())
+            .collect();
+        write_u64(writer, debug_info.len() as u64)?;
+        write_count(writer, units.len())?;
+        for offset in units {
+            let header = self.dwarf.debug_info.header_from_offset(offset)?;
+            let bytes = &debug_info[offset.0..][..header.length_including_self()];
+            write_u64(writer, offset.0 as u64)?;
+            write_bytes(writer, bytes)?;
+        }
+
+        write_count(writer, self.vtables.len())?;
+        for &(addr, unit, entry) in &self.vtables {
+            write_u64(writer, addr)?;
+            write_unit_offset(writer, unit)?;
+            write_u64(writer, entry.0 as u64)?;
+        }
+
+        write_count(writer, self.blocks.len())?;
+        for block in &self.blocks {
+            write_u64(writer, block.addr as u64)?;
+            write_bytes(writer, &block.bytes)?;
+        }
+
+        write_count(writer, self.relocations.len())?;
+        for relocation in &self.relocations {
+            write_count(writer, relocation.block)?;
+            write_u64(writer, relocation.offset as u64)?;
+            write_count(writer, relocation.target)?;
+            write_u64(writer, relocation.target_offset as u64)?;
+        }
+        Ok(())
+    }
+}
+
+/// The address at which the pointer to `addr` within `value` is stored; e.g.,
+/// of the pointer to the buffer of a `Vec`.
+fn pointer_word<P>(value: &Value<'_, '_, P>, addr: usize) -> Result<usize, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn find<P>(value: &Value<'_, '_, P>, addr: usize) -> Result<Option<usize>, crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let found = match value {
+            Value::NonNull(v) => v.addr()? == addr,
+            Value::ConstPtr(v) => v.addr() == addr,
+            Value::MutPtr(v) => v.addr() == addr,
+            Value::Struct(v) => {
+                let mut fields = v.fields()?;
+                let mut fields = fields.iter()?;
+                while let Some(field) = fields.try_next()? {
+                    if let Some(word) = find(&field.value()?, addr)? {
+                        return Ok(Some(word));
+                    }
+                }
+                false
+            }
+            _ => false,
+        };
+        Ok(found.then(|| value.bytes().as_ptr() as usize))
+    }
+
+    match find(value, addr)? {
+        Some(word) => Ok(word),
+        None => bail!(
+            "could not find the pointer to 0x{addr:x} in `{}`",
+            value.r#type()
+        ),
+    }
+}
+
+/// Copies the initialized bytes of `value` into `into`; its padding, and the
+/// contents of its `MaybeUninit`s, are not read.
+fn initialized_bytes<P>(value: &Value<'_, '_, P>, into: &mut [u8]) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    // integers (e.g., the bytes of strings) have no padding.
+    if matches!(
+        value,
+        Value::u8(_)
+            | Value::u16(_)
+            | Value::u32(_)
+            | Value::u64(_)
+            | Value::u128(_)
+            | Value::usize(_)
+            | Value::i8(_)
+            | Value::i16(_)
+            | Value::i32(_)
+            | Value::i64(_)
+            | Value::i128(_)
+            | Value::isize(_)
+    ) {
+        for (into, byte) in into.iter_mut().zip(value.bytes()) {
+            *into = unsafe { byte.assume_init() };
+        }
+        return Ok(());
+    }
+    let hexdump = value.hexdump()?;
+    for (offset, into) in into.iter_mut().enumerate().take(hexdump.len()) {
+        *into = hexdump.byte(offset).unwrap_or(0);
+    }
+    Ok(())
+}
+
+/// The section `id` of `dwarf`.
+fn section_of<R>(dwarf: &gimli::Dwarf<R>, id: SectionId) -> &R
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    match id {
+        SectionId::DebugAbbrev => dwarf.debug_abbrev.reader(),
+        SectionId::DebugAddr => dwarf.debug_addr.reader(),
+        SectionId::DebugLine => dwarf.debug_line.reader(),
+        SectionId::DebugLineStr => dwarf.debug_line_str.reader(),
+        SectionId::DebugStr => dwarf.debug_str.reader(),
+        SectionId::DebugStrOffsets => dwarf.debug_str_offsets.reader(),
+        SectionId::DebugTypes => dwarf.debug_types.reader(),
+        SectionId::DebugRanges => dwarf.ranges.debug_ranges().reader(),
+        SectionId::DebugRngLists => dwarf.ranges.debug_rnglists().reader(),
+        _ => dwarf.debug_info.reader(),
+    }
+}
+
+/// A value saved by [`Value::save_snapshot`], loaded by
+/// [`PortableSnapshot::load`].
+///
+/// Unlike an owned [`Snapshot`][crate::Snapshot], a portable snapshot
+/// re-creates the saved value as a [`Value`], which can be inspected like any
+/// other; e.g., displayed, walked, queried by path, or converted to JSON.
+pub struct PortableSnapshot {
+    provider: SnapshotProvider,
+    root: (UnitSectionOffset, UnitOffset),
+    /// The relocated blocks of the snapshot, and their sizes; the first is of
+    /// the saved value.
+    blocks: Vec<(Box<[u128]>, usize)>,
+}
+
+/// The provider of the debuginfo of a [`PortableSnapshot`].
+#[derive(Clone)]
+pub struct SnapshotProvider {
+    /// The debuginfo of the snapshot, which is freed once neither the snapshot
+    /// nor any clone of its provider refers to it.
+    context: std::rc::Rc<crate::MemoContext<crate::Addr2LineReader>>,
+    vtables: Arc<HashMap<u64, (UnitSectionOffset, UnitOffset)>>,
+}
+
+unsafe impl crate::DebugInfoProvider for SnapshotProvider {
+    type Reader = crate::Addr2LineReader;
+
+    fn info_for(&self, fn_addr: u64) -> Result<crate::DebugInfo<'_, Self::Reader>, crate::Error> {
+        Err(crate::error::symbol_not_found(fn_addr as usize))
+    }
+
+    fn info_for_vtable(
+        &self,
+        vtable_addr: u64,
+    ) -> Result<crate::DebugInfo<'_, Self::Reader>, crate::Error> {
+        let Some(&(unit, entry)) = self.vtables.get(&vtable_addr) else {
+            bail!("the snapshot does not include the vtable at 0x{vtable_addr:x}")
+        };
+        let context = &self.context;
+        let unit = crate::refs::load_unit(context.dwarf(), unit)?;
+        Ok(crate::DebugInfo::new(context, unit, entry))
+    }
+
+    // snapshots are read from files, which may be corrupt.
+    fn validates_bit_patterns(&self) -> bool {
+        true
+    }
+}
+
+impl PortableSnapshot {
+    /// Loads a snapshot written by [`Value::save_snapshot`].
+    ///
+    /// The snapshot must have been saved on a platform with the same pointer
+    /// width and byte order as this one. Its pointers are checked to refer to
+    /// within its blocks, so that a corrupt snapshot fails to load rather than
+    /// being reflected out of bounds. Its debuginfo is freed once neither the
+    /// snapshot nor any clone of its provider is alive.
+    pub fn load<R: Read>(mut reader: R) -> Result<Self, crate::Error> {
+        let reader = &mut reader;
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(crate::error::invalid_snapshot("not a snapshot"));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            let reason = format!("unsupported version {version}");
+            return Err(crate::error::invalid_snapshot(&reason));
+        }
+        let mut platform = [0; 2];
+        reader.read_exact(&mut platform)?;
+        let little_endian = platform[0] != 0;
+        if little_endian != cfg!(target_endian = "little")
+            || usize::from(platform[1]) != std::mem::size_of::<usize>()
+        {
+            return Err(crate::error::invalid_snapshot(
+                "saved on a platform with another pointer width or byte order",
+            ));
+        }
+        let endian = match little_endian {
+            true => RunTimeEndian::Little,
+            false => RunTimeEndian::Big,
+        };
+        let root = (read_unit_offset(reader)?, UnitOffset(read_usize(reader)?));
+
+        let mut sections = HashMap::new();
+        for _ in 0..read_u32(reader)? {
+            let name = String::from_utf8(read_bytes(reader)?)?;
+            let data: Arc<[u8]> = Arc::from(read_bytes(reader)?);
+            sections.insert(name, data);
+        }
+        let debug_info = read_debug_info(reader, little_endian)?;
+        sections.insert(
+            SectionId::DebugInfo.name().to_owned(),
+            Arc::from(debug_info),
+        );
+        let dwarf = gimli::Dwarf::load(|id| {
+            let data = match sections.get(id.name()) {
+                Some(data) => data.clone(),
+                None => Arc::from(&[][..]),
+            };
+            Ok::<_, crate::Error>(EndianReader::new(data, endian))
+        })?;
+        let context = addr2line::Context::from_dwarf(dwarf)?;
+        let context = std::rc::Rc::new(crate::MemoContext::new(context));
+
+        let mut vtables = HashMap::new();
+        for _ in 0..read_u32(reader)? {
+            let addr = read_u64(reader)?;
+            let unit = read_unit_offset(reader)?;
+            let entry = UnitOffset(read_usize(reader)?);
+            vtables.insert(addr, (unit, entry));
+        }
+
+        let mut blocks = vec![];
+        for _ in 0..read_u32(reader)? {
+            let _addr = read_u64(reader)?;
+            let bytes = read_bytes(reader)?;
+            // blocks are aligned for any type that is not over-aligned.
+            let mut block = vec![0u128; (bytes.len() + 15) / 16].into_boxed_slice();
+            let into = block.as_mut_ptr() as *mut u8;
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), into, bytes.len()) };
+            blocks.push((block, bytes.len()));
+        }
+        if blocks.is_empty() {
+            return Err(crate::error::invalid_snapshot("no value was saved"));
+        }
+
+        let mut relocations = HashMap::new();
+        for _ in 0..read_u32(reader)? {
+            let block = read_u32(reader)? as usize;
+            let offset = read_usize(reader)?;
+            let target = read_u32(reader)? as usize;
+            let target_offset = read_usize(reader)?;
+            let in_bounds = |block: usize, offset: usize, size: usize| matches!(blocks.get(block), Some((_, len)) if offset.saturating_add(size) <= *len);
+            if !in_bounds(block, offset, std::mem::size_of::<usize>())
+                || !in_bounds(target, target_offset, 0)
+            {
+                return Err(crate::error::invalid_snapshot("a pointer is out of bounds"));
+            }
+            let addr = blocks[target].0.as_ptr() as usize + target_offset;
+            let word = blocks[block].0.as_mut_ptr() as *mut u8;
+            unsafe { (word.add(offset) as *mut usize).write_unaligned(addr) };
+            relocations.insert((block, offset), (target, target_offset));
+        }
+
+        let snapshot = Self {
+            provider: SnapshotProvider {
+                context,
+                vtables: Arc::new(vtables),
+            },
+            root,
+            blocks,
+        };
+        let mut checker = Checker {
+            provider: &snapshot.provider,
+            blocks: &snapshot.blocks,
+            relocations: &relocations,
+            checked: HashSet::new(),
+        };
+        checker.check(snapshot.root_type()?, 0, 0)?;
+        Ok(snapshot)
+    }
+
+    /// The saved value, reflected with the saved debuginfo.
+    pub fn value(&self) -> Result<Value<'_, '_, SnapshotProvider>, crate::Error> {
+        let r#type = self.root_type()?;
+        let size = usize::try_from(r#type.size()?)?;
+        let (block, len) = &self.blocks[0];
+        if size > *len {
+            return Err(crate::error::size_mismatch(size, *len));
+        }
+        let bytes = std::ptr::slice_from_raw_parts(block.as_ptr() as *const crate::Byte, size);
+        unsafe { Value::with_type(r#type, &*bytes, &self.provider) }
+    }
+
+    /// The type of the saved value.
+    fn root_type(&self) -> Result<crate::schema::Type<'_, crate::Addr2LineReader>, crate::Error> {
+        let dwarf = self.provider.context.dwarf();
+        let unit = crate::refs::load_unit(dwarf, self.root.0)?;
+        let entry = unit.entry(self.root.1)?;
+        crate::schema::Type::from_die(dwarf, unit, entry)
+    }
+}
+
+/// Checks that the saved value of a loaded snapshot, and the values reachable
+/// from it, lie within its blocks; i.e., that each pointer that is followed
+/// when they are reflected was relocated to a block large enough for its
+/// referent. Snapshots are read from files, which may be corrupt.
+struct Checker<'a> {
+    provider: &'a SnapshotProvider,
+    blocks: &'a [(Box<[u128]>, usize)],
+    /// The target of the pointer at each offset within each block.
+    relocations: &'a HashMap<(usize, usize), (usize, usize)>,
+    /// The values that were checked, by their type and location.
+    checked: HashSet<(UnitSectionOffset, UnitOffset, usize, usize)>,
+}
+
+impl<'a> Checker<'a> {
+    /// Checks the value of `type` at `offset` within `block`.
+    fn check(
+        &mut self,
+        r#type: crate::schema::Type<'a, crate::Addr2LineReader>,
+        block: usize,
+        offset: usize,
+    ) -> Result<(), crate::Error> {
+        use crate::schema::Type;
+        let size = usize::try_from(r#type.size()?)?;
+        let bytes = self.bytes(block, offset, size)?;
+        if !has_pointers(&r#type) {
+            return Ok(());
+        }
+        let (_, unit, entry) = r#type.die();
+        // the pointers of targets with other address sizes cannot be
+        // dereferenced, and so are not followed.
+        if crate::address_size(unit) != WORD as u64 {
+            return Ok(());
+        }
+        if !self
+            .checked
+            .insert((unit.header.offset(), entry, block, offset))
+        {
+            return Ok(());
+        }
+        match &r#type {
+            Type::Box(schema) => self.follow(block, offset, schema.r#type()?, 1),
+            Type::SharedRef(schema) => self.follow(block, offset, schema.r#type()?, 1),
+            Type::UniqueRef(schema) => self.follow(block, offset, schema.r#type()?, 1),
+            Type::str(schema) => {
+                let len = usize::try_from(crate::read_address_field(schema.length(), bytes)?)?;
+                let word = offset + field_offset(&schema.data_ptr())?;
+                self.follow(block, word, schema.elt()?, len)
+            }
+            Type::Slice(schema) => {
+                let len = usize::try_from(crate::read_address_field(schema.length(), bytes)?)?;
+                let word = offset + field_offset(&schema.data_ptr())?;
+                self.follow(block, word, schema.elt()?, len)
+            }
+            Type::BoxedSlice(schema) => {
+                let len = usize::try_from(crate::read_address_field(schema.length(), bytes)?)?;
+                let word = offset + field_offset(&schema.data_ptr())?;
+                self.follow(block, word, schema.elt()?, len)
+            }
+            Type::DynRef(schema) => {
+                self.follow_dyn(block, offset, bytes, schema.pointer(), schema.vtable())
+            }
+            Type::BoxedDyn(schema) => {
+                self.follow_dyn(block, offset, bytes, schema.pointer(), schema.vtable())
+            }
+            Type::Array(schema) => {
+                let elt_type = schema.elt_type()?;
+                let elt_size = usize::try_from(elt_type.size()?)?;
+                for index in 0..usize::try_from(schema.len()?)? {
+                    self.check(elt_type.clone(), block, offset + index * elt_size)?;
+                }
+                Ok(())
+            }
+            Type::ManuallyDrop(schema) => {
+                let value = schema.value();
+                self.check(value.r#type()?, block, offset + field_offset(value)?)
+            }
+            Type::Struct(schema) => {
+                let value = unsafe { schema.clone().with_bytes(self.provider, bytes) }?;
+                match crate::rustc::container_of(schema)? {
+                    Some(Container::Vec) => {
+                        let parts = crate::rustc::vec_parts(&value)?;
+                        let word = self.word(block, &Value::Struct(value), parts.addr)?;
+                        self.follow(block, word, parts.elt_type, parts.len)
+                    }
+                    Some(Container::Shared) => {
+                        let (addr, inner) = crate::rustc::shared_pointer(&value)?;
+                        let word = self.word(block, &Value::Struct(value), addr)?;
+                        self.follow(block, word, inner, 1)
+                    }
+                    Some(Container::HashMap) => {
+                        self.check_fields(block, offset, value.fields()?)?;
+                        let table = crate::rustc::hash_table_parts(&value)?;
+                        let word = self.word(block, &Value::Struct(value), table.ctrl)?;
+                        self.check_table(block, word, table)
+                    }
+                    Some(Container::Weak) => Ok(()),
+                    Some(Container::String | Container::Mutex) | None => {
+                        self.check_fields(block, offset, value.fields()?)
+                    }
+                }
+            }
+            Type::Enum(schema) => {
+                let value = unsafe { schema.clone().with_bytes(self.provider, bytes) }?;
+                self.check_fields(block, offset, value.variant()?.fields()?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks `fields`, of the value at `offset` within `block`.
+    fn check_fields(
+        &mut self,
+        block: usize,
+        offset: usize,
+        mut fields: crate::value::Fields<'a, 'a, SnapshotProvider>,
+    ) -> Result<(), crate::Error> {
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            let schema = field.schema();
+            if schema.is_bitfield()? {
+                continue;
+            }
+            self.check(schema.r#type()?, block, offset + field_offset(schema)?)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the `len` values of `type` referred to by the pointer at the
+    /// offset `word` within `block`.
+    fn follow(
+        &mut self,
+        block: usize,
+        word: usize,
+        r#type: crate::schema::Type<'a, crate::Addr2LineReader>,
+        len: usize,
+    ) -> Result<(), crate::Error> {
+        let elt_size = usize::try_from(r#type.size()?)?;
+        let size = elt_size
+            .checked_mul(len)
+            .ok_or_else(|| crate::error::invalid_snapshot("a length is out of bounds"))?;
+        // zero-sized referents are not saved, and only their address is read.
+        if size == 0 {
+            let word = self.bytes(block, word, WORD)?;
+            // SAFETY: the bytes of blocks are initialized.
+            let addr = unsafe { (word.as_ptr() as *const usize).read_unaligned() };
+            if addr == 0 {
+                return Err(crate::error::invalid_snapshot("a pointer is null"));
+            }
+            return Ok(());
+        }
+        let Some(&(target, target_offset)) = self.relocations.get(&(block, word)) else {
+            return Err(crate::error::invalid_snapshot(
+                "a pointer was not relocated",
+            ));
+        };
+        self.bytes(target, target_offset, size)?;
+        if has_pointers(&r#type) {
+            for index in 0..len {
+                self.check(r#type.clone(), target, target_offset + index * elt_size)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the referent of the trait object at `offset` within `block`,
+    /// whose `pointer` and `vtable` fields are described by the given schemas.
+    fn follow_dyn(
+        &mut self,
+        block: usize,
+        offset: usize,
+        bytes: crate::Bytes<'a>,
+        pointer: &crate::schema::Field<'a, crate::Addr2LineReader>,
+        vtable: &crate::schema::Field<'a, crate::Addr2LineReader>,
+    ) -> Result<(), crate::Error> {
+        let vtable = crate::read_address_field(vtable, bytes)?;
+        let crate::DebugInfo {
+            context,
+            unit,
+            entry,
+        } = crate::DebugInfoProvider::info_for_vtable(self.provider, vtable)?;
+        let entry = unit.entry(entry)?;
+        let r#type = crate::schema::Type::from_die(context.dwarf(), unit, entry)?;
+        self.follow(block, offset + field_offset(pointer)?, r#type, 1)
+    }
+
+    /// Checks the buckets and control bytes of the hash table `table`, whose
+    /// control bytes are referred to by the pointer at the offset `word`
+    /// within `block`.
+    fn check_table(
+        &mut self,
+        block: usize,
+        word: usize,
+        table: crate::rustc::HashTableParts<'a, crate::Addr2LineReader>,
+    ) -> Result<(), crate::Error> {
+        // empty tables are not saved, and their control bytes are not read.
+        if table.buckets == 0 {
+            return Ok(());
+        }
+        let Some(&(target, ctrl)) = self.relocations.get(&(block, word)) else {
+            return Err(crate::error::invalid_snapshot(
+                "a pointer was not relocated",
+            ));
+        };
+        // the buckets precede their control bytes.
+        let start = table
+            .buckets
+            .checked_mul(table.bucket_size)
+            .and_then(|size| ctrl.checked_sub(size))
+            .ok_or_else(|| crate::error::invalid_snapshot("a hash table is out of bounds"))?;
+        self.bytes(target, start, ctrl - start)?;
+        let control = self.bytes(target, ctrl, table.buckets)?;
+        for (index, control) in control.iter().enumerate() {
+            // SAFETY: the bytes of blocks are initialized.
+            if unsafe { control.assume_init() } & 0x80 != 0 {
+                continue;
+            }
+            let bucket = ctrl - (index + 1) * table.bucket_size;
+            self.check(table.bucket_type.clone(), target, bucket)?;
+        }
+        Ok(())
+    }
+
+    /// The `size` bytes at `offset` within `block`.
+    fn bytes(
+        &self,
+        block: usize,
+        offset: usize,
+        size: usize,
+    ) -> Result<crate::Bytes<'a>, crate::Error> {
+        match self.blocks.get(block) {
+            Some((data, len)) if offset.checked_add(size).map_or(false, |end| end <= *len) => {
+                let data = data.as_ptr() as *const crate::Byte;
+                // SAFETY: the bytes are within the block.
+                Ok(unsafe { &*std::ptr::slice_from_raw_parts(data.add(offset), size) })
+            }
+            _ => Err(crate::error::invalid_snapshot("a value is out of bounds")),
+        }
+    }
+
+    /// The offset within `block` of the pointer to `addr` within `value`.
+    fn word(
+        &self,
+        block: usize,
+        value: &Value<'_, '_, SnapshotProvider>,
+        addr: usize,
+    ) -> Result<usize, crate::Error> {
+        let start = self.blocks[block].0.as_ptr() as usize;
+        Ok(pointer_word(value, addr)? - start)
+    }
+}
+
+/// The size of pointers.
+const WORD: usize = std::mem::size_of::<usize>();
+
+/// Whether values of `type` may contain pointers that are followed when they
+/// are reflected.
+fn has_pointers<R>(r#type: &crate::schema::Type<'_, R>) -> bool
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    use crate::schema::Type;
+    matches!(
+        r#type,
+        Type::str(_)
+            | Type::Array(_)
+            | Type::ManuallyDrop(_)
+            | Type::Box(_)
+            | Type::BoxedSlice(_)
+            | Type::BoxedDyn(_)
+            | Type::DynRef(_)
+            | Type::Slice(_)
+            | Type::Struct(_)
+            | Type::Enum(_)
+            | Type::SharedRef(_)
+            | Type::UniqueRef(_)
+    )
+}
+
+/// The offset of `field` within the value that contains it.
+fn field_offset<R>(field: &crate::schema::Field<'_, R>) -> Result<usize, crate::Error>
+where
+    R: gimli::Reader<Offset = usize>,
+{
+    Ok(usize::try_from(field.offset()?.address(0)?)?)
+}
+
+/// Reads the saved units of `.debug_info`, and fills the gaps between them
+/// with placeholder units.
+fn read_debug_info<R: Read>(reader: &mut R, little_endian: bool) -> Result<Vec<u8>, crate::Error> {
+    let len = read_usize(reader)?;
+    let mut debug_info = Vec::with_capacity(len.min(1 << 30));
+    for _ in 0..read_u32(reader)? {
+        let offset = read_usize(reader)?;
+        let unit = read_bytes(reader)?;
+        let gap = offset
+            .checked_sub(debug_info.len())
+            .ok_or_else(|| crate::error::invalid_snapshot("units overlap"))?;
+        placeholder_unit(&mut debug_info, gap, little_endian)?;
+        debug_info.extend_from_slice(&unit);
+    }
+    let gap = len
+        .checked_sub(debug_info.len())
+        .ok_or_else(|| crate::error::invalid_snapshot("units overrun `.debug_info`"))?;
+    placeholder_unit(&mut debug_info, gap, little_endian)?;
+    Ok(debug_info)
+}
+
+/// Appends a placeholder unit of `size` bytes, which has no entries, to
+/// `debug_info`.
+fn placeholder_unit(
+    debug_info: &mut Vec<u8>,
+    size: usize,
+    little_endian: bool,
+) -> Result<(), crate::Error> {
+    if size == 0 {
+        return Ok(());
+    }
+    let Some(length) = size
+        .checked_sub(4)
+        .and_then(|length| u32::try_from(length).ok())
+        .filter(|length| *length < 0xffff_fff0 && size >= UNIT_HEADER_SIZE)
+    else {
+        return Err(crate::error::invalid_snapshot("units are misaligned"));
+    };
+    let start = debug_info.len();
+    match little_endian {
+        true => debug_info.extend_from_slice(&length.to_le_bytes()),
+        false => debug_info.extend_from_slice(&length.to_be_bytes()),
+    }
+    // the version, and the offset of the abbreviations, which are not read.
+    match little_endian {
+        true => debug_info.extend_from_slice(&4u16.to_le_bytes()),
+        false => debug_info.extend_from_slice(&4u16.to_be_bytes()),
+    }
+    debug_info.extend_from_slice(&[0; 4]);
+    debug_info.push(std::mem::size_of::<usize>() as u8);
+    // a null entry, in place of the unit's root.
+    debug_info.resize(start + size, 0);
+    Ok(())
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> Result<(), crate::Error> {
+    Ok(writer.write_all(&value.to_le_bytes())?)
+}
+
+/// Writes the count or index `value`, which snapshots store as a `u32`; fails
+/// if it does not fit in one.
+fn write_count<W: Write>(writer: &mut W, value: usize) -> Result<(), crate::Error> {
+    let Ok(value) = u32::try_from(value) else {
+        bail!("a count or index of {value} does not fit in a snapshot");
+    };
+    write_u32(writer, value)
+}
+
+fn write_u64<W: Write>(writer: &mut W, value: u64) -> Result<(), crate::Error> {
+    Ok(writer.write_all(&value.to_le_bytes())?)
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), crate::Error> {
+    write_u64(writer, bytes.len() as u64)?;
+    Ok(writer.write_all(bytes)?)
+}
+
+fn write_unit_offset<W: Write>(
+    writer: &mut W,
+    offset: UnitSectionOffset,
+) -> Result<(), crate::Error> {
+    let (kind, offset) = match offset {
+        UnitSectionOffset::DebugInfoOffset(offset) => (0, offset.0),
+        UnitSectionOffset::DebugTypesOffset(offset) => (1, offset.0),
+    };
+    writer.write_all(&[kind])?;
+    write_u64(writer, offset as u64)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, crate::Error> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, crate::Error> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_usize<R: Read>(reader: &mut R) -> Result<usize, crate::Error> {
+    Ok(usize::try_from(read_u64(reader)?)?)
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>, crate::Error> {
+    let len = read_u64(reader)?;
+    let mut bytes = vec![];
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(crate::error::invalid_snapshot("truncated"));
+    }
+    Ok(bytes)
+}
+
+fn read_unit_offset<R: Read>(reader: &mut R) -> Result<UnitSectionOffset, crate::Error> {
+    let mut kind = [0];
+    reader.read_exact(&mut kind)?;
+    let offset = read_usize(reader)?;
+    match kind[0] {
+        0 => Ok(gimli::DebugInfoOffset(offset).into()),
+        1 => Ok(gimli::DebugTypesOffset(offset).into()),
+        _ => Err(crate::error::invalid_snapshot("invalid unit offset")),
+    }
+}
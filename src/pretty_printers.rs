@@ -0,0 +1,310 @@
+//! Generation of pretty-printer scripts for `gdb` and `lldb` from schemas, so
+//! that the layouts deflect reads are also used when debugging interactively.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::gimli::{Endianity, Reader, Section};
+use crate::schema::Type;
+
+/// The debugger that a script of pretty-printers is generated for; see
+/// [`pretty_printers`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Debugger {
+    /// `gdb`; load the script with `source printers.py`.
+    Gdb,
+    /// `lldb`; load the script with `command script import printers.py`.
+    Lldb,
+}
+
+/// Produces a Python script of pretty-printers, for `debugger`, of `types`
+/// and of every struct and enum reachable from them through their fields and
+/// elements; e.g., of the types produced by [`find_types`][crate::find_types],
+/// for debugging the binary they were found in.
+///
+/// Each value of those types is printed as its type (or, for enums, its
+/// active variant), with its fields as children. Enums' variants are decoded
+/// from their discriminants as the debug info describes them, including
+/// niche-encoded discriminants, which debuggers without Rust support cannot
+/// decode. Fields are read at their offsets, as values of the types that
+/// their debug info names; fields of types the debugger cannot find are
+/// omitted, and bitfields are not printed.
+pub fn pretty_printers<R>(types: &[Type<'_, R>], debugger: Debugger) -> Result<String, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    let little_endian = match types.first() {
+        Some(r#type) => r#type.die().0.debug_info.reader().endian().is_little_endian(),
+        None => cfg!(target_endian = "little"),
+    };
+    let mut pending: VecDeque<_> = types.iter().cloned().collect();
+    let mut seen = HashSet::new();
+    let mut layouts = String::new();
+    while let Some(r#type) = pending.pop_front() {
+        match &r#type {
+            Type::Struct(_) | Type::Enum(_) => {}
+            Type::Array(schema) => {
+                pending.push_back(schema.elt_type()?);
+                continue;
+            }
+            _ => continue,
+        }
+        let name = crate::format::qualified_type_name(&r#type)?;
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        writeln!(layouts, "    {}: {{", py_str(&name))?;
+        match &r#type {
+            Type::Struct(schema) => {
+                writeln!(layouts, "        \"kind\": \"struct\",")?;
+                let fields = fields_layout(schema.fields()?, &mut pending)?;
+                writeln!(layouts, "        \"fields\": {fields},")?;
+            }
+            Type::Enum(schema) => enum_layout(&mut layouts, schema, &mut pending)?,
+            _ => {}
+        }
+        writeln!(layouts, "    }},")?;
+    }
+
+    let mut script = String::new();
+    writeln!(script, "# Pretty-printers generated by deflect, from the debug info of the binary")?;
+    writeln!(script, "# they describe; regenerate them whenever it is rebuilt.")?;
+    writeln!(script)?;
+    writeln!(script, "LAYOUTS = {{")?;
+    script.push_str(&layouts);
+    writeln!(script, "}}")?;
+    writeln!(script)?;
+    match debugger {
+        Debugger::Gdb => {
+            let endian = if little_endian { "little" } else { "big" };
+            writeln!(script, "ENDIAN = \"{endian}\"")?;
+            script.push_str(GDB);
+        }
+        Debugger::Lldb => script.push_str(LLDB),
+    }
+    Ok(script)
+}
+
+/// Appends the layout of the enum `schema` to `layouts`; and the types of
+/// its fields to `pending`.
+fn enum_layout<'dwarf, R>(
+    layouts: &mut String,
+    schema: &crate::schema::Enum<'dwarf, R>,
+    pending: &mut VecDeque<Type<'dwarf, R>>,
+) -> Result<(), crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    // enums of one variant may have no discriminant.
+    let discriminant = match schema.discriminant_type() {
+        Ok(discriminant_type) => {
+            let offset = schema.discriminant_location().clone().address(0)?;
+            format!("({offset}, {})", discriminant_type.size()?)
+        }
+        Err(_) => "None".to_owned(),
+    };
+    writeln!(layouts, "        \"kind\": \"enum\",")?;
+    writeln!(layouts, "        \"discriminant\": {discriminant},")?;
+    writeln!(layouts, "        \"variants\": [")?;
+    let mut variants = schema.variants()?;
+    let mut variants = variants.iter()?;
+    while let Some(variant) = variants.try_next()? {
+        let name = variant.name()?.to_string_lossy()?.into_owned();
+        // the variant without a discriminant value is that of all other
+        // values; e.g., the dataful variant of a niche-encoded enum.
+        let value = match variant.discriminant_value() {
+            Some(value) => value.to_u64().to_string(),
+            None => "None".to_owned(),
+        };
+        let fields = fields_layout(variant.fields()?, pending)?;
+        writeln!(layouts, "            ({}, {value}, {fields}),", py_str(&name))?;
+    }
+    writeln!(layouts, "        ],")?;
+    Ok(())
+}
+
+/// A Python list of the names, offsets and type names of `fields`; the types
+/// of which are appended to `pending`.
+fn fields_layout<'dwarf, R>(
+    mut fields: crate::schema::Fields<'dwarf, R>,
+    pending: &mut VecDeque<Type<'dwarf, R>>,
+) -> Result<String, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    let mut layout = vec![];
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        if field.is_bitfield()? {
+            continue;
+        }
+        let name = field.name()?.to_string_lossy()?.into_owned();
+        let offset = field.offset()?.address(0)?;
+        let field_type = field.r#type()?;
+        let type_name = crate::format::qualified_type_name(&field_type)?;
+        layout.push(format!("({}, {offset}, {})", py_str(&name), py_str(&type_name)));
+        pending.push_back(field_type);
+    }
+    Ok(format!("[{}]", layout.join(", ")))
+}
+
+/// `s`, as a Python string literal.
+fn py_str(s: &str) -> String {
+    let mut literal = String::with_capacity(s.len() + 2);
+    literal.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(literal, "\\u{:04x}", c as u32);
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// The printers of `gdb`, which print values as their `LAYOUTS` describe.
+const GDB: &str = r#"
+import gdb
+
+
+def _variant(layout, val):
+    """The name and fields of the active variant of the enum `val`."""
+    variants = layout["variants"]
+    if layout["discriminant"] is None:
+        return variants[0][0], variants[0][2]
+    offset, size = layout["discriminant"]
+    memory = gdb.selected_inferior().read_memory(int(val.address) + offset, size)
+    discriminant = int.from_bytes(bytes(memory), ENDIAN)
+    default = None
+    for name, value, fields in variants:
+        if value == discriminant:
+            return name, fields
+        if value is None:
+            default = (name, fields)
+    return default or ("<invalid discriminant {}>".format(discriminant), [])
+
+
+class _Printer:
+    def __init__(self, name, layout, val):
+        self.name = name
+        self.layout = layout
+        self.val = val
+        self.variant, self.fields = (None, layout.get("fields", []))
+        if layout["kind"] == "enum":
+            self.variant, self.fields = _variant(layout, val)
+
+    def to_string(self):
+        if self.variant is None:
+            return self.name
+        return "{}::{}".format(self.name, self.variant)
+
+    def children(self):
+        address = int(self.val.address)
+        for name, offset, type_name in self.fields:
+            try:
+                field_type = gdb.lookup_type(type_name)
+            except gdb.error:
+                continue
+            pointer = gdb.Value(address + offset).cast(field_type.pointer())
+            yield name, pointer.dereference()
+
+
+def _lookup(val):
+    name = str(val.type.strip_typedefs())
+    layout = LAYOUTS.get(name)
+    if layout is None or val.address is None:
+        return None
+    return _Printer(name, layout, val)
+
+
+gdb.pretty_printers.append(_lookup)
+"#;
+
+/// The synthetic children and summaries of `lldb`, which present values as
+/// their `LAYOUTS` describe.
+const LLDB: &str = r#"
+import re
+
+import lldb
+
+
+def _variant(layout, valobj):
+    """The name and fields of the active variant of the enum `valobj`."""
+    variants = layout["variants"]
+    if layout["discriminant"] is None:
+        return variants[0][0], variants[0][2]
+    offset, size = layout["discriminant"]
+    error = lldb.SBError()
+    address = valobj.GetLoadAddress() + offset
+    discriminant = valobj.GetProcess().ReadUnsignedFromMemory(address, size, error)
+    default = None
+    for name, value, fields in variants:
+        if value == discriminant:
+            return name, fields
+        if value is None:
+            default = (name, fields)
+    return default or ("<invalid discriminant {}>".format(discriminant), [])
+
+
+def _layout(valobj):
+    name = valobj.GetType().GetCanonicalType().GetName()
+    return name, LAYOUTS.get(name)
+
+
+def summary(valobj, internal_dict):
+    name, layout = _layout(valobj.GetNonSyntheticValue())
+    if layout is None or layout["kind"] != "enum":
+        return name
+    return "{}::{}".format(name, _variant(layout, valobj.GetNonSyntheticValue())[0])
+
+
+class Synthetic:
+    def __init__(self, valobj, internal_dict):
+        self.valobj = valobj
+        self.fields = []
+
+    def update(self):
+        _, layout = _layout(self.valobj)
+        fields = layout.get("fields", [])
+        if layout["kind"] == "enum":
+            fields = _variant(layout, self.valobj)[1]
+        target = self.valobj.GetTarget()
+        self.fields = []
+        for name, offset, type_name in fields:
+            field_type = target.FindFirstType(type_name)
+            if field_type.IsValid():
+                self.fields.append((name, offset, field_type))
+        return False
+
+    def num_children(self):
+        return len(self.fields)
+
+    def get_child_index(self, name):
+        for index, field in enumerate(self.fields):
+            if field[0] == name:
+                return index
+        return -1
+
+    def get_child_at_index(self, index):
+        name, offset, field_type = self.fields[index]
+        return self.valobj.CreateChildAtOffset(name, offset, field_type)
+
+    def has_children(self):
+        return bool(self.fields)
+
+
+def __lldb_init_module(debugger, internal_dict):
+    for name in LAYOUTS:
+        pattern = "^" + re.escape(name) + "$"
+        debugger.HandleCommand(
+            'type synthetic add -l {}.Synthetic -x "{}"'.format(__name__, pattern)
+        )
+        debugger.HandleCommand(
+            'type summary add -F {}.summary -e -x "{}"'.format(__name__, pattern)
+        )
+"#;
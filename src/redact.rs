@@ -0,0 +1,85 @@
+//! Redaction of secrets (e.g., passwords and tokens) from reflected values, so
+//! that state can be dumped in production.
+
+/// The placeholder with which redacted values are displayed.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// A policy of which values are redacted (i.e., displayed as `<redacted>`) in
+/// all of deflect's output: their [`Display`][std::fmt::Display], JSON,
+/// snapshots and diffs; register policies with
+/// [`DefaultProvider::with_redaction`][crate::DefaultProvider::with_redaction].
+///
+/// By default, nothing is redacted.
+///
+/// ```
+/// use deflect::Redaction;
+///
+/// #[allow(dead_code)]
+/// struct Login {
+///     user: &'static str,
+///     password: &'static str,
+/// }
+///
+/// let redaction = Redaction::new().field("password").field("*_token");
+/// let context = deflect::default_provider()?.with_redaction(redaction);
+/// let login = Login { user: "root", password: "hunter2" };
+/// let erased: &dyn deflect::Reflect = &login;
+/// assert_eq!(
+///     erased.reflect(&context)?.to_string(),
+///     r#"Login { user: "root", password: <redacted> }"#,
+/// );
+/// # Ok::<_, deflect::anyhow::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Redaction {
+    types: Vec<String>,
+    fields: Vec<String>,
+}
+
+impl Redaction {
+    /// The default policy, which redacts nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redacts the structs and enums whose canonical names match the
+    /// glob `pattern` (e.g., `secrecy::Secret<*>`); `*` matches any sequence
+    /// of characters, including `::`.
+    pub fn type_name(mut self, pattern: impl Into<String>) -> Self {
+        self.types.push(pattern.into());
+        self
+    }
+
+    /// Also redacts the values of the fields whose names match the glob
+    /// `pattern` (e.g., `password`, or `*_token`); the fields of tuples are
+    /// named `__0`, `__1`, etc.
+    pub fn field(mut self, pattern: impl Into<String>) -> Self {
+        self.fields.push(pattern.into());
+        self
+    }
+
+    /// Also redacts the fields whose names match any of `patterns`; e.g., a
+    /// list of sensitive fields read from configuration.
+    pub fn fields<I>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.fields.extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether the type with the canonical name `name` is redacted.
+    pub(crate) fn redacts_type(&self, name: &str) -> bool {
+        self.types
+            .iter()
+            .any(|pattern| crate::filter::glob(pattern.as_bytes(), name.as_bytes()))
+    }
+
+    /// Whether the field named `name` is redacted.
+    pub(crate) fn redacts_field(&self, name: &[u8]) -> bool {
+        self.fields
+            .iter()
+            .any(|pattern| crate::filter::glob(pattern.as_bytes(), name))
+    }
+}
@@ -0,0 +1,234 @@
+//! The paths within a value that refer to an address; e.g., to find out what
+//! owns a leaked allocation, or still points to a freed one.
+
+use std::collections::HashSet;
+
+use crate::{rustc::Container, value::Path, Value};
+
+/// A pointer within a value that refers to an address; produced by
+/// [`find_references`].
+#[derive(Clone, Debug)]
+pub struct Referrer {
+    path: Path,
+    offset: usize,
+}
+
+impl Referrer {
+    /// The path of the pointer (or of the `Vec`, `Rc` or `Arc` that owns the
+    /// allocation), relative to the searched value.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The offset of the address within the range the pointer refers to; `0`,
+    /// if the pointer points at the address exactly.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Produces the paths of the pointers within `value` (and within the values
+/// it refers to, transitively) that point at `addr`, or that refer to a
+/// range of memory containing `addr`: the referent of a reference, box or raw
+/// pointer, the elements of a slice, the buffer of a `Vec`, or the allocation
+/// of an `Rc` or `Arc`.
+///
+/// References, boxes, slices, `Vec`s, `Rc`s and `Arc`s are followed, and
+/// each referent is searched once, even if it is reached through a cycle; raw
+/// pointers are checked, but not followed, as they may dangle. The paths are
+/// in the order in which they were reached.
+///
+/// ```
+/// let context = deflect::default_provider()?;
+/// let items = vec![1u32, 2, 3];
+/// let pair = (&items, &items[1]);
+/// let erased: &dyn deflect::Reflect = &pair;
+/// let value = erased.reflect(&context)?;
+///
+/// let found = deflect::find_references(&value, &items[1] as *const u32 as usize)?;
+/// let paths: Vec<_> = found.iter().map(|found| found.path().to_string()).collect();
+/// assert_eq!(paths, ["0", "1"]);
+/// assert_eq!(found[0].offset(), 4);
+/// # Ok::<_, deflect::anyhow::Error>(())
+/// ```
+pub fn find_references<P>(value: &Value<'_, '_, P>, addr: usize) -> Result<Vec<Referrer>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut search = Search {
+        addr,
+        visited: HashSet::new(),
+        found: vec![],
+    };
+    search.walk(&Path::default(), value)?;
+    Ok(search.found)
+}
+
+/// The state of a [`find_references`] search.
+struct Search {
+    addr: usize,
+    /// The addresses and sizes of the referents searched so far.
+    visited: HashSet<(usize, usize)>,
+    found: Vec<Referrer>,
+}
+
+impl Search {
+    /// Records the pointer at `path` if it refers to `len` bytes from
+    /// `start` that contain the searched address.
+    fn check(&mut self, path: &Path, start: usize, len: usize) {
+        // zero-sized referents are only pointed at.
+        let end = start.saturating_add(len.max(1));
+        if (start..end).contains(&self.addr) {
+            self.found.push(Referrer {
+                path: path.clone(),
+                offset: self.addr - start,
+            });
+        }
+    }
+
+    /// Whether the `len` bytes from `start` are yet to be searched; marks
+    /// them as searched.
+    fn visit(&mut self, start: usize, len: usize) -> bool {
+        self.visited.insert((start, len))
+    }
+
+    /// Checks and searches `referent`, which a pointer at `path` refers to.
+    fn referent<P>(&mut self, path: &Path, referent: Value<'_, '_, P>) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let bytes = referent.bytes();
+        let (start, len) = (bytes.as_ptr() as usize, bytes.len());
+        self.check(path, start, len);
+        if self.visit(start, len) {
+            self.walk(path, &referent)?;
+        }
+        Ok(())
+    }
+
+    /// Checks and searches the `len` elements from `start`, which a pointer
+    /// at `path` refers to.
+    fn elements<P>(
+        &mut self,
+        path: &Path,
+        start: usize,
+        len: usize,
+        elements: crate::value::Iter<'_, '_, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        // the elements of empty slices and `Vec`s may dangle.
+        if len == 0 {
+            return Ok(());
+        }
+        self.check(path, start, len);
+        if self.visit(start, len) {
+            self.walk_elements(path, elements)?;
+        }
+        Ok(())
+    }
+
+    /// Searches the pointers within `value`, which is at `path`.
+    fn walk<P>(&mut self, path: &Path, value: &Value<'_, '_, P>) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        match value {
+            Value::Box(v) => self.referent(path, v.deref()?),
+            Value::BoxedDyn(v) => self.referent(path, v.deref()?),
+            Value::DynRef(v) => self.referent(path, v.downcast_value()?),
+            Value::SharedRef(v) => self.referent(path, v.deref()?),
+            Value::UniqueRef(v) => self.referent(path, v.deref()?),
+            Value::Slice(v) => {
+                let start = v.data_ptr()?.as_ptr() as usize;
+                let elt_size = usize::try_from(v.schema().elt()?.size()?)?;
+                let len = elt_size.saturating_mul(v.length()?);
+                self.elements(path, start, len, v.iter()?)
+            }
+            Value::BoxedSlice(v) => {
+                let start = v.data_ptr()?.as_ptr() as usize;
+                let elt_size = usize::try_from(v.schema().elt()?.size()?)?;
+                let len = elt_size.saturating_mul(v.length()?);
+                self.elements(path, start, len, v.iter()?)
+            }
+            Value::ConstPtr(v) => {
+                let size = v.schema().r#type().and_then(|r#type| r#type.size());
+                self.check(path, v.addr(), usize::try_from(size.unwrap_or(0))?);
+                Ok(())
+            }
+            Value::MutPtr(v) => {
+                let size = v.schema().r#type().and_then(|r#type| r#type.size());
+                self.check(path, v.addr(), usize::try_from(size.unwrap_or(0))?);
+                Ok(())
+            }
+            Value::NonNull(v) => self.walk(path, &v.pointer()?),
+            Value::Array(v) => self.walk_elements(path, v.iter()?),
+            Value::NonZero(v) => self.walk(path, &v.value()?),
+            Value::ManuallyDrop(v) => self.walk(path, &v.value()?),
+            Value::Struct(v) => match crate::rustc::container_of(v.schema())? {
+                Some(Container::Vec) => {
+                    let buffer = crate::rustc::vec_buffer(v)?;
+                    self.elements(path, buffer.addr, buffer.size, buffer.elements)
+                }
+                Some(Container::Shared) => {
+                    // the allocation holds the counts, followed by the value.
+                    let (start, inner) = crate::rustc::shared_allocation(v)?;
+                    let bytes = inner.bytes();
+                    let end = bytes.as_ptr() as usize + bytes.len();
+                    self.check(path, start, end.saturating_sub(start));
+                    if self.visit(bytes.as_ptr() as usize, bytes.len()) {
+                        self.walk(path, &inner)?;
+                    }
+                    Ok(())
+                }
+                Some(Container::Weak) => Ok(()),
+                Some(Container::String | Container::HashMap | Container::Mutex) | None => {
+                    self.walk_fields(path, v.fields()?)
+                }
+            },
+            Value::Enum(v) => {
+                let variant = v.variant()?;
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                self.walk_fields(&path.variant(name), variant.fields()?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Searches the pointers within `elements`, which are at `path`.
+    fn walk_elements<P>(
+        &mut self,
+        path: &Path,
+        elements: crate::value::Iter<'_, '_, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        for (index, element) in elements.enumerate() {
+            self.walk(&path.index(index), &element?)?;
+        }
+        Ok(())
+    }
+
+    /// Searches the pointers within `fields`, which are at `path`; bitfields
+    /// hold no pointers, and are skipped.
+    fn walk_fields<P>(
+        &mut self,
+        path: &Path,
+        mut fields: crate::value::Fields<'_, '_, P>,
+    ) -> Result<(), crate::Error>
+    where
+        P: crate::DebugInfoProvider,
+    {
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            if field.schema().is_bitfield()? {
+                continue;
+            }
+            let name = field.schema().name()?.to_string_lossy()?.into_owned();
+            self.walk(&path.field(name), &field.value()?)?;
+        }
+        Ok(())
+    }
+}
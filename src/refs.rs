@@ -0,0 +1,208 @@
+//! Resolution of references between debugging information entries.
+//!
+//! Most references are relative to the unit of the entry they are read from,
+//! but linkers (e.g., with LTO) may emit references into other compilation
+//! units, and compilers may move types into type units identified by their
+//! signature. The units those references lead to are parsed on demand, and
+//! [memoized](crate::memo) with their [DWARF](crate::gimli::Dwarf) sections;
+//! so references into other units can only be followed in sections that are
+//! memoized.
+
+use crate::gimli::{
+    AttributeValue, DebugInfoOffset, DebugTypeSignature, Dwarf, Reader, Unit, UnitOffset,
+    UnitSectionOffset, UnitType,
+};
+use crate::memo::{with_memo, Erased};
+use std::{collections::HashMap, sync::Arc};
+
+/// Resolves the reference `value`, read from an entry of `unit`, to the unit
+/// and offset of the entry it refers to; or `None`, if `value` is not a
+/// reference.
+pub(crate) fn resolve<'dwarf, R>(
+    dwarf: &'dwarf Dwarf<R>,
+    unit: &'dwarf Unit<R>,
+    value: AttributeValue<R>,
+) -> Result<Option<(&'dwarf Unit<R>, UnitOffset)>, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    let target = match value {
+        AttributeValue::UnitRef(offset) => return Ok(Some((unit, offset))),
+        AttributeValue::DebugInfoRef(offset) => UnitSectionOffset::DebugInfoOffset(offset),
+        AttributeValue::DebugTypesRef(signature) => {
+            let (target_unit, offset) = type_unit_of(dwarf, signature)?
+                .ok_or_else(|| crate::error::missing_type_unit(signature))?;
+            return Ok(Some((unit_at(dwarf, unit, target_unit)?, offset)));
+        }
+        _ => return Ok(None),
+    };
+    // cross-unit references frequently refer back into their own unit.
+    if let Some(offset) = target.to_unit_offset(unit) {
+        return Ok(Some((unit, offset)));
+    }
+    let target_unit = unit_containing(dwarf, target)?;
+    let target_unit = unit_at(dwarf, unit, target_unit)?;
+    let offset = target
+        .to_unit_offset(target_unit)
+        .ok_or_else(|| crate::error::dangling_reference(target))?;
+    Ok(Some((target_unit, offset)))
+}
+
+/// Produces the unit at `offset`; `unit` itself, if it is at that offset.
+pub(crate) fn unit_at<'dwarf, R>(
+    dwarf: &'dwarf Dwarf<R>,
+    unit: &'dwarf Unit<R>,
+    offset: UnitSectionOffset,
+) -> Result<&'dwarf Unit<R>, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    if unit.header.offset() == offset {
+        return Ok(unit);
+    }
+    load_unit(dwarf, offset)
+}
+
+/// Produces the unit at `offset`, which is parsed once and memoized with
+/// `dwarf`; or an error, if `dwarf` is not memoized.
+pub(crate) fn load_unit<R>(
+    dwarf: &Dwarf<R>,
+    offset: UnitSectionOffset,
+) -> Result<&Unit<R>, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    type Units = HashMap<UnitSectionOffset, Erased>;
+
+    // SAFETY: the memoized unit was read from `dwarf`, and is freed with its
+    // memos, which outlive the borrow of `dwarf`.
+    let cached = with_memo(dwarf, "units", |units: &mut Units| {
+        units
+            .get(&offset)
+            .map(|cached| unsafe { cached.get::<Unit<R>>() })
+    });
+    match cached {
+        Some(Some(cached)) => return Ok(cached),
+        Some(None) => {}
+        None => return Err(crate::error::unmemoized_reference(offset)),
+    }
+
+    let header = match offset {
+        UnitSectionOffset::DebugInfoOffset(offset) => {
+            dwarf.debug_info.header_from_offset(offset)?
+        }
+        UnitSectionOffset::DebugTypesOffset(_) => {
+            let mut headers = dwarf.type_units();
+            loop {
+                match headers.next()? {
+                    Some(header) if header.offset() == offset => break header,
+                    Some(_) => continue,
+                    None => return Err(crate::error::dangling_reference(offset)),
+                }
+            }
+        }
+    };
+    // units are memoized so that, like the unit they are referred to from,
+    // they live as long as `dwarf`.
+    let target = dwarf.unit(header)?;
+    with_memo(dwarf, "units", |units: &mut Units| {
+        let target = units.entry(offset).or_insert_with(|| Erased::new(target));
+        // SAFETY: as above.
+        unsafe { target.get::<Unit<R>>() }
+    })
+    .ok_or_else(|| crate::error::unmemoized_reference(offset))
+}
+
+/// Produces the offset of the unit of `.debug_info` that contains `target`.
+fn unit_containing<R>(
+    dwarf: &Dwarf<R>,
+    target: UnitSectionOffset,
+) -> Result<UnitSectionOffset, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    type Cached = Vec<(DebugInfoOffset, usize)>;
+
+    let UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(offset)) = target else {
+        return Err(crate::error::dangling_reference(target));
+    };
+
+    let cached = with_memo(dwarf, "bounds", |bounds: &mut Option<Arc<Cached>>| {
+        bounds.clone()
+    });
+    let bounds = match cached.flatten() {
+        Some(bounds) => bounds,
+        None => {
+            let mut bounds = vec![];
+            let mut headers = dwarf.units();
+            while let Some(header) = headers.next()? {
+                if let UnitSectionOffset::DebugInfoOffset(start) = header.offset() {
+                    bounds.push((start, start.0 + header.length_including_self()));
+                }
+            }
+            let bounds = Arc::new(bounds);
+            with_memo(dwarf, "bounds", |memo: &mut Option<Arc<Cached>>| {
+                *memo = Some(bounds.clone());
+            });
+            bounds
+        }
+    };
+
+    // units are laid out in order of their offsets.
+    let index = bounds.partition_point(|&(start, _)| start.0 <= offset);
+    match index.checked_sub(1).map(|index| bounds[index]) {
+        Some((start, end)) if offset < end => Ok(UnitSectionOffset::DebugInfoOffset(start)),
+        _ => Err(crate::error::dangling_reference(target)),
+    }
+}
+
+/// Produces the unit offset and type offset of the type unit with the given
+/// `signature`, if any; whether in `.debug_types` (DWARF 4) or in
+/// `.debug_info` (DWARF 5).
+fn type_unit_of<R>(
+    dwarf: &Dwarf<R>,
+    signature: DebugTypeSignature,
+) -> Result<Option<(UnitSectionOffset, UnitOffset)>, crate::Error>
+where
+    R: Reader<Offset = usize>,
+{
+    type Signatures = HashMap<DebugTypeSignature, (UnitSectionOffset, UnitOffset)>;
+
+    let cached = with_memo(dwarf, "signatures", |memo: &mut Option<Arc<Signatures>>| {
+        memo.clone()
+    });
+    if let Some(signatures) = cached.flatten() {
+        return Ok(signatures.get(&signature).copied());
+    }
+
+    let mut signatures = Signatures::new();
+    let mut record = |offset: UnitSectionOffset, unit_type: UnitType<usize>| {
+        if let UnitType::Type {
+            type_signature,
+            type_offset,
+        }
+        | UnitType::SplitType {
+            type_signature,
+            type_offset,
+        } = unit_type
+        {
+            signatures
+                .entry(type_signature)
+                .or_insert((offset, type_offset));
+        }
+    };
+    let mut headers = dwarf.type_units();
+    while let Some(header) = headers.next()? {
+        record(header.offset(), header.type_());
+    }
+    let mut headers = dwarf.units();
+    while let Some(header) = headers.next()? {
+        record(header.offset(), header.type_());
+    }
+
+    let found = signatures.get(&signature).copied();
+    with_memo(dwarf, "signatures", |memo: &mut Option<Arc<Signatures>>| {
+        *memo = Some(Arc::new(signatures));
+    });
+    Ok(found)
+}
@@ -0,0 +1,258 @@
+//! A global registry of live objects, which can be reflected at any time;
+//! e.g., from a debug endpoint, a signal handler's thread, or a panic hook.
+
+use std::{collections::HashSet, sync::Arc};
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+
+use crate::{schema::Type, Reflect};
+
+/// The global registry of live objects.
+///
+/// Objects are registered by name, and are either `'static` (e.g., `static`s,
+/// or leaked boxes) or shared with [`Arc`]s, which the registry keeps alive
+/// until they are removed. Because registered objects may be reflected at any
+/// time, from any thread, they must be `Sync`; and, because they are read
+/// without synchronization, they must not hold data guarded by an
+/// [`UnsafeCell`][std::cell::UnsafeCell] (e.g., by a `Mutex` or an `RwLock`),
+/// other than that of atomics. Such objects are refused by
+/// [`insert`][Registry::insert].
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// static LIMITS: [u32; 2] = [10, 20];
+/// deflect::registry().insert("limits", &LIMITS)?;
+/// deflect::registry().insert("name", Arc::new("primary"))?;
+///
+/// let dump = deflect::registry().dump()?;
+/// assert_eq!(dump[0], ("limits".to_owned(), "[\n    10,\n    20,\n]".to_owned()));
+/// assert_eq!(dump[1], ("name".to_owned(), "\"primary\"".to_owned()));
+/// # Ok::<_, deflect::anyhow::Error>(())
+/// ```
+pub fn registry() -> &'static Registry {
+    static REGISTRY: Lazy<Registry> = Lazy::new(|| Registry {
+        objects: DashMap::new(),
+    });
+    &REGISTRY
+}
+
+/// A registry of live objects, by name; see [`registry`].
+pub struct Registry {
+    objects: DashMap<String, Registered>,
+}
+
+/// An object in a [`Registry`]; converted from a `&'static T` or an `Arc<T>`.
+#[derive(Clone)]
+pub struct Registered {
+    object: Object,
+}
+
+#[derive(Clone)]
+enum Object {
+    Static(&'static (dyn Reflect + Sync)),
+    Shared(Arc<dyn Reflect + Send + Sync>),
+}
+
+impl Registered {
+    /// The registered object.
+    pub(crate) fn object(&self) -> &(dyn Reflect + Sync) {
+        match &self.object {
+            Object::Static(object) => *object,
+            Object::Shared(object) => &**object,
+        }
+    }
+}
+
+impl<T> From<&'static T> for Registered
+where
+    T: Reflect + Sync,
+{
+    fn from(object: &'static T) -> Self {
+        Self {
+            object: Object::Static(object),
+        }
+    }
+}
+
+impl<T> From<Arc<T>> for Registered
+where
+    T: Reflect + Send + Sync + 'static,
+{
+    fn from(object: Arc<T>) -> Self {
+        Self {
+            object: Object::Shared(object),
+        }
+    }
+}
+
+impl Registry {
+    /// Registers `object` under `name`, replacing any object already
+    /// registered under it; fails if `object` holds data guarded by an
+    /// [`UnsafeCell`][std::cell::UnsafeCell] other than an atomic's, or a
+    /// trait object, whose contents cannot be checked.
+    pub fn insert(
+        &self,
+        name: impl Into<String>,
+        object: impl Into<Registered>,
+    ) -> Result<(), crate::Error> {
+        let name = name.into();
+        let registered = object.into();
+        let provider = crate::default_provider()?;
+        let object: &dyn Reflect = registered.object();
+        // types that cannot be reflected cannot be read, either.
+        if let Ok(r#type) = crate::do_reflect_type(object.local_type_id(), &provider) {
+            check_unguarded(&r#type, &mut HashSet::new())
+                .map_err(|err| err.context(format!("could not register `{name}`")))?;
+        }
+        self.objects.insert(name, registered);
+        Ok(())
+    }
+
+    /// Removes the object registered under `name`; returns whether there was
+    /// one.
+    pub fn remove(&self, name: &str) -> bool {
+        self.objects.remove(name).is_some()
+    }
+
+    /// The object registered under `name`.
+    #[cfg(feature = "http")]
+    pub(crate) fn get(&self, name: &str) -> Option<Registered> {
+        self.objects.get(name).map(|entry| entry.value().clone())
+    }
+
+    /// The names of the registered objects, in order.
+    pub fn names(&self) -> Vec<String> {
+        self.objects().into_iter().map(|(name, _)| name).collect()
+    }
+
+    /// The registered objects, in the order of their names.
+    ///
+    /// The registry is copied, so that no lock is held while objects are read.
+    pub(crate) fn objects(&self) -> Vec<(String, Registered)> {
+        let mut objects: Vec<_> = self
+            .objects
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        objects.sort_by(|(a, _), (b, _)| a.cmp(b));
+        objects
+    }
+
+    /// Reflects each registered object, and produces its name and its
+    /// pretty-printed (i.e., `{:#}`) form, in the order of their names; e.g.,
+    /// for serving from a debug endpoint.
+    pub fn dump(&self) -> Result<Vec<(String, String)>, crate::Error> {
+        let provider = crate::default_provider()?;
+        let mut dump = vec![];
+        for (name, registered) in self.objects() {
+            let object: &dyn Reflect = registered.object();
+            let value = object.reflect(&provider)?;
+            dump.push((name, format!("{value:#}")));
+        }
+        Ok(dump)
+    }
+
+    /// Reflects each registered object, and produces a JSON object of their
+    /// [JSON conversions][crate::Value::to_json], keyed by name.
+    #[cfg(feature = "json")]
+    pub fn dump_json(&self) -> Result<serde_json::Value, crate::Error> {
+        let provider = crate::default_provider()?;
+        let mut dump = serde_json::Map::new();
+        for (name, registered) in self.objects() {
+            let object: &dyn Reflect = registered.object();
+            dump.insert(name, object.reflect(&provider)?.to_json()?);
+        }
+        Ok(serde_json::Value::Object(dump))
+    }
+}
+
+/// The types already checked by [`check_unguarded`], by the offsets of their
+/// units and entries.
+type Checked = HashSet<(crate::gimli::UnitSectionOffset, crate::gimli::UnitOffset)>;
+
+/// Fails if the values of `r#type` hold data guarded by an `UnsafeCell`,
+/// other than an atomic's (which is read atomically), or a trait object.
+///
+/// The fields, variants, elements and pointees of `r#type` are checked, as
+/// are its generic type parameters; e.g., the `T` of a `Vec<T>`, whose
+/// elements are read through a raw pointer. Raw pointers themselves are not
+/// read through, and so are not checked.
+fn check_unguarded<R>(r#type: &Type<'_, R>, checked: &mut Checked) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let (dwarf, unit, offset) = r#type.die();
+    if !checked.insert((unit.header.offset(), offset)) {
+        return Ok(());
+    }
+    match r#type {
+        Type::Struct(schema) => {
+            if let Some(name) = crate::qualified_name_of(dwarf, unit, offset)? {
+                if name.starts_with(b"core::cell::UnsafeCell<")
+                    || name.starts_with(b"core::cell::SyncUnsafeCell<")
+                {
+                    bail!("`{}` may not be read without synchronization", r#type);
+                }
+            }
+            check_fields(schema.fields()?, checked)?;
+        }
+        Type::Enum(schema) => {
+            let mut variants = schema.variants()?;
+            let mut variants = variants.iter()?;
+            while let Some(variant) = variants.try_next()? {
+                check_fields(variant.fields()?, checked)?;
+            }
+        }
+        Type::Box(schema) => check_within(schema.r#type(), checked)?,
+        Type::SharedRef(schema) => check_within(schema.r#type(), checked)?,
+        Type::UniqueRef(schema) => check_within(schema.r#type(), checked)?,
+        Type::BoxedSlice(schema) => check_within(schema.elt(), checked)?,
+        Type::Slice(schema) => check_within(schema.elt(), checked)?,
+        Type::Array(schema) => check_within(schema.elt_type(), checked)?,
+        Type::Vector(schema) => check_within(schema.lane_type(), checked)?,
+        Type::ManuallyDrop(schema) => check_within(schema.value_type(), checked)?,
+        Type::MaybeUninit(schema) => check_within(schema.value_type(), checked)?,
+        Type::BoxedDyn(_) | Type::DynRef(_) => {
+            bail!("`{}` is a trait object, whose contents cannot be checked", r#type)
+        }
+        _ => {}
+    }
+    if matches!(r#type, Type::Struct(_) | Type::Enum(_)) {
+        for param in r#type.generic_params()? {
+            check_within(param.r#type(), checked)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the types of `fields` with [`check_unguarded`].
+fn check_fields<R>(
+    mut fields: crate::schema::Fields<'_, R>,
+    checked: &mut Checked,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        check_within(field.r#type(), checked)?;
+    }
+    Ok(())
+}
+
+/// Checks `r#type` with [`check_unguarded`], if it could be reflected; types
+/// that cannot be reflected are not read.
+fn check_within<R>(
+    r#type: Result<Type<'_, R>, crate::Error>,
+    checked: &mut Checked,
+) -> Result<(), crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    match r#type {
+        Ok(r#type) => check_unguarded(&r#type, checked),
+        Err(_) => Ok(()),
+    }
+}
@@ -0,0 +1,547 @@
+//! The version of `rustc` that produced a unit, and the layouts of the standard
+//! library's containers it implies.
+//!
+//! The fields of containers like `Vec` are implementation details that change
+//! between versions of the standard library; reading them by the layout of
+//! another version would misread memory.
+
+use std::collections::HashMap;
+
+use crate::gimli::UnitSectionOffset;
+use crate::memo::with_memo;
+
+/// A version of `rustc`; e.g., `1.84.0`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Version {
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) patch: u32,
+}
+
+impl Version {
+    pub(crate) const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// The `DW_AT_producer` of `unit`, if any; e.g., `clang LLVM (rustc version
+/// 1.84.0 (9fc6b4312 2025-01-07))`.
+pub(crate) fn producer_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R>,
+) -> Result<Option<String>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    type Producers = HashMap<UnitSectionOffset, Option<String>>;
+
+    let key = unit.header.offset();
+    let cached = with_memo(dwarf, "producers", |producers: &mut Producers| {
+        producers.get(&key).cloned()
+    });
+    if let Some(cached) = cached.flatten() {
+        return Ok(cached);
+    }
+
+    let mut entries = unit.entries();
+    let root = entries.next_dfs()?.map(|(_, root)| root);
+    let producer = match root {
+        Some(root) => root.attr_value(crate::gimli::DW_AT_producer)?,
+        None => None,
+    };
+    let producer = match producer {
+        Some(producer) => Some(dwarf.attr_string(unit, producer)?),
+        None => None,
+    };
+    let producer = match producer {
+        Some(producer) => Some(producer.to_string_lossy()?.into_owned()),
+        None => None,
+    };
+    with_memo(dwarf, "producers", |producers: &mut Producers| {
+        producers.insert(key, producer.clone());
+    });
+    Ok(producer)
+}
+
+/// The version of `rustc` named by `producer`, if it names one.
+pub(crate) fn version_of(producer: &str) -> Option<Version> {
+    let (_, version) = producer.split_once("rustc version ")?;
+    let version = version.split([' ', '-']).next()?;
+    let mut parts = version.splitn(3, '.').map(str::parse);
+    Some(Version::new(
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    ))
+}
+
+/// The paths of fields from a `Vec` to the pointer to its elements, in the
+/// layouts of the standard library of `version`; all known layouts, if the
+/// version is unknown.
+pub(crate) fn vec_ptr_paths(version: Option<Version>) -> &'static [&'static [&'static str]] {
+    /// `RawVec` delegates to an untyped `RawVecInner` since 1.84.
+    const RAW_VEC_INNER: &[&str] = &["buf", "inner", "ptr"];
+    const RAW_VEC: &[&str] = &["buf", "ptr"];
+    match version {
+        Some(version) if version >= Version::new(1, 84, 0) => &[RAW_VEC_INNER],
+        Some(_) => &[RAW_VEC],
+        None => &[RAW_VEC_INNER, RAW_VEC],
+    }
+}
+
+/// The paths of fields from a `String` to the `Vec<u8>` of its bytes, in the
+/// layouts of the standard library of `version`; all known layouts, if the
+/// version is unknown.
+pub(crate) fn string_vec_paths(version: Option<Version>) -> &'static [&'static [&'static str]] {
+    const VEC: &[&str] = &["vec"];
+    match version {
+        Some(_) | None => &[VEC],
+    }
+}
+
+/// The paths of fields from a `HashMap` to the fields of its `hashbrown` table
+/// (i.e., its `ctrl`, `bucket_mask` and `items`), in the layouts of the
+/// standard library of `version`; all known layouts, if the version is
+/// unknown. The first two fields of each path lead to the `RawTable`.
+pub(crate) fn hash_table_paths(version: Option<Version>) -> &'static [&'static [&'static str]] {
+    /// `RawTable` delegates to an untyped `RawTableInner` since 1.53.
+    const RAW_TABLE_INNER: &[&str] = &["base", "table", "table"];
+    const RAW_TABLE: &[&str] = &["base", "table"];
+    match version {
+        Some(version) if version >= Version::new(1, 53, 0) => &[RAW_TABLE_INNER],
+        Some(_) => &[RAW_TABLE],
+        None => &[RAW_TABLE_INNER, RAW_TABLE],
+    }
+}
+
+/// The paths of fields from a `Mutex` to the value it guards, in the layouts
+/// of the standard library of `version`; all known layouts, if the version
+/// is unknown.
+pub(crate) fn mutex_data_paths(version: Option<Version>) -> &'static [&'static [&'static str]] {
+    /// The `UnsafeCell` of the value.
+    const DATA: &[&str] = &["data", "value"];
+    match version {
+        Some(_) | None => &[DATA],
+    }
+}
+
+/// A container of the standard library that is read by its layout, rather
+/// than as a plain struct.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Container {
+    /// A `Vec`, which owns the buffer behind its raw pointer.
+    Vec,
+    /// A `String`, which wraps a `Vec<u8>`.
+    String,
+    /// A `HashMap`, whose entries are in the buckets of a `hashbrown` table.
+    HashMap,
+    /// A `Mutex`, which wraps the value it guards.
+    Mutex,
+    /// An `Rc` or `Arc`, which shares ownership of its allocation.
+    Shared,
+    /// A `Weak` of an `Rc` or `Arc`, which does not own its allocation.
+    Weak,
+}
+
+/// The [`Container`] that `schema` is, if any.
+pub(crate) fn container_of<R>(
+    schema: &crate::schema::Struct<'_, R>,
+) -> Result<Option<Container>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let offset = schema.entry().offset();
+    let Some(name) = crate::qualified_name_of(schema.dwarf(), schema.unit(), offset)? else {
+        return Ok(None);
+    };
+    Ok(if name.starts_with(b"alloc::vec::Vec<") {
+        Some(Container::Vec)
+    } else if &*name == b"alloc::string::String" {
+        Some(Container::String)
+    } else if name.starts_with(b"std::collections::hash::map::HashMap<") {
+        Some(Container::HashMap)
+    } else if name.starts_with(b"std::sync::mutex::Mutex<")
+        || name.starts_with(b"std::sync::poison::mutex::Mutex<")
+    {
+        Some(Container::Mutex)
+    } else if name.starts_with(b"alloc::rc::Rc<") || name.starts_with(b"alloc::sync::Arc<") {
+        Some(Container::Shared)
+    } else if name.starts_with(b"alloc::rc::Weak<") || name.starts_with(b"alloc::sync::Weak<") {
+        Some(Container::Weak)
+    } else {
+        None
+    })
+}
+
+/// The buffer of a reflected `Vec`.
+pub(crate) struct VecBuffer<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The address of the buffer; dangling, if nothing is allocated.
+    pub(crate) addr: usize,
+    /// The size of the allocated buffer, in bytes.
+    pub(crate) size: usize,
+    /// The initialized elements of the buffer.
+    pub(crate) elements: crate::value::Iter<'value, 'dwarf, P>,
+}
+
+/// The buffer of the `Vec` `value`.
+pub(crate) fn vec_buffer<'value, 'dwarf, P>(
+    value: &crate::value::Struct<'value, 'dwarf, P>,
+) -> Result<VecBuffer<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let VecParts {
+        addr,
+        size,
+        elt_type,
+        len,
+    } = vec_parts(value)?;
+    let elements = unsafe {
+        crate::value::Iter::from_raw_parts(
+            addr as *const crate::Byte,
+            elt_type,
+            len,
+            value.provider(),
+        )
+    }?;
+    Ok(VecBuffer {
+        addr,
+        size,
+        elements,
+    })
+}
+
+/// The buffer of a reflected `Vec`, which is not read.
+pub(crate) struct VecParts<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The address of the buffer; dangling, if nothing is allocated.
+    pub(crate) addr: usize,
+    /// The size of the allocated buffer, in bytes.
+    pub(crate) size: usize,
+    /// The type of the elements of the buffer.
+    pub(crate) elt_type: crate::schema::Type<'dwarf, R>,
+    /// The number of initialized elements of the buffer.
+    pub(crate) len: usize,
+}
+
+/// The buffer of the `Vec` `value`, without reading it.
+pub(crate) fn vec_parts<'dwarf, P>(
+    value: &crate::value::Struct<'_, 'dwarf, P>,
+) -> Result<VecParts<'dwarf, P::Reader>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (version, unsupported) = layout_of(value)?;
+    let generic_params = crate::schema::Type::Struct(value.schema().clone()).generic_params()?;
+    let Some(elt_type) = generic_params.into_iter().next() else {
+        return Err(unsupported);
+    };
+    let elt_type = elt_type.r#type()?;
+    let elt_size = usize::try_from(elt_type.size()?)?;
+    let Some(len) = value.field_value("len")? else {
+        return Err(unsupported);
+    };
+    let len: usize = len.try_into()?;
+
+    for path in vec_ptr_paths(version) {
+        let Some(ptr) = crate::value::field_at_path(value, path)? else {
+            continue;
+        };
+        let Some(addr) = crate::value::first_pointer(ptr)? else {
+            continue;
+        };
+        // the capacity is a sibling of the pointer.
+        let cap_path = [&path[..path.len() - 1], &["cap"]].concat();
+        let Some(cap) = crate::value::field_at_path(value, &cap_path)? else {
+            continue;
+        };
+        let Some(cap) = first_usize(cap)? else {
+            continue;
+        };
+        // the capacity of a `Vec` of zero-sized elements is `usize::MAX`.
+        let size = if elt_size == 0 {
+            0
+        } else {
+            elt_size
+                .checked_mul(cap)
+                .ok_or_else(crate::error::arithmetic_overflow)?
+        };
+        return Ok(VecParts {
+            addr: addr as usize,
+            size,
+            elt_type,
+            len,
+        });
+    }
+    Err(unsupported)
+}
+
+/// The producer of the unit of `value`'s type, the version of `rustc` it
+/// names, and the error of an unsupported layout of that type.
+fn layout_of<P>(
+    value: &crate::value::Struct<'_, '_, P>,
+) -> Result<(Option<Version>, crate::Error), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let schema = value.schema();
+    let producer = producer_of(schema.dwarf(), schema.unit())?;
+    let version = producer.as_deref().and_then(version_of);
+    let name = schema.name()?.to_string_lossy()?.into_owned();
+    Ok((version, crate::error::unsupported_std_layout(name, producer)))
+}
+
+/// The `Vec<u8>` of the bytes of the `String` `value`.
+pub(crate) fn string_vec<'value, 'dwarf, P>(
+    value: &crate::value::Struct<'value, 'dwarf, P>,
+) -> Result<crate::value::Struct<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (version, unsupported) = layout_of(value)?;
+    for path in string_vec_paths(version) {
+        if let Some(crate::Value::Struct(vec)) = crate::value::field_at_path(value, path)? {
+            return Ok(vec);
+        }
+    }
+    Err(unsupported)
+}
+
+/// The value guarded by the `Mutex` `value`.
+pub(crate) fn mutex_data<'value, 'dwarf, P>(
+    value: &crate::value::Struct<'value, 'dwarf, P>,
+) -> Result<crate::Value<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (version, unsupported) = layout_of(value)?;
+    for path in mutex_data_paths(version) {
+        if let Some(data) = crate::value::field_at_path(value, path)? {
+            return Ok(data);
+        }
+    }
+    Err(unsupported)
+}
+
+/// The table of a reflected `HashMap`.
+pub(crate) struct HashTable<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The address of the control bytes of the table, which its buckets
+    /// precede; dangling, if nothing is allocated.
+    pub(crate) ctrl: usize,
+    /// The number of buckets of the table; zero, if nothing is allocated.
+    pub(crate) buckets: usize,
+    /// The size of each bucket; i.e., of a `(K, V)`.
+    pub(crate) bucket_size: usize,
+    /// The `(K, V)` of each occupied bucket, in order of their buckets.
+    pub(crate) entries: Vec<crate::Value<'value, 'dwarf, P>>,
+}
+
+/// The table of the `HashMap` `value`.
+pub(crate) fn hash_table<'value, 'dwarf, P>(
+    value: &crate::value::Struct<'value, 'dwarf, P>,
+) -> Result<HashTable<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let HashTableParts {
+        ctrl,
+        buckets,
+        bucket_type,
+        bucket_size,
+        items,
+    } = hash_table_parts(value)?;
+    // SAFETY: each bucket has a control byte, the top bit of which is clear
+    // if the bucket is occupied.
+    let control = unsafe { std::slice::from_raw_parts(ctrl as *const u8, buckets) };
+    let mut entries = vec![];
+    for (index, control) in control.iter().enumerate() {
+        if control & 0x80 != 0 {
+            continue;
+        }
+        // the buckets are laid out in reverse, below the control bytes.
+        let offset = (index + 1)
+            .checked_mul(bucket_size)
+            .ok_or_else(crate::error::arithmetic_overflow)?;
+        let bucket = ctrl.wrapping_sub(offset) as *const crate::Byte;
+        let bytes = std::ptr::slice_from_raw_parts(bucket, bucket_size);
+        // SAFETY: occupied buckets are initialized.
+        let entry =
+            unsafe { crate::Value::with_type(bucket_type.clone(), &*bytes, value.provider()) }?;
+        entries.push(entry);
+    }
+    if entries.len() != items {
+        return Err(layout_of(value)?.1);
+    }
+    Ok(HashTable {
+        ctrl,
+        buckets,
+        bucket_size,
+        entries,
+    })
+}
+
+/// The table of a reflected `HashMap`, which is not read.
+pub(crate) struct HashTableParts<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The address of the control bytes of the table, which its buckets
+    /// precede; dangling, if nothing is allocated.
+    pub(crate) ctrl: usize,
+    /// The number of buckets of the table; zero, if it is empty.
+    pub(crate) buckets: usize,
+    /// The type of each bucket; i.e., `(K, V)`.
+    pub(crate) bucket_type: crate::schema::Type<'dwarf, R>,
+    /// The size of each bucket.
+    pub(crate) bucket_size: usize,
+    /// The number of occupied buckets.
+    pub(crate) items: usize,
+}
+
+/// The table of the `HashMap` `value`, without reading it.
+pub(crate) fn hash_table_parts<'dwarf, P>(
+    value: &crate::value::Struct<'_, 'dwarf, P>,
+) -> Result<HashTableParts<'dwarf, P::Reader>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (version, unsupported) = layout_of(value)?;
+    for path in hash_table_paths(version) {
+        let Some(crate::Value::Struct(raw)) = crate::value::field_at_path(value, &path[..2])?
+        else {
+            continue;
+        };
+        let Some(crate::Value::Struct(table)) = crate::value::field_at_path(value, path)? else {
+            continue;
+        };
+        let (Some(ctrl), Some(bucket_mask), Some(items)) = (
+            table.field_value("ctrl")?,
+            table.field_value("bucket_mask")?,
+            table.field_value("items")?,
+        ) else {
+            continue;
+        };
+        let (Some(ctrl), Some(bucket_mask), Some(items)) = (
+            crate::value::first_pointer(ctrl)?,
+            first_usize(bucket_mask)?,
+            first_usize(items)?,
+        ) else {
+            continue;
+        };
+        // the buckets of a `RawTable<(K, V)>` are of its `T`.
+        let generic_params = crate::schema::Type::Struct(raw.schema().clone()).generic_params()?;
+        let Some(bucket_type) = generic_params.into_iter().next() else {
+            continue;
+        };
+        let bucket_type = bucket_type.r#type()?;
+        let bucket_size = usize::try_from(bucket_type.size()?)?;
+        // empty tables share a static singleton of control bytes, which is
+        // not read.
+        let buckets = if items == 0 {
+            0
+        } else {
+            bucket_mask
+                .checked_add(1)
+                .ok_or_else(crate::error::arithmetic_overflow)?
+        };
+        return Ok(HashTableParts {
+            ctrl: ctrl as usize,
+            buckets,
+            bucket_type,
+            bucket_size,
+            items,
+        });
+    }
+    Err(unsupported)
+}
+
+/// The address of the allocation of the `Rc` or `Arc` `value`, and its
+/// contents; i.e., its reference counts and value.
+pub(crate) fn shared_allocation<'value, 'dwarf, P>(
+    value: &crate::value::Struct<'value, 'dwarf, P>,
+) -> Result<(usize, crate::Value<'value, 'dwarf, P>), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let (addr, r#type) = shared_pointer(value)?;
+    let size = usize::try_from(r#type.size()?)?;
+    let bytes = std::ptr::slice_from_raw_parts(addr as *const crate::Byte, size);
+    let inner = unsafe { crate::Value::with_type(r#type, &*bytes, value.provider())? };
+    Ok((addr, inner))
+}
+
+/// The address of the allocation of the `Rc` or `Arc` `value`, and the type
+/// of its contents, without reading them.
+pub(crate) fn shared_pointer<'dwarf, P>(
+    value: &crate::value::Struct<'_, 'dwarf, P>,
+) -> Result<(usize, crate::schema::Type<'dwarf, P::Reader>), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Some(crate::Value::NonNull(ptr)) = value.field_value("ptr")? else {
+        let schema = value.schema();
+        let producer = producer_of(schema.dwarf(), schema.unit())?;
+        let name = schema.name()?.to_string_lossy()?.into_owned();
+        return Err(crate::error::unsupported_std_layout(name, producer));
+    };
+    let r#type = match ptr.pointer()? {
+        crate::Value::MutPtr(ptr) => ptr.schema().r#type()?,
+        crate::Value::ConstPtr(ptr) => ptr.schema().r#type()?,
+        pointer => {
+            return Err(crate::error::type_mismatch(
+                "*mut T",
+                pointer.r#type().to_string(),
+            ))
+        }
+    };
+    Ok((ptr.addr()?, r#type))
+}
+
+/// The strong count of the contents of an `Rc` or `Arc` allocation; at least
+/// one.
+pub(crate) fn strong_count<P>(inner: &crate::Value<'_, '_, P>) -> Result<usize, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let crate::Value::Struct(inner) = inner else {
+        return Ok(1);
+    };
+    let strong = match inner.field_value("strong")? {
+        Some(strong) => first_usize(strong)?,
+        None => None,
+    };
+    Ok(strong.unwrap_or(1).max(1))
+}
+
+/// The first `usize` reachable by descending through the first field of
+/// `value`; e.g., of a `Cell<usize>` or an `AtomicUsize`.
+fn first_usize<P>(value: crate::Value<'_, '_, P>) -> Result<Option<usize>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    match value {
+        crate::Value::usize(value) => Ok(Some(*value.value())),
+        crate::Value::Atomic(value) => Ok(Some(usize::try_from(value.load_bits()?)?)),
+        crate::Value::Struct(value) => {
+            let mut fields = value.fields()?;
+            let mut fields = fields.iter()?;
+            match fields.try_next()? {
+                Some(field) => first_usize(field.value()?),
+                None => Ok(None),
+            }
+        }
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,149 @@
+use super::{Struct, Type};
+use crate::value::path::{parse, Segment};
+use std::{borrow::Cow, fmt};
+
+/// A precompiled path to a field of a [struct][Struct], produced by
+/// [`Struct::compile_accessor`].
+///
+/// The offset and type of the field are resolved when the accessor is
+/// compiled, so [`get`][Self::get] extracts the field from a value of the
+/// struct without consulting its debuginfo; e.g., for sampling metrics at a
+/// high frequency.
+#[derive(Clone)]
+pub struct Accessor<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    root: Struct<'dwarf, R>,
+    offset: u64,
+    r#type: Type<'dwarf, R>,
+}
+
+impl<'dwarf, R> Struct<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Compiles an [`Accessor`] of the field at `path`, relative to this
+    /// struct; e.g., `stats.latency[2]`.
+    ///
+    /// Paths are written as for [`Value::get_path`][crate::Value::get_path],
+    /// but may only consist of field names and array indices, which occur at
+    /// fixed offsets; dereferences and variant projections are rejected.
+    ///
+    /// Produces `None` if the path does not resolve in this struct.
+    pub fn compile_accessor(
+        &self,
+        path: &str,
+    ) -> Result<Option<Accessor<'dwarf, R>>, crate::Error> {
+        let mut offset = 0u64;
+        let mut r#type = Type::Struct(self.clone());
+        for segment in parse(path)? {
+            let (delta, next) = match segment {
+                Segment::Field(name) => {
+                    let Type::Struct(parent) = &r#type else { return Ok(None) };
+                    let name = if name.bytes().all(|b| b.is_ascii_digit()) {
+                        Cow::Owned(format!("__{name}"))
+                    } else {
+                        Cow::Borrowed(name)
+                    };
+                    let Some(field) = parent.field_by_name(&*name)? else { return Ok(None) };
+                    if field.is_bitfield()? {
+                        return Err(crate::error::bitfield());
+                    }
+                    (field.offset()?.address(0)?, field.r#type()?)
+                }
+                Segment::Index(index) => {
+                    let Type::Array(array) = &r#type else { return Ok(None) };
+                    if index as u64 >= array.len()? {
+                        return Ok(None);
+                    }
+                    let elt_type = array.elt_type()?;
+                    let delta = elt_type
+                        .size()?
+                        .checked_mul(index as u64)
+                        .ok_or_else(crate::error::arithmetic_overflow)?;
+                    (delta, elt_type)
+                }
+                Segment::Deref => {
+                    return Err(crate::error::invalid_path(
+                        path,
+                        "accessors cannot dereference pointers",
+                    ))
+                }
+                Segment::Variant(_) => {
+                    return Err(crate::error::invalid_path(
+                        path,
+                        "accessors cannot project enum variants",
+                    ))
+                }
+            };
+            offset = offset
+                .checked_add(delta)
+                .ok_or_else(crate::error::arithmetic_overflow)?;
+            r#type = next;
+        }
+        Ok(Some(Accessor {
+            root: self.clone(),
+            offset,
+            r#type,
+        }))
+    }
+}
+
+impl<'dwarf, R> Accessor<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The offset of the accessed field from the start of its struct, in
+    /// bytes.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The type of the accessed field.
+    pub fn r#type(&self) -> &Type<'dwarf, R> {
+        &self.r#type
+    }
+
+    /// Extracts the accessed field from `value`, which must be of the struct
+    /// this accessor was compiled for.
+    pub fn get<'value, P>(
+        &self,
+        value: &crate::value::Struct<'value, 'dwarf, P>,
+    ) -> Result<crate::Value<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let schema = value.schema();
+        if !std::ptr::eq(schema.dwarf(), self.root.dwarf())
+            || schema.unit().header.offset() != self.root.unit().header.offset()
+            || schema.entry().offset() != self.root.entry().offset()
+        {
+            return Err(crate::error::accessor_mismatch(
+                self.root.display_name()?,
+                schema.display_name()?,
+            ));
+        }
+        let offset = usize::try_from(self.offset)?;
+        let bytes = value
+            .bytes()
+            .get(offset..)
+            .ok_or_else(|| crate::error::size_mismatch(offset, value.bytes().len()))?;
+        // SAFETY: `value` is of the struct this accessor was compiled for, in
+        // which a field of `r#type` occurs at `offset`.
+        unsafe { crate::Value::with_type(self.r#type.clone(), bytes, value.provider()) }
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for Accessor<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("deflect::schema::Accessor")
+            .field("root", &self.root)
+            .field("offset", &self.offset)
+            .field("type", &self.r#type)
+            .finish()
+    }
+}
@@ -37,7 +37,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Function`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -51,11 +51,8 @@ where
 
     /// The element type, `T`, of this [`[T; N]`][prim@array] array.
     pub fn elt_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
-        super::Type::from_die(
-            self.dwarf,
-            self.unit,
-            self.unit.entry(crate::get_type(&self.entry)?)?,
-        )
+        let (unit, entry) = crate::get_type_res(self.dwarf, self.unit, &self.entry)?;
+        super::Type::from_die(self.dwarf, unit, entry)
     }
 
     /// The length, `N`, of this [`[T; N]`][prim@array] array.
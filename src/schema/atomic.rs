@@ -0,0 +1,167 @@
+use std::fmt;
+
+/// A schema for an atomic type (e.g., [`AtomicUsize`][std::sync::atomic::AtomicUsize]).
+#[derive(Clone)]
+#[allow(non_camel_case_types)]
+pub struct Atomic<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    cell: super::Field<'dwarf, R>,
+    value: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> Atomic<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `Atomic` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// Atomics are structs with a single field, `v` (or, of an `AtomicPtr`,
+    /// `p`), of type `UnsafeCell<T>`.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let (cell, value) = Self::cell_and_value(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self {
+            schema,
+            cell,
+            value,
+        })
+    }
+
+    /// Produces `true` if the given `entry` is of an atomic type of
+    /// `core::sync::atomic`, with the layout of one.
+    pub(crate) fn is_atomic(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        // other types may be shaped like atomics; e.g., a `Foo { v:
+        // UnsafeCell<u32> }`.
+        let Some(name) = crate::qualified_name_of(dwarf, unit, entry.offset())? else {
+            return Ok(false);
+        };
+        if !name.starts_with(b"core::sync::atomic::Atomic") {
+            return Ok(false);
+        }
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        Ok(Self::cell_and_value(&schema)?.is_some())
+    }
+
+    /// Produces the `v: UnsafeCell<T>` (or `p: UnsafeCell<*mut T>`) field of
+    /// `schema`, and the `value: T` field of that cell.
+    #[allow(clippy::type_complexity)]
+    fn cell_and_value(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<(super::Field<'dwarf, R>, super::Field<'dwarf, R>)>, crate::Error> {
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(cell) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() || !matches!(&*cell.name()?.to_slice()?, b"v" | b"p") {
+            return Ok(None);
+        }
+        let super::Type::Struct(cell_type) = cell.r#type()? else { return Ok(None) };
+        if !cell_type
+            .name()?
+            .to_slice()?
+            .starts_with(b"UnsafeCell<")
+        {
+            return Ok(None);
+        }
+        let mut cell_fields = cell_type.fields()?;
+        let mut cell_fields = cell_fields.iter()?;
+        let Some(value) = cell_fields.try_next()? else { return Ok(None) };
+        Ok(Some((cell, value)))
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `Atomic`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `Atomic`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `Atomic` abstracts
+    /// over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// This atomic, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The `v` (or, of an `AtomicPtr`, `p`) field of this atomic; an
+    /// `UnsafeCell`.
+    pub fn cell(&self) -> &super::Field<'dwarf, R> {
+        &self.cell
+    }
+
+    /// The type of the value stored in this atomic.
+    ///
+    /// For [`AtomicBool`][std::sync::atomic::AtomicBool], this is `u8`.
+    pub fn value_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
+        self.value.r#type()
+    }
+
+    /// Whether this is an [`AtomicBool`][std::sync::atomic::AtomicBool].
+    pub fn is_bool(&self) -> Result<bool, crate::Error> {
+        Ok(&*self.name()?.to_slice()? == b"AtomicBool")
+    }
+
+    /// The size of this atomic, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        self.schema.size()
+    }
+
+    /// The alignment of this atomic, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        self.schema.align()
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for Atomic<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::Atomic");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for Atomic<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
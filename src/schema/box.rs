@@ -10,6 +10,7 @@ where
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     entry: crate::gimli::UnitOffset,
     name: Option<super::Name<R>>,
+    target_unit: &'dwarf crate::gimli::Unit<R, usize>,
     target: crate::gimli::UnitOffset,
 }
 
@@ -23,31 +24,55 @@ where
         unit: &'dwarf crate::gimli::Unit<R, usize>,
         entry: crate::gimli::UnitOffset,
         name: Option<super::Name<R>>,
-        target: crate::gimli::UnitOffset,
+        (target_unit, target): (
+            &'dwarf crate::gimli::Unit<R, usize>,
+            crate::gimli::UnitOffset,
+        ),
     ) -> Self {
         Self {
             dwarf,
             unit,
             entry,
             name,
+            target_unit,
             target,
         }
     }
 
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `Box`'s debuginfo
+    /// belongs to.
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.dwarf
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `Box`'s debuginfo
+    /// belongs to.
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.unit
+    }
+
+    /// The offset of the [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `Box` abstracts
+    /// over.
+    pub(crate) fn offset(&self) -> crate::gimli::UnitOffset {
+        self.entry
+    }
+
     /// The name of this reference type.
     pub fn name(&self) -> Option<&super::Name<R>> {
         self.name.as_ref()
     }
 
-    /// The size of this type, in bytes.
+    /// The size of this type, in bytes; i.e., the size of addresses on the
+    /// target it was compiled for.
     pub fn size(&self) -> u64 {
-        core::mem::size_of::<usize>() as _
+        crate::address_size(self.unit)
     }
 
     /// The type of the referent.
     pub fn r#type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
-        let entry = self.unit.entry(self.target)?;
-        super::Type::from_die(self.dwarf, self.unit, entry)
+        let entry = self.target_unit.entry(self.target)?;
+        super::Type::from_die(self.dwarf, self.target_unit, entry)
     }
 }
 
@@ -71,7 +96,8 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(name) = self.name() {
-            name.fmt(f)
+            let name = name.to_string_lossy().map_err(crate::fmt_err)?;
+            f.write_str(&crate::format::canonical(&name))
         } else {
             f.write_str("*? ")?;
             let target = self.r#type().map_err(crate::fmt_err)?;
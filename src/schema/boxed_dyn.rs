@@ -39,7 +39,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Struct`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.schema.unit()
     }
 
@@ -66,9 +66,19 @@ where
         &self.vtable
     }
 
+    /// Whether this is a `Box<dyn Error>` (with or without auto traits, such
+    /// as `Send` and `Sync`).
+    pub fn is_error(&self) -> Result<bool, crate::Error> {
+        let name = self.schema.name()?;
+        let name = name.to_slice()?;
+        Ok([&b"<dyn core::error::Error"[..], b"<dyn std::error::Error"]
+            .iter()
+            .any(|error| name.windows(error.len()).any(|window| window == *error)))
+    }
+
     /// The size of this fat pointer, in bytes.
     pub fn size(&self) -> Result<u64, crate::Error> {
-        crate::get_size(self.entry())
+        crate::get_size(self.unit(), self.entry())
     }
 
     /// The alignment of fat pointer, in bytes.
@@ -82,7 +92,7 @@ where
     R: crate::gimli::Reader<Offset = usize>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut debug_tuple = f.debug_tuple("deflect::schema::Slice");
+        let mut debug_tuple = f.debug_tuple("deflect::schema::BoxedDyn");
         debug_tuple.field(&crate::debug::DebugEntry::new(
             self.dwarf(),
             self.unit(),
@@ -96,7 +106,7 @@ impl<'dwarf, R> fmt::Display for BoxedDyn<'dwarf, R>
 where
     R: crate::gimli::Reader<Offset = usize>,
 {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.schema.name().map_err(crate::fmt_err)?.fmt(f)
     }
 }
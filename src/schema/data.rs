@@ -11,3 +11,15 @@ pub enum Data {
     /// Eight bytes of data.
     u64(u64),
 }
+
+impl Data {
+    /// This value, zero-extended to a `u64`.
+    pub fn to_u64(self) -> u64 {
+        match self {
+            Self::u8(v) => v.into(),
+            Self::u16(v) => v.into(),
+            Self::u32(v) => v.into(),
+            Self::u64(v) => v,
+        }
+    }
+}
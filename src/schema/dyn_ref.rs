@@ -0,0 +1,147 @@
+use std::fmt;
+
+/// A schema for a `&dyn Trait` or `&mut dyn Trait` reference.
+#[derive(Clone)]
+pub struct DynRef<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    pointer: super::Field<'dwarf, R>,
+    vtable: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> DynRef<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `DynRef` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// Trait object references are structs with two fields, `pointer` and
+    /// `vtable`.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let (pointer, vtable) = Self::pointer_and_vtable(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self {
+            schema,
+            pointer,
+            vtable,
+        })
+    }
+
+    /// Produces `true` if the given `entry` has the layout of a trait object
+    /// reference.
+    pub(crate) fn is_dyn_ref(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        Ok(Self::pointer_and_vtable(&schema)?.is_some())
+    }
+
+    /// Produces the `pointer` and `vtable` fields of `schema`.
+    #[allow(clippy::type_complexity)]
+    fn pointer_and_vtable(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<(super::Field<'dwarf, R>, super::Field<'dwarf, R>)>, crate::Error> {
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(pointer) = fields.try_next()? else { return Ok(None) };
+        let Some(vtable) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some()
+            || &*pointer.name()?.to_slice()? != b"pointer"
+            || &*vtable.name()?.to_slice()? != b"vtable"
+        {
+            return Ok(None);
+        }
+        Ok(Some((pointer, vtable)))
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `DynRef`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `DynRef`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `DynRef` abstracts
+    /// over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// Whether this is a `&mut dyn Trait`.
+    pub fn is_mut(&self) -> Result<bool, crate::Error> {
+        Ok(self.name()?.to_slice()?.starts_with(b"&mut "))
+    }
+
+    /// This fat pointer, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The `pointer` field of this fat pointer.
+    pub fn pointer(&self) -> &super::Field<'dwarf, R> {
+        &self.pointer
+    }
+
+    /// The `vtable` field of this fat pointer.
+    pub fn vtable(&self) -> &super::Field<'dwarf, R> {
+        &self.vtable
+    }
+
+    /// The size of this fat pointer, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        crate::get_size(self.unit(), self.entry())
+    }
+
+    /// The alignment of this fat pointer, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        crate::get_align(self.entry())
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for DynRef<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::DynRef");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for DynRef<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
@@ -11,7 +11,10 @@ where
     dwarf: &'dwarf crate::gimli::Dwarf<R>,
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
-    discr_type_offset: crate::gimli::UnitOffset,
+    discr_type: (
+        &'dwarf crate::gimli::Unit<R, usize>,
+        crate::gimli::UnitOffset,
+    ),
     name: super::Name<R>,
     location: super::Offset<'dwarf, R>,
 }
@@ -29,14 +32,14 @@ where
     ) -> Result<Self, crate::Error> {
         crate::check_tag(&entry, crate::gimli::DW_TAG_enumeration_type)?;
         let name = super::Name::from_die(dwarf, unit, &entry)?;
-        let discr_type_offset = crate::get_type(&entry)?;
+        let discr_type = crate::get_type(dwarf, unit, &entry)?;
         let location = super::Offset::zero(unit);
 
         Ok(Self {
             dwarf,
             unit,
             entry,
-            discr_type_offset,
+            discr_type,
             name,
             location,
         })
@@ -73,7 +76,7 @@ where
 
         let dw_tag_member = unit.entry(dw_at_discr)?;
 
-        let discr_type_offset = crate::get_type(&dw_tag_member)?;
+        let discr_type = crate::get_type(dwarf, unit, &dw_tag_member)?;
 
         let location = super::Offset::from_die(unit, &dw_tag_member)?;
 
@@ -81,7 +84,7 @@ where
             dwarf,
             unit,
             entry,
-            discr_type_offset,
+            discr_type,
             name,
             location,
         })
@@ -97,7 +100,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Enum`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -109,6 +112,18 @@ where
         &self.entry
     }
 
+    /// Whether this enum is an instance of the generic type at `path`; e.g.,
+    /// `core::option::Option`. Enums named alike elsewhere are not.
+    pub(crate) fn is_instance_of(&self, path: &str) -> Result<bool, crate::Error> {
+        let offset = self.entry.offset();
+        let Some(name) = crate::qualified_name_of(self.dwarf, self.unit, offset)? else {
+            return Ok(false);
+        };
+        Ok(name
+            .strip_prefix(path.as_bytes())
+            .map_or(false, |arguments| arguments.starts_with(b"<")))
+    }
+
     /// The name of this type.
     pub fn name(&self) -> &super::Name<R> {
         &self.name
@@ -116,12 +131,12 @@ where
 
     /// The discriminant of this type.
     pub fn discriminant_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
-        let entry = self.unit.entry(self.discr_type_offset)?;
-        super::Type::from_die(self.dwarf, self.unit, entry)
+        let (unit, offset) = self.discr_type;
+        super::Type::from_die(self.dwarf, unit, unit.entry(offset)?)
     }
 
     /// The discriminant of this type.
-    pub fn discriminant_location(&self) -> &super::Offset<R> {
+    pub fn discriminant_location(&self) -> &super::Offset<'dwarf, R> {
         &self.location
     }
 
@@ -142,10 +157,7 @@ where
                 }
                 self.unit.entries_tree(variant_part)?
             }
-            _ => unimplemented!(
-                "unhandled enum representation: {:#?}",
-                crate::debug::DebugEntry::new(self.dwarf, self.unit, &self.entry)
-            ),
+            tag => return Err(crate::error::unsupported_layout(tag)),
         };
         Ok(super::Variants::from_tree(
             self.dwarf,
@@ -155,9 +167,148 @@ where
         ))
     }
 
+    /// The number of variants of this enum.
+    pub fn variant_count(&self) -> Result<usize, crate::Error> {
+        let mut variants = self.variants()?;
+        let mut variants = variants.iter()?;
+        let mut count = 0;
+        while variants.try_next()?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The variant of this enum with the given name, if any.
+    pub fn variant_by_name<N>(
+        &self,
+        variant_name: N,
+    ) -> Result<Option<super::Variant<'dwarf, R>>, crate::Error>
+    where
+        N: AsRef<[u8]>,
+    {
+        let target_name = variant_name.as_ref();
+        let mut variants = self.variants()?;
+        let mut variants = variants.iter()?;
+        while let Some(variant) = variants.try_next()? {
+            if target_name == &*variant.name()?.to_slice()? {
+                return Ok(Some(variant));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The variant of this enum that a discriminant of `value` (read from the
+    /// [discriminant location][Self::discriminant_location]) denotes; i.e.,
+    /// the variant with that [discriminant
+    /// value][super::Variant::discriminant_value] or, failing that, the
+    /// variant without one (e.g., the dataful variant of a niche-optimized
+    /// enum), if any.
+    pub fn variant_for_discriminant(
+        &self,
+        value: u64,
+    ) -> Result<Option<super::Variant<'dwarf, R>>, crate::Error> {
+        let mut default = None;
+        let mut variants = self.variants()?;
+        let mut variants = variants.iter()?;
+        while let Some(variant) = variants.try_next()? {
+            match variant.discriminant_value() {
+                Some(discriminant) if discriminant.to_u64() == value => return Ok(Some(variant)),
+                Some(_) => {}
+                None => default = Some(variant),
+            }
+        }
+        Ok(default)
+    }
+
+    /// Whether this enum is the state machine of a coroutine; e.g., the
+    /// [`Future`][std::future::Future] of an `async fn` or `async` block.
+    ///
+    /// The variants of a coroutine are its states: `Unresumed`, `Returned`,
+    /// `Panicked`, and one `SuspendN` per `.await` or `yield` point, whose
+    /// fields are the locals live across that point.
+    pub fn is_coroutine(&self) -> Result<bool, crate::Error> {
+        Ok(self.coroutine_kind()?.is_some())
+    }
+
+    /// Whether this enum is the state machine of an `async fn` or `async`
+    /// block; i.e., a compiler-generated [`Future`][std::future::Future].
+    pub fn is_future(&self) -> Result<bool, crate::Error> {
+        let name = self.name.to_slice()?;
+        Ok(name.starts_with(b"{async_fn_env#") || name.starts_with(b"{async_block_env#"))
+    }
+
+    /// If this enum is the state machine of a coroutine, the kind of that
+    /// coroutine, as `rustc` describes it.
+    fn coroutine_kind(&self) -> Result<Option<&'static str>, crate::Error> {
+        const KINDS: &[(&[u8], &str)] = &[
+            (b"{async_fn_env#", "async fn body"),
+            (b"{async_block_env#", "async block"),
+            (b"{async_gen_fn_env#", "async gen fn body"),
+            (b"{async_gen_block_env#", "async gen block"),
+            (b"{gen_fn_env#", "gen fn body"),
+            (b"{gen_block_env#", "gen block"),
+            (b"{coroutine_env#", "coroutine"),
+        ];
+        let name = self.name.to_slice()?;
+        Ok(KINDS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+            .map(|&(_, kind)| kind))
+    }
+
+    /// If this enum is the state machine of a coroutine, the file and line
+    /// that coroutine was defined at (if available).
+    pub fn coroutine_location(&self) -> Result<Option<(String, u64)>, crate::Error> {
+        if !self.is_coroutine()? {
+            return Ok(None);
+        }
+        let mut tree = self.unit.entries_tree(Some(self.entry.offset()))?;
+        let root = tree.root()?;
+        let mut children = root.children();
+        while let Some(child) = children.next()? {
+            if child.entry().tag() != crate::gimli::DW_TAG_variant_part {
+                continue;
+            }
+            // the first variant is `Unresumed`, which is declared where the
+            // coroutine is defined.
+            let mut variants = child.children();
+            while let Some(variant) = variants.next()? {
+                if variant.entry().tag() != crate::gimli::DW_TAG_variant {
+                    continue;
+                }
+                let mut members = variant.children();
+                let Some(member) = members.next()? else { return Ok(None) };
+                return crate::get_decl_location(self.dwarf, self.unit, member.entry());
+            }
+        }
+        Ok(None)
+    }
+
+    /// The name of this enum, as it should be displayed; coroutines are
+    /// displayed as `rustc` describes them; e.g., `{async block@file:line}` or
+    /// `{async fn body of path::to::f()}`. Other names are
+    /// [canonicalized][crate::schema::Type::display_name].
+    pub(crate) fn display_name(&self) -> Result<String, crate::Error> {
+        let Some(kind) = self.coroutine_kind()? else {
+            return Ok(crate::format::canonical(&self.name.to_string_lossy()?));
+        };
+        if kind.ends_with(" fn body") {
+            let path = crate::namespace_path_of(self.dwarf, self.unit, self.entry.offset())?;
+            let path = path
+                .iter()
+                .map(|name| String::from_utf8_lossy(name))
+                .join("::");
+            return Ok(format!("{{{kind} of {path}()}}"));
+        }
+        if let Some((file, line)) = self.coroutine_location()? {
+            return Ok(format!("{{{kind}@{file}:{line}}}"));
+        }
+        Ok(crate::format::canonical(&self.name.to_string_lossy()?))
+    }
+
     /// The size of this type, in bytes.
     pub fn size(&self) -> Result<u64, crate::Error> {
-        crate::get_size(self.entry())
+        crate::get_size(self.unit, self.entry())
     }
 
     /// The alignment of this type, in bytes.
@@ -188,7 +339,7 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("enum ")?;
 
-        self.name().fmt(f)?;
+        self.display_name().map_err(crate::fmt_err)?.fmt(f)?;
 
         f.write_str(" {")?;
 
@@ -200,8 +351,10 @@ where
 
         let mut variants = self.variants().map_err(crate::fmt_err)?;
         let variants = variants.iter().map_err(crate::fmt_err)?;
+        let variants: Vec<_> = variants.try_collect().map_err(crate::fmt_err)?;
 
         variants
+            .iter()
             .format(if f.alternate() { ",\n    " } else { ", " })
             .fmt(f)?;
 
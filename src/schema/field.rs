@@ -10,6 +10,9 @@ where
     dwarf: &'dwarf crate::gimli::Dwarf<R>,
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    /// The offset of the subobject declaring this field; e.g., of a C++ base
+    /// class within its derived class.
+    base: u64,
 }
 
 impl<'dwarf, R> Field<'dwarf, R>
@@ -23,10 +26,19 @@ where
         unit: &'dwarf crate::gimli::Unit<R, usize>,
         entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
     ) -> Result<Self, crate::Error> {
-        let _tree = unit.entries_tree(Some(entry.offset()))?;
-        //crate::debug::inspect_tree(&mut tree, dwarf, unit);
         crate::check_tag(&entry, crate::gimli::DW_TAG_member)?;
-        Ok(Self { dwarf, unit, entry })
+        Ok(Self {
+            dwarf,
+            unit,
+            entry,
+            base: 0,
+        })
+    }
+
+    /// Produces this field, made relative to a subobject at `base`.
+    pub(crate) fn rebased(mut self, base: u64) -> Self {
+        self.base += base;
+        self
     }
 
     /// The [DWARF](crate::gimli::Dwarf) sections that this `Field`'s debuginfo
@@ -39,7 +51,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Field`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -51,30 +63,144 @@ where
         &self.entry
     }
 
-    /// The name of this primitive type.
+    /// The name of this field.
     pub fn name(&self) -> Result<Name<R>, crate::Error> {
         Name::from_die(self.dwarf(), self.unit(), self.entry())
     }
 
-    /// The size of this field, in bytes.
-    pub fn size(&self) -> Result<Option<u64>, crate::Error> {
-        crate::get_size_opt(self.entry())
+    /// Where this field was declared; or `None`, if its debuginfo does not
+    /// say.
+    pub fn decl_location(&self) -> Result<Option<super::DeclLocation>, crate::Error> {
+        super::DeclLocation::from_die(self.dwarf, self.unit, &self.entry)
+    }
+
+    /// The size of this field, in bytes; for bitfields, the size of the
+    /// storage unit containing them.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        match crate::get_size_opt(self.unit, self.entry())? {
+            Some(size) => Ok(size),
+            None => self.r#type()?.size(),
+        }
     }
 
-    /// The alignment of this field, in bytes.
+    /// The alignment of this field, in bytes, if known; that of its
+    /// [type][Self::r#type], unless the field is declared with a stricter
+    /// alignment.
     pub fn align(&self) -> Result<Option<u64>, crate::Error> {
-        crate::get_align(self.entry())
+        match crate::get_align(self.entry())? {
+            Some(align) => Ok(Some(align)),
+            None => self.r#type()?.align(),
+        }
+    }
+
+    /// The offset at which this field occurs, from the start of its parent; in
+    /// bytes, as [`offset.address(0)`][Offset::address].
+    pub fn offset(&self) -> Result<Offset<'dwarf, R>, crate::Error> {
+        Ok(Offset::from_die(self.unit, &self.entry)?.rebased(self.base))
+    }
+
+    /// The size of this field, in bits, if it is a bitfield.
+    pub fn bit_size(&self) -> Result<Option<u64>, crate::Error> {
+        Ok(self
+            .entry
+            .attr_value(crate::gimli::DW_AT_bit_size)?
+            .and_then(|bit_size| bit_size.udata_value()))
+    }
+
+    /// The offset of this field from the start of its parent, in bits, if it
+    /// is a bitfield; as for `DW_AT_data_bit_offset`, bits are counted from
+    /// the least significant bit of each byte on little-endian targets, and
+    /// from the most significant on big-endian ones.
+    pub fn bit_offset(&self) -> Result<Option<u64>, crate::Error> {
+        let Some(bit_size) = self.bit_size()? else { return Ok(None) };
+        let bits = |bytes: u64| bytes.checked_mul(8);
+        if let Some(bit_offset) = self
+            .entry
+            .attr_value(crate::gimli::DW_AT_data_bit_offset)?
+            .and_then(|bit_offset| bit_offset.udata_value())
+        {
+            return bits(self.base)
+                .and_then(|base| base.checked_add(bit_offset))
+                .map(Some)
+                .ok_or_else(crate::error::arithmetic_overflow);
+        }
+        let byte_offset = self
+            .entry
+            .attr_value(crate::gimli::DW_AT_data_member_location)?
+            .and_then(|byte_offset| byte_offset.udata_value())
+            .unwrap_or(0)
+            .checked_add(self.base)
+            .ok_or_else(crate::error::arithmetic_overflow)?;
+        let Some(legacy_bit_offset) = self
+            .entry
+            .attr_value(crate::gimli::DW_AT_bit_offset)?
+            .and_then(|bit_offset| bit_offset.udata_value())
+        else {
+            return bits(byte_offset)
+                .map(Some)
+                .ok_or_else(crate::error::arithmetic_overflow);
+        };
+        // DWARF 2 and 3 count `DW_AT_bit_offset` from the most significant bit
+        // of a storage unit of `DW_AT_byte_size` bytes; on a big-endian
+        // target, that is the storage unit's first bit, and on a little-endian
+        // target, its last.
+        if self.is_big_endian() {
+            return bits(byte_offset)
+                .and_then(|byte_offset| byte_offset.checked_add(legacy_bit_offset))
+                .map(Some)
+                .ok_or_else(crate::error::arithmetic_overflow);
+        }
+        bits(self.size()?)
+            .and_then(|storage_bits| {
+                storage_bits.checked_sub(legacy_bit_offset.checked_add(bit_size)?)
+            })
+            .and_then(|bit_offset| bits(byte_offset)?.checked_add(bit_offset))
+            .map(Some)
+            .ok_or_else(crate::error::arithmetic_overflow)
     }
 
-    /// The offset at which this field occurs.
-    pub fn offset(&'dwarf self) -> Result<Offset<'dwarf, R>, crate::Error> {
-        Offset::from_die(self.unit(), self.entry())
+    /// Whether the target that this field's debuginfo describes is
+    /// big-endian.
+    pub(crate) fn is_big_endian(&self) -> bool {
+        use crate::gimli::{Endianity, Section};
+        self.dwarf.debug_info.reader().endian().is_big_endian()
+    }
+
+    /// Whether this field is a bitfield; i.e., whether it does not occupy a
+    /// whole number of bytes.
+    pub fn is_bitfield(&self) -> Result<bool, crate::Error> {
+        Ok(self.bit_size()?.is_some())
     }
 
     /// The type of the field.
     pub fn r#type(&self) -> Result<Type<'dwarf, R>, crate::Error> {
-        let r#type = crate::get_type_res(self.unit, &self.entry)?;
-        super::Type::from_die(self.dwarf, self.unit, r#type)
+        use anyhow::Context;
+        crate::get_type_res(self.dwarf, self.unit, &self.entry)
+            .and_then(|(unit, r#type)| super::Type::from_die(self.dwarf, unit, r#type))
+            .with_context(|| {
+                crate::error::reading_field(self.dwarf, self.unit, self.entry.offset())
+            })
+    }
+
+    /// The name of the `typedef` the type of this field is declared with (if
+    /// any); [`r#type`][Self::r#type] resolves through such aliases, and
+    /// through `const`, `volatile` and `restrict` qualifiers.
+    pub fn type_alias(&self) -> Result<Option<Name<R>>, crate::Error> {
+        let (mut unit, mut r#type) = crate::get_type_res(self.dwarf, self.unit, &self.entry)?;
+        loop {
+            match r#type.tag() {
+                crate::gimli::DW_TAG_typedef => {
+                    return Name::from_die_opt(self.dwarf, unit, &r#type);
+                }
+                crate::gimli::DW_TAG_const_type
+                | crate::gimli::DW_TAG_volatile_type
+                | crate::gimli::DW_TAG_restrict_type
+                | crate::gimli::DW_TAG_atomic_type => {
+                    (unit, r#type) = crate::get_type_res(self.dwarf, unit, &r#type)?;
+                }
+                _ => return Ok(None),
+            }
+        }
     }
 }
 
@@ -0,0 +1,167 @@
+use crate::value::Path;
+
+/// The innermost field that owns a byte of a type, or the padding that it
+/// belongs to; produced by [`Type::field_at_offset`][super::Type::field_at_offset].
+#[derive(Clone, Debug)]
+pub struct FieldAt {
+    path: Path,
+    offset: u64,
+    padding: bool,
+}
+
+impl FieldAt {
+    /// The path of the innermost field that owns the byte; or, if the byte is
+    /// [padding][Self::is_padding], of the innermost value whose padding it
+    /// is.
+    ///
+    /// Fields that are not structs, enums or arrays (e.g., references, or
+    /// integers) are not further subdivided. Bytes of enums are attributed to
+    /// the fields of a variant only if no other variant overlaps them; the
+    /// bytes of their discriminants are at `<discriminant>`.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The offset of the byte within the value at [`path`][Self::path].
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Whether the byte is padding; i.e., belongs to no field.
+    pub fn is_padding(&self) -> bool {
+        self.padding
+    }
+}
+
+impl<'dwarf, R> super::Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The (possibly nested) field of this type that owns the byte at
+    /// `offset`; e.g., to label the bytes of a hexdump, or the address of a
+    /// watchpoint. `None`, if `offset` is out of bounds.
+    pub fn field_at_offset(&self, offset: u64) -> Result<Option<FieldAt>, crate::Error> {
+        if offset >= self.size()? {
+            return Ok(None);
+        }
+        field_at(self, Path::default(), offset).map(Some)
+    }
+}
+
+/// The field that owns the byte at `offset` within `r#type`, which is at
+/// `path`.
+fn field_at<R>(r#type: &super::Type<'_, R>, path: Path, offset: u64) -> Result<FieldAt, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    use super::Type;
+    let leaf = |path, padding| FieldAt {
+        path,
+        offset,
+        padding,
+    };
+    match r#type {
+        Type::Array(array) => {
+            let elt_type = array.elt_type()?;
+            let elt_size = elt_type.size()?;
+            if elt_size == 0 {
+                return Ok(leaf(path, false));
+            }
+            let index = usize::try_from(offset / elt_size)?;
+            field_at(&elt_type, path.index(index), offset % elt_size)
+        }
+        Type::Struct(schema) => match owner_in(schema.fields()?, &path, offset)? {
+            Some(owner) => owner.descend(),
+            None => Ok(leaf(path, true)),
+        },
+        Type::Enum(schema) => {
+            let mut owners = vec![];
+            let mut variants = schema.variants()?;
+            let mut variants = variants.iter()?;
+            while let Some(variant) = variants.try_next()? {
+                let name = variant.name()?.to_string_lossy()?.into_owned();
+                if let Some(owner) = owner_in(variant.fields()?, &path.variant(name), offset)? {
+                    owners.push(owner);
+                }
+            }
+            // niche-encoded discriminants overlap a field, and so own only
+            // the bytes no variant does.
+            if owners.is_empty() {
+                let discriminant = schema.discriminant_location().clone().address(0)?;
+                let size = schema.discriminant_type()?.size()?;
+                if (discriminant..discriminant + size).contains(&offset) {
+                    return Ok(FieldAt {
+                        path: path.field("<discriminant>".to_owned()),
+                        offset: offset - discriminant,
+                        padding: false,
+                    });
+                }
+                return Ok(leaf(path, true));
+            }
+            match owners.len() {
+                1 => owners.remove(0).descend(),
+                _ => Ok(leaf(path, false)),
+            }
+        }
+        _ => Ok(leaf(path, false)),
+    }
+}
+
+/// The field that owns a byte among the fields of a value.
+enum Owner<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// A bitfield, which is not subdivided.
+    Bits(FieldAt),
+    /// A field at the path, of the type, that owns the byte at the offset
+    /// within it.
+    Field(Path, super::Type<'dwarf, R>, u64),
+}
+
+impl<'dwarf, R> Owner<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The innermost field that owns the byte within this owner.
+    fn descend(self) -> Result<FieldAt, crate::Error> {
+        match self {
+            Owner::Bits(at) => Ok(at),
+            Owner::Field(path, r#type, offset) => field_at(&r#type, path, offset),
+        }
+    }
+}
+
+/// The owner of the byte at `offset` among `fields`, which belong to a value
+/// at `path`.
+fn owner_in<'dwarf, R>(
+    mut fields: super::Fields<'dwarf, R>,
+    path: &Path,
+    offset: u64,
+) -> Result<Option<Owner<'dwarf, R>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let name = field.name()?.to_string_lossy()?.into_owned();
+        // bitfields own the bytes their bits occupy.
+        if let (Some(bit_offset), Some(bit_size)) = (field.bit_offset()?, field.bit_size()?) {
+            let (first, last) = (bit_offset / 8, (bit_offset + bit_size + 7) / 8);
+            if (first..last).contains(&offset) {
+                return Ok(Some(Owner::Bits(FieldAt {
+                    path: path.field(name),
+                    offset: offset - first,
+                    padding: false,
+                })));
+            }
+            continue;
+        }
+        let start = field.offset()?.address(0)?;
+        let r#type = field.r#type()?;
+        if (start..start + r#type.size()?).contains(&offset) {
+            return Ok(Some(Owner::Field(path.field(name), r#type, offset - start)));
+        }
+    }
+    Ok(None)
+}
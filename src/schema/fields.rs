@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 /// Fields of a [struct][super::Struct] or an [enum variant][super::Variant].
 ///
 /// Call [`iter`][Self::iter] to iterate over fields.
@@ -28,6 +30,7 @@ where
             dwarf: self.dwarf,
             unit: self.unit,
             iter: self.tree.root()?.children(),
+            inherited: VecDeque::new(),
         })
     }
 }
@@ -40,6 +43,9 @@ where
     dwarf: &'dwarf crate::gimli::Dwarf<R>,
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     iter: crate::gimli::EntriesTreeIter<'dwarf, 'dwarf, 'tree, R>,
+    /// The fields of the base class most recently encountered, yet to be
+    /// produced.
+    inherited: VecDeque<super::Field<'dwarf, R>>,
 }
 
 impl<'dwarf, 'tree, R: crate::gimli::Reader<Offset = usize>> FieldsIter<'dwarf, 'tree, R>
@@ -49,9 +55,37 @@ where
     /// Produces the next field, if any.
     pub fn try_next(&mut self) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
         loop {
+            if let Some(field) = self.inherited.pop_front() {
+                return Ok(Some(field));
+            }
             let Some(next) = self.iter.next()? else { return Ok(None) };
             let entry = next.entry();
-            if entry.tag() != crate::gimli::DW_TAG_member {
+            if entry.tag() == crate::gimli::DW_TAG_inheritance {
+                // the fields of C++ base classes are flattened into those of
+                // the derived class; the offsets of virtual bases are read
+                // from the object's vtable, and so cannot be.
+                if let Some(crate::gimli::AttributeValue::Virtuality(virtuality)) =
+                    entry.attr_value(crate::gimli::DW_AT_virtuality)?
+                {
+                    if virtuality != crate::gimli::DW_VIRTUALITY_none {
+                        return Err(crate::error::virtual_base());
+                    }
+                }
+                let base = super::Offset::from_die(self.unit, entry)?.address(0)?;
+                let (unit, base_type) = crate::get_type_res(self.dwarf, self.unit, entry)?;
+                let base_type =
+                    super::Struct::from_dw_tag_structure_type(self.dwarf, unit, base_type)?;
+                let mut base_fields = base_type.fields()?;
+                let mut base_fields = base_fields.iter()?;
+                while let Some(field) = base_fields.try_next()? {
+                    self.inherited.push_back(field.rebased(base));
+                }
+                continue;
+            }
+            if entry.tag() != crate::gimli::DW_TAG_member
+                || entry.attr_value(crate::gimli::DW_AT_declaration)?.is_some()
+            {
+                // skip member functions, and (in DWARF 4) static members.
                 continue;
             }
             return Ok(Some(super::Field::from_dw_tag_member(
@@ -66,12 +100,9 @@ where
 impl<'dwarf, 'tree, R: crate::gimli::Reader<Offset = usize>> Iterator
     for FieldsIter<'dwarf, 'tree, R>
 {
-    type Item = super::Field<'dwarf, R>;
+    type Item = Result<super::Field<'dwarf, R>, crate::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(next) => next,
-            Err(err) => panic!("could not read next field: {err}"),
-        }
+        self.try_next().transpose()
     }
 }
@@ -37,7 +37,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Function`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -51,12 +51,33 @@ where
 
     /// The name of this type.
     pub fn name(&self) -> Result<Name<R>, crate::Error> {
-        println!(
-            "{:?}",
-            crate::debug::DebugEntry::new(self.dwarf, self.unit, &self.entry)
-        );
         Name::from_die(self.dwarf(), self.unit(), self.entry())
     }
+
+    /// The types of this function's parameters, in order.
+    pub fn parameters(&self) -> Result<Vec<super::Type<'dwarf, R>>, crate::Error> {
+        let mut tree = self.unit.entries_tree(Some(self.entry.offset()))?;
+        let root = tree.root()?;
+        let mut children = root.children();
+        let mut parameters = vec![];
+        while let Some(child) = children.next()? {
+            let entry = child.entry();
+            if entry.tag() == crate::gimli::DW_TAG_formal_parameter {
+                let (unit, r#type) = crate::get_type_res(self.dwarf, self.unit, entry)?;
+                parameters.push(super::Type::from_die(self.dwarf, unit, r#type)?);
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// The return type of this function, or `None` if it returns `()`.
+    pub fn return_type(&self) -> Result<Option<super::Type<'dwarf, R>>, crate::Error> {
+        if self.entry.attr_value(crate::gimli::DW_AT_type)?.is_none() {
+            return Ok(None);
+        }
+        let (unit, entry) = crate::get_type_res(self.dwarf, self.unit, &self.entry)?;
+        super::Type::from_die(self.dwarf, unit, entry).map(Some)
+    }
 }
 
 impl<'dwarf, R> fmt::Debug for Function<'dwarf, R>
@@ -79,6 +100,19 @@ where
     R: crate::gimli::Reader<Offset = usize>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("fn()")
+        f.write_str("fn(")?;
+        let parameters = self.parameters().map_err(crate::fmt_err)?;
+        for (i, parameter) in parameters.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            parameter.fmt(f)?;
+        }
+        f.write_str(")")?;
+        if let Some(return_type) = self.return_type().map_err(crate::fmt_err)? {
+            f.write_str(" -> ")?;
+            return_type.fmt(f)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,100 @@
+use super::{Name, Type};
+use std::fmt;
+
+/// A generic type parameter of a type; e.g., the `T` of a `Vec<T>`.
+#[derive(Clone)]
+pub struct GenericParam<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R, usize>,
+    entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+}
+
+impl<'dwarf, R> GenericParam<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `GenericParam` from a
+    /// [`DW_TAG_template_type_parameter`][crate::gimli::DW_TAG_template_type_parameter].
+    pub(crate) fn from_dw_tag_template_type_parameter(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        crate::check_tag(&entry, crate::gimli::DW_TAG_template_type_parameter)?;
+        Ok(Self { dwarf, unit, entry })
+    }
+
+    /// The name of this parameter; e.g., `T`.
+    pub fn name(&self) -> Result<Name<R>, crate::Error> {
+        Name::from_die(self.dwarf, self.unit, &self.entry)
+    }
+
+    /// The type this parameter is instantiated with; e.g., `u8` for the `T`
+    /// of a `Vec<u8>`.
+    pub fn r#type(&self) -> Result<Type<'dwarf, R>, crate::Error> {
+        let (unit, r#type) = crate::get_type_res(self.dwarf, self.unit, &self.entry)?;
+        Type::from_die(self.dwarf, unit, r#type)
+    }
+}
+
+/// The generic type parameters declared by the entry at `offset`, in order;
+/// `rustc` declares those of an enum on each of its variants.
+pub(crate) fn generic_params_of<'dwarf, R>(
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R, usize>,
+    offset: crate::gimli::UnitOffset,
+) -> Result<Vec<GenericParam<'dwarf, R>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut tree = unit.entries_tree(Some(offset))?;
+    let root = tree.root()?;
+    let mut children = root.children();
+    let mut params = vec![];
+    let mut variants = vec![];
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        match entry.tag() {
+            crate::gimli::DW_TAG_template_type_parameter => {
+                let entry = entry.clone();
+                let param = GenericParam::from_dw_tag_template_type_parameter(dwarf, unit, entry)?;
+                params.push(param);
+            }
+            crate::gimli::DW_TAG_structure_type => variants.push(entry.offset()),
+            _ => {}
+        }
+    }
+    if params.is_empty() {
+        if let Some(&variant) = variants.first() {
+            return generic_params_of(dwarf, unit, variant);
+        }
+    }
+    Ok(params)
+}
+
+impl<'dwarf, R> fmt::Debug for GenericParam<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::GenericParam");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf,
+            self.unit,
+            &self.entry,
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for GenericParam<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
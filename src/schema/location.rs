@@ -0,0 +1,73 @@
+use std::fmt;
+
+/// Where a type or field was declared in its source; e.g., to hyperlink from a
+/// reflected field to its definition.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeclLocation {
+    file: String,
+    line: Option<u64>,
+}
+
+impl DeclLocation {
+    /// Construct a new `DeclLocation` from a given `entry`'s
+    /// `DW_AT_decl_file` and `DW_AT_decl_line` attributes; or `None`, if it
+    /// has no `DW_AT_decl_file`, or its file is not in `unit`'s line program.
+    pub(crate) fn from_die<R>(
+        dwarf: &crate::gimli::Dwarf<R>,
+        unit: &crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<R>,
+    ) -> Result<Option<Self>, crate::Error>
+    where
+        R: crate::gimli::Reader<Offset = usize>,
+    {
+        if entry.attr_value(crate::gimli::DW_AT_decl_file)?.is_none() {
+            return Ok(None);
+        }
+        let Some(file) = crate::get_file(dwarf, unit, entry)? else { return Ok(None) };
+        let mut file = file.into_owned();
+        if let Some(comp_dir) = &unit.comp_dir {
+            let comp_dir = comp_dir.to_string_lossy()?;
+            if let Some(relative) = file.strip_prefix(&*comp_dir) {
+                file = relative.trim_start_matches('/').to_owned();
+            }
+        }
+        let line = entry
+            .attr_value(crate::gimli::DW_AT_decl_line)?
+            .and_then(|line| line.udata_value());
+        Ok(Some(Self { file, line }))
+    }
+
+    /// The path of the file of the declaration; relative to the directory in
+    /// which its compilation unit was compiled, if it is within it, and
+    /// otherwise as recorded (typically, absolute).
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The 1-based line of the declaration, if known.
+    pub fn line(&self) -> Option<u64> {
+        self.line
+    }
+}
+
+impl fmt::Display for DeclLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'dwarf, R> super::Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Where this type was declared; or `None`, if its debuginfo does not
+    /// say (e.g., for primitives).
+    pub fn decl_location(&self) -> Result<Option<DeclLocation>, crate::Error> {
+        let (dwarf, unit, offset) = self.die();
+        DeclLocation::from_die(dwarf, unit, &unit.entry(offset)?)
+    }
+}
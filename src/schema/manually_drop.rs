@@ -0,0 +1,149 @@
+use std::fmt;
+
+/// A schema for [`ManuallyDrop<T>`][std::mem::ManuallyDrop].
+#[derive(Clone)]
+pub struct ManuallyDrop<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    value: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> ManuallyDrop<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `ManuallyDrop` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// `ManuallyDrop<T>` is a struct with a single field, `value`, of type `T`
+    /// or (in newer toolchains) `MaybeDangling<T>`.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let value = Self::value_of(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self { schema, value })
+    }
+
+    /// Produces `true` if the given `entry` has the layout of a
+    /// `ManuallyDrop`.
+    pub(crate) fn is_manually_drop(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        // other types may be shaped like `ManuallyDrop`s; e.g., a
+        // `ManuallyDropGuard<T> { value: T }`.
+        let Some(name) = crate::qualified_name_of(dwarf, unit, entry.offset())? else {
+            return Ok(false);
+        };
+        if !name.starts_with(b"core::mem::manually_drop::ManuallyDrop<") {
+            return Ok(false);
+        }
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        Ok(Self::value_of(&schema)?.is_some())
+    }
+
+    /// Produces the field of type `T` wrapped by `schema`, descending through
+    /// `MaybeDangling<T>`.
+    fn value_of(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(value) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() || &*value.name()?.to_slice()? != b"value" {
+            return Ok(None);
+        }
+        let super::Type::Struct(dangling) = value.r#type()? else { return Ok(Some(value)) };
+        if !dangling.name()?.to_slice()?.starts_with(b"MaybeDangling<") {
+            return Ok(Some(value));
+        }
+        let mut inner = dangling.fields()?;
+        let mut inner = inner.iter()?;
+        let Some(inner) = inner.try_next()? else { return Ok(None) };
+        let base = value.offset()?.address(0)?;
+        Ok(Some(inner.rebased(base)))
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `ManuallyDrop`'s
+    /// debuginfo belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `ManuallyDrop`'s
+    /// debuginfo belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `ManuallyDrop`
+    /// abstracts over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// This `ManuallyDrop`, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The field holding the wrapped value.
+    pub fn value(&self) -> &super::Field<'dwarf, R> {
+        &self.value
+    }
+
+    /// The type, `T`, of the wrapped value.
+    pub fn value_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
+        self.value.r#type()
+    }
+
+    /// The size of this type, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        self.schema.size()
+    }
+
+    /// The alignment of this type, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        self.schema.align()
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for ManuallyDrop<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::ManuallyDrop");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for ManuallyDrop<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// A schema for [`MaybeUninit<T>`][std::mem::MaybeUninit].
+#[derive(Clone)]
+pub struct MaybeUninit<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R, usize>,
+    entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    value: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> MaybeUninit<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `MaybeUninit` from a
+    /// [`DW_TAG_union_type`][crate::gimli::DW_TAG_union_type].
+    ///
+    /// `MaybeUninit<T>` is a union of `uninit: ()` and `value:
+    /// ManuallyDrop<T>`.
+    pub(crate) fn from_dw_tag_union_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        crate::check_tag(&entry, crate::gimli::DW_TAG_union_type)?;
+        let tree = unit.entries_tree(Some(entry.offset()))?;
+        let mut fields = super::Fields::from_tree(dwarf, unit, tree);
+        let mut fields = fields.iter()?;
+        let mut value = None;
+        while let Some(field) = fields.try_next()? {
+            if &*field.name()?.to_slice()? == b"value" {
+                value = Some(field);
+            }
+        }
+        let value = value.ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self {
+            dwarf,
+            unit,
+            entry,
+            value,
+        })
+    }
+
+    /// Produces `true` if the given `entry` is a `MaybeUninit`.
+    pub(crate) fn is_maybe_uninit(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        // other unions may be named like `MaybeUninit`s; e.g., a
+        // `MaybeUninitBuf<T>`.
+        let Some(name) = crate::qualified_name_of(dwarf, unit, entry.offset())? else {
+            return Ok(false);
+        };
+        Ok(name.starts_with(b"core::mem::maybe_uninit::MaybeUninit<"))
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `MaybeUninit`'s
+    /// debuginfo belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.dwarf
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `MaybeUninit`'s
+    /// debuginfo belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.unit
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `MaybeUninit`
+    /// abstracts over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        &self.entry
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        super::Name::from_die(self.dwarf, self.unit, &self.entry)
+    }
+
+    /// The `value` field of this union; a [`ManuallyDrop<T>`][super::ManuallyDrop].
+    pub fn value(&self) -> &super::Field<'dwarf, R> {
+        &self.value
+    }
+
+    /// The type, `T`, of the possibly-uninitialized value.
+    pub fn value_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
+        match self.value.r#type()? {
+            super::Type::ManuallyDrop(manually_drop) => manually_drop.value_type(),
+            r#type => Ok(r#type),
+        }
+    }
+
+    /// The size of this type, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        crate::get_size(self.unit, &self.entry)
+    }
+
+    /// The alignment of this type, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        crate::get_align(&self.entry)
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for MaybeUninit<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::MaybeUninit");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf,
+            self.unit,
+            &self.entry,
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for MaybeUninit<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
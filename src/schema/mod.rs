@@ -1,40 +1,67 @@
 //! Reflections of Rust types.
 
+mod accessor;
 mod array;
+mod atomic;
 mod r#box;
 mod boxed_dyn;
 mod boxed_slice;
 mod data;
+mod dyn_ref;
 mod r#enum;
 mod field;
+mod field_at;
 mod fields;
 mod function;
+mod generic_param;
+mod location;
+mod manually_drop;
+mod maybe_uninit;
 mod name;
+mod non_null;
+mod non_zero;
 mod offset;
 mod pointer;
+mod provenance;
+mod shape;
 mod slice;
 mod str_impl;
 mod r#struct;
 mod variant;
 mod variants;
+mod vector;
 
+pub use accessor::Accessor;
 pub use array::Array;
+pub use atomic::Atomic;
 pub use boxed_dyn::BoxedDyn;
 pub use boxed_slice::BoxedSlice;
 pub use data::Data;
+pub use dyn_ref::DynRef;
 pub use fields::{Fields, FieldsIter};
 pub use function::Function;
+pub use generic_param::GenericParam;
+pub use location::DeclLocation;
+pub use manually_drop::ManuallyDrop;
+pub use maybe_uninit::MaybeUninit;
 pub use name::Name;
+pub use non_null::NonNull;
+pub use non_zero::NonZero;
 pub use offset::Offset;
-pub use pointer::{Const, Mut, Pointer, Reference, Shared, Unique};
+pub use pointer::{Const, Mut, Pointer, Raw, Reference, Shared, Unique};
+pub use provenance::CompilationUnit;
 pub use r#box::Box;
 pub use r#enum::Enum;
 pub use r#field::Field;
+pub use field_at::FieldAt;
 pub use r#struct::Struct;
 pub use r#variant::Variant;
 pub use slice::Slice;
 pub use str_impl::str;
 pub use variants::{Variants, VariantsIter};
+pub use vector::Vector;
+
+use shape::Shape;
 
 /// A reflected shared reference type.
 pub type SharedRef<'dwarf, R> = crate::schema::Pointer<'dwarf, crate::schema::Shared, R>;
@@ -57,10 +84,71 @@ where
         unit: &'dwarf crate::gimli::Unit<R>,
         entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
     ) -> Result<Self, crate::Error> {
-        Ok(match entry.tag() {
-            crate::gimli::DW_TAG_base_type => {
+        use crate::memo::{with_memo, Erased};
+        use anyhow::Context;
+
+        type Types = std::collections::HashMap<Key, Erased>;
+        type Key = (crate::gimli::UnitSectionOffset, crate::gimli::UnitOffset);
+
+        let key = (unit.header.offset(), entry.offset());
+        let cached = with_memo(dwarf, "types", |types: &mut Types| {
+            // SAFETY: memoized types refer only to `dwarf`, and to the units
+            // memoized of it, which outlive the borrow of `dwarf`.
+            types.get(&key).map(|cached| unsafe { cached.get::<Self>() }.clone())
+        });
+        let r#type = match cached {
+            Some(Some(cached)) => return Ok(cached),
+            // types of sections that are not memoized are read anew.
+            None => {
+                return Self::from_die_without_context(dwarf, unit, entry)
+                    .with_context(|| crate::error::reading_type(dwarf, unit, key.1))
+            }
+            Some(None) => {
+                // the type is read from the memoized unit, rather than from
+                // `unit`, which may be freed before the memoized type is.
+                let unit = crate::refs::load_unit(dwarf, key.0)?;
+                let entry = unit.entry(key.1)?;
+                Self::from_die_without_context(dwarf, unit, entry)
+                    .with_context(|| crate::error::reading_type(dwarf, unit, key.1))?
+            }
+        };
+        with_memo(dwarf, "types", |types: &mut Types| {
+            types.entry(key).or_insert_with(|| Erased::new(r#type.clone()));
+        });
+        Ok(r#type)
+    }
+
+    /// Construct the schema of an `isize` (if `signed`) or `usize` of `unit`,
+    /// as the integer of the same size as the addresses of `unit`.
+    fn from_address_sized_dw_tag_base_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+        signed: std::primitive::bool,
+    ) -> Result<Self, crate::Error> {
+        Ok(match (crate::address_size(unit), signed) {
+            (2, true) => Self::i16(i16::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (4, true) => Self::i32(i32::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (8, true) => Self::i64(i64::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (2, false) => Self::u16(u16::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (4, false) => Self::u32(u32::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (8, false) => Self::u64(u64::from_sized_dw_tag_base_type(dwarf, unit, entry)?),
+            (size, _) => bail!("unsupported address size of {size} bytes"),
+        })
+    }
+
+    fn from_die_without_context(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        Ok(match shape::shape_of(dwarf, unit, &entry)? {
+            Shape::Primitive => {
                 let name = Name::from_die(dwarf, unit, &entry)?;
                 let name = name.to_slice()?;
+                let host_address_size = std::mem::size_of::<std::primitive::usize>();
+                let foreign_address_size =
+                    crate::address_size(unit) != host_address_size as std::primitive::u64;
                 return match name.as_ref() {
                     b"bool" => bool::from_dw_tag_base_type(dwarf, unit, entry).map(Self::bool),
                     b"char" => char::from_dw_tag_base_type(dwarf, unit, entry).map(Self::char),
@@ -71,6 +159,13 @@ where
                     b"i32" => i32::from_dw_tag_base_type(dwarf, unit, entry).map(Self::i32),
                     b"i64" => i64::from_dw_tag_base_type(dwarf, unit, entry).map(Self::i64),
                     b"i128" => i128::from_dw_tag_base_type(dwarf, unit, entry).map(Self::i128),
+                    // the `isize`s and `usize`s of targets with addresses of
+                    // another size than this host's are reflected as the
+                    // integers of that size.
+                    b"isize" | b"usize" if foreign_address_size => {
+                        let signed = name.as_ref() == b"isize";
+                        Self::from_address_sized_dw_tag_base_type(dwarf, unit, entry, signed)
+                    }
                     b"isize" => isize::from_dw_tag_base_type(dwarf, unit, entry).map(Self::isize),
                     b"u8" => u8::from_dw_tag_base_type(dwarf, unit, entry).map(Self::u8),
                     b"u16" => u16::from_dw_tag_base_type(dwarf, unit, entry).map(Self::u16),
@@ -79,137 +174,98 @@ where
                     b"u128" => u128::from_dw_tag_base_type(dwarf, unit, entry).map(Self::u128),
                     b"usize" => usize::from_dw_tag_base_type(dwarf, unit, entry).map(Self::usize),
                     b"()" => unit::from_dw_tag_base_type(dwarf, unit, entry).map(Self::unit),
-                    _ => unimplemented!(
-                        "unhandled primitive: {:#?}",
-                        crate::debug::DebugEntry::new(dwarf, unit, &entry)
-                    ),
+                    _ => Err(crate::error::unsupported_layout(entry.tag())),
                 };
             }
-            crate::gimli::DW_TAG_structure_type => {
-                let name = Name::from_die(dwarf, unit, &entry)?;
-                let name_slice = name.to_slice()?;
-                if name_slice.starts_with(b"&[") {
-                    return Ok(Self::Slice(Slice::from_dw_tag_structure_type(
-                        dwarf, unit, entry,
-                    )?));
-                } else if &*name_slice == b"&str" {
-                    return Ok(Self::str(str::from_dw_tag_structure_type(
-                        dwarf, unit, entry,
-                    )?));
-                } else if name_slice.starts_with(b"alloc::boxed::Box<") {
-                    // boxedslice: data_ptr + length
-                    let schema = Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
-                    let mut fields = schema.fields()?;
-                    let mut fields = fields.iter()?;
-                    let pointer = fields.try_next()?;
-                    let pointer = pointer
-                        .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
-                    let metadata = fields.try_next()?;
-                    let metadata = metadata
-                        .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
-                    let metadata_name = metadata.name()?;
-                    let metadata_name_slice = metadata_name.to_slice()?;
-                    return match metadata_name_slice.as_ref() {
-                        b"length" => {
-                            BoxedSlice::new(schema, pointer, metadata).map(Self::BoxedSlice)
-                        }
-                        b"vtable" => BoxedDyn::new(schema, pointer, metadata).map(Self::BoxedDyn),
-                        _ => Err(crate::error::name_mismatch(
-                            "`length` or `vtable`",
-                            metadata_name.to_string_lossy()?.into_owned(),
-                        ))?,
-                    };
+            Shape::Slice => Self::Slice(Slice::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::str => Self::str(str::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::DynRef => Self::DynRef(DynRef::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::Atomic => Self::Atomic(Atomic::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::NonZero => {
+                Self::NonZero(NonZero::from_dw_tag_structure_type(dwarf, unit, entry)?)
+            }
+            Shape::NonNull => {
+                Self::NonNull(NonNull::from_dw_tag_structure_type(dwarf, unit, entry)?)
+            }
+            Shape::ManuallyDrop => Self::ManuallyDrop(ManuallyDrop::from_dw_tag_structure_type(
+                dwarf, unit, entry,
+            )?),
+            Shape::Vector => Self::Vector(Vector::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            shape @ (Shape::BoxedSlice | Shape::BoxedDyn) => {
+                // boxedslice: data_ptr + length
+                let schema = Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+                let mut fields = schema.fields()?;
+                let mut fields = fields.iter()?;
+                let pointer = fields.try_next()?;
+                let pointer = pointer
+                    .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+                let metadata = fields.try_next()?;
+                let metadata = metadata
+                    .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+                return if shape == Shape::BoxedSlice {
+                    BoxedSlice::new(schema, pointer, metadata).map(Self::BoxedSlice)
                 } else {
-                    let mut tree = unit.entries_tree(Some(entry.offset()))?;
-                    let root = tree.root()?;
-                    let mut children = root.children();
-                    let mut variants = None;
-
-                    while let Some(child) = children.next()? {
-                        if child.entry().tag() == crate::gimli::DW_TAG_variant_part {
-                            variants = Some(child.entry().clone());
-                            break;
-                        }
-                    }
-
-                    if let Some(_variants) = variants {
-                        Self::Enum(Enum::from_dw_tag_structure_type(dwarf, unit, entry)?)
-                    } else {
-                        Self::Struct(Struct::from_dw_tag_structure_type(dwarf, unit, entry)?)
-                    }
-                }
+                    BoxedDyn::new(schema, pointer, metadata).map(Self::BoxedDyn)
+                };
+            }
+            Shape::Struct => Self::Struct(Struct::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::Enum => Self::Enum(Enum::from_dw_tag_structure_type(dwarf, unit, entry)?),
+            Shape::MaybeUninit => {
+                Self::MaybeUninit(MaybeUninit::from_dw_tag_union_type(dwarf, unit, entry)?)
             }
-            crate::gimli::DW_TAG_enumeration_type => {
+            Shape::Enumeration => {
                 Self::Enum(Enum::from_dw_tag_enumeration_type(dwarf, unit, entry)?)
             }
-            crate::gimli::DW_TAG_pointer_type => {
+            Shape::MutPtr => {
                 let name = Name::from_die_opt(dwarf, unit, &entry)?;
-                let target = crate::get_type_ref(&entry)?;
-                if let Some(name) = name {
-                    let name_as_slice = name.to_slice()?;
-                    if name_as_slice.starts_with(b"*mut ") {
-                        Self::MutPtr(Pointer::new(
-                            dwarf,
-                            unit,
-                            entry.offset(),
-                            Some(name),
-                            target,
-                        ))
-                    } else if name_as_slice.starts_with(b"*const ") {
-                        Self::ConstPtr(Pointer::new(
-                            dwarf,
-                            unit,
-                            entry.offset(),
-                            Some(name),
-                            target,
-                        ))
-                    } else if name_as_slice.starts_with(b"&mut ") {
-                        Self::UniqueRef(Pointer::new(
-                            dwarf,
-                            unit,
-                            entry.offset(),
-                            Some(name),
-                            target,
-                        ))
-                    } else if name_as_slice.starts_with(b"&") || name_as_slice.starts_with(b"fn") {
-                        Self::SharedRef(Pointer::new(
-                            dwarf,
-                            unit,
-                            entry.offset(),
-                            Some(name),
-                            target,
-                        ))
-                    } else if name_as_slice.starts_with(b"alloc::boxed::Box<") {
-                        Self::Box(Box::new(dwarf, unit, entry.offset(), Some(name), target))
-                    } else {
-                        eprintln!(
-                            "{:#?}",
-                            &crate::debug::DebugEntry::new(dwarf, unit, &entry,)
-                        );
-
-                        return Err(crate::error::invalid_attr(crate::gimli::DW_AT_name));
-                    }
-                } else {
-                    // the `data_ptr` field of slices points to a pointer type that doesn't have a
-                    // name.
-                    Self::MutPtr(Pointer::new(dwarf, unit, entry.offset(), None, target))
-                }
+                let target = crate::get_type(dwarf, unit, &entry)?;
+                Self::MutPtr(Pointer::new(dwarf, unit, entry.offset(), name, target))
             }
-            crate::gimli::DW_TAG_subroutine_type => {
-                Self::Function(Function::from_dw_tag_subroutine_type(dwarf, unit, entry)?)
+            Shape::ConstPtr => {
+                let name = Name::from_die(dwarf, unit, &entry)?;
+                let target = crate::get_type(dwarf, unit, &entry)?;
+                Self::ConstPtr(Pointer::new(
+                    dwarf,
+                    unit,
+                    entry.offset(),
+                    Some(name),
+                    target,
+                ))
             }
-            crate::gimli::DW_TAG_array_type => {
-                Self::Array(Array::from_dw_tag_array_type(dwarf, unit, entry)?)
+            Shape::UniqueRef => {
+                let name = Name::from_die(dwarf, unit, &entry)?;
+                let target = crate::get_type(dwarf, unit, &entry)?;
+                Self::UniqueRef(Pointer::new(
+                    dwarf,
+                    unit,
+                    entry.offset(),
+                    Some(name),
+                    target,
+                ))
             }
-            _otherwise => {
-                eprintln!(
-                    "UNHANDLED DEBUG ENTRY:\n{:#?}",
-                    &crate::debug::DebugEntry::new(dwarf, unit, &entry,)
-                );
-                anyhow::bail!(
-                    "Unhandled debug info kind:\n{:#?}",
-                    crate::debug::DebugEntry::new(dwarf, unit, &entry,)
-                )
+            Shape::SharedRef => {
+                let name = Name::from_die(dwarf, unit, &entry)?;
+                let target = crate::get_type(dwarf, unit, &entry)?;
+                Self::SharedRef(Pointer::new(
+                    dwarf,
+                    unit,
+                    entry.offset(),
+                    Some(name),
+                    target,
+                ))
+            }
+            Shape::Box => {
+                let name = Name::from_die(dwarf, unit, &entry)?;
+                let target = crate::get_type(dwarf, unit, &entry)?;
+                Self::Box(Box::new(dwarf, unit, entry.offset(), Some(name), target))
+            }
+            Shape::Function => {
+                Self::Function(Function::from_dw_tag_subroutine_type(dwarf, unit, entry)?)
+            }
+            Shape::Array => Self::Array(Array::from_dw_tag_array_type(dwarf, unit, entry)?),
+            Shape::Alias(target_unit, target) => {
+                let target_unit = crate::refs::unit_at(dwarf, unit, target_unit)?;
+                return Self::from_die(dwarf, target_unit, target_unit.entry(target)?);
             }
         })
     }
@@ -237,16 +293,119 @@ where
             Self::Box(v) => Ok(v.size()),
             Self::BoxedSlice(v) => v.size(),
             Self::BoxedDyn(v) => v.size(),
+            Self::DynRef(v) => v.size(),
             Self::Array(v) => v.bytes(),
+            Self::Atomic(v) => v.size(),
+            Self::NonZero(v) => v.size(),
+            Self::NonNull(v) => v.size(),
+            Self::Vector(v) => v.size(),
+            Self::ManuallyDrop(v) => v.size(),
+            Self::MaybeUninit(v) => v.size(),
             Self::Slice(v) => v.size(),
             Self::str(v) => v.size(),
             Self::Struct(v) => v.size(),
             Self::Enum(v) => v.size(),
             Self::Function(_) => Ok(0),
-            Self::SharedRef(_) => Ok(std::mem::size_of::<std::primitive::usize>() as _),
-            Self::UniqueRef(_) => Ok(std::mem::size_of::<std::primitive::usize>() as _),
-            Self::ConstPtr(_) => Ok(std::mem::size_of::<std::primitive::usize>() as _),
-            Self::MutPtr(_) => Ok(std::mem::size_of::<std::primitive::usize>() as _),
+            Self::SharedRef(v) => Ok(v.size()),
+            Self::UniqueRef(v) => Ok(v.size()),
+            Self::ConstPtr(v) => Ok(v.size()),
+            Self::MutPtr(v) => Ok(v.size()),
+        }
+    }
+
+    /// The alignment of the type, in bytes, if it is described by its debug
+    /// info; primitives and pointers are aligned to their size, and arrays to
+    /// their elements.
+    pub fn align(&self) -> Result<Option<std::primitive::u64>, crate::Error> {
+        let (_, unit, offset) = self.die();
+        let entry = unit.entry(offset)?;
+        if let Some(align) = crate::get_align(&entry)? {
+            return Ok(Some(align));
+        }
+        match entry.tag() {
+            crate::gimli::DW_TAG_base_type | crate::gimli::DW_TAG_pointer_type => {
+                self.size().map(Some)
+            }
+            _ => match self {
+                Self::Array(v) => v.elt_type()?.align(),
+                Self::Struct(v) => v.align(),
+                _ => Ok(None),
+            },
+        }
+    }
+
+    /// The name of this type, as `rustc` would display it; e.g., `Vec<u8>`,
+    /// rather than `Vec<u8, alloc::alloc::Global>`, as its debug info names
+    /// it.
+    ///
+    /// Default allocators are elided from generic arguments, closures and
+    /// coroutines are named by where they are defined (e.g.,
+    /// `{closure@src/main.rs:10}`), and environments in generic arguments are
+    /// named like their bodies (e.g., `{closure#0}`, rather than
+    /// `{closure_env#0}`). Unlike this type's [`Display`][std::fmt::Display],
+    /// the name omits the fields of structs and enums. Values are displayed
+    /// with these names.
+    pub fn display_name(&self) -> Result<String, crate::Error> {
+        crate::format::type_name(self)
+    }
+
+    /// The generic type parameters of this type, in order; e.g., `T` and `A`
+    /// (its allocator) for a `Vec<T, A>`.
+    ///
+    /// Empty for types without generic type parameters, and for pointers;
+    /// e.g., the `T` of a `Box<T>` is its [pointee][Box::r#type].
+    pub fn generic_params(&self) -> Result<Vec<GenericParam<'dwarf, R>>, crate::Error> {
+        let (dwarf, unit, offset) = self.die();
+        generic_param::generic_params_of(dwarf, unit, offset)
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections, [unit][crate::gimli::Unit],
+    /// and entry offset of the debuginfo describing this type.
+    pub(crate) fn die(
+        &self,
+    ) -> (
+        &'dwarf crate::gimli::Dwarf<R>,
+        &'dwarf crate::gimli::Unit<R, std::primitive::usize>,
+        crate::gimli::UnitOffset,
+    ) {
+        match self {
+            Self::bool(v) => (v.dwarf, v.unit, v.entry),
+            Self::char(v) => (v.dwarf, v.unit, v.entry),
+            Self::f32(v) => (v.dwarf, v.unit, v.entry),
+            Self::f64(v) => (v.dwarf, v.unit, v.entry),
+            Self::i8(v) => (v.dwarf, v.unit, v.entry),
+            Self::i16(v) => (v.dwarf, v.unit, v.entry),
+            Self::i32(v) => (v.dwarf, v.unit, v.entry),
+            Self::i64(v) => (v.dwarf, v.unit, v.entry),
+            Self::i128(v) => (v.dwarf, v.unit, v.entry),
+            Self::isize(v) => (v.dwarf, v.unit, v.entry),
+            Self::u8(v) => (v.dwarf, v.unit, v.entry),
+            Self::u16(v) => (v.dwarf, v.unit, v.entry),
+            Self::u32(v) => (v.dwarf, v.unit, v.entry),
+            Self::u64(v) => (v.dwarf, v.unit, v.entry),
+            Self::u128(v) => (v.dwarf, v.unit, v.entry),
+            Self::usize(v) => (v.dwarf, v.unit, v.entry),
+            Self::unit(v) => (v.dwarf, v.unit, v.entry),
+            Self::Box(v) => (v.dwarf(), v.unit(), v.offset()),
+            Self::SharedRef(v) => (v.dwarf(), v.unit(), v.offset()),
+            Self::UniqueRef(v) => (v.dwarf(), v.unit(), v.offset()),
+            Self::ConstPtr(v) => (v.dwarf(), v.unit(), v.offset()),
+            Self::MutPtr(v) => (v.dwarf(), v.unit(), v.offset()),
+            Self::BoxedSlice(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::BoxedDyn(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::DynRef(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Array(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Atomic(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::NonZero(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::NonNull(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Vector(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::ManuallyDrop(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::MaybeUninit(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Slice(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::str(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Struct(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Enum(v) => (v.dwarf(), v.unit(), v.entry().offset()),
+            Self::Function(v) => (v.dwarf(), v.unit(), v.entry().offset()),
         }
     }
 }
@@ -299,9 +458,23 @@ macro_rules! generate_primitive {
                     Err(crate::error::name_mismatch(expected, actual))?;
                 }
 
-                let size: std::primitive::usize = crate::get_size(&entry)?
-                    .try_into()?;
+                Self::from_sized_dw_tag_base_type(dwarf, unit, entry)
+            }
+
+            /// Construct a new schema from a base type of this type's size, but
+            /// possibly of another name; e.g., a `usize` of a target with
+            /// addresses of this type's size.
+            pub(crate) fn from_sized_dw_tag_base_type(
+                dwarf: &'dwarf crate::gimli::Dwarf<R>,
+                unit: &'dwarf crate::gimli::Unit<R, std::primitive::usize>,
+                entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+            ) -> Result<Self, crate::Error> {
                 let expected = core::mem::size_of::<std::primitive::$t>();
+                // optimized builds may omit the sizes of primitives.
+                let size: std::primitive::usize = match crate::get_size_opt(unit, &entry)? {
+                    Some(size) => size.try_into()?,
+                    None => expected,
+                };
                 if size != expected {
                     Err(crate::error::size_mismatch(expected, size))?;
                 }
@@ -314,7 +487,7 @@ macro_rules! generate_primitive {
             }
 
 
-            /// The size of this type.
+            /// The name of this type; e.g., `u8`.
             pub fn name(&self) -> &'static std::primitive::str {
                 std::any::type_name::<std::primitive::$t>()
             }
@@ -406,8 +579,11 @@ where
             Err(crate::error::name_mismatch(expected, actual))?;
         }
 
-        let size: std::primitive::usize = crate::get_size(&entry)?.try_into()?;
         let expected = core::mem::size_of::<()>();
+        let size: std::primitive::usize = match crate::get_size_opt(unit, &entry)? {
+            Some(size) => size.try_into()?,
+            None => expected,
+        };
         if size != expected {
             Err(crate::error::size_mismatch(expected, size))?;
         }
@@ -419,7 +595,7 @@ where
         })
     }
 
-    /// The size of this type.
+    /// The name of this type; i.e., `()`.
     pub fn name(&self) -> &'static std::primitive::str {
         std::any::type_name::<()>()
     }
@@ -20,9 +20,8 @@ where
         unit: &crate::gimli::Unit<R, usize>,
         entry: &crate::gimli::DebuggingInformationEntry<'_, '_, R>,
     ) -> Result<Self, crate::Error> {
-        let name = crate::get(entry, crate::gimli::DW_AT_name)?;
-        let name = dwarf.attr_string(unit, name)?;
-        Ok(Self { name })
+        Self::from_die_opt(dwarf, unit, entry)?
+            .ok_or_else(|| crate::error::missing_attr(crate::gimli::DW_AT_name))
     }
 
     /// Constructs a `Name` from the [`DW_AT_name`][crate::gimli::DW_AT_name]
@@ -32,7 +31,7 @@ where
         unit: &crate::gimli::Unit<R, usize>,
         entry: &crate::gimli::DebuggingInformationEntry<'_, '_, R>,
     ) -> Result<Option<Self>, crate::Error> {
-        let name = crate::get_opt(entry, crate::gimli::DW_AT_name)?;
+        let name = crate::get_origin_opt(unit, entry, crate::gimli::DW_AT_name)?;
         Ok(if let Some(name) = name {
             let name = dwarf.attr_string(unit, name)?;
             Some(Self { name })
@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// A schema for [`NonNull`][std::ptr::NonNull].
+#[derive(Clone)]
+pub struct NonNull<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    pointer: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> NonNull<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `NonNull` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// `NonNull`s are structs with a single field, `pointer`, of raw pointer
+    /// type.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let pointer = Self::pointer_field(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self { schema, pointer })
+    }
+
+    /// Produces `true` if the given `entry` has the layout of a `NonNull`.
+    pub(crate) fn is_non_null(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        // other types may be shaped like `NonNull`s; e.g., a `NonNullHandle<T>
+        // { pointer: *const T }`.
+        let Some(name) = crate::qualified_name_of(dwarf, unit, entry.offset())? else {
+            return Ok(false);
+        };
+        if !name.starts_with(b"core::ptr::non_null::NonNull<") {
+            return Ok(false);
+        }
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        Ok(Self::pointer_field(&schema)?.is_some())
+    }
+
+    /// Produces the `pointer` field of `schema`.
+    fn pointer_field(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(pointer) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() || &*pointer.name()?.to_slice()? != b"pointer" {
+            return Ok(None);
+        }
+        match pointer.r#type()? {
+            super::Type::ConstPtr(_) | super::Type::MutPtr(_) => Ok(Some(pointer)),
+            _ => Ok(None),
+        }
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `NonNull`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `NonNull`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `NonNull` abstracts
+    /// over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// This `NonNull`, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The `pointer` field of this `NonNull`.
+    pub fn pointer(&self) -> &super::Field<'dwarf, R> {
+        &self.pointer
+    }
+
+    /// The size of this type, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        self.schema.size()
+    }
+
+    /// The alignment of this type, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        self.schema.align()
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for NonNull<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::NonNull");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for NonNull<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// A schema for a non-zero integer (e.g., [`NonZeroU32`][std::num::NonZeroU32]).
+#[derive(Clone)]
+pub struct NonZero<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    value: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> NonZero<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `NonZero` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// Non-zero integers are (possibly nested) single-field structs wrapping
+    /// an integer primitive.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let value = Self::integer(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self { schema, value })
+    }
+
+    /// Produces `true` if the given `entry` has the layout of a non-zero
+    /// integer.
+    pub(crate) fn is_non_zero(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        // other types may be shaped like non-zero integers; e.g., a
+        // `NonZeroCount(u32)`.
+        let Some(name) = crate::qualified_name_of(dwarf, unit, entry.offset())? else {
+            return Ok(false);
+        };
+        if !name.starts_with(b"core::num::nonzero::NonZero") {
+            return Ok(false);
+        }
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        Ok(Self::integer(&schema)?.is_some())
+    }
+
+    /// Produces the integer field wrapped by `schema`, descending through
+    /// single-field structs at offset zero.
+    fn integer(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
+        use super::Type;
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(field) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() || field.offset()?.address(0)? != 0 {
+            return Ok(None);
+        }
+        match field.r#type()? {
+            Type::i8(_)
+            | Type::i16(_)
+            | Type::i32(_)
+            | Type::i64(_)
+            | Type::i128(_)
+            | Type::isize(_)
+            | Type::u8(_)
+            | Type::u16(_)
+            | Type::u32(_)
+            | Type::u64(_)
+            | Type::u128(_)
+            | Type::usize(_) => Ok(Some(field)),
+            Type::NonZero(inner) => Ok(Some(inner.value)),
+            Type::Struct(inner) => Self::integer(&inner),
+            _ => Ok(None),
+        }
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `NonZero`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `NonZero`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `NonZero` abstracts
+    /// over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// This non-zero integer, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The integer type wrapped by this type.
+    pub fn value_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
+        self.value.r#type()
+    }
+
+    /// The size of this type, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        self.schema.size()
+    }
+
+    /// The alignment of this type, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        self.schema.align()
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for NonZero<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::NonZero");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for NonZero<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
@@ -6,6 +6,9 @@ where
 {
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     inner: OffsetInner<R>,
+    /// The offset of the subobject this offset is relative to; e.g., of a C++
+    /// base class within its derived class.
+    base: u64,
 }
 
 #[derive(Copy)]
@@ -25,15 +28,34 @@ where
         Self {
             unit,
             inner: OffsetInner::Udata(0),
+            base: 0,
         }
     }
 
     /// Construct a new `Offset` from a given `entry`'s
     /// `DW_AT_data_member_location` attribute.
+    ///
+    /// Bitfields described only by a `DW_AT_data_bit_offset` are located at
+    /// the byte containing their first bit.
     pub(crate) fn from_die<'entry>(
         unit: &'dwarf crate::gimli::Unit<R, usize>,
         entry: &'entry crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
     ) -> Result<Self, crate::Error> {
+        if entry
+            .attr_value(crate::gimli::DW_AT_data_member_location)?
+            .is_none()
+        {
+            if let Some(bit_offset) = entry
+                .attr_value(crate::gimli::DW_AT_data_bit_offset)?
+                .and_then(|bit_offset| bit_offset.udata_value())
+            {
+                return Ok(Self {
+                    unit,
+                    inner: OffsetInner::Udata(bit_offset / 8),
+                    base: 0,
+                });
+            }
+        }
         let location = crate::get(entry, crate::gimli::DW_AT_data_member_location)?;
         let inner = if let Some(offset) = location.udata_value() {
             OffsetInner::Udata(offset)
@@ -44,28 +66,47 @@ where
                 crate::gimli::DW_AT_data_member_location,
             ));
         };
-        Ok(Self { unit, inner })
+        Ok(Self {
+            unit,
+            inner,
+            base: 0,
+        })
+    }
+
+    /// Produces this offset, made relative to a subobject at `base`.
+    pub(crate) fn rebased(mut self, base: u64) -> Self {
+        self.base += base;
+        self
     }
 
     /// Produces the actual memory address referred to by this offset.
     pub fn address(self, start: u64) -> Result<u64, crate::Error> {
+        let start = start
+            .checked_add(self.base)
+            .ok_or_else(crate::error::arithmetic_overflow)?;
         match self.inner {
-            OffsetInner::Udata(offset) => Ok(start + offset),
+            OffsetInner::Udata(offset) => start
+                .checked_add(offset)
+                .ok_or_else(crate::error::arithmetic_overflow),
             OffsetInner::Expression(expression) => {
                 let mut eval = expression.evaluation(self.unit.encoding());
                 eval.set_initial_value(start);
-                if let crate::gimli::EvaluationResult::Complete = eval.evaluate()? {
-                    let result = eval.result();
-                    match result[..] {
-                        [crate::gimli::Piece {
-                            size_in_bits: None,
-                            bit_offset: None,
-                            location: crate::gimli::Location::Address { address },
-                        }] => Ok(address),
-                        _ => unimplemented!("unsupported evaluation result {:?}", result,),
-                    }
-                } else {
-                    unimplemented!("unsupported evaluation result");
+                // member locations are computed from the address of their
+                // object alone; they cannot, e.g., read memory.
+                let crate::gimli::EvaluationResult::Complete = eval.evaluate()? else {
+                    return Err(crate::error::invalid_attr(
+                        crate::gimli::DW_AT_data_member_location,
+                    ));
+                };
+                match eval.result()[..] {
+                    [crate::gimli::Piece {
+                        size_in_bits: None,
+                        bit_offset: None,
+                        location: crate::gimli::Location::Address { address },
+                    }] => Ok(address),
+                    _ => Err(crate::error::invalid_attr(
+                        crate::gimli::DW_AT_data_member_location,
+                    )),
                 }
             }
         }
@@ -80,6 +121,7 @@ where
         Self {
             unit: self.unit,
             inner: self.inner.clone(),
+            base: self.base,
         }
     }
 }
@@ -29,6 +29,17 @@ pub trait Reference: reference_seal::Sealed {}
 impl Reference for Unique {}
 impl Reference for Shared {}
 
+mod raw_seal {
+    pub trait Sealed {}
+    impl Sealed for super::Const {}
+    impl Sealed for super::Mut {}
+}
+
+/// A trait implemented by raw pointer kinds.
+pub trait Raw: raw_seal::Sealed {}
+impl Raw for Const {}
+impl Raw for Mut {}
+
 /// A schema for a shared reference (i.e., `&T`).
 #[derive(Clone)]
 pub struct Pointer<'dwarf, K, R>
@@ -39,6 +50,7 @@ where
     unit: &'dwarf crate::gimli::Unit<R, usize>,
     entry: UnitOffset,
     name: Option<super::Name<R>>,
+    target_unit: &'dwarf crate::gimli::Unit<R, usize>,
     target: UnitOffset,
     kind: PhantomData<K>,
 }
@@ -53,32 +65,53 @@ where
         unit: &'dwarf crate::gimli::Unit<R, usize>,
         entry: UnitOffset,
         name: Option<super::Name<R>>,
-        target: UnitOffset,
+        (target_unit, target): (&'dwarf crate::gimli::Unit<R, usize>, UnitOffset),
     ) -> Self {
         Self {
             dwarf,
             unit,
             entry,
             name,
+            target_unit,
             target,
             kind: PhantomData,
         }
     }
 
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `Pointer`'s debuginfo
+    /// belongs to.
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.dwarf
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `Pointer`'s debuginfo
+    /// belongs to.
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.unit
+    }
+
+    /// The offset of the [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `Pointer` abstracts
+    /// over.
+    pub(crate) fn offset(&self) -> crate::gimli::UnitOffset {
+        self.entry
+    }
+
     /// The name of this reference type.
     pub fn name(&self) -> Option<&super::Name<R>> {
         self.name.as_ref()
     }
 
-    /// The size of this type, in bytes.
+    /// The size of this type, in bytes; i.e., the size of addresses on the
+    /// target it was compiled for.
     pub fn size(&self) -> u64 {
-        core::mem::size_of::<usize>() as _
+        crate::address_size(self.unit)
     }
 
     /// The type of the referent.
     pub fn r#type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
-        let entry = self.unit.entry(self.target)?;
-        super::Type::from_die(self.dwarf, self.unit, entry)
+        let entry = self.target_unit.entry(self.target)?;
+        super::Type::from_die(self.dwarf, self.target_unit, entry)
     }
 }
 
@@ -102,7 +135,8 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(name) = self.name() {
-            name.fmt(f)
+            let name = name.to_string_lossy().map_err(crate::fmt_err)?;
+            f.write_str(&crate::format::canonical(&name))
         } else {
             f.write_str("*? ")?;
             let target = self.r#type().map_err(crate::fmt_err)?;
@@ -0,0 +1,100 @@
+use std::fmt;
+
+/// The compilation unit whose debuginfo describes a type; produced by
+/// [`Type::compilation_unit`][super::Type::compilation_unit].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompilationUnit {
+    name: Option<String>,
+    comp_dir: Option<String>,
+    producer: Option<String>,
+}
+
+impl CompilationUnit {
+    /// The `DW_AT_name` of the unit; for units produced by `rustc`, e.g.,
+    /// `src/lib.rs/@/deflect.3a1f8c2e-cgu.0`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The directory the unit was compiled in.
+    pub fn comp_dir(&self) -> Option<&str> {
+        self.comp_dir.as_deref()
+    }
+
+    /// The `DW_AT_producer` of the unit; e.g., `clang LLVM (rustc version
+    /// 1.84.0 (9fc6b4312 2025-01-07))`.
+    pub fn producer(&self) -> Option<&str> {
+        self.producer.as_deref()
+    }
+
+    /// The name of the crate the unit was compiled from, as `rustc` names its
+    /// units (e.g., `deflect` in `src/lib.rs/@/deflect.3a1f8c2e-cgu.0`).
+    fn crate_name(&self) -> Option<&str> {
+        let (_, codegen_unit) = self.name.as_deref()?.rsplit_once("/@/")?;
+        let (krate, _) = codegen_unit.split_once('.')?;
+        Some(krate)
+    }
+}
+
+impl fmt::Display for CompilationUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name().unwrap_or("<unnamed unit>"))
+    }
+}
+
+impl<'dwarf, R> super::Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// The compilation unit whose debuginfo describes this type.
+    ///
+    /// Generic types are described by each unit that instantiates them, so
+    /// this is not necessarily a unit of the crate that defines this type.
+    pub fn compilation_unit(&self) -> Result<CompilationUnit, crate::Error> {
+        let (dwarf, unit, _) = self.die();
+        let string = |attr: &Option<R>| -> Result<Option<String>, crate::Error> {
+            attr.as_ref()
+                .map(|attr| Ok(attr.to_string_lossy()?.into_owned()))
+                .transpose()
+        };
+        Ok(CompilationUnit {
+            name: string(&unit.name)?,
+            comp_dir: string(&unit.comp_dir)?,
+            producer: crate::rustc::producer_of(dwarf, unit)?,
+        })
+    }
+
+    /// The name of the crate that defines this type, as best as can be told
+    /// from its debuginfo: the outermost namespace of its declaration (e.g.,
+    /// `tokio` for `tokio::sync::Mutex<u8>`); or, for types declared outside
+    /// any namespace, the crate of its [compilation unit][Self::compilation_unit].
+    ///
+    /// `None` for types that belong to no crate; e.g., primitives, pointers,
+    /// tuples and slices.
+    pub fn crate_name(&self) -> Result<Option<String>, crate::Error> {
+        let (dwarf, unit, offset) = self.die();
+        let entry = unit.entry(offset)?;
+        let tag = entry.tag();
+        if tag != crate::gimli::DW_TAG_structure_type
+            && tag != crate::gimli::DW_TAG_union_type
+            && tag != crate::gimli::DW_TAG_enumeration_type
+        {
+            return Ok(None);
+        }
+        if let Some(krate) = crate::namespace_path_of(dwarf, unit, offset)?.first() {
+            return Ok(Some(String::from_utf8_lossy(krate).into_owned()));
+        }
+        // structural types, like tuples and slices, are also declared outside
+        // any namespace, but belong to no crate.
+        let Some(name) = entry.attr_value(crate::gimli::DW_AT_name)? else {
+            return Ok(None);
+        };
+        let name = dwarf.attr_string(unit, name)?;
+        let name = name.to_slice()?;
+        let structural: [&[u8]; 5] = [b"&", b"*", b"(", b"[", b"dyn "];
+        if structural.iter().any(|prefix| name.starts_with(prefix)) {
+            return Ok(None);
+        }
+        Ok(self.compilation_unit()?.crate_name().map(str::to_owned))
+    }
+}
@@ -0,0 +1,150 @@
+use super::{
+    Atomic, DynRef, ManuallyDrop, MaybeUninit, Name, NonNull, NonZero, Struct, Vector,
+};
+
+/// The variant of [`Type`][super::Type] that reflects a debuginfo entry.
+///
+/// Telling some variants apart (e.g., a trait object reference from a struct)
+/// requires walking the entry's children; this happens only the first time an
+/// entry is reflected, as [types are memoized][super::Type::from_die].
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Shape {
+    Primitive,
+    Slice,
+    str,
+    DynRef,
+    Atomic,
+    NonZero,
+    NonNull,
+    ManuallyDrop,
+    Vector,
+    BoxedSlice,
+    BoxedDyn,
+    Struct,
+    Enum,
+    MaybeUninit,
+    Enumeration,
+    MutPtr,
+    ConstPtr,
+    UniqueRef,
+    SharedRef,
+    Box,
+    Function,
+    Array,
+    /// A type alias or qualifier of the type at the given offset, in the unit
+    /// at the given offset.
+    Alias(crate::gimli::UnitSectionOffset, crate::gimli::UnitOffset),
+}
+
+/// Produces the [`Shape`] of `entry`.
+pub(crate) fn shape_of<'dwarf, R>(
+    dwarf: &'dwarf crate::gimli::Dwarf<R>,
+    unit: &'dwarf crate::gimli::Unit<R, usize>,
+    entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+) -> Result<Shape, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    Ok(match entry.tag() {
+        crate::gimli::DW_TAG_base_type => Shape::Primitive,
+        crate::gimli::DW_TAG_structure_type | crate::gimli::DW_TAG_class_type => {
+            let name = Name::from_die(dwarf, unit, entry)?;
+            let name = name.to_slice()?;
+            if name.starts_with(b"&[") {
+                Shape::Slice
+            } else if &*name == b"&str" {
+                Shape::str
+            } else if name.starts_with(b"&") && DynRef::is_dyn_ref(dwarf, unit, entry)? {
+                Shape::DynRef
+            } else if name.starts_with(b"Atomic") && Atomic::is_atomic(dwarf, unit, entry)? {
+                Shape::Atomic
+            } else if name.starts_with(b"NonZero") && NonZero::is_non_zero(dwarf, unit, entry)? {
+                Shape::NonZero
+            } else if name.starts_with(b"NonNull<") && NonNull::is_non_null(dwarf, unit, entry)? {
+                Shape::NonNull
+            } else if name.starts_with(b"ManuallyDrop<")
+                && ManuallyDrop::is_manually_drop(dwarf, unit, entry)?
+            {
+                Shape::ManuallyDrop
+            } else if Vector::is_vector(dwarf, unit, entry)? {
+                Shape::Vector
+            } else if name.starts_with(b"alloc::boxed::Box<") {
+                // boxedslice: data_ptr + length
+                let schema = Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+                let mut fields = schema.fields()?;
+                let mut fields = fields.iter()?;
+                fields.try_next()?;
+                let metadata = fields.try_next()?;
+                let metadata = metadata
+                    .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+                let metadata_name = metadata.name()?;
+                let metadata_name_slice = metadata_name.to_slice()?;
+                match metadata_name_slice.as_ref() {
+                    b"length" => Shape::BoxedSlice,
+                    b"vtable" => Shape::BoxedDyn,
+                    _ => Err(crate::error::name_mismatch(
+                        "`length` or `vtable`",
+                        metadata_name.to_string_lossy()?.into_owned(),
+                    ))?,
+                }
+            } else {
+                let mut tree = unit.entries_tree(Some(entry.offset()))?;
+                let root = tree.root()?;
+                let mut children = root.children();
+                let mut shape = Shape::Struct;
+                while let Some(child) = children.next()? {
+                    if child.entry().tag() == crate::gimli::DW_TAG_variant_part {
+                        shape = Shape::Enum;
+                        break;
+                    }
+                }
+                shape
+            }
+        }
+        crate::gimli::DW_TAG_union_type if MaybeUninit::is_maybe_uninit(dwarf, unit, entry)? => {
+            Shape::MaybeUninit
+        }
+        crate::gimli::DW_TAG_enumeration_type => Shape::Enumeration,
+        crate::gimli::DW_TAG_pointer_type => {
+            let Some(name) = Name::from_die_opt(dwarf, unit, entry)? else {
+                // the `data_ptr` field of slices points to a pointer type that
+                // doesn't have a name.
+                return Ok(Shape::MutPtr);
+            };
+            let name = name.to_slice()?;
+            if name.starts_with(b"*mut ") {
+                Shape::MutPtr
+            } else if name.starts_with(b"*const ") {
+                Shape::ConstPtr
+            } else if name.starts_with(b"&mut ") {
+                Shape::UniqueRef
+            } else if name.starts_with(b"&")
+                || name.starts_with(b"fn")
+                || name.starts_with(b"unsafe ")
+                || name.starts_with(b"extern \"")
+            {
+                // function pointers; e.g., `unsafe extern "C" fn()`.
+                Shape::SharedRef
+            } else if name.starts_with(b"alloc::boxed::Box<") {
+                Shape::Box
+            } else {
+                let name = String::from_utf8_lossy(&name).into_owned();
+                return Err(crate::error::unrecognized_pointer(name));
+            }
+        }
+        crate::gimli::DW_TAG_subroutine_type => Shape::Function,
+        crate::gimli::DW_TAG_array_type => Shape::Array,
+        crate::gimli::DW_TAG_typedef
+        | crate::gimli::DW_TAG_const_type
+        | crate::gimli::DW_TAG_volatile_type
+        | crate::gimli::DW_TAG_restrict_type
+        | crate::gimli::DW_TAG_atomic_type => {
+            // type aliases and qualifiers (e.g., from C headers) are
+            // transparent; see `Field::type_alias` for the alias name.
+            let (target_unit, target) = crate::get_type(dwarf, unit, entry)?;
+            Shape::Alias(target_unit.header.offset(), target)
+        }
+        tag => return Err(crate::error::unsupported_layout(tag)),
+    })
+}
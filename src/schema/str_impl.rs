@@ -62,7 +62,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Struct`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.schema.unit()
     }
 
@@ -86,15 +86,15 @@ where
 
     /// The element type of this slice.
     pub fn elt(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
-        if let super::Type::MutPtr(r#ref) = self.data_ptr().r#type()? {
-            return r#ref.r#type();
+        match self.data_ptr().r#type()? {
+            super::Type::MutPtr(r#ref) => r#ref.r#type(),
+            r#type => Err(crate::error::type_mismatch("*mut u8", r#type.to_string())),
         }
-        panic!()
     }
 
     /// The size of this slice, in bytes.
     pub fn size(&self) -> Result<u64, crate::Error> {
-        crate::get_size(self.entry())
+        crate::get_size(self.unit(), self.entry())
     }
 
     /// The alignment of this slice, in bytes.
@@ -122,7 +122,7 @@ impl<'dwarf, R> fmt::Display for str<'dwarf, R>
 where
     R: crate::gimli::Reader<Offset = usize>,
 {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.schema.name().map_err(crate::fmt_err)?.fmt(f)
     }
 }
@@ -18,13 +18,16 @@ where
     R: crate::gimli::Reader<Offset = usize>,
 {
     /// Construct a new `Struct` from a
-    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type] or a
+    /// C++ [`DW_TAG_class_type`][crate::gimli::DW_TAG_class_type].
     pub(crate) fn from_dw_tag_structure_type(
         dwarf: &'dwarf crate::gimli::Dwarf<R>,
         unit: &'dwarf crate::gimli::Unit<R, usize>,
         entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
     ) -> Result<Self, crate::Error> {
-        crate::check_tag(&entry, crate::gimli::DW_TAG_structure_type)?;
+        if entry.tag() != crate::gimli::DW_TAG_class_type {
+            crate::check_tag(&entry, crate::gimli::DW_TAG_structure_type)?;
+        }
         Ok(Self { dwarf, unit, entry })
     }
 
@@ -38,7 +41,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Struct`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -50,19 +53,50 @@ where
         &self.entry
     }
 
-    /// The name of this primitive type.
+    /// The name of this struct.
     pub fn name(&self) -> Result<Name<R>, crate::Error> {
         Name::from_die(self.dwarf(), self.unit(), self.entry())
     }
 
-    /// The size of this field, in bytes.
+    /// The size of this struct, in bytes; if its debuginfo omits it (as may
+    /// that of optimized builds), the extent of its fields, padded to its
+    /// alignment.
     pub fn size(&self) -> Result<u64, crate::Error> {
-        crate::get_size(self.entry())
+        if let Some(size) = crate::get_size_opt(self.unit, self.entry())? {
+            return Ok(size);
+        }
+        let mut extent = 0u64;
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            let end = field
+                .offset()?
+                .address(0)?
+                .checked_add(field.size()?)
+                .ok_or_else(crate::error::arithmetic_overflow)?;
+            extent = extent.max(end);
+        }
+        let align = self.align()?.unwrap_or(1).max(1);
+        extent
+            .checked_add(align - 1)
+            .map(|extent| extent / align * align)
+            .ok_or_else(crate::error::arithmetic_overflow)
     }
 
-    /// The alignment of this field, in bytes.
+    /// The alignment of this struct, in bytes, if known; if its debuginfo
+    /// omits it, that of its most-aligned field.
     pub fn align(&self) -> Result<Option<u64>, crate::Error> {
-        crate::get_align(self.entry())
+        if let Some(align) = crate::get_align(self.entry())? {
+            return Ok(Some(align));
+        }
+        let mut align = None;
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            let Some(field_align) = field.align()? else { return Ok(None) };
+            align = align.max(Some(field_align));
+        }
+        Ok(align)
     }
 
     /// The fields of this struct.
@@ -70,6 +104,149 @@ where
         let tree = self.unit.entries_tree(Some(self.entry.offset()))?;
         Ok(super::Fields::from_tree(self.dwarf, self.unit, tree))
     }
+
+    /// The field of this struct with the given name, if any; the fields of
+    /// tuple structs are named `__0`, `__1`, etc.
+    pub fn field_by_name<N>(&self, field_name: N) -> Result<Option<super::Field<'dwarf, R>>, crate::Error>
+    where
+        N: AsRef<[u8]>,
+    {
+        let target_name = field_name.as_ref();
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            if target_name == &*field.name()?.to_slice()? {
+                return Ok(Some(field));
+            }
+        }
+        Ok(None)
+    }
+
+    /// If this struct is a newtype (i.e., a tuple struct with a single field,
+    /// like `UserId(u64)`), that field.
+    pub fn transparent_inner(&self) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
+        if self.is_closure()? {
+            return Ok(None);
+        }
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(inner) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() || &*inner.name()?.to_slice()? != b"__0" {
+            return Ok(None);
+        }
+        Ok(Some(inner))
+    }
+
+    /// Whether this struct is the environment of a closure; i.e., whether its
+    /// fields are the closure's captures.
+    pub fn is_closure(&self) -> Result<bool, crate::Error> {
+        Ok(self.name()?.to_slice()?.starts_with(b"{closure_env#"))
+    }
+
+    /// If this struct is the environment of a closure, the file and line the
+    /// closure was defined at (if available).
+    pub fn closure_location(&self) -> Result<Option<(String, u64)>, crate::Error> {
+        let name = self.name()?;
+        let name = name.to_slice()?;
+        let Some(index) = name.strip_prefix(b"{closure_env#") else { return Ok(None) };
+        let index: Vec<u8> = index.iter().copied().take_while(u8::is_ascii_digit).collect();
+        let mut closure_name = b"{closure#".to_vec();
+        closure_name.extend(index);
+        closure_name.push(b'}');
+
+        let mut captures = vec![];
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            captures.push(field.name()?.to_slice()?.into_owned());
+        }
+
+        // the closure's body is declared in the same namespace as its
+        // environment, but not necessarily in the same unit. closure names are
+        // not unique within a namespace (e.g., a function and a constant may
+        // share one), so candidates are told apart by their captures, which
+        // the body declares as variables.
+        let path = crate::namespace_path_of(self.dwarf, self.unit, self.entry.offset())?;
+        let mut exact = vec![];
+        let mut inexact = vec![];
+        let mut headers = self.dwarf.units();
+        while let Some(header) = headers.next()? {
+            let unit = self.dwarf.unit(header)?;
+            let mut namespaces: Vec<Option<Vec<u8>>> = vec![];
+            let mut entries = unit.entries();
+            while let Some((delta_depth, entry)) = entries.next_dfs()? {
+                let depth = (namespaces.len() as isize + delta_depth).max(1) as usize;
+                namespaces.truncate(depth - 1);
+                let is_namespace = entry.tag() == crate::gimli::DW_TAG_namespace;
+                let is_subprogram = entry.tag() == crate::gimli::DW_TAG_subprogram;
+                let name = match entry.attr_value(crate::gimli::DW_AT_name)? {
+                    Some(name) if is_namespace || is_subprogram => {
+                        Some(self.dwarf.attr_string(&unit, name)?.to_slice()?.into_owned())
+                    }
+                    _ => None,
+                };
+                if is_subprogram
+                    && name.as_deref() == Some(&closure_name[..])
+                    && namespaces.iter().flatten().eq(path.iter())
+                {
+                    let variables = variables_of(self.dwarf, &unit, entry.offset())?;
+                    if captures.iter().all(|capture| variables.contains(capture)) {
+                        if let Some(location) = crate::get_decl_location(self.dwarf, &unit, entry)? {
+                            if variables.len() == captures.len() {
+                                exact.push(location);
+                            } else {
+                                inexact.push(location);
+                            }
+                        }
+                    }
+                }
+                namespaces.push(if is_namespace { name } else { None });
+            }
+        }
+        // a generic closure's body may be duplicated across units
+        exact.sort();
+        exact.dedup();
+        inexact.sort();
+        inexact.dedup();
+        let candidates = if exact.is_empty() { inexact } else { exact };
+        let [location] = &candidates[..] else { return Ok(None) };
+        Ok(Some(location.clone()))
+    }
+
+    /// The name of this struct, as it should be displayed; closures are
+    /// displayed as `{closure@file:line}`, and names are
+    /// [canonicalized][crate::schema::Type::display_name].
+    pub(crate) fn display_name(&self) -> Result<String, crate::Error> {
+        if let Some((file, line)) = self.closure_location()? {
+            return Ok(format!("{{closure@{file}:{line}}}"));
+        }
+        Ok(crate::format::canonical(&self.name()?.to_string_lossy()?))
+    }
+}
+
+/// The names of the variables declared directly within `subprogram`.
+fn variables_of<R>(
+    dwarf: &crate::gimli::Dwarf<R>,
+    unit: &crate::gimli::Unit<R, usize>,
+    subprogram: crate::gimli::UnitOffset,
+) -> Result<Vec<Vec<u8>>, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let mut variables = vec![];
+    let mut tree = unit.entries_tree(Some(subprogram))?;
+    let root = tree.root()?;
+    let mut children = root.children();
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        if entry.tag() != crate::gimli::DW_TAG_variable {
+            continue;
+        }
+        if let Some(name) = entry.attr_value(crate::gimli::DW_AT_name)? {
+            variables.push(dwarf.attr_string(unit, name)?.to_slice()?.into_owned());
+        }
+    }
+    Ok(variables)
 }
 
 impl<'dwarf, R> fmt::Debug for Struct<'dwarf, R>
@@ -92,8 +269,7 @@ where
     R: crate::gimli::Reader<Offset = usize>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let type_name = self.name().map_err(crate::fmt_err)?;
-        let type_name = type_name.to_string_lossy().map_err(crate::fmt_err)?;
+        let type_name = self.display_name().map_err(crate::fmt_err)?;
         let mut debug_struct = f.debug_struct(&type_name);
         let mut fields = self.fields().map_err(crate::fmt_err)?;
         let mut fields = fields.iter().map_err(crate::fmt_err)?;
@@ -41,7 +41,7 @@ where
     /// The DWARF [unit][crate::gimli::Unit] that this `Variant`'s debuginfo
     /// belongs to.
     #[allow(dead_code)]
-    pub(crate) fn unit(&self) -> &crate::gimli::Unit<R, usize> {
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
         self.unit
     }
 
@@ -60,7 +60,7 @@ where
 
     /// The size of this variant, in bytes.
     pub fn size(&self) -> Result<Option<u64>, crate::Error> {
-        crate::get_size_opt(self.entry())
+        crate::get_size_opt(self.unit, self.entry())
     }
 
     /// The alignment of this variant, in bytes.
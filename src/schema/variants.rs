@@ -64,31 +64,33 @@ where
                 crate::gimli::DW_TAG_variant => {
                     let discriminant_value = entry
                         .attr_value(crate::gimli::DW_AT_discr_value)?
-                        .and_then(|dw_at_discr_value| dw_at_discr_value.udata_value())
+                        .and_then(discriminant_bits)
                         .map(|dw_at_discr_value| {
                             discriminant_value(self.discriminant_type, dw_at_discr_value)
-                        });
+                        })
+                        .transpose()?;
 
                     let mut entry = next.children();
                     let entry = entry.next()?;
                     let entry = entry
                         .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
-                    let entry = crate::get_type(entry.entry())?;
-                    let entry = self.unit.entry(entry)?;
+                    let (unit, entry) = crate::get_type_res(self.dwarf, self.unit, entry.entry())?;
                     return Ok(Some(super::Variant::new(
                         self.dwarf,
-                        self.unit,
+                        unit,
                         entry,
                         discriminant_value,
                     )));
                 }
                 crate::gimli::DW_TAG_enumerator => {
+                    // enumerators are valued by `DW_AT_const_value`.
                     let discriminant_value = entry
-                        .attr_value(crate::gimli::DW_AT_discr_value)?
-                        .and_then(|dw_at_discr_value| dw_at_discr_value.udata_value())
+                        .attr_value(crate::gimli::DW_AT_const_value)?
+                        .and_then(discriminant_bits)
                         .map(|dw_at_discr_value| {
                             discriminant_value(self.discriminant_type, dw_at_discr_value)
-                        });
+                        })
+                        .transpose()?;
 
                     return Ok(Some(super::Variant::new(
                         self.dwarf,
@@ -98,13 +100,7 @@ where
                     )));
                 }
                 crate::gimli::DW_TAG_member => continue,
-                other => {
-                    anyhow::bail!(
-                        "Cannot find discriminant value in {:?} at {:x?}",
-                        other.static_string(),
-                        entry.offset()
-                    );
-                }
+                other => return Err(crate::error::unsupported_layout(other)),
             }
         }
     }
@@ -113,25 +109,36 @@ where
 impl<'dwarf, 'tree, R: crate::gimli::Reader<Offset = usize>> Iterator
     for VariantsIter<'dwarf, 'tree, R>
 {
-    type Item = super::Variant<'dwarf, R>;
+    type Item = Result<super::Variant<'dwarf, R>, crate::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(next) => next,
-            Err(err) => panic!("could not read next variant: {err}"),
-        }
+        self.try_next().transpose()
     }
 }
 
-fn discriminant_value<'dwarf, R>(ty: &super::Type<'dwarf, R>, v: u64) -> super::Data
+fn discriminant_value<'dwarf, R>(
+    ty: &super::Type<'dwarf, R>,
+    v: u64,
+) -> Result<super::Data, crate::Error>
 where
     R: crate::gimli::Reader<Offset = usize>,
 {
-    match ty {
-        super::Type::u8(_) => super::Data::u8(v as _),
-        super::Type::u16(_) => super::Data::u16(v as _),
-        super::Type::u32(_) => super::Data::u32(v as _),
-        super::Type::u64(_) => super::Data::u64(v as _),
-        _ => unimplemented!(),
-    }
+    Ok(match ty {
+        // signed discriminants are stored as their two's-complement bits.
+        super::Type::u8(_) | super::Type::i8(_) => super::Data::u8(v as _),
+        super::Type::u16(_) | super::Type::i16(_) => super::Data::u16(v as _),
+        super::Type::u32(_) | super::Type::i32(_) => super::Data::u32(v as _),
+        super::Type::u64(_) | super::Type::i64(_) => super::Data::u64(v as _),
+        super::Type::usize(_) | super::Type::isize(_) => super::Data::u64(v as _),
+        _ => return Err(crate::error::type_mismatch("an integer", ty.to_string())),
+    })
+}
+
+/// The bits of the discriminant value `attr`, which may be signed.
+fn discriminant_bits<R>(attr: crate::gimli::AttributeValue<R>) -> Option<u64>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    attr.udata_value()
+        .or_else(|| attr.sdata_value().map(|value| value as u64))
 }
@@ -0,0 +1,154 @@
+use std::fmt;
+
+/// A schema for a SIMD vector (e.g., [`__m128`][std::arch::x86_64::__m128], or
+/// `std::simd::Simd<f32, 4>`).
+#[derive(Clone)]
+#[allow(non_camel_case_types)]
+pub struct Vector<'dwarf, R: crate::gimli::Reader<Offset = usize>>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    schema: super::Struct<'dwarf, R>,
+    lanes: super::Field<'dwarf, R>,
+}
+
+impl<'dwarf, R> Vector<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Construct a new `Vector` from a
+    /// [`DW_TAG_structure_type`][crate::gimli::DW_TAG_structure_type].
+    ///
+    /// Vectors are structs with a single field, `__0`, of type `[T; N]`.
+    pub(crate) fn from_dw_tag_structure_type(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<Self, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry)?;
+        let lanes = Self::lanes_of(&schema)?
+            .ok_or_else(|| crate::error::missing_child(crate::gimli::DW_TAG_member))?;
+        Ok(Self { schema, lanes })
+    }
+
+    /// Produces `true` if the given `entry` has the name and layout of a SIMD
+    /// vector type.
+    pub(crate) fn is_vector(
+        dwarf: &'dwarf crate::gimli::Dwarf<R>,
+        unit: &'dwarf crate::gimli::Unit<R, usize>,
+        entry: &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R>,
+    ) -> Result<bool, crate::Error> {
+        let schema = super::Struct::from_dw_tag_structure_type(dwarf, unit, entry.clone())?;
+        let name = schema.name()?;
+        let name = name.to_slice()?;
+        // `std::simd`'s vectors, x86's `__m128` et al., and ARM's
+        // `float32x4_t` et al.
+        let is_vector_name = name.starts_with(b"Simd<")
+            || name.starts_with(b"__m")
+            || (name.ends_with(b"_t") && name.contains(&b'x'));
+        Ok(is_vector_name && Self::lanes_of(&schema)?.is_some())
+    }
+
+    /// Produces the sole array field of `schema`, if any.
+    fn lanes_of(
+        schema: &super::Struct<'dwarf, R>,
+    ) -> Result<Option<super::Field<'dwarf, R>>, crate::Error> {
+        let mut fields = schema.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(lanes) = fields.try_next()? else { return Ok(None) };
+        if fields.try_next()?.is_some() {
+            return Ok(None);
+        }
+        let super::Type::Array(_) = lanes.r#type()? else { return Ok(None) };
+        Ok(Some(lanes))
+    }
+
+    /// The [DWARF](crate::gimli::Dwarf) sections that this `Vector`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn dwarf(&self) -> &'dwarf crate::gimli::Dwarf<R> {
+        self.schema.dwarf()
+    }
+
+    /// The DWARF [unit][crate::gimli::Unit] that this `Vector`'s debuginfo
+    /// belongs to.
+    #[allow(dead_code)]
+    pub(crate) fn unit(&self) -> &'dwarf crate::gimli::Unit<R, usize> {
+        self.schema.unit()
+    }
+
+    /// The [debugging information
+    /// entry][crate::gimli::DebuggingInformationEntry] this `Vector` abstracts
+    /// over.
+    #[allow(dead_code)]
+    pub(crate) fn entry(&self) -> &crate::gimli::DebuggingInformationEntry<'dwarf, 'dwarf, R> {
+        self.schema.entry()
+    }
+
+    /// The name of this type.
+    pub fn name(&self) -> Result<super::Name<R>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// This vector, interpreted as a struct.
+    pub fn as_struct(&self) -> &super::Struct<'dwarf, R> {
+        &self.schema
+    }
+
+    /// The field holding the lanes of this vector; an array.
+    pub fn lanes(&self) -> &super::Field<'dwarf, R> {
+        &self.lanes
+    }
+
+    /// The lanes of this vector, interpreted as an array.
+    pub fn as_array(&self) -> Result<super::Array<'dwarf, R>, crate::Error> {
+        match self.lanes.r#type()? {
+            super::Type::Array(array) => Ok(array),
+            r#type => Err(crate::error::type_mismatch("[T; N]", r#type.to_string())),
+        }
+    }
+
+    /// The type of each lane of this vector.
+    pub fn lane_type(&self) -> Result<super::Type<'dwarf, R>, crate::Error> {
+        self.as_array()?.elt_type()
+    }
+
+    /// The number of lanes in this vector.
+    pub fn lane_count(&self) -> Result<u64, crate::Error> {
+        self.as_array()?.len()
+    }
+
+    /// The size of this vector, in bytes.
+    pub fn size(&self) -> Result<u64, crate::Error> {
+        self.schema.size()
+    }
+
+    /// The alignment of this vector, in bytes.
+    pub fn align(&self) -> Result<Option<u64>, crate::Error> {
+        self.schema.align()
+    }
+}
+
+impl<'dwarf, R> fmt::Debug for Vector<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::schema::Vector");
+        debug_tuple.field(&crate::debug::DebugEntry::new(
+            self.dwarf(),
+            self.unit(),
+            self.entry(),
+        ));
+        debug_tuple.finish()
+    }
+}
+
+impl<'dwarf, R> fmt::Display for Vector<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
@@ -0,0 +1,832 @@
+//! Presentation of reflected values to [`serde`]; e.g., to deserialize them
+//! into stable, versioned mirrors of the types that were reflected, or to
+//! serialize them with any format.
+
+use std::fmt;
+
+use ::serde::de::{self, DeserializeSeed, Deserializer, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use ::serde::ser::{Error as _, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::rustc::Container;
+use crate::value::field::Bits;
+use crate::Value;
+
+/// An error produced while deserializing a reflected [`Value`]; either an
+/// error reflecting the value, or a mismatch between the value and the type
+/// it is deserialized into.
+///
+/// Requires the `serde` feature.
+#[derive(Debug)]
+pub struct DeserializeError(crate::Error);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(anyhow!("{msg}"))
+    }
+}
+
+impl From<crate::Error> for DeserializeError {
+    fn from(error: crate::Error) -> Self {
+        Self(error)
+    }
+}
+
+/// Deserializes reflected values into other types with [`serde`]; e.g., with
+/// `Config::deserialize(value)`, where `Config` is a mirror of the reflected
+/// type that derives `Deserialize`.
+///
+/// Values are presented to `serde` as they are converted to JSON by default
+/// (with the `json` feature): structs are deserialized from maps of their
+/// fields, tuple structs from sequences, and newtypes from their inner value.
+/// Enums are deserialized as enums with the names of their variants; `Option`s
+/// and `Cow`s, as their contents. `Vec`s, `String`s and `HashMap`s are
+/// deserialized as their contents, as are the values of `Rc`s, `Arc`s and
+/// `Mutex`es (which are not locked), and references and boxes are followed.
+/// Raw pointers are deserialized as their addresses, and bitfields as their
+/// bits.
+///
+/// Values of overridden types are deserialized as strings, as they are
+/// displayed by their [format overrides][crate::FormatOverride], and redacted
+/// values as `"<redacted>"`.
+///
+/// Requires the `serde` feature.
+impl<'value, 'dwarf, P> Deserializer<'value> for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        use crate::schema::Type;
+        if let Some(overridden) = self.overridden()? {
+            return visitor.visit_string(overridden.render()?);
+        }
+        match self {
+            Value::bool(v) => visitor.visit_bool(*v.value()),
+            Value::char(v) => visitor.visit_char(*v.value()),
+            Value::f32(v) => visitor.visit_f32(*v.value()),
+            Value::f64(v) => visitor.visit_f64(*v.value()),
+            Value::i8(v) => visitor.visit_i8(*v.value()),
+            Value::i16(v) => visitor.visit_i16(*v.value()),
+            Value::i32(v) => visitor.visit_i32(*v.value()),
+            Value::i64(v) => visitor.visit_i64(*v.value()),
+            Value::i128(v) => visitor.visit_i128(*v.value()),
+            Value::isize(v) => visitor.visit_i64(*v.value() as i64),
+            Value::u8(v) => visitor.visit_u8(*v.value()),
+            Value::u16(v) => visitor.visit_u16(*v.value()),
+            Value::u32(v) => visitor.visit_u32(*v.value()),
+            Value::u64(v) => visitor.visit_u64(*v.value()),
+            Value::u128(v) => visitor.visit_u128(*v.value()),
+            Value::usize(v) => visitor.visit_u64(*v.value() as u64),
+            Value::unit(_) => visitor.visit_unit(),
+            Value::str(v) => visitor.visit_borrowed_str(v.value()?),
+            Value::Array(v) => visitor.visit_seq(Elements(v.iter()?)),
+            Value::Slice(v) => visitor.visit_seq(Elements(v.iter()?)),
+            Value::BoxedSlice(v) => visitor.visit_seq(Elements(v.iter()?)),
+            Value::Vector(v) => visitor.visit_seq(Elements(v.iter()?)),
+            Value::Box(v) => v.deref()?.deserialize_any(visitor),
+            Value::BoxedDyn(v) => v.deref()?.deserialize_any(visitor),
+            Value::DynRef(v) => v.downcast_value()?.deserialize_any(visitor),
+            Value::SharedRef(v) => v.deref()?.deserialize_any(visitor),
+            Value::UniqueRef(v) => v.deref()?.deserialize_any(visitor),
+            Value::NonNull(v) => visitor.visit_u64(v.addr()? as u64),
+            Value::ConstPtr(v) => visitor.visit_u64(v.addr() as u64),
+            Value::MutPtr(v) => visitor.visit_u64(v.addr() as u64),
+            Value::Function(v) => visitor.visit_u64(v.addr() as u64),
+            Value::NonZero(v) => v.value()?.deserialize_any(visitor),
+            Value::ManuallyDrop(v) => v.value()?.deserialize_any(visitor),
+            Value::MaybeUninit(_) => visitor.visit_unit(),
+            Value::Atomic(v) => {
+                let bits = v.load_bits()?;
+                if v.schema().is_bool()? {
+                    return visitor.visit_bool(bits != 0);
+                }
+                match v.schema().value_type()? {
+                    Type::i8(_) => visitor.visit_i8(bits as i8),
+                    Type::i16(_) => visitor.visit_i16(bits as i16),
+                    Type::i32(_) => visitor.visit_i32(bits as i32),
+                    Type::i64(_) | Type::isize(_) => visitor.visit_i64(bits as i64),
+                    _ => visitor.visit_u64(bits),
+                }
+            }
+            Value::Struct(v) => Form::of_struct(&v)?.visit(visitor),
+            Value::Enum(v) => Form::of_enum(&v)?.visit(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        let value = resolved(self)?;
+        match &value {
+            Value::Enum(v) if v.as_option()?.is_some() => value.deserialize_any(visitor),
+            Value::unit(_) | Value::MaybeUninit(_) => visitor.visit_none(),
+            _ => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        let value = resolved(self)?;
+        if let Value::Struct(v) = &value {
+            if let Some(inner) = v.transparent_inner()? {
+                return visitor.visit_newtype_struct(Entry::of(&inner)?);
+            }
+        }
+        visitor.visit_newtype_struct(value)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        let value = resolved(self)?;
+        match &value {
+            Value::Enum(v) if value.overridden()?.is_none() => visitor.visit_enum(Variant::of(v)?),
+            _ => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        // ignored values are not read; e.g., fields missing from a mirror.
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'value>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// The value that `value` (transitively) refers to or wraps; e.g., the
+/// referent of a box or reference, or the value of a `ManuallyDrop`.
+fn resolved<'value, 'dwarf, P>(
+    mut value: Value<'value, 'dwarf, P>,
+) -> Result<Value<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    loop {
+        value = match value {
+            Value::Box(v) => v.deref()?,
+            Value::BoxedDyn(v) => v.deref()?,
+            Value::DynRef(v) => v.downcast_value()?,
+            Value::SharedRef(v) => v.deref()?,
+            Value::UniqueRef(v) => v.deref()?,
+            Value::NonZero(v) => v.value()?,
+            Value::ManuallyDrop(v) => v.value()?,
+            value => return Ok(value),
+        }
+    }
+}
+
+/// How a struct or enum is presented to `serde`.
+enum Form<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// A sequence; e.g., the elements of a `Vec`, or of a `Cow<[T]>`.
+    Elements(crate::value::Iter<'value, 'dwarf, P>),
+    /// The value of a container; e.g., of an `Rc`.
+    Entry(Entry<'value, 'dwarf, P>),
+    /// The contents of a `Cow<str>`.
+    Str(&'value str),
+    /// The contents of a `String`.
+    String(String),
+    /// A `Weak`, whose value is not read.
+    Unit,
+    /// A `None`.
+    None,
+    /// The value of a `Some`.
+    Some(Value<'value, 'dwarf, P>),
+    /// The fields of a struct.
+    Fields(Contents<'value, 'dwarf, P>),
+    /// The keys and values of a `HashMap`.
+    Map(Vec<(Entry<'value, 'dwarf, P>, Entry<'value, 'dwarf, P>)>),
+    /// The active variant of an enum.
+    Variant(Variant<'value, 'dwarf, P>),
+}
+
+impl<'value, 'dwarf, P> Form<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The form of the struct `value`; the elements or value of a container,
+    /// or its fields.
+    fn of_struct(value: &crate::value::Struct<'value, 'dwarf, P>) -> Result<Self, crate::Error> {
+        match value.adapted()? {
+            Some(crate::Adapted::Elements(elements)) => return Ok(Form::Elements(elements)),
+            Some(crate::Adapted::Deref(target)) => return Ok(Form::Entry(Entry::Value(target))),
+            None => {}
+        }
+        match crate::rustc::container_of(value.schema())? {
+            Some(Container::Vec) => {
+                let buffer = crate::rustc::vec_buffer(value)?;
+                return Ok(Form::Elements(buffer.elements));
+            }
+            Some(Container::Shared) => {
+                let (_, inner) = crate::rustc::shared_allocation(value)?;
+                let Value::Struct(inner) = inner else {
+                    return Ok(Form::Entry(Entry::Value(inner)));
+                };
+                // the value of the allocation follows its reference counts.
+                let mut value = None;
+                for field in inner.fields()?.iter()? {
+                    value = Some(field?);
+                }
+                return Ok(match value {
+                    Some(value) => Form::Entry(Entry::of(&value)?),
+                    None => Form::Unit,
+                });
+            }
+            Some(Container::String) => {
+                let vec = crate::rustc::string_vec(value)?;
+                return Ok(Form::String(string(&vec)?));
+            }
+            Some(Container::HashMap) => {
+                let table = crate::rustc::hash_table(value)?;
+                let mut entries = vec![];
+                for entry in table.entries {
+                    entries.push(map_entry(&entry)?);
+                }
+                return Ok(Form::Map(entries));
+            }
+            Some(Container::Mutex) => {
+                let data = crate::rustc::mutex_data(value)?;
+                return Ok(Form::Entry(Entry::Value(data)));
+            }
+            Some(Container::Weak) => return Ok(Form::Unit),
+            None => {}
+        }
+        Ok(Form::Fields(Contents(Entry::all(value.fields()?)?)))
+    }
+
+    /// The form of the enum `value`; the contents of an `Option` or `Cow`, or
+    /// its active variant.
+    fn of_enum(value: &crate::value::Enum<'value, 'dwarf, P>) -> Result<Self, crate::Error> {
+        if let Some(cow) = value.as_cow()? {
+            return Ok(match cow {
+                crate::value::CowData::Str(value) => Form::Str(value),
+                crate::value::CowData::Slice(iter) => Form::Elements(iter),
+            });
+        }
+        if let Some(option) = value.as_option()? {
+            return Ok(match option {
+                Some(value) => Form::Some(value),
+                None => Form::None,
+            });
+        }
+        Ok(Form::Variant(Variant::of(value)?))
+    }
+
+    /// Visits this form with `visitor`.
+    fn visit<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        match self {
+            Form::Elements(elements) => visitor.visit_seq(Elements(elements)),
+            Form::Entry(entry) => entry.deserialize_any(visitor),
+            Form::Str(value) => visitor.visit_borrowed_str(value),
+            Form::String(value) => visitor.visit_string(value),
+            Form::Unit => visitor.visit_unit(),
+            Form::None => visitor.visit_none(),
+            Form::Some(value) => visitor.visit_some(value),
+            Form::Fields(contents) => contents.deserialize_any(visitor),
+            Form::Map(entries) => {
+                visitor.visit_map(de::value::MapDeserializer::new(entries.into_iter()))
+            }
+            Form::Variant(variant) if variant.contents.0.is_empty() => {
+                visitor.visit_string(variant.name)
+            }
+            // as `serde` represents enums by default; i.e., as a map from the
+            // name of the variant to its contents.
+            Form::Variant(variant) => {
+                let entry = std::iter::once((variant.name, variant.contents));
+                visitor.visit_map(de::value::MapDeserializer::new(entry))
+            }
+        }
+    }
+}
+
+/// The contents of the `Vec<u8>` of a `String`.
+fn string<P>(vec: &crate::value::Struct<'_, '_, P>) -> Result<String, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut bytes = vec![];
+    for byte in crate::rustc::vec_buffer(vec)?.elements {
+        match byte? {
+            Value::u8(byte) => bytes.push(*byte.value()),
+            byte => return Err(crate::error::type_mismatch("u8", byte.r#type().to_string())),
+        }
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// The key and value of the `(K, V)` `entry` of a `HashMap`.
+fn map_entry<'value, 'dwarf, P>(
+    entry: &Value<'value, 'dwarf, P>,
+) -> Result<(Entry<'value, 'dwarf, P>, Entry<'value, 'dwarf, P>), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mismatch = || crate::error::type_mismatch("(K, V)", entry.r#type().to_string());
+    let Value::Struct(pair) = entry else { return Err(mismatch()) };
+    let mut fields = Entry::all(pair.fields()?)?.into_iter();
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some((_, key)), Some((_, value)), None) => Ok((key, value)),
+        _ => Err(mismatch()),
+    }
+}
+
+/// The elements of an array, slice or container, as a sequence.
+struct Elements<'value, 'dwarf, P>(crate::value::Iter<'value, 'dwarf, P>)
+where
+    P: crate::DebugInfoProvider;
+
+impl<'value, 'dwarf, P> de::SeqAccess<'value> for Elements<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeserializeError>
+    where
+        T: DeserializeSeed<'value>,
+    {
+        match self.0.next() {
+            Some(element) => seed.deserialize(element?).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The contents of a field of a struct or variant.
+#[allow(clippy::large_enum_variant)]
+enum Entry<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The value of a field.
+    Value(Value<'value, 'dwarf, P>),
+    /// The bits of a bitfield, interpreted as its type.
+    Bits(Bits),
+    /// A redacted field, which is not read.
+    Redacted,
+}
+
+impl<'value, 'dwarf, P> Entry<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The contents of `field`.
+    fn of(field: &crate::value::Field<'value, 'dwarf, P>) -> Result<Self, crate::Error> {
+        if field.is_redacted()? {
+            return Ok(Entry::Redacted);
+        }
+        Ok(match field.typed_bits()? {
+            Some(bits) => Entry::Bits(bits),
+            None => Entry::Value(field.value()?),
+        })
+    }
+
+    /// The names and contents of `fields`.
+    fn all(
+        mut fields: crate::value::Fields<'value, 'dwarf, P>,
+    ) -> Result<Vec<(String, Self)>, crate::Error> {
+        let mut entries = vec![];
+        for field in fields.iter()? {
+            let field = field?;
+            let name = field.schema().name()?.to_string_lossy()?.into_owned();
+            entries.push((name, Self::of(&field)?));
+        }
+        Ok(entries)
+    }
+}
+
+impl<'value, 'dwarf, P> Deserializer<'value> for Entry<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        match self {
+            Entry::Value(value) => value.deserialize_any(visitor),
+            Entry::Bits(Bits::Bool(bits)) => visitor.visit_bool(bits),
+            Entry::Bits(Bits::Signed(bits)) => visitor.visit_i64(bits),
+            Entry::Bits(Bits::Unsigned(bits)) => visitor.visit_u64(bits),
+            Entry::Redacted => visitor.visit_borrowed_str(crate::redact::REDACTED),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        match self {
+            Entry::Value(value) => value.deserialize_option(visitor),
+            entry => visitor.visit_some(entry),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        match self {
+            Entry::Value(value) => value.deserialize_newtype_struct(name, visitor),
+            entry => visitor.visit_newtype_struct(entry),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        match self {
+            Entry::Value(value) => value.deserialize_enum(name, variants, visitor),
+            entry => entry.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'value>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// The fields of a struct or variant; deserialized from a sequence if they
+/// are the fields of a tuple (named `__0`, `__1`, etc.), from their sole
+/// value if they are the field of a newtype, and otherwise from a map.
+struct Contents<'value, 'dwarf, P>(Vec<(String, Entry<'value, 'dwarf, P>)>)
+where
+    P: crate::DebugInfoProvider;
+
+impl<'value, 'dwarf, P> Deserializer<'value> for Contents<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        let Contents(mut entries) = self;
+        if entries.is_empty() {
+            return visitor.visit_unit();
+        }
+        if entries.len() == 1 && entries[0].0 == "__0" {
+            let (_, entry) = entries.remove(0);
+            return entry.deserialize_any(visitor);
+        }
+        if entries.iter().all(|(name, _)| name.starts_with("__")) {
+            let elements = entries.into_iter().map(|(_, entry)| entry);
+            return visitor.visit_seq(de::value::SeqDeserializer::new(elements));
+        }
+        visitor.visit_map(de::value::MapDeserializer::new(entries.into_iter()))
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'value>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'value, 'dwarf, P> IntoDeserializer<'value, DeserializeError> for Entry<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'value, 'dwarf, P> IntoDeserializer<'value, DeserializeError> for Contents<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// The active variant of an enum, and its fields.
+struct Variant<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    name: String,
+    contents: Contents<'value, 'dwarf, P>,
+}
+
+impl<'value, 'dwarf, P> Variant<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The active variant of `value`.
+    fn of(value: &crate::value::Enum<'value, 'dwarf, P>) -> Result<Self, crate::Error> {
+        let variant = value.variant()?;
+        Ok(Self {
+            name: variant.name()?.to_string_lossy()?.into_owned(),
+            contents: Contents(Entry::all(variant.fields()?)?),
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> de::EnumAccess<'value> for Variant<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self), DeserializeError>
+    where
+        T: DeserializeSeed<'value>,
+    {
+        let name: de::value::StrDeserializer<'_, DeserializeError> =
+            self.name.as_str().into_deserializer();
+        let variant = seed.deserialize(name)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'value, 'dwarf, P> de::VariantAccess<'value> for Variant<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), DeserializeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value, DeserializeError>
+    where
+        T: DeserializeSeed<'value>,
+    {
+        if self.contents.0.len() != 1 {
+            return Err(anyhow!("`{}` is not a newtype variant", self.name).into());
+        }
+        let (_, entry) = self.contents.0.remove(0);
+        seed.deserialize(entry)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        let elements = self.contents.0.into_iter().map(|(_, entry)| entry);
+        visitor.visit_seq(de::value::SeqDeserializer::new(elements))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'value>,
+    {
+        visitor.visit_map(de::value::MapDeserializer::new(self.contents.0.into_iter()))
+    }
+}
+
+/// Serializes reflected values with [`serde`]; e.g., with
+/// `serde_json::to_string(&value)`. With the `erased-serde` feature, values
+/// are also `erased_serde::Serialize`, and so can be recorded by frameworks
+/// that accept type-erased values.
+///
+/// Values are serialized as they are [deserialized][Deserializer]; enums are
+/// serialized as the names of their variants, if they are unit variants, and
+/// otherwise as maps from the names of their variants to their contents.
+///
+/// Requires the `serde` feature.
+impl<'value, 'dwarf, P> Serialize for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use crate::schema::Type;
+        if let Some(overridden) = self.overridden().map_err(S::Error::custom)? {
+            return serializer.serialize_str(&overridden.render().map_err(S::Error::custom)?);
+        }
+        match self {
+            Value::bool(v) => serializer.serialize_bool(*v.value()),
+            Value::char(v) => serializer.serialize_char(*v.value()),
+            Value::f32(v) => serializer.serialize_f32(*v.value()),
+            Value::f64(v) => serializer.serialize_f64(*v.value()),
+            Value::i8(v) => serializer.serialize_i8(*v.value()),
+            Value::i16(v) => serializer.serialize_i16(*v.value()),
+            Value::i32(v) => serializer.serialize_i32(*v.value()),
+            Value::i64(v) => serializer.serialize_i64(*v.value()),
+            Value::i128(v) => serializer.serialize_i128(*v.value()),
+            Value::isize(v) => serializer.serialize_i64(*v.value() as i64),
+            Value::u8(v) => serializer.serialize_u8(*v.value()),
+            Value::u16(v) => serializer.serialize_u16(*v.value()),
+            Value::u32(v) => serializer.serialize_u32(*v.value()),
+            Value::u64(v) => serializer.serialize_u64(*v.value()),
+            Value::u128(v) => serializer.serialize_u128(*v.value()),
+            Value::usize(v) => serializer.serialize_u64(*v.value() as u64),
+            Value::unit(_) => serializer.serialize_unit(),
+            Value::str(v) => serializer.serialize_str(v.value().map_err(S::Error::custom)?),
+            Value::Array(v) => serialize_elements(v.iter().map_err(S::Error::custom)?, serializer),
+            Value::Slice(v) => serialize_elements(v.iter().map_err(S::Error::custom)?, serializer),
+            Value::BoxedSlice(v) => {
+                serialize_elements(v.iter().map_err(S::Error::custom)?, serializer)
+            }
+            Value::Vector(v) => serialize_elements(v.iter().map_err(S::Error::custom)?, serializer),
+            Value::Box(v) => v.deref().map_err(S::Error::custom)?.serialize(serializer),
+            Value::BoxedDyn(v) => v.deref().map_err(S::Error::custom)?.serialize(serializer),
+            Value::DynRef(v) => v
+                .downcast_value()
+                .map_err(S::Error::custom)?
+                .serialize(serializer),
+            Value::SharedRef(v) => v.deref().map_err(S::Error::custom)?.serialize(serializer),
+            Value::UniqueRef(v) => v.deref().map_err(S::Error::custom)?.serialize(serializer),
+            Value::NonNull(v) => {
+                serializer.serialize_u64(v.addr().map_err(S::Error::custom)? as u64)
+            }
+            Value::ConstPtr(v) => serializer.serialize_u64(v.addr() as u64),
+            Value::MutPtr(v) => serializer.serialize_u64(v.addr() as u64),
+            Value::Function(v) => serializer.serialize_u64(v.addr() as u64),
+            Value::NonZero(v) => v.value().map_err(S::Error::custom)?.serialize(serializer),
+            Value::ManuallyDrop(v) => v.value().map_err(S::Error::custom)?.serialize(serializer),
+            Value::MaybeUninit(_) => serializer.serialize_unit(),
+            Value::Atomic(v) => {
+                let bits = v.load_bits().map_err(S::Error::custom)?;
+                if v.schema().is_bool().map_err(S::Error::custom)? {
+                    return serializer.serialize_bool(bits != 0);
+                }
+                match v.schema().value_type().map_err(S::Error::custom)? {
+                    Type::i8(_) => serializer.serialize_i8(bits as i8),
+                    Type::i16(_) => serializer.serialize_i16(bits as i16),
+                    Type::i32(_) => serializer.serialize_i32(bits as i32),
+                    Type::i64(_) | Type::isize(_) => serializer.serialize_i64(bits as i64),
+                    _ => serializer.serialize_u64(bits),
+                }
+            }
+            Value::Struct(v) => Form::of_struct(v)
+                .map_err(S::Error::custom)?
+                .serialize(serializer),
+            Value::Enum(v) => Form::of_enum(v)
+                .map_err(S::Error::custom)?
+                .serialize(serializer),
+        }
+    }
+}
+
+/// Serializes the elements produced by `elements` as a sequence.
+fn serialize_elements<P, S>(
+    elements: crate::value::Iter<'_, '_, P>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    P: crate::DebugInfoProvider,
+    S: Serializer,
+{
+    let (lower, upper) = elements.size_hint();
+    let mut seq = serializer.serialize_seq(upper.filter(|&upper| upper == lower))?;
+    for element in elements {
+        seq.serialize_element(&element.map_err(S::Error::custom)?)?;
+    }
+    seq.end()
+}
+
+impl<'value, 'dwarf, P> Serialize for Form<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Form::Elements(elements) => serialize_elements(elements.clone(), serializer),
+            Form::Entry(entry) => entry.serialize(serializer),
+            Form::Str(value) => serializer.serialize_str(value),
+            Form::String(value) => serializer.serialize_str(value),
+            Form::Unit => serializer.serialize_unit(),
+            Form::None => serializer.serialize_none(),
+            Form::Some(value) => serializer.serialize_some(value),
+            Form::Fields(contents) => contents.serialize(serializer),
+            Form::Map(entries) => {
+                serializer.collect_map(entries.iter().map(|(key, value)| (key, value)))
+            }
+            Form::Variant(variant) if variant.contents.0.is_empty() => {
+                serializer.serialize_str(&variant.name)
+            }
+            Form::Variant(variant) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&variant.name, &variant.contents)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> Serialize for Entry<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Entry::Value(value) => value.serialize(serializer),
+            Entry::Bits(Bits::Bool(bits)) => serializer.serialize_bool(*bits),
+            Entry::Bits(Bits::Signed(bits)) => serializer.serialize_i64(*bits),
+            Entry::Bits(Bits::Unsigned(bits)) => serializer.serialize_u64(*bits),
+            Entry::Redacted => serializer.serialize_str(crate::redact::REDACTED),
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> Serialize for Contents<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Contents(entries) = self;
+        if entries.is_empty() {
+            return serializer.serialize_unit();
+        }
+        if entries.len() == 1 && entries[0].0 == "__0" {
+            return entries[0].1.serialize(serializer);
+        }
+        if entries.iter().all(|(name, _)| name.starts_with("__")) {
+            return serializer.collect_seq(entries.iter().map(|(_, entry)| entry));
+        }
+        serializer.collect_map(entries.iter().map(|(name, entry)| (name, entry)))
+    }
+}
@@ -0,0 +1,290 @@
+//! Owned snapshots of reflected values.
+
+use std::fmt;
+
+/// An owned, `'static` snapshot of a reflected [`Value`][crate::Value];
+/// produced by [`Value::to_owned_snapshot`][crate::Value::to_owned_snapshot].
+///
+/// Unlike a `Value`, a snapshot does not borrow the reflected data or its
+/// debuginfo, so it can be stored, sent to another thread, or inspected after
+/// the original data is gone. It is displayed like the `Value` it was taken of.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Snapshot {
+    /// A [`prim@bool`].
+    bool(bool),
+    /// A [`prim@char`].
+    char(char),
+    /// A [`prim@f32`].
+    f32(f32),
+    /// A [`prim@f64`].
+    f64(f64),
+    /// An [`prim@i8`].
+    i8(i8),
+    /// An [`prim@i16`].
+    i16(i16),
+    /// An [`prim@i32`].
+    i32(i32),
+    /// An [`prim@i64`].
+    i64(i64),
+    /// An [`prim@i128`].
+    i128(i128),
+    /// An [`prim@isize`].
+    isize(isize),
+    /// A [`prim@u8`].
+    u8(u8),
+    /// A [`prim@u16`].
+    u16(u16),
+    /// A [`prim@u32`].
+    u32(u32),
+    /// A [`prim@u64`].
+    u64(u64),
+    /// A [`prim@u128`].
+    u128(u128),
+    /// A [`prim@usize`].
+    usize(usize),
+    /// A [`()`][prim@unit].
+    unit,
+    /// The contents of a [`str`][prim@str].
+    str(String),
+    /// The elements of an [array][prim@array].
+    Array(Vec<Snapshot>),
+    /// The elements of a [slice][prim@slice].
+    Slice(Vec<Snapshot>),
+    /// The referent of a shared reference.
+    Ref(Box<Snapshot>),
+    /// The referent of a unique reference.
+    RefMut(Box<Snapshot>),
+    /// The contents of a [`Box`].
+    Box(Box<Snapshot>),
+    /// A struct.
+    Struct {
+        /// The name of the struct's type.
+        name: String,
+        /// The names and values of the struct's fields.
+        fields: Vec<(String, Snapshot)>,
+    },
+    /// An enum.
+    Enum {
+        /// The name of the enum's type.
+        name: String,
+        /// The name of the enum's active variant.
+        variant: String,
+        /// The names and values of the active variant's fields.
+        fields: Vec<(String, Snapshot)>,
+    },
+    /// A value that is not snapshotted structurally (e.g., a raw pointer,
+    /// which is not followed), as it was displayed.
+    Opaque(String),
+}
+
+impl<'value, 'dwarf, P> crate::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Copies this value, and the values reachable from it by references and
+    /// boxes, into an owned [`Snapshot`].
+    ///
+    /// Raw pointers (including [`NonNull`][std::ptr::NonNull]) are not
+    /// followed, since their referents may be invalid; they are snapshotted as
+    /// their addresses.
+    pub fn to_owned_snapshot(&self) -> Result<Snapshot, crate::Error> {
+        use crate::Value;
+        if let Some(overridden) = self.overridden()? {
+            return Ok(Snapshot::Opaque(overridden.render()?));
+        }
+        Ok(match self {
+            Value::bool(v) => Snapshot::bool(*v.value()),
+            Value::char(v) => Snapshot::char(*v.value()),
+            Value::f32(v) => Snapshot::f32(*v.value()),
+            Value::f64(v) => Snapshot::f64(*v.value()),
+            Value::i8(v) => Snapshot::i8(*v.value()),
+            Value::i16(v) => Snapshot::i16(*v.value()),
+            Value::i32(v) => Snapshot::i32(*v.value()),
+            Value::i64(v) => Snapshot::i64(*v.value()),
+            Value::i128(v) => Snapshot::i128(*v.value()),
+            Value::isize(v) => Snapshot::isize(*v.value()),
+            Value::u8(v) => Snapshot::u8(*v.value()),
+            Value::u16(v) => Snapshot::u16(*v.value()),
+            Value::u32(v) => Snapshot::u32(*v.value()),
+            Value::u64(v) => Snapshot::u64(*v.value()),
+            Value::u128(v) => Snapshot::u128(*v.value()),
+            Value::usize(v) => Snapshot::usize(*v.value()),
+            Value::unit(_) => Snapshot::unit,
+            Value::str(v) => Snapshot::str(v.to_string_lossy().into_owned()),
+            Value::Array(v) => Snapshot::Array(elements(v.iter()?)?),
+            Value::Slice(v) => {
+                let elements = Snapshot::Slice(elements(v.iter()?)?);
+                Snapshot::Ref(Box::new(elements))
+            }
+            Value::BoxedSlice(v) => {
+                let elements = Snapshot::Slice(elements(v.iter()?)?);
+                Snapshot::Box(Box::new(elements))
+            }
+            Value::Box(v) => Snapshot::Box(Box::new(v.deref()?.to_owned_snapshot()?)),
+            Value::BoxedDyn(v) => Snapshot::Box(Box::new(v.deref()?.to_owned_snapshot()?)),
+            Value::DynRef(v) => {
+                let referent = Box::new(v.downcast_value()?.to_owned_snapshot()?);
+                if v.schema().is_mut()? {
+                    Snapshot::RefMut(referent)
+                } else {
+                    Snapshot::Ref(referent)
+                }
+            }
+            Value::SharedRef(v) => match v.deref()? {
+                // function pointers are displayed as their signature and address
+                referent @ Value::Function(_) => referent.to_owned_snapshot()?,
+                referent => Snapshot::Ref(Box::new(referent.to_owned_snapshot()?)),
+            },
+            Value::UniqueRef(v) => Snapshot::RefMut(Box::new(v.deref()?.to_owned_snapshot()?)),
+            Value::NonZero(v) => v.value()?.to_owned_snapshot()?,
+            Value::ManuallyDrop(v) => v.value()?.to_owned_snapshot()?,
+            Value::Struct(v) => Snapshot::Struct {
+                name: v.schema().display_name()?,
+                fields: fields(v.fields()?)?,
+            },
+            Value::Enum(v) => match v.as_cow()? {
+                // as when displayed, a `Cow` is snapshotted as its contents.
+                Some(crate::value::CowData::Str(value)) => Snapshot::str(value.to_owned()),
+                Some(crate::value::CowData::Slice(iter)) => Snapshot::Slice(elements(iter)?),
+                None => {
+                    let variant = v.variant()?;
+                    Snapshot::Enum {
+                        name: v.schema().display_name()?,
+                        variant: variant.name()?.to_string_lossy()?.into_owned(),
+                        fields: fields(variant.fields()?)?,
+                    }
+                }
+            },
+            value => Snapshot::Opaque(value.to_string()),
+        })
+    }
+}
+
+/// Snapshots the elements produced by `iter`.
+fn elements<P>(iter: crate::value::Iter<'_, '_, P>) -> Result<Vec<Snapshot>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    iter.map(|elt| elt?.to_owned_snapshot()).collect()
+}
+
+/// Snapshots the names and values of `fields`.
+fn fields<P>(
+    mut fields: crate::value::Fields<'_, '_, P>,
+) -> Result<Vec<(String, Snapshot)>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut snapshots = vec![];
+    for field in fields.iter()? {
+        let field = field?;
+        let name = field.schema().name()?.to_string_lossy()?.into_owned();
+        let snapshot = if field.schema().is_bitfield()? || field.is_redacted()? {
+            Snapshot::Opaque(field.display_value().to_string())
+        } else {
+            field.value()?.to_owned_snapshot()?
+        };
+        snapshots.push((name, snapshot));
+    }
+    Ok(snapshots)
+}
+
+/// Displays `fields` as those of the struct or variant `name`.
+fn fmt_variant(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    fields: &[(String, Snapshot)],
+) -> fmt::Result {
+    if fields.is_empty() {
+        return f.write_str(name);
+    }
+    // the fields of tuple variants are named `__0`, `__1`, etc.
+    if fields
+        .iter()
+        .all(|(field_name, _)| field_name.starts_with("__"))
+    {
+        let mut debug_tuple = f.debug_tuple(name);
+        for (_, value) in fields {
+            debug_tuple.field(&crate::DebugDisplay(value));
+        }
+        return debug_tuple.finish();
+    }
+    let mut debug_struct = f.debug_struct(name);
+    for (field_name, value) in fields {
+        debug_struct.field(field_name, &crate::DebugDisplay(value));
+    }
+    debug_struct.finish()
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::bool(v) => fmt::Debug::fmt(v, f),
+            Self::char(v) => fmt::Debug::fmt(v, f),
+            Self::f32(v) => fmt::Debug::fmt(v, f),
+            Self::f64(v) => fmt::Debug::fmt(v, f),
+            Self::i8(v) => fmt::Debug::fmt(v, f),
+            Self::i16(v) => fmt::Debug::fmt(v, f),
+            Self::i32(v) => fmt::Debug::fmt(v, f),
+            Self::i64(v) => fmt::Debug::fmt(v, f),
+            Self::i128(v) => fmt::Debug::fmt(v, f),
+            Self::isize(v) => fmt::Debug::fmt(v, f),
+            Self::u8(v) => fmt::Debug::fmt(v, f),
+            Self::u16(v) => fmt::Debug::fmt(v, f),
+            Self::u32(v) => fmt::Debug::fmt(v, f),
+            Self::u64(v) => fmt::Debug::fmt(v, f),
+            Self::u128(v) => fmt::Debug::fmt(v, f),
+            Self::usize(v) => fmt::Debug::fmt(v, f),
+            Self::unit => f.write_str("()"),
+            Self::str(v) => fmt::Debug::fmt(v, f),
+            Self::Array(elements) | Self::Slice(elements) => f
+                .debug_list()
+                .entries(elements.iter().map(crate::DebugDisplay))
+                .finish(),
+            // referents are formatted with `f`, so that its flags (e.g.,
+            // `{:#}`) apply to them.
+            Self::Ref(referent) => {
+                f.write_str("&")?;
+                referent.fmt(f)
+            }
+            Self::RefMut(referent) => {
+                f.write_str("&mut ")?;
+                referent.fmt(f)
+            }
+            Self::Box(referent) => {
+                f.write_str("box ")?;
+                referent.fmt(f)
+            }
+            Self::Struct { name, fields } => {
+                if crate::format::transparent_newtypes() && !name.starts_with('{') {
+                    if let [(field_name, inner)] = &fields[..] {
+                        if field_name == "__0" {
+                            return inner.fmt(f);
+                        }
+                    }
+                }
+                let name = if name.starts_with('(') {
+                    ""
+                } else {
+                    crate::format::debug_type_name(name)
+                };
+                fmt_variant(f, name, fields)
+            }
+            Self::Enum {
+                name,
+                variant,
+                fields,
+            } => {
+                // as with `derive(Debug)`, variants are unqualified, except
+                // for those of coroutines.
+                if name.starts_with('{') {
+                    write!(f, "{name}::")?;
+                }
+                fmt_variant(f, variant, fields)
+            }
+            Self::Opaque(v) => f.write_str(v),
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Recording of reflected values as structured data with [`valuable`]; e.g.,
+//! as the fields of `tracing` events.
+
+use ::valuable::{
+    EnumDef, Enumerable, Fields, Listable, NamedField, NamedValues, StructDef, Structable,
+    Valuable, Variant, VariantDef, Visit,
+};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+
+use crate::Value;
+
+/// The names of the reflected types and variants recorded so far, which
+/// `valuable`'s definitions of structs and enums borrow.
+static NAMES: Lazy<DashMap<String, &'static str>> = Lazy::new(DashMap::new);
+
+/// A `'static` copy of `name`; each distinct name is leaked only once.
+fn intern(name: &str) -> &'static str {
+    if let Some(interned) = NAMES.get(name) {
+        return *interned;
+    }
+    *NAMES
+        .entry(name.to_owned())
+        .or_insert_with(|| Box::leak(name.to_owned().into_boxed_str()))
+}
+
+/// The value that `value` transparently wraps (e.g., the referent of a box or
+/// reference, or the value of a `Some`); `None`, if it wraps no value.
+fn inner<'value, 'dwarf, P>(
+    value: &Value<'value, 'dwarf, P>,
+) -> Result<Option<Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    Ok(Some(match value {
+        Value::Box(v) => v.deref()?,
+        Value::BoxedDyn(v) => v.deref()?,
+        Value::DynRef(v) => v.downcast_value()?,
+        Value::SharedRef(v) => v.deref()?,
+        Value::UniqueRef(v) => v.deref()?,
+        Value::NonZero(v) => v.value()?,
+        Value::ManuallyDrop(v) => v.value()?,
+        Value::Enum(v) => match v.as_option()? {
+            Some(Some(value)) => value,
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    }))
+}
+
+/// Applies `f` to the value that `value` (transitively) wraps, or to `value`
+/// itself, if it wraps no value.
+fn resolved<'value, 'dwarf, P, T>(
+    value: &Value<'value, 'dwarf, P>,
+    f: impl FnOnce(&Value<'value, 'dwarf, P>) -> Result<T, crate::Error>,
+) -> Result<T, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Some(mut resolved) = inner(value)? else {
+        return f(value);
+    };
+    while let Some(next) = inner(&resolved)? {
+        resolved = next;
+    }
+    f(&resolved)
+}
+
+/// Records `resolved`, which `this` (transitively) wraps, as a `valuable`
+/// value; values that are not primitive are recorded by `this`.
+fn as_value<'a, 'value, 'dwarf, P>(
+    this: &'a Value<'value, 'dwarf, P>,
+    resolved: &Value<'value, 'dwarf, P>,
+) -> Result<::valuable::Value<'a>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    use ::valuable::Value as V;
+    Ok(match resolved {
+        Value::bool(v) => V::Bool(*v.value()),
+        Value::char(v) => V::Char(*v.value()),
+        Value::f32(v) => V::F32(*v.value()),
+        Value::f64(v) => V::F64(*v.value()),
+        Value::i8(v) => V::I8(*v.value()),
+        Value::i16(v) => V::I16(*v.value()),
+        Value::i32(v) => V::I32(*v.value()),
+        Value::i64(v) => V::I64(*v.value()),
+        Value::i128(v) => V::I128(*v.value()),
+        Value::isize(v) => V::Isize(*v.value()),
+        Value::u8(v) => V::U8(*v.value()),
+        Value::u16(v) => V::U16(*v.value()),
+        Value::u32(v) => V::U32(*v.value()),
+        Value::u64(v) => V::U64(*v.value()),
+        Value::u128(v) => V::U128(*v.value()),
+        Value::usize(v) => V::Usize(*v.value()),
+        Value::str(v) => V::String(v.value()?),
+        Value::Atomic(v) => V::U64(v.load_bits()?),
+        Value::NonNull(v) => V::Usize(v.addr()?),
+        Value::ConstPtr(v) => V::Usize(v.addr()),
+        Value::MutPtr(v) => V::Usize(v.addr()),
+        Value::Function(v) => V::Usize(v.addr()),
+        Value::Array(_) | Value::Slice(_) | Value::BoxedSlice(_) | Value::Vector(_) => {
+            V::Listable(this)
+        }
+        Value::Struct(_) => V::Structable(this),
+        Value::Enum(v) if v.as_option()?.is_some() => V::Unit,
+        Value::Enum(_) => V::Enumerable(this),
+        _ => V::Unit,
+    })
+}
+
+/// Visits the fields of a struct or variant with `visit`; as unnamed fields,
+/// if they are the fields of a tuple (named `__0`, `__1`, etc.) or a unit.
+fn visit_fields<P>(
+    mut fields: crate::value::Fields<'_, '_, P>,
+    visit: &mut dyn Visit,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut names = vec![];
+    let mut values = vec![];
+    for field in fields.iter()? {
+        let field = field?;
+        names.push(field.schema().name()?.to_string_lossy()?.into_owned());
+        values.push(match field.typed_bits()? {
+            Some(bits) => Err(bits),
+            None => Ok(field.value()?),
+        });
+    }
+    let values: Vec<_> = values
+        .iter()
+        .map(|value| match value {
+            Ok(value) => value.as_value(),
+            Err(crate::value::field::Bits::Bool(bits)) => ::valuable::Value::Bool(*bits),
+            Err(crate::value::field::Bits::Signed(bits)) => ::valuable::Value::I64(*bits),
+            Err(crate::value::field::Bits::Unsigned(bits)) => ::valuable::Value::U64(*bits),
+        })
+        .collect();
+    if is_tuple(&names) {
+        visit.visit_unnamed_fields(&values);
+    } else {
+        let names: Vec<_> = names.iter().map(|name| NamedField::new(name)).collect();
+        visit.visit_named_fields(&NamedValues::new(&names, &values));
+    }
+    Ok(())
+}
+
+/// The shape of the fields of a struct or variant.
+fn fields_of<P>(
+    mut fields: crate::value::Fields<'_, '_, P>,
+) -> Result<Fields<'static>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut names = vec![];
+    for field in fields.iter()? {
+        names.push(field?.schema().name()?.to_string_lossy()?.into_owned());
+    }
+    Ok(match is_tuple(&names) {
+        true => Fields::Unnamed(names.len()),
+        false => Fields::Named(&[]),
+    })
+}
+
+/// Whether `names` are the names of the fields of a tuple, or of a unit.
+fn is_tuple(names: &[String]) -> bool {
+    names.iter().all(|name| name.starts_with("__"))
+}
+
+/// The elements of `value`; `None`, if it is not a sequence.
+fn elements<'value, 'dwarf, P>(
+    value: &Value<'value, 'dwarf, P>,
+) -> Result<Option<crate::value::Iter<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    Ok(Some(match value {
+        Value::Array(v) => v.iter()?,
+        Value::Slice(v) => v.iter()?,
+        Value::BoxedSlice(v) => v.iter()?,
+        Value::Vector(v) => v.iter()?,
+        _ => return Ok(None),
+    }))
+}
+
+/// Records reflected values with [`valuable`]; e.g., so that values of any
+/// type can be recorded as structured fields of `tracing` events, with
+/// `tracing::info!(state = state.as_value())`.
+///
+/// Boxes, references and `Some`s are recorded as the values they wrap, `None`s
+/// as `()`, tuples, tuple structs and unit structs as unnamed fields, and
+/// structs and enums as dynamically-defined [`Structable`]s and
+/// [`Enumerable`]s. Raw pointers are recorded as their addresses, and
+/// bitfields as their bits. Values that cannot be read (e.g., `MaybeUninit`s)
+/// are recorded as `()`.
+impl<'value, 'dwarf, P> Valuable for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn as_value(&self) -> ::valuable::Value<'_> {
+        resolved(self, |resolved| as_value(self, resolved)).unwrap_or(::valuable::Value::Unit)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let _ = resolved(self, |resolved| match resolved {
+            Value::Struct(v) => visit_fields(v.fields()?, visit),
+            Value::Enum(v) => visit_fields(v.variant()?.fields()?, visit),
+            resolved => match elements(resolved)? {
+                Some(elements) => {
+                    for element in elements {
+                        visit.visit_value(element?.as_value());
+                    }
+                    Ok(())
+                }
+                None => {
+                    visit.visit_value(self.as_value());
+                    Ok(())
+                }
+            },
+        });
+    }
+}
+
+impl<'value, 'dwarf, P> Structable for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn definition(&self) -> StructDef<'_> {
+        let name = resolved(self, |resolved| {
+            let name = intern(&crate::format::type_name(&resolved.r#type())?);
+            let fields = match resolved {
+                Value::Struct(v) => fields_of(v.fields()?)?,
+                _ => Fields::Named(&[]),
+            };
+            Ok((name, fields))
+        });
+        let (name, fields) = name.unwrap_or(("?", Fields::Named(&[])));
+        StructDef::new_dynamic(name, fields)
+    }
+}
+
+impl<'value, 'dwarf, P> Enumerable for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn definition(&self) -> EnumDef<'_> {
+        let name = resolved(self, |resolved| {
+            crate::format::type_name(&resolved.r#type()).map(|name| intern(&name))
+        });
+        EnumDef::new_dynamic(name.unwrap_or("?"), &[])
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        let variant = resolved(self, |resolved| {
+            let Value::Enum(v) = resolved else {
+                return Ok(("?", Fields::Unnamed(0)));
+            };
+            let variant = v.variant()?;
+            let name = intern(&variant.name()?.to_string_lossy()?);
+            Ok((name, fields_of(variant.fields()?)?))
+        });
+        let (name, fields) = variant.unwrap_or(("?", Fields::Unnamed(0)));
+        Variant::Dynamic(VariantDef::new(name, fields))
+    }
+}
+
+impl<'value, 'dwarf, P> Listable for Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size_hint = resolved(self, |resolved| {
+            Ok(elements(resolved)?.map(|elements| elements.size_hint()))
+        });
+        size_hint.ok().flatten().unwrap_or((0, Some(0)))
+    }
+}
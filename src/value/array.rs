@@ -30,7 +30,9 @@ where
             .checked_mul(elt_size)
             .ok_or_else(crate::error::arithmetic_overflow)?;
         let bytes = usize::try_from(bytes)?;
-        let value = &value[..bytes];
+        let value = value
+            .get(..bytes)
+            .ok_or_else(|| crate::error::size_mismatch(bytes, value.len()))?;
         Ok(Array {
             value,
             schema: self,
@@ -48,6 +50,11 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
     /// An iterator over values in the array.
     pub fn iter(&self) -> Result<super::Iter<'value, 'dwarf, P>, crate::Error> {
         let elt_type = self.schema.elt_type()?;
@@ -57,6 +64,20 @@ where
         let length: usize = length.try_into()?;
         Ok(unsafe { super::Iter::new(self.value, elt_size, elt_type, length, self.provider) })
     }
+
+    /// Whether this array is empty.
+    pub fn is_empty(&self) -> Result<bool, crate::Error> {
+        Ok(self.schema.len()? == 0)
+    }
+
+    /// The value at `index` in this array, if it is in bounds; its location is
+    /// computed directly from the size of the array's elements.
+    pub fn get(
+        &self,
+        index: usize,
+    ) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error> {
+        self.iter()?.nth(index).transpose()
+    }
 }
 
 impl<'value, 'dwarf, P> fmt::Debug for Array<'value, 'dwarf, P>
@@ -77,10 +98,31 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut debug_list = f.debug_list();
-        for maybe_elt in self.iter().map_err(crate::fmt_err)? {
-            let elt = maybe_elt.map_err(crate::fmt_err)?;
-            debug_list.entry(&crate::DebugDisplay(elt));
-        }
+        crate::format::entries(&mut debug_list, self.iter().map_err(crate::fmt_err)?)?;
         debug_list.finish()
     }
 }
+
+impl<'value, 'dwarf, P> IntoIterator for Array<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<super::Value<'value, 'dwarf, P>, crate::Error>;
+    type IntoIter = super::IntoIter<'value, 'dwarf, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        super::IntoIter::new(self.iter())
+    }
+}
+
+impl<'a, 'value, 'dwarf, P> IntoIterator for &'a Array<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<super::Value<'value, 'dwarf, P>, crate::Error>;
+    type IntoIter = super::IntoIter<'value, 'dwarf, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        super::IntoIter::new(self.iter())
+    }
+}
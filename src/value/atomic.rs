@@ -0,0 +1,193 @@
+use std::{
+    fmt,
+    sync::atomic::{self, Ordering},
+};
+
+/// A reflected atomic value (e.g., [`AtomicUsize`][std::sync::atomic::AtomicUsize]).
+pub struct Atomic<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::Atomic<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::Atomic<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<Atomic<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(Atomic {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> Atomic<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::Atomic<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This atomic, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// Loads the raw bits of this atomic, with [`Relaxed`][Ordering::Relaxed]
+    /// ordering, zero-extended to a `u64`.
+    pub fn load_bits(&self) -> Result<u64, crate::Error> {
+        let ptr = self.value.as_ptr();
+        if ptr as usize % self.value.len().max(1) != 0 {
+            bail!("atomic is misaligned")
+        }
+        // SAFETY: `self.value` is a properly aligned `Atomic*` of the given size.
+        Ok(unsafe {
+            match self.value.len() {
+                1 => (*(ptr as *const atomic::AtomicU8)).load(Ordering::Relaxed) as u64,
+                2 => (*(ptr as *const atomic::AtomicU16)).load(Ordering::Relaxed) as u64,
+                4 => (*(ptr as *const atomic::AtomicU32)).load(Ordering::Relaxed) as u64,
+                8 => (*(ptr as *const atomic::AtomicU64)).load(Ordering::Relaxed),
+                size => bail!("unsupported atomic size: {size} bytes"),
+            }
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for Atomic<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::Atomic");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for Atomic<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::schema::Type;
+        let bits = self.load_bits().map_err(crate::fmt_err)?;
+        if self.schema.is_bool().map_err(crate::fmt_err)? {
+            return (bits != 0).fmt(f);
+        }
+        match self.schema.value_type().map_err(crate::fmt_err)? {
+            Type::i8(_) => (bits as i8).fmt(f),
+            Type::i16(_) => (bits as i16).fmt(f),
+            Type::i32(_) => (bits as i32).fmt(f),
+            Type::i64(_) => (bits as i64).fmt(f),
+            Type::isize(_) => (bits as isize).fmt(f),
+            Type::ConstPtr(r#type) => {
+                write!(f, "{} as {type}", crate::format::Address(bits as usize))
+            }
+            Type::MutPtr(r#type) => {
+                write!(f, "{} as {type}", crate::format::Address(bits as usize))
+            }
+            _ => bits.fmt(f),
+        }
+    }
+}
+
+macro_rules! generate_atomic_conversions {
+    ($($t:ident,)*) => {
+        $(
+            impl<'a, 'value, 'dwarf, P> TryFrom<&'a Atomic<'value, 'dwarf, P>> for std::primitive::$t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::Error;
+
+                fn try_from(value: &'a Atomic<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    let is_bool = value.schema.is_bool()?;
+                    if let (crate::schema::Type::$t(_), false) = (value.schema.value_type()?, is_bool) {
+                        Ok(value.load_bits()? as _)
+                    } else {
+                        Err(crate::DowncastErr::new::<&'a Atomic<'value, 'dwarf, P>, Self>())?
+                    }
+                }
+            }
+
+            impl<'value, 'dwarf, P> TryFrom<Atomic<'value, 'dwarf, P>> for std::primitive::$t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::Error;
+
+                fn try_from(value: Atomic<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    Self::try_from(&value)
+                }
+            }
+        )*
+    };
+}
+
+generate_atomic_conversions! {
+    i8,
+    i16,
+    i32,
+    i64,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    usize,
+}
+
+impl<'a, 'value, 'dwarf, P> TryFrom<&'a Atomic<'value, 'dwarf, P>> for bool
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: &'a Atomic<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+        if value.schema.is_bool()? {
+            Ok(value.load_bits()? != 0)
+        } else {
+            Err(crate::DowncastErr::new::<&'a Atomic<'value, 'dwarf, P>, Self>())?
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> TryFrom<Atomic<'value, 'dwarf, P>> for bool
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: Atomic<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
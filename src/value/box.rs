@@ -22,6 +22,8 @@ where
     where
         P: crate::DebugInfoProvider<Reader = R>,
     {
+        // boxes are as wide as the addresses of their target.
+        crate::read_address(self.dwarf(), self.unit(), value)?;
         Ok(Box {
             schema: self,
             value,
@@ -39,9 +41,15 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
     /// The reflected value behind this reference.
     pub fn deref(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
-        let value = unsafe { *(self.value.as_ptr() as *const *const crate::Byte) };
+        let value = crate::read_address(self.schema.dwarf(), self.schema.unit(), self.value)?;
+        let value = crate::host_address(self.schema.unit(), value)?;
         let r#type = self.schema.r#type()?;
         let size = r#type.size()?;
         let size = size.try_into()?;
@@ -85,7 +93,8 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.deref().map_err(crate::fmt_err)?;
         f.write_str("box ")?;
-        self.deref().map_err(crate::fmt_err)?.fmt(f)
+        crate::format::follow(f, value.bytes(), |f| value.fmt(f))
     }
 }
\ No newline at end of file
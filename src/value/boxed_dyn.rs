@@ -38,52 +38,76 @@ where
     pub fn schema(&self) -> &crate::schema::BoxedDyn<'dwarf, P::Reader> {
         &self.schema
     }
-    
-    fn data(&self, size: usize) -> Result<crate::Bytes<'value>, crate::Error> {
-        let field =
-            unsafe { super::Field::new(self.schema.pointer().clone(), self.value, self.provider) };
-        let value = field.value()?;
-        let value: super::Pointer<crate::schema::Mut, _> = value.try_into()?;
-        let ptr = value.deref_raw_dyn(size)?;
-        Ok(ptr)
-    }
 
-    fn vtable(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
-        let vtable = self.schema.vtable().clone();
-        let field = unsafe { super::Field::new(vtable, self.value, self.provider) };
-        let value = field.value()?;
-        Ok(value)
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
     }
 
-    /// [`Box`]'d `dyn Trait` object value.
+    /// [`Box`]'d `dyn Trait` object value, reflected with the schema of its
+    /// concrete type (as recovered from its vtable).
     pub fn deref(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
-        let vtable = self.vtable()?;
-        let vtable: super::Pointer<crate::schema::Shared, _> = vtable.try_into()?;
-        let vtable = vtable.deref()?;
-        let vtable: super::Array<_> = vtable.try_into()?;
-        let mut vtable = vtable.iter()?;
-        let drop_glue = vtable.next();
-        let drop_glue = drop_glue.unwrap();
-        let drop_glue = drop_glue?;
-        let drop_glue: usize = drop_glue.try_into()?;
-
-        let size = vtable.next();
-        let size = size.unwrap();
-        let size = size?;
-        let size: usize = size.try_into()?;
-
-        let data = self.data(size)?;
+        unsafe {
+            super::dyn_ref::deref_dyn(
+                self.schema.pointer(),
+                self.schema.vtable(),
+                self.value,
+                self.provider,
+            )
+        }
+    }
 
-        let crate::DebugInfo {
-            context,
-            unit,
-            entry,
-        } = self.provider.info_for(drop_glue as _)?;
-        let entry = unit.entry(entry)?;
-        let schema = crate::schema::Type::from_die(context.dwarf(), unit, entry)?;
+    /// If this is a `Box<dyn Error>`, its concrete error, followed by its
+    /// chain of sources; i.e., the concrete errors of the boxed errors in the
+    /// `source` or `cause` field of each error (or their `Some`).
+    ///
+    /// Sources that are not stored in such fields (e.g., those that
+    /// [`Error::source`][std::error::Error::source] computes) cannot be
+    /// found without running code, and so end the chain.
+    pub fn error_chain(&self) -> Result<Vec<super::Value<'value, 'dwarf, P>>, crate::Error> {
+        // bounds chains that are (unusually) cyclic.
+        const MAX_CHAIN: usize = 32;
+        let mut chain = vec![];
+        if !self.schema.is_error()? {
+            return Ok(chain);
+        }
+        let mut next = Some(self.deref()?);
+        while let Some(error) = next.take() {
+            next = error_source(&error)?;
+            chain.push(error);
+            if chain.len() == MAX_CHAIN {
+                break;
+            }
+        }
+        Ok(chain)
+    }
+}
 
-        unsafe { crate::Value::with_type(schema, data, self.provider) }
+/// The concrete error of the boxed error in the `source` or `cause` field of
+/// `error`, if any.
+fn error_source<'value, 'dwarf, P>(
+    error: &super::Value<'value, 'dwarf, P>,
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let super::Value::Struct(error) = error else { return Ok(None) };
+    for name in ["source", "cause"] {
+        let Some(mut source) = error.field_value(name)? else { continue };
+        if let super::Value::Enum(option) = &source {
+            match option.as_option()? {
+                Some(Some(some)) => source = some,
+                Some(None) => return Ok(None),
+                None => {}
+            }
+        }
+        if let super::Value::BoxedDyn(source) = source {
+            if source.schema().is_error()? {
+                return source.deref().map(Some);
+            }
+        }
     }
+    Ok(None)
 }
 
 impl<'value, 'dwarf, P> fmt::Debug for BoxedDyn<'value, 'dwarf, P>
@@ -105,6 +129,19 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = self.deref().map_err(crate::fmt_err)?;
         f.write_str("box ")?;
-        value.fmt(f)
+        // e.g., `box dyn Error(app::ConfigError { source: box dyn Error(…) })`,
+        // so that the concrete type of each error in a chain is evident.
+        if crate::format::std_adapters() && self.schema.is_error().map_err(crate::fmt_err)? {
+            let (dwarf, unit, offset) = value.r#type().die();
+            let namespaces =
+                crate::namespace_path_of(dwarf, unit, offset).map_err(crate::fmt_err)?;
+            f.write_str("dyn Error(")?;
+            for namespace in namespaces {
+                write!(f, "{}::", String::from_utf8_lossy(&namespace))?;
+            }
+            crate::format::follow(f, value.bytes(), |f| value.fmt(f))?;
+            return f.write_str(")");
+        }
+        crate::format::follow(f, value.bytes(), |f| value.fmt(f))
     }
 }
@@ -39,6 +39,11 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
     /// The `data_ptr` field of this boxed slice.
     pub fn data_ptr(&self) -> Result<crate::Bytes<'value>, crate::Error> {
         let field =
@@ -51,27 +56,28 @@ where
 
     /// The `length` field of this boxed slice.
     pub fn length(&self) -> Result<usize, crate::Error> {
-        let field =
-            unsafe { super::Field::new(self.schema.length().clone(), self.value, self.provider) };
-        let value = field.value()?;
-        let len: usize = value.try_into()?;
-        Ok(len)
+        // lengths are as wide as the addresses of their target.
+        let len = crate::read_address_field(self.schema.length(), self.value)?;
+        Ok(usize::try_from(len)?)
     }
 
     /// An iterator over the values in this slice.
     pub fn iter(&self) -> Result<super::Iter<'value, 'dwarf, P>, crate::Error> {
         let elt_type = self.schema.elt()?;
-        let elt_size = elt_type.size()?;
-        let elt_size = usize::try_from(elt_size)?;
-
         let length = self.length()?;
-        let bytes = elt_size * length;
-
-        let value = self.data_ptr()?.as_ptr();
-        let value = std::ptr::slice_from_raw_parts(value, bytes);
-        let value = unsafe { &*value };
+        let data = self.data_ptr()?.as_ptr();
+        unsafe { super::Iter::from_raw_parts(data, elt_type, length, self.provider) }
+    }
 
-        Ok(unsafe { super::Iter::new(value, elt_size, elt_type, length, self.provider) })
+    /// The elements of this boxed slice, if it is a boxed slice of `u8`s.
+    pub fn as_bytes(&self) -> Result<&'value [u8], crate::Error> {
+        let elt_type = self.schema.elt()?;
+        if !matches!(elt_type, crate::schema::Type::u8(_)) {
+            return Err(crate::error::type_mismatch("u8", elt_type.to_string()));
+        }
+        let value = self.data_ptr()?.as_ptr() as *const u8;
+        let value = std::ptr::slice_from_raw_parts(value, self.length()?);
+        Ok(unsafe { &*value })
     }
 }
 
@@ -92,13 +98,18 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = self.data_ptr().map_err(crate::fmt_err)?;
         f.write_str("box ")?;
-        let mut debug_list = f.debug_list();
-        for maybe_elt in self.iter().map_err(crate::fmt_err)? {
-            let elt = maybe_elt.map_err(crate::fmt_err)?;
-            debug_list.entry(&crate::DebugDisplay(elt));
-        }
-        debug_list.finish()?;
+        crate::format::follow(f, data, |f| {
+            if let Ok(bytes) = self.as_bytes() {
+                if let Some(result) = crate::format::bytes(f, bytes) {
+                    return result;
+                }
+            }
+            let mut debug_list = f.debug_list();
+            crate::format::entries(&mut debug_list, self.iter().map_err(crate::fmt_err)?)?;
+            debug_list.finish()
+        })?;
         f.write_str("[..]")
     }
 }
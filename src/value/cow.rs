@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// The active side of a reflected [`Cow`][std::borrow::Cow] of a `str` or
+/// slice; produced by [`Enum::as_cow`][super::Enum::as_cow].
+#[allow(clippy::large_enum_variant)]
+pub enum CowData<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The contents of a `Cow<str>`.
+    Str(&'value str),
+    /// The elements of a `Cow<[T]>`.
+    Slice(super::Iter<'value, 'dwarf, P>),
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for CowData<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Str(value) => f.debug_tuple("deflect::value::CowData::Str").field(value).finish(),
+            Self::Slice(iter) => f.debug_tuple("deflect::value::CowData::Slice").field(iter).finish(),
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for CowData<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Str(value) => fmt::Debug::fmt(value, f),
+            Self::Slice(iter) => {
+                let mut debug_list = f.debug_list();
+                crate::format::entries(&mut debug_list, iter.clone())?;
+                debug_list.finish()
+            }
+        }
+    }
+}
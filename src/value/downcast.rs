@@ -0,0 +1,74 @@
+impl<'value, 'dwarf, P> super::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Whether this value is a `T`; see [`Type::matches`][crate::schema::Type::matches].
+    pub fn is<T>(&self, provider: &'dwarf P) -> Result<bool, crate::Error> {
+        self.r#type().matches::<T, P>(provider)
+    }
+
+    /// Downcasts this value into a `&T`, if it is a `T`.
+    ///
+    /// Unlike the [`TryFrom`] conversions into primitives, which only check
+    /// the kind of this value, this verifies that the type of this value is
+    /// `T`, as reflected by [`reflect_type`][crate::reflect_type]; i.e., that
+    /// both types are described by the same debuginfo, or by debuginfo
+    /// describing a type of the same path, name, layout and size.
+    pub fn downcast_ref<T>(&self, provider: &'dwarf P) -> Result<&'value T, crate::Error> {
+        if !self.is::<T>(provider)? {
+            let expected = std::any::type_name::<T>();
+            return Err(crate::error::type_mismatch(expected, self.r#type().to_string()));
+        }
+        let bytes = self.bytes();
+        let size = std::mem::size_of::<T>();
+        if bytes.len() < size {
+            return Err(crate::error::size_mismatch(size, bytes.len()));
+        }
+        let value = bytes.as_ptr() as *const T;
+        if value as usize % std::mem::align_of::<T>() != 0 {
+            bail!("value is misaligned")
+        }
+        Ok(unsafe { &*value })
+    }
+}
+
+impl<'dwarf, R> crate::schema::Type<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    /// Whether this is the type `T`, as reflected by
+    /// [`reflect_type`][crate::reflect_type]; i.e., whether both types are
+    /// described by the same debuginfo, or by debuginfo describing a type of
+    /// the same path, name and size.
+    pub fn matches<T, P>(&self, provider: &'dwarf P) -> Result<bool, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        same_type(&crate::reflect_type::<T, P>(provider)?, self)
+    }
+}
+
+/// Whether `a` and `b` are the same type.
+pub(crate) fn same_type<R>(
+    a: &crate::schema::Type<'_, R>,
+    b: &crate::schema::Type<'_, R>,
+) -> Result<bool, crate::Error>
+where
+    R: crate::gimli::Reader<Offset = usize>,
+{
+    let (a_dwarf, a_unit, a_offset) = a.die();
+    let (b_dwarf, b_unit, b_offset) = b.die();
+    if std::ptr::eq(a_dwarf, b_dwarf)
+        && a_unit.header.offset() == b_unit.header.offset()
+        && a_offset == b_offset
+    {
+        return Ok(true);
+    }
+    // the same type may be described by each of the units that use it.
+    if a.size()? != b.size()? {
+        return Ok(false);
+    }
+    let a_path = crate::namespace_path_of(a_dwarf, a_unit, a_offset)?;
+    let b_path = crate::namespace_path_of(b_dwarf, b_unit, b_offset)?;
+    Ok(a_path == b_path && a.to_string() == b.to_string())
+}
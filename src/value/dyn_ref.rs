@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// A reflected `&dyn Trait` or `&mut dyn Trait` value.
+pub struct DynRef<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::DynRef<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::DynRef<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<DynRef<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(DynRef {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> DynRef<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::DynRef<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This fat pointer, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// The referent of this trait object, reflected with the schema of its
+    /// concrete type (as recovered from its vtable).
+    pub fn downcast_value(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            deref_dyn(
+                self.schema.pointer(),
+                self.schema.vtable(),
+                self.value,
+                self.provider,
+            )
+        }
+    }
+}
+
+/// Reflects the referent of the trait object whose `pointer` and `vtable`
+/// fields are described by the given schemas.
+///
+/// ## Safety
+/// `value` must be a valid trait object fat pointer.
+pub(crate) unsafe fn deref_dyn<'value, 'dwarf, P>(
+    pointer: &crate::schema::Field<'dwarf, P::Reader>,
+    vtable: &crate::schema::Field<'dwarf, P::Reader>,
+    value: crate::Bytes<'value>,
+    provider: &'dwarf P,
+) -> Result<super::Value<'value, 'dwarf, P>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let read_addr = |field: &crate::schema::Field<'dwarf, P::Reader>| {
+        let addr = crate::read_address_field(field, value)?;
+        Ok::<_, crate::Error>(crate::host_address(field.unit(), addr)? as usize)
+    };
+
+    let data = read_addr(pointer)?;
+    let vtable = read_addr(vtable)?;
+
+    let crate::DebugInfo {
+        context,
+        unit,
+        entry,
+    } = provider.info_for_vtable(vtable as _)?;
+    let entry = unit.entry(entry)?;
+    let schema = crate::schema::Type::from_die(context.dwarf(), unit, entry)?;
+
+    let size = schema.size()?.try_into()?;
+    let data = std::ptr::slice_from_raw_parts(data as *const crate::Byte, size);
+    super::Value::with_type(schema, &*data, provider)
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for DynRef<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::DynRef");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for DynRef<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.downcast_value().map_err(crate::fmt_err)?;
+        if self.schema.is_mut().map_err(crate::fmt_err)? {
+            f.write_str("&mut ")?;
+        } else {
+            f.write_str("&")?;
+        }
+        crate::format::follow(f, value.bytes(), |f| value.fmt(f))
+    }
+}
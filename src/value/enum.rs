@@ -22,8 +22,10 @@ where
     where
         P: crate::DebugInfoProvider<Reader = R>,
     {
-        let size = self.size()?.try_into()?;
-        let value = &value[..size];
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
         Ok(Enum {
             schema: self,
             value,
@@ -41,40 +43,243 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// The debuginfo provider of this value.
+    pub(crate) fn provider(&self) -> &'dwarf P {
+        self.provider
+    }
+
     /// The variant of this enum.
     pub fn variant(&self) -> Result<super::Variant<'value, 'dwarf, P>, crate::Error> {
-        let mut default = None;
-        let mut matched = None;
-
         let schema = self.schema();
         let discr_loc = schema.discriminant_location().clone();
         let enum_addr = self.value.as_ptr() as *const () as u64;
         let discr_addr = discr_loc.address(enum_addr)?;
+        let discr_size = schema.discriminant_type()?.size()?;
+        // the discriminant must lie within the bytes of this enum.
+        let discr_end = discr_addr
+            .checked_sub(enum_addr)
+            .and_then(|offset| offset.checked_add(discr_size))
+            .ok_or_else(crate::error::arithmetic_overflow)?;
+        if discr_end > self.value.len() as u64 {
+            return Err(crate::error::size_mismatch(discr_end as usize, self.value.len()));
+        }
+        let discriminant = match discr_size {
+            1 => unsafe { std::ptr::read_unaligned(discr_addr as *const u8) }.into(),
+            2 => unsafe { std::ptr::read_unaligned(discr_addr as *const u16) }.into(),
+            4 => unsafe { std::ptr::read_unaligned(discr_addr as *const u32) }.into(),
+            8 => unsafe { std::ptr::read_unaligned(discr_addr as *const u64) },
+            size => bail!("unsupported discriminant of {size} bytes"),
+        };
+        let schema = schema
+            .variant_for_discriminant(discriminant)?
+            .ok_or_else(crate::error::enum_destructure)?;
+        Ok(unsafe { super::Variant::new(schema, self.value, self.provider) })
+    }
 
-        let mut variants = schema.variants()?;
-        let mut variants = variants.iter()?;
+    /// If this is a [coroutine][crate::schema::Enum::is_coroutine] parked at
+    /// an `.await` or `yield`, the index of that point within its body.
+    pub fn suspend_point(&self) -> Result<Option<usize>, crate::Error> {
+        if !self.schema.is_coroutine()? {
+            return Ok(None);
+        }
+        let variant = self.variant()?;
+        let name = variant.schema().name()?;
+        let name = name.to_string_lossy()?;
+        Ok(name.strip_prefix("Suspend").and_then(|index| index.parse().ok()))
+    }
+
+    /// If this is an [`Option`], its payload (if any).
+    ///
+    /// Produces `Ok(None)` if this enum is not an `Option`.
+    #[allow(clippy::type_complexity)]
+    pub fn as_option(
+        &self,
+    ) -> Result<Option<Option<super::Value<'value, 'dwarf, P>>>, crate::Error> {
+        if !self.schema.is_instance_of("core::option::Option")? {
+            return Ok(None);
+        }
+        let variant = self.variant()?;
+        let variant_name = variant.schema().name()?;
+        Ok(match &*variant_name.to_slice()? {
+            b"None" => Some(None),
+            b"Some" => sole_field_value(&variant)?.map(Some),
+            _ => None,
+        })
+    }
 
+    /// If this is a [`Result`], its `Ok` or `Err` payload.
+    ///
+    /// Produces `Ok(None)` if this enum is not a `Result`.
+    #[allow(clippy::type_complexity)]
+    pub fn as_result(
+        &self,
+    ) -> Result<
+        Option<Result<super::Value<'value, 'dwarf, P>, super::Value<'value, 'dwarf, P>>>,
+        crate::Error,
+    > {
+        if !self.schema.is_instance_of("core::result::Result")? {
+            return Ok(None);
+        }
+        let variant = self.variant()?;
+        let variant_name = variant.schema().name()?;
+        Ok(match &*variant_name.to_slice()? {
+            b"Ok" => sole_field_value(&variant)?.map(Ok),
+            b"Err" => sole_field_value(&variant)?.map(Err),
+            _ => None,
+        })
+    }
+
+    /// If this is a [`Cow`][std::borrow::Cow] of a `str` or slice, the
+    /// contents of whichever variant is active.
+    pub fn as_cow(&self) -> Result<Option<super::CowData<'value, 'dwarf, P>>, crate::Error> {
+        use crate::schema::Type;
+
+        if !self.schema.is_instance_of("alloc::borrow::Cow")? {
+            return Ok(None);
+        }
+
+        // the element type of the owned side is recovered from the `&str` or
+        // `&[T]` of the borrowed side.
+        let mut borrowed_type = None;
+        let mut variants = self.schema.variants()?;
+        let mut variants = variants.iter()?;
         while let Some(variant) = variants.try_next()? {
-            if let Some(discriminant) = variant.discriminant_value() {
-                use crate::schema::Data;
-                let matches = match discriminant {
-                    Data::u8(v) => (unsafe { *(discr_addr as *const u8) } == *v),
-                    Data::u16(v) => (unsafe { *(discr_addr as *const u16) } == *v),
-                    Data::u32(v) => (unsafe { *(discr_addr as *const u32) } == *v),
-                    Data::u64(v) => (unsafe { *(discr_addr as *const u64) } == *v),
-                };
-                if matches {
-                    matched = Some(variant.clone());
+            if &*variant.name()?.to_slice()? == b"Borrowed" {
+                let mut fields = variant.fields()?;
+                let mut fields = fields.iter()?;
+                if let Some(field) = fields.try_next()? {
+                    borrowed_type = Some(field.r#type()?);
                 }
-            } else {
-                default = Some(variant.clone());
             }
         }
+        let Some(borrowed_type) = borrowed_type else { return Ok(None) };
 
-        let schema = matched
-            .or(default)
-            .ok_or_else(crate::error::enum_destructure)?;
-        Ok(unsafe { super::Variant::new(schema, self.value, self.provider) })
+        let variant = self.variant()?;
+        let variant_name = variant.schema().name()?;
+        let mut fields = variant.fields()?;
+        let mut fields = fields.iter()?;
+        let Some(field) = fields.try_next()? else { return Ok(None) };
+        let value = field.value()?;
+
+        Ok(match (&*variant_name.to_slice()?, value, borrowed_type) {
+            (b"Borrowed", super::Value::str(value), _) => Some(super::CowData::Str(value.value()?)),
+            (b"Borrowed", super::Value::Slice(value), _) => Some(super::CowData::Slice(value.iter()?)),
+            (b"Owned", value, Type::str(_)) => {
+                let Some((data, length)) = vec_parts(&value)? else { return Ok(None) };
+                let value = std::ptr::slice_from_raw_parts(data as *const u8, length);
+                let value = std::str::from_utf8(unsafe { &*value })?;
+                Some(super::CowData::Str(value))
+            }
+            (b"Owned", value, Type::Slice(slice)) => {
+                let Some((data, length)) = vec_parts(&value)? else { return Ok(None) };
+                let elt_type = slice.elt()?;
+                Some(super::CowData::Slice(unsafe {
+                    super::Iter::from_raw_parts(data, elt_type, length, self.provider)?
+                }))
+            }
+            _ => None,
+        })
+    }
+}
+
+/// The value of the only field of `variant`, if it has exactly one field.
+fn sole_field_value<'value, 'dwarf, P>(
+    variant: &super::Variant<'value, 'dwarf, P>,
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut fields = variant.fields()?;
+    let mut fields = fields.iter()?;
+    let Some(field) = fields.try_next()? else { return Ok(None) };
+    if fields.try_next()?.is_some() {
+        return Ok(None);
+    }
+    field.value().map(Some)
+}
+
+/// The data pointer and length of a reflected `String` or `Vec`.
+pub(crate) fn vec_parts<'value, 'dwarf, P>(
+    value: &super::Value<'value, 'dwarf, P>,
+) -> Result<Option<(*const crate::Byte, usize)>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let super::Value::Struct(value) = value else { return Ok(None) };
+    if let Some(vec) = value.field("vec")? {
+        return vec_parts(&vec.value()?);
+    }
+    // other structs than `Vec`s have no `buf` and `len`.
+    let (Some(_), Some(len)) = (value.field("buf")?, value.field("len")?) else {
+        return Ok(None);
+    };
+    let len: usize = len.value()?.try_into()?;
+    let schema = value.schema();
+    let producer = crate::rustc::producer_of(schema.dwarf(), schema.unit())?;
+    let version = producer.as_deref().and_then(crate::rustc::version_of);
+    let unsupported = || -> Result<crate::Error, crate::Error> {
+        let name = schema.name()?.to_string_lossy()?.into_owned();
+        Ok(crate::error::unsupported_std_layout(name, producer.clone()))
+    };
+    for path in crate::rustc::vec_ptr_paths(version) {
+        let Some(ptr) = field_at_path(value, path)? else { continue };
+        if let Some(data) = first_pointer(ptr)? {
+            return Ok(Some((data, len)));
+        }
+    }
+    Err(unsupported()?)
+}
+
+/// The value of the field reached by following the field names of `path` from
+/// `value`, if any.
+pub(crate) fn field_at_path<'value, 'dwarf, P>(
+    value: &super::Struct<'value, 'dwarf, P>,
+    path: &[&str],
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let Some((first, rest)) = path.split_first() else { return Ok(None) };
+    let Some(field) = value.field_value(*first)? else { return Ok(None) };
+    match (rest, field) {
+        ([], field) => Ok(Some(field)),
+        (rest, super::Value::Struct(field)) => field_at_path(&field, rest),
+        _ => Ok(None),
+    }
+}
+
+/// The address of the first pointer reachable by descending through the first
+/// field of `value`.
+pub(crate) fn first_pointer<'value, 'dwarf, P>(
+    value: super::Value<'value, 'dwarf, P>,
+) -> Result<Option<*const crate::Byte>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    match value {
+        super::Value::NonNull(value) => Ok(Some(value.addr()? as *const crate::Byte)),
+        super::Value::ConstPtr(value) => {
+            let addr = value.addr() as u64;
+            Ok(Some(crate::host_address(value.schema().unit(), addr)?))
+        }
+        super::Value::MutPtr(value) => {
+            let addr = value.addr() as u64;
+            Ok(Some(crate::host_address(value.schema().unit(), addr)?))
+        }
+        super::Value::Struct(value) => {
+            let mut fields = value.fields()?;
+            let mut fields = fields.iter()?;
+            match fields.try_next()? {
+                Some(field) => first_pointer(field.value()?),
+                None => Ok(None),
+            }
+        }
+        _ => Ok(None),
     }
 }
 
@@ -95,8 +300,17 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.schema().name().fmt(f)?;
-        f.write_str("::")?;
-        self.variant().map_err(crate::fmt_err)?.fmt(f)
+        if let Some(cow) = self.as_cow().map_err(crate::fmt_err)? {
+            return cow.fmt(f);
+        }
+        let variant = self.variant().map_err(crate::fmt_err)?;
+        let enum_name = self.schema().display_name().map_err(crate::fmt_err)?;
+        // as with `derive(Debug)`, variants are unqualified; only the
+        // variants of coroutines, which are not otherwise identifiable, are
+        // qualified by their coroutine.
+        if enum_name.starts_with('{') {
+            write!(f, "{enum_name}::")?;
+        }
+        variant.fmt(f)
     }
 }
@@ -32,13 +32,148 @@ where
     }
 
     /// The value of this field.
+    ///
+    /// Fails if this field is a [bitfield][crate::schema::Field::is_bitfield];
+    /// read those with [`bits`][Self::bits].
     pub fn value(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
         let schema = self.schema();
+        if schema.is_bitfield()? {
+            return Err(crate::error::bitfield());
+        }
         let r#type = schema.r#type()?;
         let offset = schema.offset()?.address(0)? as usize;
-        let value = &self.value[offset..];
+        let value = self
+            .value
+            .get(offset..)
+            .ok_or_else(|| crate::error::size_mismatch(offset, self.value.len()))?;
         unsafe { super::Value::with_type(r#type, value, self.provider) }
     }
+
+    /// If this field is a [bitfield][crate::schema::Field::is_bitfield], its
+    /// bits, shifted down to the least significant end of a `u64`.
+    pub fn bits(&self) -> Result<Option<u64>, crate::Error> {
+        let schema = self.schema();
+        let (Some(bit_offset), Some(bit_size)) = (schema.bit_offset()?, schema.bit_size()?) else {
+            return Ok(None);
+        };
+        if bit_size > 64 {
+            bail!("bitfield of {bit_size} bits does not fit in a u64");
+        }
+        let start = usize::try_from(bit_offset / 8)?;
+        let shift = bit_offset % 8;
+        let len = usize::try_from((shift + bit_size + 7) / 8)?;
+        let bytes = self
+            .value
+            .get(start..)
+            .and_then(|bytes| bytes.get(..len))
+            .ok_or_else(|| {
+                crate::error::size_mismatch(start.saturating_add(len), self.value.len())
+            })?;
+        // SAFETY: the bytes spanned by an initialized bitfield are initialized.
+        let bytes = bytes.iter().map(|byte| unsafe { byte.assume_init() } as u128);
+        let raw = if schema.is_big_endian() {
+            // bits are counted from the most significant bit of the first byte.
+            let raw = bytes.fold(0u128, |raw, byte| raw << 8 | byte);
+            raw >> (len as u64 * 8 - shift - bit_size)
+        } else {
+            let raw = bytes.rev().fold(0u128, |raw, byte| raw << 8 | byte);
+            raw >> shift
+        };
+        let mask = (1u128 << bit_size) - 1;
+        Ok(Some((raw & mask) as u64))
+    }
+
+    /// If this field is a [bitfield][crate::schema::Field::is_bitfield], its
+    /// bits, interpreted as its type.
+    pub(crate) fn typed_bits(&self) -> Result<Option<Bits>, crate::Error> {
+        use crate::schema::Type;
+        let Some(bits) = self.bits()? else {
+            return Ok(None);
+        };
+        Ok(Some(match self.schema().r#type()? {
+            Type::bool(_) => Bits::Bool(bits != 0),
+            Type::i8(_) | Type::i16(_) | Type::i32(_) | Type::i64(_) | Type::isize(_) => {
+                let bit_size = self.schema().bit_size()?.unwrap_or(64);
+                // sign-extend the bits; those of zero-width bitfields are
+                // all (zero) bits of their sign.
+                let unused = 64 - bit_size as u32;
+                let signed = bits.checked_shl(unused).map_or(0, |bits| (bits as i64) >> unused);
+                Bits::Signed(signed)
+            }
+            _ => Bits::Unsigned(bits),
+        }))
+    }
+
+    /// Whether the value of this field is [redacted][crate::Redaction] by its
+    /// name.
+    pub(crate) fn is_redacted(&self) -> Result<bool, crate::Error> {
+        let Some(redaction) = self.provider.redaction() else {
+            return Ok(false);
+        };
+        Ok(redaction.redacts_field(&self.schema().name()?.to_slice()?))
+    }
+
+    /// Displays the value of this field; bitfields are displayed according to
+    /// their type, and redacted fields as `<redacted>`.
+    pub(crate) fn display_value(&self) -> DisplayValue<'_, 'value, 'dwarf, P> {
+        DisplayValue(self)
+    }
+}
+
+/// Displays the value of a [`Field`], including bitfields.
+pub(crate) struct DisplayValue<'a, 'value, 'dwarf, P>(&'a Field<'value, 'dwarf, P>)
+where
+    P: crate::DebugInfoProvider;
+
+impl<'a, 'value, 'dwarf, P> fmt::Display for DisplayValue<'a, 'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // an unreadable field is displayed as a placeholder, so that the
+        // remaining fields are still displayed.
+        crate::format::recover(f, |f| self.fmt_value(f))
+    }
+}
+
+impl<'a, 'value, 'dwarf, P> DisplayValue<'a, 'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Display;
+        let field = self.0;
+        if field.is_redacted().map_err(crate::fmt_err)? {
+            return f.write_str(crate::redact::REDACTED);
+        }
+        if crate::format::field_types() {
+            let r#type = field.schema().r#type().map_err(crate::fmt_err)?;
+            let type_name = crate::format::type_name(&r#type).map_err(crate::fmt_err)?;
+            write!(f, "{type_name} = ")?;
+        }
+        match field.typed_bits().map_err(crate::fmt_err)? {
+            Some(bits) => bits.fmt(f),
+            None => field.value().map_err(crate::fmt_err)?.fmt(f),
+        }
+    }
+}
+
+/// The bits of a bitfield, interpreted as its type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Bits {
+    Bool(bool),
+    Signed(i64),
+    Unsigned(u64),
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bits::Bool(bits) => bits.fmt(f),
+            Bits::Signed(bits) => bits.fmt(f),
+            Bits::Unsigned(bits) => bits.fmt(f),
+        }
+    }
 }
 
 impl<'value, 'dwarf, P> fmt::Display for Field<'value, 'dwarf, P>
@@ -48,6 +183,6 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.schema().name().map_err(crate::fmt_err)?.fmt(f)?;
         f.write_str(" : ")?;
-        self.value().map_err(crate::fmt_err)?.fmt(f)
+        self.display_value().fmt(f)
     }
 }
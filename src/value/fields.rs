@@ -65,12 +65,9 @@ impl<'value, 'tree, 'dwarf, P> Iterator for FieldsIter<'value, 'tree, 'dwarf, P>
 where
     P: crate::DebugInfoProvider,
 {
-    type Item = super::Field<'value, 'dwarf, P>;
+    type Item = Result<super::Field<'value, 'dwarf, P>, crate::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(next) => next,
-            Err(err) => panic!("could not read next field: {err}"),
-        }
+        self.try_next().transpose()
     }
 }
@@ -5,7 +5,7 @@ pub struct Function<'value, 'dwarf, P = crate::DefaultProvider>
 where
     P: crate::DebugInfoProvider,
 {
-    _value: crate::Bytes<'value>,
+    value: crate::Bytes<'value>,
     schema: crate::schema::Function<'dwarf, P::Reader>,
     _provider: &'dwarf P,
 }
@@ -24,7 +24,7 @@ where
     {
         Ok(Function {
             schema: self,
-            _value: value,
+            value,
             _provider: provider,
         })
     }
@@ -38,6 +38,16 @@ where
     pub fn schema(&self) -> &crate::schema::Function<'dwarf, P::Reader> {
         &self.schema
     }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// The address of this function.
+    pub fn addr(&self) -> usize {
+        self.value.as_ptr() as usize
+    }
 }
 
 impl<'value, 'dwarf, P> fmt::Debug for Function<'value, 'dwarf, P>
@@ -45,7 +55,10 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("fn(){}")
+        let mut debug_struct = f.debug_struct("deflect::value::Function");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value.as_ptr());
+        debug_struct.finish()
     }
 }
 
@@ -54,7 +67,7 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.schema.fmt(f)
+        write!(f, "{} @ {}", self.schema, crate::format::Address(self.addr()))
     }
 }
 
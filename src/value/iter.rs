@@ -29,6 +29,60 @@ where
             provider,
         }
     }
+
+    /// An iterator over the `length` elements of type `elt_type` starting at
+    /// `data`; e.g., those of a slice.
+    ///
+    /// Zero-sized elements occupy no memory, so are produced without reading
+    /// from `data` (which may dangle).
+    ///
+    /// ## Safety
+    /// Unless its elements are zero-sized, `data` must point to `length`
+    /// initialized elements of type `elt_type`.
+    pub(crate) unsafe fn from_raw_parts(
+        data: *const crate::Byte,
+        elt_type: crate::schema::Type<'dwarf, P::Reader>,
+        length: usize,
+        provider: &'dwarf P,
+    ) -> Result<Self, crate::Error> {
+        let elt_size = usize::try_from(elt_type.size()?)?;
+        let value: crate::Bytes<'value> = if elt_size == 0 {
+            &[]
+        } else {
+            let size = elt_size
+                .checked_mul(length)
+                .ok_or_else(crate::error::arithmetic_overflow)?;
+            &*std::ptr::slice_from_raw_parts(data, size)
+        };
+        Ok(Self::new(value, elt_size, elt_type, length, provider))
+    }
+}
+
+impl<'value, 'dwarf, P> Clone for Iter<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            elt_size: self.elt_size,
+            elt_type: self.elt_type.clone(),
+            length: self.length,
+            provider: self.provider,
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> std::fmt::Debug for Iter<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::Iter");
+        debug_struct.field("elt_type", &self.elt_type);
+        debug_struct.field("length", &self.length);
+        debug_struct.finish()
+    }
 }
 
 impl<'value, 'dwarf, P> Iterator for Iter<'value, 'dwarf, P>
@@ -48,4 +102,83 @@ where
 
         Some(unsafe { super::Value::with_type(self.elt_type.clone(), elt, self.provider) })
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.length {
+            self.value = &self.value[self.value.len()..];
+            self.length = 0;
+            return None;
+        }
+        // skip directly to the `n`th element, without reflecting its
+        // predecessors.
+        self.value = &self.value[n * self.elt_size..];
+        self.length -= n;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'value, 'dwarf, P> ExactSizeIterator for Iter<'value, 'dwarf, P> where
+    P: crate::DebugInfoProvider
+{
+}
+
+/// An iterator over items in an [array][super::Array] or [slice][super::Slice],
+/// produced by their [`IntoIterator`] implementations.
+///
+/// If the items cannot be reflected, this iterator produces a single error.
+pub struct IntoIter<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    iter: Result<Iter<'value, 'dwarf, P>, Option<crate::Error>>,
+}
+
+impl<'value, 'dwarf, P> IntoIter<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    pub(crate) fn new(iter: Result<Iter<'value, 'dwarf, P>, crate::Error>) -> Self {
+        Self {
+            iter: iter.map_err(Some),
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> std::fmt::Debug for IntoIter<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug_tuple = f.debug_tuple("deflect::value::IntoIter");
+        match &self.iter {
+            Ok(iter) => debug_tuple.field(iter),
+            Err(err) => debug_tuple.field(err),
+        };
+        debug_tuple.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> Iterator for IntoIter<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<crate::Value<'value, 'dwarf, P>, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.iter {
+            Ok(iter) => iter.next(),
+            Err(err) => err.take().map(Err),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.iter {
+            Ok(iter) => iter.nth(n),
+            Err(err) => err.take().map(Err),
+        }
+    }
 }
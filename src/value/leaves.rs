@@ -0,0 +1,231 @@
+use std::{fmt, rc::Rc};
+
+/// The path to a value yielded by [`Value::leaves`][super::Value::leaves],
+/// relative to the value that `leaves` was called on.
+///
+/// Paths are cheap to clone and extend, and display in the syntax accepted by
+/// [`Value::get_path`][super::Value::get_path]; e.g., `servers[2]::Some.0`.
+/// Dereferences of references and boxes are implicit, and so do not appear in
+/// paths.
+#[derive(Clone, Default)]
+pub struct Path {
+    last: Option<Rc<Node>>,
+}
+
+/// The last segment of a [`Path`], and the path that precedes it.
+struct Node {
+    parent: Path,
+    segment: Segment,
+}
+
+/// A segment of a [`Path`].
+enum Segment {
+    /// A field of a struct or of an enum's active variant.
+    Field(String),
+    /// An element of an array, slice, or SIMD vector.
+    Index(usize),
+    /// The active variant of an enum.
+    Variant(String),
+}
+
+impl Path {
+    /// Whether this path is empty; i.e., refers to the value that
+    /// [`leaves`][super::Value::leaves] was called on.
+    pub fn is_root(&self) -> bool {
+        self.last.is_none()
+    }
+
+    /// This path, extended by the field `name`.
+    pub(crate) fn field(&self, name: String) -> Self {
+        self.join(Segment::Field(name))
+    }
+
+    /// This path, extended by the element at `index`.
+    pub(crate) fn index(&self, index: usize) -> Self {
+        self.join(Segment::Index(index))
+    }
+
+    /// This path, extended by the variant `name`.
+    pub(crate) fn variant(&self, name: String) -> Self {
+        self.join(Segment::Variant(name))
+    }
+
+    /// This path, extended by `segment`.
+    fn join(&self, segment: Segment) -> Self {
+        Self {
+            last: Some(Rc::new(Node {
+                parent: self.clone(),
+                segment,
+            })),
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(node) = &self.last else { return Ok(()) };
+        node.parent.fmt(f)?;
+        match &node.segment {
+            Segment::Field(name) => {
+                if !node.parent.is_root() {
+                    f.write_str(".")?;
+                }
+                // tuple fields are named `__0`, `__1`, etc. in DWARF.
+                match name.strip_prefix("__") {
+                    Some(index) if index.bytes().all(|b| b.is_ascii_digit()) => f.write_str(index),
+                    _ => f.write_str(name),
+                }
+            }
+            Segment::Index(index) => write!(f, "[{index}]"),
+            Segment::Variant(name) => write!(f, "::{name}"),
+        }
+    }
+}
+
+impl fmt::Debug for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Path").field(&self.to_string()).finish()
+    }
+}
+
+/// An iterator over the leaves of a value, produced by
+/// [`Value::leaves`][super::Value::leaves].
+pub struct Leaves<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The values yet to be visited, in reverse order.
+    stack: Vec<(Path, super::Value<'value, 'dwarf, P>)>,
+}
+
+impl<'value, 'dwarf, P> Iterator for Leaves<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<(Path, super::Value<'value, 'dwarf, P>), crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, value)) = self.stack.pop() {
+            let mut children = vec![];
+            match push_children(&mut children, &path, &value) {
+                Ok(true) => self.stack.extend(children.into_iter().rev()),
+                Ok(false) => return Some(Ok((path, value))),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
+/// Pushes the immediate children of `value` (at `path`) onto `children`, in
+/// order; produces `false` if `value` is a leaf.
+fn push_children<'value, 'dwarf, P>(
+    children: &mut Vec<(Path, super::Value<'value, 'dwarf, P>)>,
+    path: &Path,
+    value: &super::Value<'value, 'dwarf, P>,
+) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    use super::Value;
+    let elements = match value {
+        Value::bool(_)
+        | Value::char(_)
+        | Value::f32(_)
+        | Value::f64(_)
+        | Value::i8(_)
+        | Value::i16(_)
+        | Value::i32(_)
+        | Value::i64(_)
+        | Value::i128(_)
+        | Value::isize(_)
+        | Value::u8(_)
+        | Value::u16(_)
+        | Value::u32(_)
+        | Value::u64(_)
+        | Value::u128(_)
+        | Value::usize(_)
+        | Value::unit(_)
+        | Value::str(_)
+        | Value::Atomic(_) => return Ok(false),
+        Value::Array(v) => v.iter()?,
+        Value::Slice(v) => v.iter()?,
+        Value::BoxedSlice(v) => v.iter()?,
+        Value::Vector(v) => v.iter()?,
+        Value::Box(v) => return push(children, path.clone(), v.deref()?),
+        Value::BoxedDyn(v) => return push(children, path.clone(), v.deref()?),
+        Value::DynRef(v) => return push(children, path.clone(), v.downcast_value()?),
+        Value::SharedRef(v) => return push(children, path.clone(), v.deref()?),
+        Value::UniqueRef(v) => return push(children, path.clone(), v.deref()?),
+        Value::NonZero(v) => return push(children, path.clone(), v.value()?),
+        Value::ManuallyDrop(v) => return push(children, path.clone(), v.value()?),
+        Value::NonNull(_)
+        | Value::ConstPtr(_)
+        | Value::MutPtr(_)
+        | Value::Function(_)
+        | Value::MaybeUninit(_) => return Ok(true),
+        Value::Struct(v) => return push_fields(children, path, v.fields()?),
+        Value::Enum(v) => {
+            let variant = v.variant()?;
+            let name = variant.name()?.to_string_lossy()?.into_owned();
+            let path = path.join(Segment::Variant(name));
+            return push_fields(children, &path, variant.fields()?);
+        }
+    };
+    for (index, elt) in elements.enumerate() {
+        children.push((path.join(Segment::Index(index)), elt?));
+    }
+    Ok(true)
+}
+
+/// Pushes `value` (at `path`) onto `children`.
+fn push<'value, 'dwarf, P>(
+    children: &mut Vec<(Path, super::Value<'value, 'dwarf, P>)>,
+    path: Path,
+    value: super::Value<'value, 'dwarf, P>,
+) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    children.push((path, value));
+    Ok(true)
+}
+
+/// Pushes the values of `fields` (of the value at `path`) onto `children`,
+/// skipping bitfields.
+fn push_fields<'value, 'dwarf, P>(
+    children: &mut Vec<(Path, super::Value<'value, 'dwarf, P>)>,
+    path: &Path,
+    mut fields: super::Fields<'value, 'dwarf, P>,
+) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        if field.bits()?.is_some() {
+            continue;
+        }
+        let name = field.schema().name()?.to_string_lossy()?.into_owned();
+        children.push((path.join(Segment::Field(name)), field.value()?));
+    }
+    Ok(true)
+}
+
+impl<'value, 'dwarf, P> super::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// An iterator over the primitive leaves of this value (i.e., its
+    /// [`bool`], [`char`], number, [`()`][prim@unit], [`str`], and atomic
+    /// values), depth-first, with their paths relative to this value.
+    ///
+    /// As with [`visit`][Self::visit], references and boxes are followed,
+    /// but raw pointers, functions, and bitfields are skipped. Each value is
+    /// expanded only as the iterator reaches it.
+    pub fn leaves(self) -> Leaves<'value, 'dwarf, P> {
+        Leaves {
+            stack: vec![(Path::default(), self)],
+        }
+    }
+}
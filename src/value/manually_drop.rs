@@ -0,0 +1,88 @@
+use std::fmt;
+
+/// A reflected [`ManuallyDrop<T>`][std::mem::ManuallyDrop] value.
+pub struct ManuallyDrop<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::ManuallyDrop<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::ManuallyDrop<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<ManuallyDrop<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(ManuallyDrop {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> ManuallyDrop<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::ManuallyDrop<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This `ManuallyDrop`, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// The wrapped value.
+    pub fn value(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
+        let offset = self.schema.value().offset()?.address(0)? as usize;
+        let r#type = self.schema.value_type()?;
+        unsafe { super::Value::with_type(r#type, &self.value[offset..], self.provider) }
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for ManuallyDrop<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::ManuallyDrop");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for ManuallyDrop<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
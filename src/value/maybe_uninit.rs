@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// A reflected [`MaybeUninit<T>`][std::mem::MaybeUninit] value.
+///
+/// Whether the wrapped value is initialized cannot be known by reflection; it
+/// is displayed as `MaybeUninit<T>(<possibly uninitialized>)`.
+pub struct MaybeUninit<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::MaybeUninit<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::MaybeUninit<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<MaybeUninit<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(MaybeUninit {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> MaybeUninit<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::MaybeUninit<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// The wrapped value.
+    ///
+    /// # Safety
+    /// As with [`MaybeUninit::assume_init_ref`][std::mem::MaybeUninit::assume_init_ref],
+    /// the wrapped value must be initialized.
+    pub unsafe fn assume_init_value(
+        &self,
+    ) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
+        let field = self.schema.value();
+        let offset = field.offset()?.address(0)? as usize;
+        let value = super::Value::with_type(field.r#type()?, &self.value[offset..], self.provider)?;
+        match value {
+            super::Value::ManuallyDrop(value) => value.value(),
+            value => Ok(value),
+        }
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for MaybeUninit<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::MaybeUninit");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for MaybeUninit<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.schema.fmt(f)?;
+        f.write_str("(<possibly uninitialized>)")
+    }
+}
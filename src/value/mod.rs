@@ -1,34 +1,66 @@
 //! Reflections of Rust values.
 
 mod array;
+mod atomic;
 mod r#box;
 mod boxed_dyn;
 mod boxed_slice;
+mod cow;
+mod downcast;
+mod dyn_ref;
 mod r#enum;
-mod field;
+pub(crate) mod field;
 mod fields;
 mod function;
 mod iter;
+mod leaves;
+mod manually_drop;
+mod maybe_uninit;
+mod non_null;
+mod non_zero;
+mod numeric;
+pub(crate) mod path;
+mod place;
 mod pointer;
 mod slice_impl;
 mod str_impl;
+mod structural;
 mod r#struct;
 mod variant;
+mod vector;
+mod visit;
 
 pub use array::Array;
+pub use atomic::Atomic;
 pub use boxed_dyn::BoxedDyn;
 pub use boxed_slice::BoxedSlice;
+pub use cow::CowData;
+pub use dyn_ref::DynRef;
 pub use field::Field;
 pub use fields::{Fields, FieldsIter};
 pub use function::Function;
-pub use iter::Iter;
+pub use iter::{IntoIter, Iter};
+pub use leaves::{Leaves, Path};
+pub use manually_drop::ManuallyDrop;
+pub use maybe_uninit::MaybeUninit;
+pub use non_null::NonNull;
+pub use non_zero::NonZero;
+pub use place::Place;
 pub use pointer::Pointer;
 pub use r#box::Box;
 pub use r#enum::Enum;
+pub(crate) use r#enum::{field_at_path, first_pointer, vec_parts};
 pub use r#struct::Struct;
 pub use slice_impl::Slice;
 pub use str_impl::str;
 pub use variant::Variant;
+pub use vector::Vector;
+pub use visit::{walk_field, walk_struct, walk_value, walk_variant, Visit};
+
+/// A reflected raw pointer value; `K` is either [`Const`][crate::schema::Const]
+/// or [`Mut`][crate::schema::Mut].
+pub type Ptr<'value, 'dwarf, K, P = crate::DefaultProvider> =
+    crate::value::Pointer<'value, 'dwarf, K, P>;
 
 /// A reflected shared reference value.
 pub type SharedRef<'value, 'dwarf, P = crate::DefaultProvider> =
@@ -75,21 +107,6 @@ macro_rules! generate_primitive_conversions {
             }
         }
 
-        impl<'a, 'value, 'dwarf, P> TryFrom<&'a Value<'value, 'dwarf, P>> for std::primitive::$t
-        where
-            P: crate::DebugInfoProvider,
-        {
-            type Error = crate::DowncastErr;
-
-            fn try_from(value: &'a Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
-                if let Value::$t(value) = value {
-                    Ok(*value.value())
-                } else {
-                    Err(crate::DowncastErr::new::<&'a Value<'value, 'dwarf, P>, Self>())
-                }
-            }
-        }
-
         impl<'value, 'dwarf, P> TryFrom<Value<'value, 'dwarf, P>> for &'value std::primitive::$t
         where
             P: crate::DebugInfoProvider,
@@ -104,21 +121,44 @@ macro_rules! generate_primitive_conversions {
                 }
             }
         }
+    };
+}
 
-        impl<'value, 'dwarf, P> TryFrom<Value<'value, 'dwarf, P>> for std::primitive::$t
-        where
-            P: crate::DebugInfoProvider,
-        {
-            type Error = crate::DowncastErr;
+/// Generates the conversions of reflected primitives into the Rust values
+/// of exactly their type.
+macro_rules! generate_exact_conversions {
+    ($($t:ident,)*) => {
+        $(
+            impl<'a, 'value, 'dwarf, P> TryFrom<&'a Value<'value, 'dwarf, P>> for std::primitive::$t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::DowncastErr;
+
+                fn try_from(value: &'a Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    if let Value::$t(value) = value {
+                        Ok(*value.value())
+                    } else {
+                        Err(crate::DowncastErr::new::<&'a Value<'value, 'dwarf, P>, Self>())
+                    }
+                }
+            }
 
-            fn try_from(value: Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
-                if let Value::$t(value) = value {
-                    Ok(*value.value())
-                } else {
-                    Err(crate::DowncastErr::new::<Value<'value, 'dwarf, P>, Self>())
+            impl<'value, 'dwarf, P> TryFrom<Value<'value, 'dwarf, P>> for std::primitive::$t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::DowncastErr;
+
+                fn try_from(value: Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    if let Value::$t(value) = value {
+                        Ok(*value.value())
+                    } else {
+                        Err(crate::DowncastErr::new::<Value<'value, 'dwarf, P>, Self>())
+                    }
                 }
             }
-        }
+        )*
     };
 }
 
@@ -154,12 +194,17 @@ macro_rules! generate_primitive {
         where
             R: crate::gimli::Reader<Offset = std::primitive::usize>
         {
-            pub(crate) unsafe fn with_bytes<'value, P>(self, _provider: &'dwarf P, bytes: crate::Bytes<'value>) -> Result<$t<'value, 'dwarf, P>, crate::Error>
+            pub(crate) unsafe fn with_bytes<'value, P>(self, provider: &'dwarf P, bytes: crate::Bytes<'value>) -> Result<$t<'value, 'dwarf, P>, crate::Error>
             where
                 P: crate::DebugInfoProvider<Reader = R>,
             {
                 let size = self.size() as std::primitive::usize;
-                let value = &bytes[..size];
+                let value = bytes
+                    .get(..size)
+                    .ok_or_else(|| crate::error::size_mismatch(size, bytes.len()))?;
+                if provider.validates_bit_patterns() {
+                    check_bit_pattern(stringify!($t), value)?;
+                }
                 let (&[], [value], &[]) = value.align_to() else {
                     bail!("primitive is misaligned")
                 };
@@ -184,6 +229,13 @@ macro_rules! generate_primitive {
             pub fn value(&self) -> &'value std::primitive::$t {
                 self.value
             }
+
+            /// The bytes of this value.
+            pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+                let value = self.value as *const std::primitive::$t as *const crate::Byte;
+                let size = std::mem::size_of::<std::primitive::$t>();
+                unsafe { std::slice::from_raw_parts(value, size) }
+            }
         }
 
         impl<'value, 'dwarf, P> std::fmt::Debug for $t<'value, 'dwarf, P>
@@ -203,7 +255,9 @@ macro_rules! generate_primitive {
             P: crate::DebugInfoProvider
         {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                self.value.fmt(f)
+                // as with `derive(Debug)`; e.g., chars are quoted, and floats
+                // are rendered as `1.0`, rather than `1`.
+                std::fmt::Debug::fmt(self.value, f)
             }
         }
 
@@ -211,6 +265,31 @@ macro_rules! generate_primitive {
     };
 }
 
+/// Checks that `bytes` are a valid instance of the primitive named `name`;
+/// i.e., that a `bool` is `0` or `1`, and a `char` is a Unicode scalar value.
+///
+/// ## Safety
+/// `bytes` must be initialized.
+unsafe fn check_bit_pattern(name: &'static std::primitive::str, bytes: crate::Bytes<'_>) -> Result<(), crate::Error> {
+    if !matches!(name, "bool" | "char") {
+        return Ok(());
+    }
+    let bytes: Vec<std::primitive::u8> = bytes.iter().map(|byte| byte.assume_init()).collect();
+    let valid = match bytes[..] {
+        [byte] => byte <= 1,
+        [a, b, c, d] => {
+            std::primitive::char::from_u32(std::primitive::u32::from_ne_bytes([a, b, c, d]))
+                .is_some()
+        }
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::error::invalid_bit_pattern(name, bytes))
+    }
+}
+
 generate_primitive! {
     bool,
     char,
@@ -230,6 +309,24 @@ generate_primitive! {
     usize,
 }
 
+// `u64`s, `i64`s and `f64`s are converted from any number that they
+// represent exactly; see the `numeric` module.
+generate_exact_conversions! {
+    bool,
+    char,
+    f32,
+    i8,
+    i16,
+    i32,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u128,
+    usize,
+}
+
 /// A reflected [`()`][prim@unit] value.
 #[allow(non_camel_case_types)]
 #[derive(Clone)]
@@ -254,7 +351,9 @@ where
         P: crate::DebugInfoProvider<Reader = R>,
     {
         let size = self.size() as std::primitive::usize;
-        let value = &bytes[..size];
+        let value = bytes
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, bytes.len()))?;
         let value = &*(value.as_ptr() as *const _);
         Ok(unit {
             value,
@@ -276,6 +375,11 @@ where
     pub fn schema(&self) -> &crate::schema::unit<'dwarf, P::Reader> {
         &self.schema
     }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        &[]
+    }
 }
 
 impl<'value, 'dwarf, P> std::fmt::Debug for unit<'value, 'dwarf, P>
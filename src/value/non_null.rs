@@ -0,0 +1,127 @@
+use std::fmt;
+
+/// A reflected [`NonNull`][std::ptr::NonNull].
+pub struct NonNull<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::NonNull<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::NonNull<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<NonNull<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(NonNull {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> NonNull<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::NonNull<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This `NonNull`, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// The raw pointer wrapped by this `NonNull`.
+    pub fn pointer(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
+        let field = self.schema.pointer().clone();
+        unsafe { super::Field::new(field, self.value, self.provider) }.value()
+    }
+
+    /// The address this `NonNull` points to.
+    ///
+    /// Fails if this `NonNull` was described for a target whose addresses are
+    /// not as wide as this one's.
+    pub fn addr(&self) -> Result<usize, crate::Error> {
+        let field = self.schema.pointer();
+        let addr = crate::read_address_field(field, self.value)?;
+        Ok(crate::host_address(field.unit(), addr)? as usize)
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for NonNull<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::NonNull");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for NonNull<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let addr = crate::format::Address(self.addr().map_err(crate::fmt_err)?);
+        let pointee = match self.schema.pointer().r#type().map_err(crate::fmt_err)? {
+            crate::schema::Type::ConstPtr(r#type) => r#type.r#type(),
+            crate::schema::Type::MutPtr(r#type) => r#type.r#type(),
+            _ => return Err(fmt::Error),
+        };
+        let pointee = pointee.map_err(crate::fmt_err)?;
+        write!(f, "{addr} as *mut {pointee}")
+    }
+}
+
+impl<'a, 'value, 'dwarf, P> TryFrom<&'a NonNull<'value, 'dwarf, P>> for std::ptr::NonNull<()>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: &'a NonNull<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+        let ptr = value.addr()? as *mut ();
+        std::ptr::NonNull::new(ptr).ok_or_else(|| anyhow!("`NonNull` was null"))
+    }
+}
+
+impl<'value, 'dwarf, P> TryFrom<NonNull<'value, 'dwarf, P>> for std::ptr::NonNull<()>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: NonNull<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+/// A reflected non-zero integer (e.g., [`NonZeroU32`][std::num::NonZeroU32]).
+pub struct NonZero<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::NonZero<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::NonZero<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<NonZero<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(NonZero {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> NonZero<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::NonZero<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This non-zero integer, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// The integer value of this non-zero integer.
+    pub fn value(&self) -> Result<super::Value<'value, 'dwarf, P>, crate::Error> {
+        let r#type = self.schema.value_type()?;
+        unsafe { super::Value::with_type(r#type, self.value, self.provider) }
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for NonZero<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::NonZero");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for NonZero<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value().map_err(crate::fmt_err)?.fmt(f)
+    }
+}
+
+macro_rules! generate_non_zero_conversions {
+    ($($t:ident => $nz:ident,)*) => {
+        $(
+            impl<'a, 'value, 'dwarf, P> TryFrom<&'a NonZero<'value, 'dwarf, P>> for std::num::$nz
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::Error;
+
+                fn try_from(value: &'a NonZero<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    let n: std::primitive::$t = value.value()?.try_into()?;
+                    std::num::$nz::new(n).ok_or_else(|| anyhow!("non-zero integer was zero"))
+                }
+            }
+
+            impl<'value, 'dwarf, P> TryFrom<NonZero<'value, 'dwarf, P>> for std::num::$nz
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::Error;
+
+                fn try_from(value: NonZero<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    Self::try_from(&value)
+                }
+            }
+        )*
+    };
+}
+
+generate_non_zero_conversions! {
+    i8 => NonZeroI8,
+    i16 => NonZeroI16,
+    i32 => NonZeroI32,
+    i64 => NonZeroI64,
+    i128 => NonZeroI128,
+    isize => NonZeroIsize,
+    u8 => NonZeroU8,
+    u16 => NonZeroU16,
+    u32 => NonZeroU32,
+    u64 => NonZeroU64,
+    u128 => NonZeroU128,
+    usize => NonZeroUsize,
+}
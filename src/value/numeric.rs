@@ -0,0 +1,112 @@
+//! Conversions of reflected numbers into Rust numbers, regardless of their
+//! width and signedness.
+
+use super::Value;
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// This value, widened to a `u128`, if it is a non-negative integer (or a
+    /// `NonZero` of one) of any width and signedness.
+    pub fn as_u128(&self) -> Option<u128> {
+        Some(match self {
+            Value::u8(v) => (*v.value()).into(),
+            Value::u16(v) => (*v.value()).into(),
+            Value::u32(v) => (*v.value()).into(),
+            Value::u64(v) => (*v.value()).into(),
+            Value::u128(v) => *v.value(),
+            Value::usize(v) => *v.value() as u128,
+            Value::NonZero(v) => return v.value().ok()?.as_u128(),
+            _ => return u128::try_from(self.as_i128()?).ok(),
+        })
+    }
+
+    /// This value, widened to an `i128`, if it is an integer (or a `NonZero`
+    /// of one) of any width and signedness, no greater than [`i128::MAX`].
+    pub fn as_i128(&self) -> Option<i128> {
+        Some(match self {
+            Value::i8(v) => (*v.value()).into(),
+            Value::i16(v) => (*v.value()).into(),
+            Value::i32(v) => (*v.value()).into(),
+            Value::i64(v) => (*v.value()).into(),
+            Value::i128(v) => *v.value(),
+            Value::isize(v) => *v.value() as i128,
+            Value::u8(_)
+            | Value::u16(_)
+            | Value::u32(_)
+            | Value::u64(_)
+            | Value::u128(_)
+            | Value::usize(_) => return i128::try_from(self.as_u128()?).ok(),
+            Value::NonZero(v) => return v.value().ok()?.as_i128(),
+            _ => return None,
+        })
+    }
+
+    /// This value, converted to an `f64`, if it is a float or an integer (or
+    /// a `NonZero` of one) of any width and signedness; integers beyond
+    /// ±2<sup>53</sup> are rounded to the nearest `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::f32(v) => Some((*v.value()).into()),
+            Value::f64(v) => Some(*v.value()),
+            _ => match self.as_i128() {
+                Some(v) => Some(v as f64),
+                None => self.as_u128().map(|v| v as f64),
+            },
+        }
+    }
+
+    /// This value, if it is a float, or an integer that an `f64` represents
+    /// exactly.
+    pub(crate) fn exact_f64(&self) -> Option<f64> {
+        const MAX_EXACT: i128 = 1 << f64::MANTISSA_DIGITS;
+        match self {
+            Value::f32(v) => Some((*v.value()).into()),
+            Value::f64(v) => Some(*v.value()),
+            _ => self
+                .as_i128()
+                .filter(|v| v.abs() <= MAX_EXACT)
+                .map(|v| v as f64),
+        }
+    }
+}
+
+macro_rules! generate_numeric_conversions {
+    ($($t:ident: |$value:ident| $convert:expr,)*) => {
+        $(
+            impl<'a, 'value, 'dwarf, P> TryFrom<&'a Value<'value, 'dwarf, P>> for $t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::DowncastErr;
+
+                fn try_from($value: &'a Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    $convert.ok_or_else(|| {
+                        crate::DowncastErr::new::<&'a Value<'value, 'dwarf, P>, Self>()
+                    })
+                }
+            }
+
+            impl<'value, 'dwarf, P> TryFrom<Value<'value, 'dwarf, P>> for $t
+            where
+                P: crate::DebugInfoProvider,
+            {
+                type Error = crate::DowncastErr;
+
+                fn try_from(value: Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+                    Self::try_from(&value)
+                        .map_err(|_| crate::DowncastErr::new::<Value<'value, 'dwarf, P>, Self>())
+                }
+            }
+        )*
+    };
+}
+
+// integers are converted from integers of any width and signedness, and
+// floats from floats and integers, if they are in range and exact.
+generate_numeric_conversions! {
+    u64: |value| value.as_u128().and_then(|value| u64::try_from(value).ok()),
+    i64: |value| value.as_i128().and_then(|value| i64::try_from(value).ok()),
+    f64: |value| value.exact_f64(),
+}
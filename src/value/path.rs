@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+/// A segment of a path passed to [`Value::get_path`][super::Value::get_path].
+pub(crate) enum Segment<'path> {
+    /// `name` or `.name`; a field of a struct or of an enum's active variant.
+    Field(&'path str),
+    /// `[i]`; an element of an array, slice, or SIMD vector.
+    Index(usize),
+    /// `*` or `.*`; the referent of a reference or box.
+    Deref,
+    /// `::Name`; the active variant of an enum, if it is named `Name`.
+    Variant(&'path str),
+}
+
+/// Parses `path` into its segments.
+pub(crate) fn parse(path: &str) -> Result<Vec<Segment<'_>>, crate::Error> {
+    let invalid = |reason| crate::error::invalid_path(path, reason);
+    let mut segments = vec![];
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('[') {
+            let (index, tail) = tail
+                .split_once(']')
+                .ok_or_else(|| invalid("unclosed `[`"))?;
+            let index = index
+                .trim()
+                .parse()
+                .map_err(|_| invalid("indices must be unsigned integers"))?;
+            segments.push(Segment::Index(index));
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("::") {
+            let (name, tail) = split_name(tail);
+            if name.is_empty() {
+                return Err(invalid("expected a variant name after `::`"));
+            }
+            segments.push(Segment::Variant(name));
+            rest = tail;
+        } else {
+            let tail = if segments.is_empty() {
+                rest
+            } else {
+                rest.strip_prefix('.')
+                    .ok_or_else(|| invalid("expected `.`, `[`, or `::`"))?
+            };
+            if let Some(tail) = tail.strip_prefix('*') {
+                segments.push(Segment::Deref);
+                rest = tail;
+            } else {
+                let (name, tail) = split_name(tail);
+                if name.is_empty() {
+                    return Err(invalid("expected a field name"));
+                }
+                segments.push(Segment::Field(name));
+                rest = tail;
+            }
+        }
+    }
+    if segments
+        .iter()
+        .all(|segment| matches!(segment, Segment::Variant(_)))
+    {
+        return Err(invalid("expected a field, index, or dereference"));
+    }
+    Ok(segments)
+}
+
+/// Splits the leading field or variant name off of `path`.
+fn split_name(path: &str) -> (&str, &str) {
+    let end = path.find(['.', '[', ':', '*']).unwrap_or(path.len());
+    path.split_at(end)
+}
+
+impl<'value, 'dwarf, P> super::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Reflects the value at `path`, relative to this value; e.g.,
+    /// `config.servers[2].addr`.
+    ///
+    /// A path is a sequence of:
+    /// - field names, separated by `.`; the fields of tuples and tuple structs
+    ///   are numbered, as in Rust (e.g., `pair.0`),
+    /// - indices into arrays, slices, and SIMD vectors (e.g., `[2]`),
+    /// - dereferences of references and boxes (e.g., `.*`), and
+    /// - variant projections (e.g., `::Some`), which resolve only if the
+    ///   enum's active variant has the given name.
+    ///
+    /// As in Rust, field accesses and indices implicitly dereference
+    /// references and boxes, and the fields of an enum are those of its active
+    /// variant.
+    ///
+    /// Produces `None` if the path does not resolve in this value, and an
+    /// error if `path` is malformed.
+    pub fn get_path(&self, path: &str) -> Result<Option<Self>, crate::Error> {
+        let mut current = None;
+        for segment in parse(path)? {
+            let value = current.as_ref().unwrap_or(self);
+            let next = match segment {
+                Segment::Field(name) => field(value, name)?,
+                Segment::Index(index) => element(value, index)?,
+                Segment::Deref => deref(value)?,
+                Segment::Variant(name) => {
+                    if is_variant(value, name)? {
+                        continue;
+                    }
+                    None
+                }
+            };
+            let Some(next) = next else { return Ok(None) };
+            current = Some(next);
+        }
+        Ok(current)
+    }
+}
+
+/// The referent of `value`, if it is a reference or box.
+fn deref<'value, 'dwarf, P>(
+    value: &super::Value<'value, 'dwarf, P>,
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    Ok(Some(match value {
+        super::Value::SharedRef(value) => value.deref()?,
+        super::Value::UniqueRef(value) => value.deref()?,
+        super::Value::Box(value) => value.deref()?,
+        super::Value::BoxedDyn(value) => value.deref()?,
+        super::Value::DynRef(value) => value.downcast_value()?,
+        super::Value::ManuallyDrop(value) => value.value()?,
+        _ => return Ok(None),
+    }))
+}
+
+/// The field of `value` named `name`, dereferencing `value` as needed.
+fn field<'value, 'dwarf, P>(
+    value: &super::Value<'value, 'dwarf, P>,
+    name: &str,
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let name = if name.bytes().all(|b| b.is_ascii_digit()) {
+        Cow::Owned(format!("__{name}"))
+    } else {
+        Cow::Borrowed(name)
+    };
+    match value {
+        super::Value::Struct(value) => value.field_value(&*name),
+        super::Value::Enum(value) => {
+            let variant = value.variant()?;
+            let Some(field) = variant.field(&*name)? else { return Ok(None) };
+            field.value().map(Some)
+        }
+        value => match deref(value)? {
+            Some(referent) => field(&referent, &name),
+            None => Ok(None),
+        },
+    }
+}
+
+/// The element of `value` at `index`, dereferencing `value` as needed.
+fn element<'value, 'dwarf, P>(
+    value: &super::Value<'value, 'dwarf, P>,
+    index: usize,
+) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut elements = match value {
+        super::Value::Array(value) => value.iter()?,
+        super::Value::Slice(value) => value.iter()?,
+        super::Value::BoxedSlice(value) => value.iter()?,
+        super::Value::Vector(value) => value.iter()?,
+        value => {
+            return match deref(value)? {
+                Some(referent) => element(&referent, index),
+                None => Ok(None),
+            }
+        }
+    };
+    elements.nth(index).transpose()
+}
+
+/// Whether `value` is an enum whose active variant is named `name`.
+fn is_variant<P>(value: &super::Value<'_, '_, P>, name: &str) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let super::Value::Enum(value) = value else { return Ok(false) };
+    Ok(&*value.variant()?.name()?.to_slice()? == name.as_bytes())
+}
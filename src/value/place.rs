@@ -0,0 +1,163 @@
+use std::{fmt, marker::PhantomData};
+
+/// A reflected value that may be overwritten; produced by
+/// [`Reflect::reflect_mut`][crate::Reflect::reflect_mut].
+///
+/// Unlike a [`Value`][super::Value], which is read through a shared reference,
+/// a place holds the exclusive borrow of its bytes; so its fields may be
+/// overwritten, while no value [read][Self::value] from it is live.
+pub struct Place<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    r#type: crate::schema::Type<'dwarf, P::Reader>,
+    ptr: *mut crate::Byte,
+    len: usize,
+    provider: &'dwarf P,
+    value: PhantomData<&'value mut [crate::Byte]>,
+}
+
+impl<'value, 'dwarf, P> Place<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// ## Safety
+    /// `ptr` must be valid for reads and writes of `len` bytes, for `'value`,
+    /// and the bytes must be a value of `r#type`.
+    pub(crate) unsafe fn new(
+        r#type: crate::schema::Type<'dwarf, P::Reader>,
+        ptr: *mut crate::Byte,
+        len: usize,
+        provider: &'dwarf P,
+    ) -> Self {
+        Self {
+            r#type,
+            ptr,
+            len,
+            provider,
+            value: PhantomData,
+        }
+    }
+
+    /// The type of this place.
+    pub fn r#type(&self) -> &crate::schema::Type<'dwarf, P::Reader> {
+        &self.r#type
+    }
+
+    /// The bytes of this place, borrowed for as long as it is not written.
+    fn bytes(&self) -> crate::Bytes<'_> {
+        // SAFETY: `ptr` is valid for reads of `len` bytes; and the bytes are
+        // only written through `&mut self`, which this borrow precludes.
+        unsafe { &*std::ptr::slice_from_raw_parts(self.ptr as *const crate::Byte, self.len) }
+    }
+
+    /// The value of this place.
+    pub fn value(&self) -> Result<super::Value<'_, 'dwarf, P>, crate::Error> {
+        unsafe { super::Value::with_type(self.r#type.clone(), self.bytes(), self.provider) }
+    }
+
+    /// Overwrites the field named `field_name` of this struct with `bytes`;
+    /// fails if this place is not a struct, if it has no such field, if that
+    /// field is a [bitfield][crate::schema::Field::is_bitfield], or if `bytes`
+    /// are not exactly as many as its type occupies.
+    ///
+    /// The previous value of the field is not dropped.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be a valid value of the field's type, and of the field of
+    /// this struct; e.g., the length of a `Vec` must not exceed its capacity.
+    pub unsafe fn write_field_unchecked<N>(
+        &mut self,
+        field_name: N,
+        bytes: &[u8],
+    ) -> Result<(), crate::Error>
+    where
+        N: AsRef<[u8]>,
+    {
+        let field = self.field(field_name.as_ref())?;
+        self.write_raw(&field, bytes.as_ptr(), bytes.len())
+    }
+
+    /// Overwrites the field named `field_name` of this struct with a copy of
+    /// `value`; fails if there is no such field, or if its type is not `T`,
+    /// as checked by [`downcast_ref`][super::Value::downcast_ref].
+    ///
+    /// # Safety
+    ///
+    /// `value` must be a valid value of the field of this struct; e.g., the
+    /// length of a `Vec` must not exceed its capacity.
+    pub unsafe fn set_field<N, T>(&mut self, field_name: N, value: &T) -> Result<(), crate::Error>
+    where
+        N: AsRef<[u8]>,
+        T: Copy,
+    {
+        let field = self.field(field_name.as_ref())?;
+        let expected = crate::reflect_type::<T, P>(self.provider)?;
+        let actual = field.r#type()?;
+        if !super::downcast::same_type(&expected, &actual)? {
+            let expected = std::any::type_name::<T>();
+            return Err(crate::error::type_mismatch(expected, actual.to_string()));
+        }
+        self.write_raw(&field, value as *const T as *const u8, std::mem::size_of::<T>())
+    }
+
+    /// The schema of the field named `field_name` of this struct.
+    fn field(
+        &self,
+        field_name: &[u8],
+    ) -> Result<crate::schema::Field<'dwarf, P::Reader>, crate::Error> {
+        let crate::schema::Type::Struct(schema) = &self.r#type else {
+            return Err(crate::error::type_mismatch("a struct", self.r#type.to_string()));
+        };
+        let Some(field) = schema.field_by_name(field_name)? else {
+            let name = String::from_utf8_lossy(field_name);
+            bail!("`{}` has no field named `{name}`", schema.name()?);
+        };
+        Ok(field)
+    }
+
+    /// Overwrites `field` of this struct with the `len` bytes at `src`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`write_field_unchecked`][Self::write_field_unchecked]; and
+    /// `src` must be valid for reads of `len` bytes.
+    unsafe fn write_raw(
+        &mut self,
+        field: &crate::schema::Field<'dwarf, P::Reader>,
+        src: *const u8,
+        len: usize,
+    ) -> Result<(), crate::Error> {
+        if field.is_bitfield()? {
+            return Err(crate::error::bitfield());
+        }
+        let size = usize::try_from(field.r#type()?.size()?)?;
+        if len != size {
+            return Err(crate::error::size_mismatch(size, len));
+        }
+        let offset = usize::try_from(field.offset()?.address(0)?)?;
+        let end = offset
+            .checked_add(size)
+            .ok_or_else(crate::error::arithmetic_overflow)?;
+        if end > self.len {
+            return Err(crate::error::size_mismatch(end, self.len));
+        }
+        // SAFETY: `ptr` is valid for writes of `len` bytes, of which the
+        // field's are in bounds.
+        std::ptr::copy_nonoverlapping(src, self.ptr.add(offset) as *mut u8, size);
+        Ok(())
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for Place<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::Place");
+        debug_struct.field("type", &self.r#type);
+        debug_struct.field("value", &self.bytes());
+        debug_struct.finish()
+    }
+}
@@ -22,6 +22,8 @@ where
     where
         P: crate::DebugInfoProvider<Reader = R>,
     {
+        // pointers are as wide as the addresses of their target.
+        crate::read_address(self.dwarf(), self.unit(), value)?;
         Ok(Pointer {
             schema: self,
             value,
@@ -40,7 +42,7 @@ where
     where
         K: crate::schema::Reference,
     {
-        let value = unsafe { *(self.value.as_ptr() as *const *const crate::Byte) };
+        let value = crate::host_address(self.schema.unit(), self.address()?)?;
         let r#type = self.schema.r#type()?;
         let size = r#type.size()?;
         let size = size.try_into()?;
@@ -59,9 +61,19 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// The address stored in this pointer.
+    fn address(&self) -> Result<u64, crate::Error> {
+        crate::read_address(self.schema.dwarf(), self.schema.unit(), self.value)
+    }
+
     /// The unreflected value behind this reference.
     pub(crate) fn deref_raw(&self) -> Result<crate::Bytes<'value>, crate::Error> {
-        let value = unsafe { *(self.value.as_ptr() as *const *const crate::Byte) };
+        let value = crate::host_address(self.schema.unit(), self.address()?)?;
         let r#type = self.schema.r#type()?;
         let size = r#type.size()?;
         let size = size.try_into()?;
@@ -69,13 +81,20 @@ where
         let value = unsafe { &*value };
         Ok(value)
     }
+}
 
-    /// The unreflected value behind this reference.
-    pub(crate) fn deref_raw_dyn(&self, size: usize) -> Result<crate::Bytes<'value>, crate::Error> {
-        let value = unsafe { *(self.value.as_ptr() as *const *const crate::Byte) };
-        let value = std::ptr::slice_from_raw_parts(value, size);
-        let value = unsafe { &*value };
-        Ok(value)
+impl<'value, 'dwarf, K, P> Pointer<'value, 'dwarf, K, P>
+where
+    K: crate::schema::Raw,
+    P: crate::DebugInfoProvider,
+{
+    /// The address this raw pointer points to.
+    ///
+    /// The referent is not read, and need not be valid.
+    pub fn addr(&self) -> usize {
+        // the address is read when this pointer is reflected, so cannot fail;
+        // addresses of wider targets are truncated.
+        self.address().unwrap_or_default() as usize
     }
 }
 
@@ -96,8 +115,13 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.deref().map_err(crate::fmt_err)?;
+        // function pointers are rendered as their signature and address
+        if matches!(value, super::Value::Function(_)) {
+            return value.fmt(f);
+        }
         f.write_str("&")?;
-        self.deref().map_err(crate::fmt_err)?.fmt(f)
+        crate::format::follow(f, value.bytes(), |f| value.fmt(f))
     }
 }
 
@@ -106,32 +130,19 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.deref().map_err(crate::fmt_err)?;
         f.write_str("&mut ")?;
-        self.deref().map_err(crate::fmt_err)?.fmt(f)
-    }
-}
-
-impl<'value, 'dwarf, P> fmt::Display for Pointer<'value, 'dwarf, crate::schema::Const, P>
-where
-    P: crate::DebugInfoProvider,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let value = self.deref_raw().map_err(crate::fmt_err)?;
-        let addr = value.as_ptr() as usize;
-        addr.fmt(f)?;
-        f.write_str(" as *const _")
+        crate::format::follow(f, value.bytes(), |f| value.fmt(f))
     }
 }
 
-impl<'value, 'dwarf, P> fmt::Display for Pointer<'value, 'dwarf, crate::schema::Mut, P>
+impl<'value, 'dwarf, K, P> fmt::Display for Pointer<'value, 'dwarf, K, P>
 where
+    K: crate::schema::Raw,
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let value = self.deref_raw().map_err(crate::fmt_err)?;
-        let addr = value.as_ptr() as usize;
-        addr.fmt(f)?;
-        f.write_str(" as *mut _")
+        let addr = crate::format::Address(self.addr());
+        write!(f, "{addr} as {}", self.schema)
     }
 }
-
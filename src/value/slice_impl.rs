@@ -39,6 +39,11 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
     /// The value of the `data_ptr` field of this slice.
     pub fn data_ptr(&self) -> Result<crate::Bytes<'value>, crate::Error> {
         let field =
@@ -51,27 +56,42 @@ where
 
     /// The value of the `length` field of this slice.
     pub fn length(&self) -> Result<usize, crate::Error> {
-        let field =
-            unsafe { super::Field::new(self.schema.length().clone(), self.value, self.provider) };
-        let value = field.value()?;
-        let len: usize = value.try_into()?;
-        Ok(len)
+        // lengths are as wide as the addresses of their target.
+        let len = crate::read_address_field(self.schema.length(), self.value)?;
+        Ok(usize::try_from(len)?)
     }
 
     /// An iterator over values of this slice.
     pub fn iter(&self) -> Result<super::Iter<'value, 'dwarf, P>, crate::Error> {
         let elt_type = self.schema.elt()?;
-        let elt_size = elt_type.size()?;
-        let elt_size = usize::try_from(elt_size)?;
-
         let length = self.length()?;
-        let bytes = elt_size * length;
+        let data = self.data_ptr()?.as_ptr();
+        unsafe { super::Iter::from_raw_parts(data, elt_type, length, self.provider) }
+    }
+
+    /// The elements of this slice, if it is a slice of `u8`s.
+    pub fn as_bytes(&self) -> Result<&'value [u8], crate::Error> {
+        let elt_type = self.schema.elt()?;
+        if !matches!(elt_type, crate::schema::Type::u8(_)) {
+            return Err(crate::error::type_mismatch("u8", elt_type.to_string()));
+        }
+        let value = self.data_ptr()?.as_ptr() as *const u8;
+        let value = std::ptr::slice_from_raw_parts(value, self.length()?);
+        Ok(unsafe { &*value })
+    }
 
-        let value = self.data_ptr()?.as_ptr();
-        let value = std::ptr::slice_from_raw_parts(value, bytes);
-        let value = unsafe { &*value };
+    /// Whether this slice is empty.
+    pub fn is_empty(&self) -> Result<bool, crate::Error> {
+        Ok(self.length()? == 0)
+    }
 
-        Ok(unsafe { super::Iter::new(value, elt_size, elt_type, length, self.provider) })
+    /// The value at `index` in this slice, if it is in bounds; its location is
+    /// computed directly from the size of the slice's elements.
+    pub fn get(
+        &self,
+        index: usize,
+    ) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error> {
+        self.iter()?.nth(index).transpose()
     }
 }
 
@@ -92,12 +112,41 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = self.data_ptr().map_err(crate::fmt_err)?;
         f.write_str("&")?;
-        let mut debug_list = f.debug_list();
-        for maybe_elt in self.iter().map_err(crate::fmt_err)? {
-            let elt = maybe_elt.map_err(crate::fmt_err)?;
-            debug_list.entry(&crate::DebugDisplay(elt));
-        }
-        debug_list.finish()
+        crate::format::follow(f, data, |f| {
+            if let Ok(bytes) = self.as_bytes() {
+                if let Some(result) = crate::format::bytes(f, bytes) {
+                    return result;
+                }
+            }
+            let mut debug_list = f.debug_list();
+            crate::format::entries(&mut debug_list, self.iter().map_err(crate::fmt_err)?)?;
+            debug_list.finish()
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> IntoIterator for Slice<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<super::Value<'value, 'dwarf, P>, crate::Error>;
+    type IntoIter = super::IntoIter<'value, 'dwarf, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        super::IntoIter::new(self.iter())
+    }
+}
+
+impl<'a, 'value, 'dwarf, P> IntoIterator for &'a Slice<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    type Item = Result<super::Value<'value, 'dwarf, P>, crate::Error>;
+    type IntoIter = super::IntoIter<'value, 'dwarf, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        super::IntoIter::new(self.iter())
     }
 }
@@ -6,7 +6,8 @@ pub struct str<'value, 'dwarf, P = crate::DefaultProvider>
 where
     P: crate::DebugInfoProvider,
 {
-    value: &'value std::primitive::str,
+    value: &'value [std::primitive::u8],
+    bytes: crate::Bytes<'value>,
     schema: crate::schema::str<'dwarf, P::Reader>,
     _provider: &'dwarf P,
 }
@@ -23,20 +24,23 @@ where
     where
         P: crate::DebugInfoProvider<Reader = R>,
     {
+        let bytes = value;
         let data_ptr = unsafe { super::Field::new(self.data_ptr().clone(), value, provider) };
         let data_ptr = data_ptr.value()?;
         let data_ptr: super::Pointer<crate::schema::Mut, _> = data_ptr.try_into()?;
         let data = data_ptr.deref_raw()?.as_ptr();
 
-        let length = unsafe { super::Field::new(self.length().clone(), value, provider) };
-        let length = length.value()?;
-        let length = length.try_into()?;
+        // lengths are as wide as the addresses of their target.
+        let length = crate::read_address_field(self.length(), value)?;
+        let length = std::primitive::usize::try_from(length)?;
 
-        let value = std::ptr::slice_from_raw_parts(data, length);
-        let value = unsafe { &*(value as *const std::primitive::str) };
+        // the string is only interpreted as UTF-8 once it is validated.
+        let value = std::ptr::slice_from_raw_parts(data as *const std::primitive::u8, length);
+        let value = unsafe { &*value };
 
         Ok(str {
             value,
+            bytes,
             schema: self,
             _provider: provider,
         })
@@ -52,10 +56,30 @@ where
         &self.schema
     }
 
-    /// The Rust value corresponding to this reflected value.
-    pub fn value(&self) -> &'value std::primitive::str {
+    /// The bytes of this value; i.e., of the `&str` itself, not of the string
+    /// it refers to.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.bytes
+    }
+
+    /// The Rust value corresponding to this reflected value, if its bytes are
+    /// valid UTF-8; e.g., they may not be if they were read from corrupt
+    /// memory.
+    pub fn value(&self) -> Result<&'value std::primitive::str, crate::Error> {
+        Ok(std::str::from_utf8(self.value)?)
+    }
+
+    /// The bytes of the string this value refers to, which may not be valid
+    /// UTF-8.
+    pub fn as_bytes(&self) -> &'value [std::primitive::u8] {
         self.value
     }
+
+    /// The string this value refers to, with any invalid UTF-8 replaced by
+    /// [`U+FFFD`][std::char::REPLACEMENT_CHARACTER].
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'value, std::primitive::str> {
+        String::from_utf8_lossy(self.value)
+    }
 }
 
 impl<'value, 'dwarf, P> fmt::Debug for str<'value, 'dwarf, P>
@@ -63,9 +87,9 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut debug_struct = f.debug_struct("deflect::value::Slice");
+        let mut debug_struct = f.debug_struct("deflect::value::str");
         debug_struct.field("schema", &self.schema);
-        debug_struct.field("value", &self.value);
+        debug_struct.field("value", &self.to_string_lossy());
         debug_struct.finish()
     }
 }
@@ -75,16 +99,22 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(self.value(), f)
+        if crate::format::lossy_strings() {
+            fmt::Debug::fmt(&self.to_string_lossy(), f)
+        } else {
+            fmt::Debug::fmt(self.value().map_err(crate::fmt_err)?, f)
+        }
     }
 }
 
-impl<'value, 'dwarf, P> From<str<'value, 'dwarf, P>> for &'value std::primitive::str
+impl<'value, 'dwarf, P> TryFrom<str<'value, 'dwarf, P>> for &'value std::primitive::str
 where
     P: crate::DebugInfoProvider,
 {
-    fn from(atom: str<'value, 'dwarf, P>) -> Self {
-        atom.value()
+    type Error = std::str::Utf8Error;
+
+    fn try_from(atom: str<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
+        std::str::from_utf8(atom.as_bytes())
     }
 }
 
@@ -96,14 +126,13 @@ where
     type Error = crate::error::DowncastErr;
 
     fn try_from(value: &'a super::Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
-        if let super::Value::str(value) = value {
-            Ok(value.value())
-        } else {
-            Err(crate::error::DowncastErr::new::<
-                &'a super::Value<'value, 'dwarf, P>,
-                Self,
-            >())
+        match value {
+            super::Value::str(value) => value.value().ok(),
+            _ => None,
         }
+        .ok_or_else(|| {
+            crate::error::DowncastErr::new::<&'a super::Value<'value, 'dwarf, P>, Self>()
+        })
     }
 }
 
@@ -114,13 +143,10 @@ where
     type Error = crate::error::DowncastErr;
 
     fn try_from(value: super::Value<'value, 'dwarf, P>) -> Result<Self, Self::Error> {
-        if let super::Value::str(value) = value {
-            Ok(value.value())
-        } else {
-            Err(crate::error::DowncastErr::new::<
-                super::Value<'value, 'dwarf, P>,
-                Self,
-            >())
+        match value {
+            super::Value::str(value) => value.value().ok(),
+            _ => None,
         }
+        .ok_or_else(crate::error::DowncastErr::new::<super::Value<'value, 'dwarf, P>, Self>)
     }
 }
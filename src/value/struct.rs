@@ -39,22 +39,40 @@ where
         &self.schema
     }
 
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// The debuginfo provider of this value.
+    pub(crate) fn provider(&self) -> &'dwarf P {
+        self.provider
+    }
+
     /// Get a field of this struct by name.
     pub fn field<N>(&self, field_name: N) -> Result<Option<super::Field<'value, 'dwarf, P>>, crate::Error>
     where
         N: AsRef<[u8]>,
     {
-        let target_name = field_name.as_ref();
-        let mut fields = self.fields()?;
-        let mut fields = fields.iter()?;
-        while let Some(field) = fields.try_next()? {
-            let field_name = field.schema().name()?;
-            let field_name = field_name.to_slice()?;
-            if target_name == field_name.as_ref() {
-                return Ok(Some(field));
-            }
-        }
-        Ok(None)
+        let Some(field) = self.schema.field_by_name(field_name)? else { return Ok(None) };
+        Ok(Some(unsafe { super::Field::new(field, self.value, self.provider) }))
+    }
+
+    /// Get the value of a field of this struct by name.
+    pub fn field_value<N>(&self, field_name: N) -> Result<Option<super::Value<'value, 'dwarf, P>>, crate::Error>
+    where
+        N: AsRef<[u8]>,
+    {
+        self.field(field_name)?.map(|field| field.value()).transpose()
+    }
+
+    /// If this struct is a [newtype][crate::schema::Struct::transparent_inner],
+    /// its sole field.
+    pub fn transparent_inner(
+        &self,
+    ) -> Result<Option<super::Field<'value, 'dwarf, P>>, crate::Error> {
+        let Some(inner) = self.schema.transparent_inner()? else { return Ok(None) };
+        Ok(Some(unsafe { super::Field::new(inner, self.value, self.provider) }))
     }
 
     /// The fields of this struct.
@@ -81,17 +99,54 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.adapted().map_err(crate::fmt_err)? {
+            Some(crate::Adapted::Elements(elements)) => {
+                let mut debug_list = f.debug_list();
+                crate::format::entries(&mut debug_list, elements)?;
+                return debug_list.finish();
+            }
+            Some(crate::Adapted::Deref(target)) => {
+                return crate::format::follow(f, target.bytes(), |f| target.fmt(f));
+            }
+            None => {}
+        }
+        if crate::format::transparent_newtypes() {
+            if let Some(inner) = self.transparent_inner().map_err(crate::fmt_err)? {
+                return inner.display_value().fmt(f);
+            }
+        }
         let schema = self.schema();
-        let type_name = schema.name().map_err(crate::fmt_err)?;
-        let type_name = type_name.to_string_lossy().map_err(crate::fmt_err)?;
-        let mut debug_struct = f.debug_struct(&type_name);
+        let type_name = schema.display_name().map_err(crate::fmt_err)?;
         let mut fields = self.fields().map_err(crate::fmt_err)?;
         let mut fields = fields.iter().map_err(crate::fmt_err)?;
+        let mut named_fields = vec![];
         while let Some(field) = fields.try_next().map_err(crate::fmt_err)? {
             let field_name = field.schema().name().map_err(crate::fmt_err)?;
             let field_name = field_name.to_string_lossy().map_err(crate::fmt_err)?;
-            let field_value = field.value().map_err(crate::fmt_err)?;
-            debug_struct.field(&field_name, &crate::DebugDisplay(field_value));
+            named_fields.push((field_name.into_owned(), field));
+        }
+        // as with `derive(Debug)`, tuples and tuple structs (whose fields are
+        // named `__0`, `__1`, etc.) are rendered as `(42, true)` and
+        // `TupleStruct(42)`.
+        let is_tuple = !named_fields.is_empty()
+            && named_fields
+                .iter()
+                .all(|(field_name, _)| field_name.starts_with("__"));
+        if is_tuple {
+            let type_name = if type_name.starts_with('(') {
+                ""
+            } else {
+                crate::format::debug_type_name(&type_name)
+            };
+            let mut debug_tuple = f.debug_tuple(type_name);
+            for (_, field) in &named_fields {
+                debug_tuple.field(&crate::DebugDisplay(field.display_value()));
+            }
+            return debug_tuple.finish();
+        }
+        let mut debug_struct = f.debug_struct(crate::format::debug_type_name(&type_name));
+        for (field_name, field) in &named_fields {
+            debug_struct.field(field_name, &crate::DebugDisplay(field.display_value()));
         }
         debug_struct.finish()
     }
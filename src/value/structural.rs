@@ -0,0 +1,235 @@
+use std::hash::{Hash, Hasher};
+
+impl<'value, 'dwarf, P> super::Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Whether this value is structurally equal to `other`.
+    ///
+    /// Values are compared according to their schemas, rather than their
+    /// bytes: padding is ignored, and the referents of references, boxes, and
+    /// slices are compared, rather than their addresses. Values of different
+    /// types are unequal. As with a derived [`PartialEq`], floats are compared
+    /// numerically (so `NaN` is unequal to itself).
+    ///
+    /// Raw pointers, [`NonNull`][std::ptr::NonNull]s and functions are
+    /// compared by address. Fails if either value is a
+    /// [`MaybeUninit`][std::mem::MaybeUninit], whose contents cannot be read.
+    pub fn structural_eq<'other_value>(
+        &self,
+        other: &super::Value<'other_value, 'dwarf, P>,
+    ) -> Result<bool, crate::Error> {
+        use super::Value;
+        Ok(match (self, other) {
+            (Value::bool(a), Value::bool(b)) => a.value() == b.value(),
+            (Value::char(a), Value::char(b)) => a.value() == b.value(),
+            (Value::f32(a), Value::f32(b)) => a.value() == b.value(),
+            (Value::f64(a), Value::f64(b)) => a.value() == b.value(),
+            (Value::i8(a), Value::i8(b)) => a.value() == b.value(),
+            (Value::i16(a), Value::i16(b)) => a.value() == b.value(),
+            (Value::i32(a), Value::i32(b)) => a.value() == b.value(),
+            (Value::i64(a), Value::i64(b)) => a.value() == b.value(),
+            (Value::i128(a), Value::i128(b)) => a.value() == b.value(),
+            (Value::isize(a), Value::isize(b)) => a.value() == b.value(),
+            (Value::u8(a), Value::u8(b)) => a.value() == b.value(),
+            (Value::u16(a), Value::u16(b)) => a.value() == b.value(),
+            (Value::u32(a), Value::u32(b)) => a.value() == b.value(),
+            (Value::u64(a), Value::u64(b)) => a.value() == b.value(),
+            (Value::u128(a), Value::u128(b)) => a.value() == b.value(),
+            (Value::usize(a), Value::usize(b)) => a.value() == b.value(),
+            (Value::unit(_), Value::unit(_)) => true,
+            (Value::str(a), Value::str(b)) => a.as_bytes() == b.as_bytes(),
+            (Value::Array(a), Value::Array(b)) => elements_eq(a.iter()?, b.iter()?)?,
+            (Value::Slice(a), Value::Slice(b)) => elements_eq(a.iter()?, b.iter()?)?,
+            (Value::BoxedSlice(a), Value::BoxedSlice(b)) => elements_eq(a.iter()?, b.iter()?)?,
+            (Value::Vector(a), Value::Vector(b)) => elements_eq(a.iter()?, b.iter()?)?,
+            (Value::Box(a), Value::Box(b)) => a.deref()?.structural_eq(&b.deref()?)?,
+            (Value::BoxedDyn(a), Value::BoxedDyn(b)) => a.deref()?.structural_eq(&b.deref()?)?,
+            (Value::DynRef(a), Value::DynRef(b)) => {
+                a.downcast_value()?.structural_eq(&b.downcast_value()?)?
+            }
+            (Value::SharedRef(a), Value::SharedRef(b)) => a.deref()?.structural_eq(&b.deref()?)?,
+            (Value::UniqueRef(a), Value::UniqueRef(b)) => a.deref()?.structural_eq(&b.deref()?)?,
+            (Value::NonZero(a), Value::NonZero(b)) => a.value()?.structural_eq(&b.value()?)?,
+            (Value::ManuallyDrop(a), Value::ManuallyDrop(b)) => {
+                a.value()?.structural_eq(&b.value()?)?
+            }
+            (Value::Atomic(a), Value::Atomic(b)) => a.load_bits()? == b.load_bits()?,
+            (Value::NonNull(a), Value::NonNull(b)) => a.addr()? == b.addr()?,
+            (Value::ConstPtr(a), Value::ConstPtr(b)) => a.addr() == b.addr(),
+            (Value::MutPtr(a), Value::MutPtr(b)) => a.addr() == b.addr(),
+            (Value::Function(a), Value::Function(b)) => a.addr() == b.addr(),
+            (Value::MaybeUninit(_), _) | (_, Value::MaybeUninit(_)) => {
+                bail!("possibly uninitialized values cannot be compared")
+            }
+            (Value::Struct(a), Value::Struct(b)) => {
+                a.schema().name()?.to_slice()? == b.schema().name()?.to_slice()?
+                    && fields_eq(a.fields()?, b.fields()?)?
+            }
+            (Value::Enum(a), Value::Enum(b)) => {
+                use super::CowData;
+                match (a.as_cow()?, b.as_cow()?) {
+                    // as with `Cow`'s `PartialEq`, borrowed and owned data are
+                    // compared by their contents.
+                    (Some(CowData::Str(a)), Some(CowData::Str(b))) => a == b,
+                    (Some(CowData::Slice(a)), Some(CowData::Slice(b))) => elements_eq(a, b)?,
+                    _ => {
+                        let (a_variant, b_variant) = (a.variant()?, b.variant()?);
+                        a.schema().name().to_slice()? == b.schema().name().to_slice()?
+                            && a_variant.name()?.to_slice()? == b_variant.name()?.to_slice()?
+                            && fields_eq(a_variant.fields()?, b_variant.fields()?)?
+                    }
+                }
+            }
+            _ => false,
+        })
+    }
+
+    /// Feeds this value into `state`, according to its schema.
+    ///
+    /// The leaves of this value are hashed in schema order: padding is
+    /// skipped, as are the addresses of pointers; the referents of references,
+    /// boxes, and slices are hashed instead. Raw pointers,
+    /// [`NonNull`][std::ptr::NonNull]s and functions contribute only their
+    /// kind. Values that are [structurally equal][Self::structural_eq] hash
+    /// equally.
+    ///
+    /// Fails if this value contains a [`MaybeUninit`][std::mem::MaybeUninit],
+    /// whose contents cannot be read.
+    pub fn structural_hash<H: Hasher>(&self, state: &mut H) -> Result<(), crate::Error> {
+        use super::Value;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::bool(v) => v.value().hash(state),
+            Value::char(v) => v.value().hash(state),
+            // `0.0` and `-0.0` are equal, so must hash equally.
+            Value::f32(v) => (v.value() + 0.0).to_bits().hash(state),
+            Value::f64(v) => (v.value() + 0.0).to_bits().hash(state),
+            Value::i8(v) => v.value().hash(state),
+            Value::i16(v) => v.value().hash(state),
+            Value::i32(v) => v.value().hash(state),
+            Value::i64(v) => v.value().hash(state),
+            Value::i128(v) => v.value().hash(state),
+            Value::isize(v) => v.value().hash(state),
+            Value::u8(v) => v.value().hash(state),
+            Value::u16(v) => v.value().hash(state),
+            Value::u32(v) => v.value().hash(state),
+            Value::u64(v) => v.value().hash(state),
+            Value::u128(v) => v.value().hash(state),
+            Value::usize(v) => v.value().hash(state),
+            Value::unit(_) => {}
+            Value::str(v) => v.as_bytes().hash(state),
+            Value::Array(v) => hash_elements(v.iter()?, state)?,
+            Value::Slice(v) => hash_elements(v.iter()?, state)?,
+            Value::BoxedSlice(v) => hash_elements(v.iter()?, state)?,
+            Value::Vector(v) => hash_elements(v.iter()?, state)?,
+            Value::Box(v) => v.deref()?.structural_hash(state)?,
+            Value::BoxedDyn(v) => v.deref()?.structural_hash(state)?,
+            Value::DynRef(v) => v.downcast_value()?.structural_hash(state)?,
+            Value::SharedRef(v) => v.deref()?.structural_hash(state)?,
+            Value::UniqueRef(v) => v.deref()?.structural_hash(state)?,
+            Value::NonZero(v) => v.value()?.structural_hash(state)?,
+            Value::ManuallyDrop(v) => v.value()?.structural_hash(state)?,
+            Value::Atomic(v) => v.load_bits()?.hash(state),
+            Value::NonNull(_) | Value::ConstPtr(_) | Value::MutPtr(_) | Value::Function(_) => {}
+            Value::MaybeUninit(_) => bail!("possibly uninitialized values cannot be hashed"),
+            Value::Struct(v) => {
+                v.schema().name()?.to_slice()?.hash(state);
+                hash_fields(v.fields()?, state)?;
+            }
+            Value::Enum(v) => match v.as_cow()? {
+                Some(super::CowData::Str(value)) => value.hash(state),
+                Some(super::CowData::Slice(iter)) => hash_elements(iter, state)?,
+                None => {
+                    let variant = v.variant()?;
+                    v.schema().name().to_slice()?.hash(state);
+                    variant.name()?.to_slice()?.hash(state);
+                    hash_fields(variant.fields()?, state)?;
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Whether the elements produced by `a` and `b` are pairwise structurally
+/// equal.
+fn elements_eq<P>(
+    a: super::Iter<'_, '_, P>,
+    b: super::Iter<'_, '_, P>,
+) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+    for (a, b) in a.zip(b) {
+        if !a?.structural_eq(&b?)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether `a` and `b` have the same names and structurally equal values.
+fn fields_eq<P>(
+    mut a: super::Fields<'_, '_, P>,
+    mut b: super::Fields<'_, '_, P>,
+) -> Result<bool, crate::Error>
+where
+    P: crate::DebugInfoProvider,
+{
+    let mut a = a.iter()?;
+    let mut b = b.iter()?;
+    loop {
+        let (a, b) = match (a.try_next()?, b.try_next()?) {
+            (Some(a), Some(b)) => (a, b),
+            (None, None) => return Ok(true),
+            _ => return Ok(false),
+        };
+        if a.schema().name()?.to_slice()? != b.schema().name()?.to_slice()? {
+            return Ok(false);
+        }
+        let equal = match (a.bits()?, b.bits()?) {
+            (None, None) => a.value()?.structural_eq(&b.value()?)?,
+            (a, b) => a == b,
+        };
+        if !equal {
+            return Ok(false);
+        }
+    }
+}
+
+/// Feeds the number of elements produced by `iter`, and those elements, into
+/// `state`.
+fn hash_elements<P, H>(iter: super::Iter<'_, '_, P>, state: &mut H) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    H: Hasher,
+{
+    iter.len().hash(state);
+    for elt in iter {
+        elt?.structural_hash(state)?;
+    }
+    Ok(())
+}
+
+/// Feeds the values of `fields` into `state`.
+fn hash_fields<P, H>(
+    mut fields: super::Fields<'_, '_, P>,
+    state: &mut H,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    H: Hasher,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        match field.bits()? {
+            Some(bits) => bits.hash(state),
+            None => field.value()?.structural_hash(state)?,
+        }
+    }
+    Ok(())
+}
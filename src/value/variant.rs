@@ -31,6 +31,30 @@ where
         &self.schema
     }
 
+    /// The name of this variant.
+    pub fn name(&self) -> Result<crate::schema::Name<P::Reader>, crate::Error> {
+        self.schema.name()
+    }
+
+    /// The field of this variant with the given name, if any; the fields of
+    /// tuple variants are named `__0`, `__1`, etc.
+    pub fn field<N>(&self, field_name: N) -> Result<Option<super::Field<'value, 'dwarf, P>>, crate::Error>
+    where
+        N: AsRef<[u8]>,
+    {
+        let target_name = field_name.as_ref();
+        let mut fields = self.fields()?;
+        let mut fields = fields.iter()?;
+        while let Some(field) = fields.try_next()? {
+            let field_name = field.schema().name()?;
+            let field_name = field_name.to_slice()?;
+            if target_name == field_name.as_ref() {
+                return Ok(Some(field));
+            }
+        }
+        Ok(None)
+    }
+
     /// The fields of this variant.
     pub fn fields(&self) -> Result<super::Fields<'value, 'dwarf, P>, crate::Error> {
         let fields = self.schema.fields()?;
@@ -43,16 +67,33 @@ where
     P: crate::DebugInfoProvider,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let variant_name = self.schema().name().map_err(crate::fmt_err)?;
+        let variant_name = self.name().map_err(crate::fmt_err)?;
         let variant_name = variant_name.to_string_lossy().map_err(crate::fmt_err)?;
-        let mut debug_struct = f.debug_struct(&variant_name);
         let mut fields = self.fields().map_err(crate::fmt_err)?;
         let mut fields = fields.iter().map_err(crate::fmt_err)?;
+        let mut named_fields = vec![];
         while let Some(field) = fields.try_next().map_err(crate::fmt_err)? {
             let field_name = field.schema().name().map_err(crate::fmt_err)?;
             let field_name = field_name.to_string_lossy().map_err(crate::fmt_err)?;
-            let field_value = field.value().map_err(crate::fmt_err)?;
-            debug_struct.field(&field_name, &crate::DebugDisplay(field_value));
+            named_fields.push((field_name.into_owned(), field));
+        }
+        if named_fields.is_empty() {
+            return f.write_str(&variant_name);
+        }
+        // the fields of tuple variants are named `__0`, `__1`, etc.
+        if named_fields
+            .iter()
+            .all(|(field_name, _)| field_name.starts_with("__"))
+        {
+            let mut debug_tuple = f.debug_tuple(&variant_name);
+            for (_, field) in &named_fields {
+                debug_tuple.field(&crate::DebugDisplay(field.display_value()));
+            }
+            return debug_tuple.finish();
+        }
+        let mut debug_struct = f.debug_struct(&variant_name);
+        for (field_name, field) in &named_fields {
+            debug_struct.field(field_name, &crate::DebugDisplay(field.display_value()));
         }
         debug_struct.finish()
     }
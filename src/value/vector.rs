@@ -0,0 +1,106 @@
+use std::fmt;
+
+/// A reflected SIMD vector value (e.g., [`__m128`][std::arch::x86_64::__m128],
+/// or `std::simd::Simd<f32, 4>`).
+pub struct Vector<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    value: crate::Bytes<'value>,
+    schema: crate::schema::Vector<'dwarf, P::Reader>,
+    provider: &'dwarf P,
+}
+
+impl<'dwarf, R> crate::schema::Vector<'dwarf, R>
+where
+    R: crate::gimli::Reader<Offset = std::primitive::usize>,
+{
+    pub(crate) unsafe fn with_bytes<'value, P>(
+        self,
+        provider: &'dwarf P,
+        value: crate::Bytes<'value>,
+    ) -> Result<Vector<'value, 'dwarf, P>, crate::Error>
+    where
+        P: crate::DebugInfoProvider<Reader = R>,
+    {
+        let size: usize = self.size()?.try_into()?;
+        let value = value
+            .get(..size)
+            .ok_or_else(|| crate::error::size_mismatch(size, value.len()))?;
+        Ok(Vector {
+            value,
+            schema: self,
+            provider,
+        })
+    }
+}
+
+impl<'value, 'dwarf, P> Vector<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// The schema of this value.
+    pub fn schema(&self) -> &crate::schema::Vector<'dwarf, P::Reader> {
+        &self.schema
+    }
+
+    /// The bytes of this value.
+    pub(crate) fn bytes(&self) -> crate::Bytes<'value> {
+        self.value
+    }
+
+    /// This vector, interpreted as a struct.
+    pub fn as_struct(&self) -> Result<super::Struct<'value, 'dwarf, P>, crate::Error> {
+        unsafe {
+            self.schema
+                .as_struct()
+                .clone()
+                .with_bytes(self.provider, self.value)
+        }
+    }
+
+    /// The lanes of this vector, interpreted as an array.
+    pub fn as_array(&self) -> Result<super::Array<'value, 'dwarf, P>, crate::Error> {
+        let offset = self.schema.lanes().offset()?.address(0)? as usize;
+        let value = &self.value[offset..];
+        unsafe { self.schema.as_array()?.with_bytes(self.provider, value) }
+    }
+
+    /// An iterator over the lanes of this vector.
+    pub fn iter(&self) -> Result<super::Iter<'value, 'dwarf, P>, crate::Error> {
+        self.as_array()?.iter()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Debug for Vector<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("deflect::value::Vector");
+        debug_struct.field("schema", &self.schema);
+        debug_struct.field("value", &self.value);
+        debug_struct.finish()
+    }
+}
+
+impl<'value, 'dwarf, P> fmt::Display for Vector<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lane_type = self.schema.lane_type().map_err(crate::fmt_err)?;
+        let lane_count = self.schema.lane_count().map_err(crate::fmt_err)?;
+        let mut debug_tuple = f.debug_tuple(&format!("{lane_type}x{lane_count}"));
+        for lane in self.iter().map_err(crate::fmt_err)? {
+            match lane.map_err(crate::fmt_err)? {
+                // floats are rendered as `1.0`, rather than `1`, to
+                // distinguish them from integer lanes.
+                super::Value::f32(lane) => debug_tuple.field(lane.value()),
+                super::Value::f64(lane) => debug_tuple.field(lane.value()),
+                lane => debug_tuple.field(&crate::DebugDisplay(lane)),
+            };
+        }
+        debug_tuple.finish()
+    }
+}
@@ -0,0 +1,232 @@
+use super::Value;
+
+/// A visitor of reflected values; see [`Value::visit`].
+///
+/// Each method is called when the visitor reaches a value of its kind. By
+/// default, methods visit the children of the value (e.g., the fields of a
+/// struct, or the referent of a reference) with the corresponding `walk_*`
+/// function; to continue into the children of a value after overriding its
+/// method, call that function.
+///
+/// Values are visited in place, without building intermediate strings or
+/// trees; any error aborts the traversal.
+pub trait Visit<'value, 'dwarf, P = crate::DefaultProvider>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Visits any value; by default, dispatches to the method for its kind
+    /// with [`walk_value`].
+    fn visit_value(&mut self, value: &Value<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        walk_value(self, value)
+    }
+
+    /// Visits a leaf value; i.e., a [`prim@bool`], [`prim@char`], number,
+    /// [`()`][prim@unit], [`prim@str`], or atomic.
+    fn visit_primitive(&mut self, value: &Value<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a struct; by default, visits its fields with [`walk_struct`].
+    fn visit_struct(
+        &mut self,
+        value: &super::Struct<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        walk_struct(self, value)
+    }
+
+    /// Visits an enum; by default, visits its active variant.
+    fn visit_enum(&mut self, value: &super::Enum<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        self.visit_variant(&value.variant()?)
+    }
+
+    /// Visits the active variant of an enum; by default, visits its fields
+    /// with [`walk_variant`].
+    fn visit_variant(
+        &mut self,
+        variant: &super::Variant<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        walk_variant(self, variant)
+    }
+
+    /// Visits a field of a struct or variant; by default, visits its value
+    /// with [`walk_field`].
+    fn visit_field(&mut self, field: &super::Field<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        walk_field(self, field)
+    }
+
+    /// Visits a [bitfield][crate::schema::Field::is_bitfield], which has no
+    /// value, and its `bits`.
+    fn visit_bitfield(
+        &mut self,
+        field: &super::Field<'value, 'dwarf, P>,
+        bits: u64,
+    ) -> Result<(), crate::Error> {
+        let _ = (field, bits);
+        Ok(())
+    }
+
+    /// Visits the element at `index` of an array, slice, or SIMD vector; by
+    /// default, visits its value.
+    fn visit_slice_element(
+        &mut self,
+        index: usize,
+        value: &Value<'value, 'dwarf, P>,
+    ) -> Result<(), crate::Error> {
+        let _ = index;
+        self.visit_value(value)
+    }
+
+    /// Visits the `referent` of a reference or box.
+    fn visit_referent(&mut self, referent: &Value<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        self.visit_value(referent)
+    }
+
+    /// Visits a value whose contents are not visited; i.e., a raw pointer,
+    /// [`NonNull`][std::ptr::NonNull], function, or
+    /// [`MaybeUninit`][std::mem::MaybeUninit].
+    fn visit_opaque(&mut self, value: &Value<'value, 'dwarf, P>) -> Result<(), crate::Error> {
+        let _ = value;
+        Ok(())
+    }
+}
+
+/// Dispatches `value` to the method of `visitor` for its kind.
+///
+/// [`NonZero`][std::num::NonZeroU8] and
+/// [`ManuallyDrop`][std::mem::ManuallyDrop] values are transparent; their
+/// inner values are visited in their place.
+pub fn walk_value<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    value: &Value<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    match value {
+        Value::bool(_)
+        | Value::char(_)
+        | Value::f32(_)
+        | Value::f64(_)
+        | Value::i8(_)
+        | Value::i16(_)
+        | Value::i32(_)
+        | Value::i64(_)
+        | Value::i128(_)
+        | Value::isize(_)
+        | Value::u8(_)
+        | Value::u16(_)
+        | Value::u32(_)
+        | Value::u64(_)
+        | Value::u128(_)
+        | Value::usize(_)
+        | Value::unit(_)
+        | Value::str(_)
+        | Value::Atomic(_) => visitor.visit_primitive(value),
+        Value::Array(v) => walk_elements(visitor, v.iter()?),
+        Value::Slice(v) => walk_elements(visitor, v.iter()?),
+        Value::BoxedSlice(v) => walk_elements(visitor, v.iter()?),
+        Value::Vector(v) => walk_elements(visitor, v.iter()?),
+        Value::Box(v) => visitor.visit_referent(&v.deref()?),
+        Value::BoxedDyn(v) => visitor.visit_referent(&v.deref()?),
+        Value::DynRef(v) => visitor.visit_referent(&v.downcast_value()?),
+        Value::SharedRef(v) => visitor.visit_referent(&v.deref()?),
+        Value::UniqueRef(v) => visitor.visit_referent(&v.deref()?),
+        Value::NonZero(v) => visitor.visit_value(&v.value()?),
+        Value::ManuallyDrop(v) => visitor.visit_value(&v.value()?),
+        Value::NonNull(_)
+        | Value::ConstPtr(_)
+        | Value::MutPtr(_)
+        | Value::Function(_)
+        | Value::MaybeUninit(_) => visitor.visit_opaque(value),
+        Value::Struct(v) => visitor.visit_struct(v),
+        Value::Enum(v) => visitor.visit_enum(v),
+    }
+}
+
+/// Visits the fields of `value` with `visitor`.
+pub fn walk_struct<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    value: &super::Struct<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    walk_fields(visitor, value.fields()?)
+}
+
+/// Visits the fields of `variant` with `visitor`.
+pub fn walk_variant<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    variant: &super::Variant<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    walk_fields(visitor, variant.fields()?)
+}
+
+/// Visits the value (or, if it is a bitfield, the bits) of `field` with
+/// `visitor`.
+pub fn walk_field<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    field: &super::Field<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    match field.bits()? {
+        Some(bits) => visitor.visit_bitfield(field, bits),
+        None => visitor.visit_value(&field.value()?),
+    }
+}
+
+/// Visits each of `fields` with `visitor`.
+fn walk_fields<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    mut fields: super::Fields<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        visitor.visit_field(&field)?;
+    }
+    Ok(())
+}
+
+/// Visits each of the elements produced by `iter` with `visitor`.
+fn walk_elements<'value, 'dwarf, P, V>(
+    visitor: &mut V,
+    iter: super::Iter<'value, 'dwarf, P>,
+) -> Result<(), crate::Error>
+where
+    P: crate::DebugInfoProvider,
+    V: Visit<'value, 'dwarf, P> + ?Sized,
+{
+    for (index, elt) in iter.enumerate() {
+        visitor.visit_slice_element(index, &elt?)?;
+    }
+    Ok(())
+}
+
+impl<'value, 'dwarf, P> Value<'value, 'dwarf, P>
+where
+    P: crate::DebugInfoProvider,
+{
+    /// Walks this value, and the values reachable from it, with `visitor`.
+    ///
+    /// References and boxes are followed; raw pointers are not.
+    pub fn visit<V>(&self, visitor: &mut V) -> Result<(), crate::Error>
+    where
+        V: Visit<'value, 'dwarf, P> + ?Sized,
+    {
+        visitor.visit_value(self)
+    }
+}
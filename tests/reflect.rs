@@ -1,4 +1,5 @@
 use std::fmt;
+#[allow(dead_code)]
 struct DisplayDebug<T>(T);
 
 impl<T> fmt::Display for DisplayDebug<T>
@@ -10,6 +11,311 @@ where
     }
 }
 
+/// Exclusive use of the global registry, for a test; on drop, the objects
+/// registered under `names` are unregistered.
+struct RegistryGuard {
+    names: &'static [&'static str],
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+fn lock_registry(names: &'static [&'static str]) -> RegistryGuard {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    RegistryGuard {
+        names,
+        // a test that failed while holding the lock still unregistered its
+        // objects.
+        _lock: LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner()),
+    }
+}
+
+impl Drop for RegistryGuard {
+    fn drop(&mut self) {
+        for name in self.names {
+            deflect::registry().remove(name);
+        }
+    }
+}
+
+/// Debuginfo written with `gimli::write`; e.g., of C types, or of other
+/// compilers or targets than this test's, which `rustc` does not emit for it.
+struct Fixture {
+    dwarf: gimli::write::Dwarf,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        Self {
+            dwarf: gimli::write::Dwarf::new(),
+        }
+    }
+
+    /// Adds a unit, with addresses of `address_size` bytes, that was produced
+    /// by `producer`.
+    fn unit(&mut self, address_size: u8, producer: Option<&str>) -> gimli::write::UnitId {
+        let encoding = gimli::Encoding {
+            format: gimli::Format::Dwarf32,
+            version: 4,
+            address_size,
+        };
+        let unit = gimli::write::Unit::new(encoding, gimli::write::LineProgram::none());
+        let unit = self.dwarf.units.add(unit);
+        if let Some(producer) = producer {
+            let root = self.dwarf.units.get(unit).root();
+            let producer = gimli::write::AttributeValue::String(producer.into());
+            let unit = self.dwarf.units.get_mut(unit);
+            unit.get_mut(root).set(gimli::DW_AT_producer, producer);
+        }
+        unit
+    }
+
+    /// Adds an entry of `tag`, with `attrs`, as a child of `parent`; or of the
+    /// root of `unit`, if `None`.
+    fn entry(
+        &mut self,
+        unit: gimli::write::UnitId,
+        parent: Option<gimli::write::UnitEntryId>,
+        tag: gimli::DwTag,
+        attrs: Vec<(gimli::DwAt, gimli::write::AttributeValue)>,
+    ) -> gimli::write::UnitEntryId {
+        let unit = self.dwarf.units.get_mut(unit);
+        let parent = parent.unwrap_or_else(|| unit.root());
+        let entry = unit.add(parent, tag);
+        for (name, value) in attrs {
+            unit.get_mut(entry).set(name, value);
+        }
+        entry
+    }
+
+    /// Adds a base type named `name`, of `size` bytes, in the `encoding`.
+    fn base_type(
+        &mut self,
+        unit: gimli::write::UnitId,
+        name: &str,
+        encoding: gimli::DwAte,
+        size: u64,
+    ) -> gimli::write::UnitEntryId {
+        use gimli::write::AttributeValue;
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String(name.into())),
+            (gimli::DW_AT_encoding, AttributeValue::Encoding(encoding)),
+            (gimli::DW_AT_byte_size, AttributeValue::Udata(size)),
+        ];
+        self.entry(unit, None, gimli::DW_TAG_base_type, attrs)
+    }
+
+    /// Adds a struct named `name`, of `size` bytes, as a child of `parent`.
+    fn structure(
+        &mut self,
+        unit: gimli::write::UnitId,
+        parent: Option<gimli::write::UnitEntryId>,
+        name: &str,
+        size: u64,
+    ) -> gimli::write::UnitEntryId {
+        use gimli::write::AttributeValue;
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String(name.into())),
+            (gimli::DW_AT_byte_size, AttributeValue::Udata(size)),
+        ];
+        self.entry(unit, parent, gimli::DW_TAG_structure_type, attrs)
+    }
+
+    /// Adds a field named `name` of the type `r#type`, at `offset` bytes into
+    /// `parent`.
+    fn member(
+        &mut self,
+        unit: gimli::write::UnitId,
+        parent: gimli::write::UnitEntryId,
+        name: &str,
+        r#type: gimli::write::UnitEntryId,
+        offset: u64,
+    ) -> gimli::write::UnitEntryId {
+        use gimli::write::AttributeValue;
+        let location = AttributeValue::Udata(offset);
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String(name.into())),
+            (gimli::DW_AT_type, AttributeValue::UnitRef(r#type)),
+            (gimli::DW_AT_data_member_location, location),
+        ];
+        self.entry(unit, Some(parent), gimli::DW_TAG_member, attrs)
+    }
+
+    /// Gives `unit`, as if compiled in `comp_dir`, a line program listing the
+    /// files at `paths`; entries may then be declared in the files of the
+    /// returned ids.
+    fn files(
+        &mut self,
+        unit: gimli::write::UnitId,
+        comp_dir: &str,
+        paths: &[&str],
+    ) -> Vec<gimli::write::FileId> {
+        use gimli::write::{AttributeValue, LineProgram, LineString};
+        let unit = self.dwarf.units.get_mut(unit);
+        let mut program = LineProgram::new(
+            unit.encoding(),
+            gimli::LineEncoding::default(),
+            LineString::String(comp_dir.into()),
+            LineString::String(b"lib.rs".to_vec()),
+            None,
+        );
+        let files = paths
+            .iter()
+            .map(|path| {
+                let (directory, name) = path.rsplit_once('/').expect("paths have directories");
+                let directory = program.add_directory(LineString::String(directory.into()));
+                program.add_file(LineString::String(name.into()), directory, None)
+            })
+            .collect();
+        unit.line_program = program;
+        let root = unit.root();
+        let comp_dir = AttributeValue::String(comp_dir.into());
+        unit.get_mut(root).set(gimli::DW_AT_comp_dir, comp_dir);
+        files
+    }
+
+    /// The sections of the units, and the offsets of their entries.
+    fn sections(
+        &mut self,
+    ) -> (
+        gimli::write::Sections<gimli::write::EndianVec<gimli::NativeEndian>>,
+        gimli::write::DebugInfoOffsets,
+    ) {
+        use gimli::write::{EndianVec, Sections};
+
+        let mut sections = Sections::new(EndianVec::new(gimli::NativeEndian));
+        let line_strings = self.dwarf.line_strings.write(&mut sections.debug_line_str);
+        let strings = self.dwarf.strings.write(&mut sections.debug_str);
+        let offsets = self
+            .dwarf
+            .units
+            .write(&mut sections, &line_strings.unwrap(), &strings.unwrap())
+            .unwrap();
+        (sections, offsets)
+    }
+
+    /// The bytes of a portable snapshot of a value of the type `root`, whose
+    /// bytes are the first of `blocks`. Each of `relocations` is a pointer, at
+    /// an offset in a block, to an offset in a block.
+    fn snapshot(
+        &mut self,
+        root: (gimli::write::UnitId, gimli::write::UnitEntryId),
+        blocks: &[&[u8]],
+        relocations: &[(u32, u64, u32, u64)],
+    ) -> Vec<u8> {
+        let (sections, offsets) = self.sections();
+
+        fn bytes(into: &mut Vec<u8>, bytes: &[u8]) {
+            into.extend((bytes.len() as u64).to_le_bytes());
+            into.extend(bytes);
+        }
+        let mut snapshot = b"DEFLECT\0".to_vec();
+        snapshot.extend(1u32.to_le_bytes());
+        snapshot.push(cfg!(target_endian = "little") as u8);
+        snapshot.push(std::mem::size_of::<usize>() as u8);
+        let unit = offsets.unit(root.0).0;
+        let entry = offsets.entry(root.0, root.1).0 - unit;
+        snapshot.push(0);
+        snapshot.extend((unit as u64).to_le_bytes());
+        snapshot.extend((entry as u64).to_le_bytes());
+
+        snapshot.extend(2u32.to_le_bytes());
+        bytes(&mut snapshot, b".debug_abbrev");
+        bytes(&mut snapshot, sections.debug_abbrev.slice());
+        bytes(&mut snapshot, b".debug_str");
+        bytes(&mut snapshot, sections.debug_str.slice());
+        // all units are saved, in one piece.
+        let debug_info = sections.debug_info.slice();
+        snapshot.extend((debug_info.len() as u64).to_le_bytes());
+        snapshot.extend(1u32.to_le_bytes());
+        snapshot.extend(0u64.to_le_bytes());
+        bytes(&mut snapshot, debug_info);
+        // no vtables.
+        snapshot.extend(0u32.to_le_bytes());
+
+        snapshot.extend((blocks.len() as u32).to_le_bytes());
+        for (index, block) in blocks.iter().enumerate() {
+            snapshot.extend((0x1000 * (index as u64 + 1)).to_le_bytes());
+            bytes(&mut snapshot, block);
+        }
+        snapshot.extend((relocations.len() as u32).to_le_bytes());
+        for &(block, offset, target, target_offset) in relocations {
+            snapshot.extend(block.to_le_bytes());
+            snapshot.extend(offset.to_le_bytes());
+            snapshot.extend(target.to_le_bytes());
+            snapshot.extend(target_offset.to_le_bytes());
+        }
+        snapshot
+    }
+
+    /// A provider that reflects every value as the type `root`; the bytes of
+    /// values must be aligned as those of its fields.
+    fn provider(&mut self, root: (gimli::write::UnitId, gimli::write::UnitEntryId)) -> Synthetic {
+        self.provider_with(root, |context| {
+            Box::leak(Box::new(deflect::MemoContext::new(context)))
+        })
+    }
+
+    /// A provider that reflects every value as the type `root`, with the
+    /// context `leak` produces of the sections of this fixture.
+    fn provider_with(
+        &mut self,
+        root: (gimli::write::UnitId, gimli::write::UnitEntryId),
+        leak: impl FnOnce(SyntheticContext) -> &'static SyntheticContext,
+    ) -> Synthetic {
+        let (sections, offsets) = self.sections();
+        let mut bytes = std::collections::HashMap::new();
+        sections
+            .for_each(|id, section| -> Result<(), ()> {
+                bytes.insert(id, section.slice().to_vec());
+                Ok(())
+            })
+            .unwrap();
+        // leaked, as are the contexts of providers of this program.
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, ()> {
+            let section = bytes.remove(&id).unwrap_or_default();
+            let section: &'static [u8] = Box::leak(section.into_boxed_slice());
+            Ok(gimli::EndianSlice::new(section, gimli::NativeEndian))
+        })
+        .unwrap();
+        let context = leak(deflect::addr2line::Context::from_dwarf(dwarf).unwrap());
+        let unit = offsets.unit(root.0);
+        let header = context.dwarf().debug_info.header_from_offset(unit).unwrap();
+        let entry = gimli::UnitOffset(offsets.entry(root.0, root.1).0 - unit.0);
+        Synthetic {
+            context,
+            unit: Box::leak(Box::new(context.dwarf().unit(header).unwrap())),
+            entry,
+        }
+    }
+}
+
+type SyntheticReader = gimli::EndianSlice<'static, gimli::NativeEndian>;
+
+type SyntheticContext = deflect::addr2line::Context<SyntheticReader>;
+
+/// A provider of the debuginfo of a [`Fixture`].
+#[derive(Clone)]
+struct Synthetic {
+    context: &'static SyntheticContext,
+    unit: &'static gimli::Unit<SyntheticReader>,
+    entry: gimli::UnitOffset,
+}
+
+unsafe impl deflect::DebugInfoProvider for Synthetic {
+    type Reader = SyntheticReader;
+
+    fn info_for(
+        &self,
+        _: u64,
+    ) -> Result<deflect::DebugInfo<'_, Self::Reader>, deflect::anyhow::Error> {
+        Ok(deflect::DebugInfo::new(self.context, self.unit, self.entry))
+    }
+}
+
+/// Bytes aligned as those of any primitive; e.g., of a value of a [`Fixture`]'s
+/// type.
+#[repr(align(16))]
+struct Aligned<const N: usize>([u8; N]);
+
 #[test]
 fn phantom_data() -> Result<(), Box<dyn std::error::Error>> {
     use std::marker::PhantomData;
@@ -33,11 +339,11 @@ fn unit_struct() -> Result<(), Box<dyn std::error::Error>> {
 
 #[test]
 fn tuple_struct() -> Result<(), Box<dyn std::error::Error>> {
-    struct TupleStruct(u8);
+    struct TupleStruct(#[allow(dead_code)] u8);
     let erased: &dyn deflect::Reflect = &TupleStruct(42);
     let context = deflect::default_provider()?;
     let value = erased.reflect(&context)?;
-    assert_eq!(value.to_string(), "TupleStruct { __0: 42 }");
+    assert_eq!(value.to_string(), "TupleStruct(42)");
     Ok(())
 }
 
@@ -88,6 +394,38 @@ mod slice {
 
         assert_eq!(data, collected);
 
+        // zero-sized elements are produced without reading memory, so any
+        // may be skipped to.
+        assert_eq!(data.len(), value.iter()?.skip(data.len() / 2).count() + data.len() / 2);
+        assert_eq!(format!("&{:?}", slice), value.to_string());
+
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn of_phantoms(len: u8) -> Result<(), Box<dyn std::error::Error>> {
+        use std::marker::PhantomData;
+        let data: Vec<PhantomData<u64>> = vec![PhantomData; len.into()];
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        // the buffer of a `Vec` of zero-sized elements is not allocated.
+        assert_eq!(deflect::deep_size_of(&value)?, std::mem::size_of_val(&data));
+
+        let slice = data.as_slice();
+        let erased: &dyn deflect::Reflect = &slice;
+        let value = erased.reflect(&context)?;
+        let value: deflect::value::Slice<_> = value.try_into()?;
+        assert_eq!(data.len(), value.length()?);
+        assert_eq!(data.len(), value.iter()?.count());
+
+        let boxed = data.into_boxed_slice();
+        let erased: &dyn deflect::Reflect = &boxed;
+        let value = erased.reflect(&context)?;
+        let value: deflect::value::BoxedSlice<_> = value.try_into()?;
+        assert_eq!(boxed.len(), value.length()?);
+        assert_eq!(boxed.len(), value.iter()?.count());
+
         Ok(())
     }
 
@@ -197,7 +535,7 @@ mod primitive {
         let erased: &dyn deflect::Reflect = &n;
         let context = deflect::default_provider()?;
         let value = erased.reflect(&context)?;
-        assert_eq!(n.to_string(), value.to_string());
+        assert_eq!(format!("{n:?}"), value.to_string());
         assert!(ptr::eq(
             &n,
             <&_>::try_from(value).expect("failed to downcast")
@@ -210,7 +548,7 @@ mod primitive {
         let erased: &dyn deflect::Reflect = &n;
         let context = deflect::default_provider()?;
         let value = erased.reflect(&context)?;
-        assert_eq!(n.to_string(), value.to_string());
+        assert_eq!(format!("{n:?}"), value.to_string());
         assert!(ptr::eq(
             &n,
             <&_>::try_from(value).expect("failed to downcast")
@@ -223,7 +561,7 @@ mod primitive {
         let erased: &dyn deflect::Reflect = &n;
         let context = deflect::default_provider()?;
         let value = erased.reflect(&context)?;
-        assert_eq!(n.to_string(), value.to_string());
+        assert_eq!(format!("{n:?}"), value.to_string());
         assert!(ptr::eq(
             &n,
             <&_>::try_from(value).expect("failed to downcast")
@@ -387,3 +725,5007 @@ mod primitive {
         Ok(())
     }
 }
+
+mod atomic {
+    use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize};
+
+    #[quickcheck_macros::quickcheck]
+    fn usize(n: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let data = AtomicUsize::new(n);
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(n.to_string(), value.to_string());
+        let value: deflect::value::Atomic = value.try_into()?;
+        assert_eq!(n, usize::try_from(&value)?);
+        assert!(bool::try_from(&value).is_err());
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn i32(n: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let data = AtomicI32::new(n);
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(n.to_string(), value.to_string());
+        let value: deflect::value::Atomic = value.try_into()?;
+        assert_eq!(n, i32::try_from(value)?);
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn bool(n: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let data = AtomicBool::new(n);
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(n.to_string(), value.to_string());
+        let value: deflect::value::Atomic = value.try_into()?;
+        assert_eq!(n, bool::try_from(value)?);
+        Ok(())
+    }
+
+    #[test]
+    fn ptr() -> Result<(), Box<dyn std::error::Error>> {
+        let mut n = 42u8;
+        let data = std::sync::atomic::AtomicPtr::new(&mut n);
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        let addr = &mut n as *mut u8 as usize;
+        assert_eq!(value.to_string(), format!("{addr:#x} as *mut u8"));
+        let hidden = deflect::FormatOptions::new().addresses(deflect::AddressFormat::Hidden);
+        assert_eq!(value.display_with(hidden).to_string(), "<ptr> as *mut u8");
+        let value: deflect::value::Atomic = value.try_into()?;
+        assert_eq!(value.load_bits()?, addr as u64);
+        Ok(())
+    }
+
+    #[test]
+    fn lookalike() -> Result<(), Box<dyn std::error::Error>> {
+        // shaped, and named, like an atomic; but not one.
+        struct AtomicLookalike {
+            #[allow(dead_code)]
+            v: std::cell::UnsafeCell<u32>,
+        }
+        let data = AtomicLookalike {
+            v: std::cell::UnsafeCell::new(7),
+        };
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), "AtomicLookalike { v: UnsafeCell { value: 7 } }");
+        let _: deflect::value::Struct = value.try_into()?;
+        Ok(())
+    }
+}
+
+mod non_zero {
+    use std::num::{NonZeroI64, NonZeroU32};
+
+    #[quickcheck_macros::quickcheck]
+    fn u32(n: NonZeroU32) -> Result<(), Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = &n;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(n.to_string(), value.to_string());
+        let value: deflect::value::NonZero = value.try_into()?;
+        assert_eq!(n, NonZeroU32::try_from(value)?);
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn i64(n: i64) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(n) = NonZeroI64::new(n) else { return Ok(()) };
+        let erased: &dyn deflect::Reflect = &n;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(n.to_string(), value.to_string());
+        let value: deflect::value::NonZero = value.try_into()?;
+        assert_eq!(n, NonZeroI64::try_from(&value)?);
+        assert!(NonZeroU32::try_from(&value).is_err());
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn option(n: Option<NonZeroU32>) -> Result<(), Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = &n;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        let expected = match n {
+            Some(n) => format!("Some({n})"),
+            None => "None".to_string(),
+        };
+        assert_eq!(expected, value.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn lookalike() -> Result<(), Box<dyn std::error::Error>> {
+        // shaped and named like a non-zero integer, but not one.
+        struct NonZeroCount(u32);
+
+        let count = NonZeroCount(0);
+        let erased: &dyn deflect::Reflect = &count;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert!(matches!(value, deflect::Value::Struct(_)));
+        assert_eq!(count.0, 0);
+        Ok(())
+    }
+}
+
+#[test]
+fn non_null() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = 42u8;
+    let ptr = std::ptr::NonNull::from(&mut data);
+    let erased: &dyn deflect::Reflect = &ptr;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), format!("{:#x} as *mut u8", ptr.as_ptr() as usize));
+    let value: deflect::value::NonNull = value.try_into()?;
+    assert_eq!(std::ptr::NonNull::<()>::try_from(value)?, ptr.cast());
+
+    // shaped and named like a `NonNull`, but not one.
+    #[allow(dead_code)]
+    struct NonNull<T> {
+        pointer: *const T,
+    }
+
+    let lookalike = NonNull { pointer: std::ptr::null::<u8>() };
+    let erased: &dyn deflect::Reflect = &lookalike;
+    let value = erased.reflect(&context)?;
+    assert!(matches!(value, deflect::Value::Struct(_)));
+    Ok(())
+}
+
+mod cow {
+    use std::borrow::Cow;
+
+    #[quickcheck_macros::quickcheck]
+    fn str(data: String, owned: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let data: Cow<str> = if owned {
+            Cow::Owned(data.clone())
+        } else {
+            Cow::Borrowed(&data)
+        };
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(format!("{:?}", data), value.to_string());
+        let value: deflect::value::Enum = value.try_into()?;
+        let Some(deflect::value::CowData::Str(str)) = value.as_cow()? else {
+            panic!("expected a `str`");
+        };
+        assert_eq!(str, data);
+        Ok(())
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn slice(data: Vec<u16>, owned: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let data: Cow<[u16]> = if owned {
+            Cow::Owned(data.clone())
+        } else {
+            Cow::Borrowed(&data)
+        };
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(format!("{:?}", data), value.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn lookalike() -> Result<(), Box<dyn std::error::Error>> {
+        // named, and shaped, like a `Cow`; but not one.
+        #[allow(dead_code)]
+        enum Cow<'a, T: ?Sized> {
+            Borrowed(&'a T),
+            Owned(String),
+        }
+        let data: Cow<str> = Cow::Borrowed("hello");
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), r#"Borrowed("hello")"#);
+        let value: deflect::value::Enum = value.try_into()?;
+        assert!(value.as_cow()?.is_none());
+        Ok(())
+    }
+}
+
+#[quickcheck_macros::quickcheck]
+fn as_option(data: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Enum = value.try_into()?;
+    let option = value.as_option()?.expect("`Option` was not recognized");
+    let option = option.map(u32::try_from).transpose()?;
+    assert_eq!(data, option);
+    assert!(value.as_result()?.is_none());
+    Ok(())
+}
+
+#[quickcheck_macros::quickcheck]
+fn as_result(data: Result<u32, i8>) -> Result<(), Box<dyn std::error::Error>> {
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Enum = value.try_into()?;
+    let result = match value.as_result()?.expect("`Result` was not recognized") {
+        Ok(ok) => Ok(u32::try_from(ok)?),
+        Err(err) => Err(i8::try_from(err)?),
+    };
+    assert_eq!(data, result);
+    assert!(value.as_option()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn as_option_lookalikes() -> Result<(), Box<dyn std::error::Error>> {
+    // named, and shaped, like an `Option` and a `Result`; but neither.
+    #[allow(dead_code)]
+    enum Option<T> {
+        None,
+        Some(T),
+    }
+    #[allow(dead_code)]
+    enum Result<T, E> {
+        Ok(T),
+        Err(E),
+    }
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &Option::Some(1u8);
+    let value: deflect::value::Enum = erased.reflect(&context)?.try_into()?;
+    assert!(value.as_option()?.is_none());
+    let erased: &dyn deflect::Reflect = &Result::<u8, u8>::Ok(1);
+    let value: deflect::value::Enum = erased.reflect(&context)?.try_into()?;
+    assert!(value.as_result()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn raw_pointers() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = 42i32;
+    let context = deflect::default_provider()?;
+
+    let ptr = &data as *const i32;
+    let erased: &dyn deflect::Reflect = &ptr;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), format!("{:#x} as *const i32", ptr as usize));
+    let value: deflect::value::Ptr<deflect::schema::Const> = value.try_into()?;
+    assert_eq!(value.addr(), ptr as usize);
+
+    let ptr = &mut data as *mut i32;
+    let erased: &dyn deflect::Reflect = &ptr;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), format!("{:#x} as *mut i32", ptr as usize));
+
+    // dangling pointers are rendered without being dereferenced
+    let ptr = std::ptr::null::<u64>();
+    let erased: &dyn deflect::Reflect = &ptr;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "0x0 as *const u64");
+    Ok(())
+}
+
+#[test]
+fn function_pointer() -> Result<(), Box<dyn std::error::Error>> {
+    fn is_big(n: u32) -> bool {
+        n > 9000
+    }
+
+    extern "C" fn double(n: u32) -> u32 {
+        n * 2
+    }
+
+    unsafe fn noop() {}
+
+    struct Callbacks {
+        predicate: fn(u32) -> bool,
+        g: extern "C" fn(u32) -> u32,
+        h: unsafe fn(),
+    }
+
+    let data = Callbacks {
+        predicate: is_big,
+        g: double,
+        h: noop,
+    };
+    assert!(!(data.predicate)(42));
+    assert_eq!((data.g)(21), 42);
+    unsafe { (data.h)() };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let addr = is_big as fn(u32) -> bool as *const () as usize;
+    let g = double as extern "C" fn(u32) -> u32 as *const () as usize;
+    let h = noop as unsafe fn() as *const () as usize;
+    assert_eq!(
+        value.to_string(),
+        format!(
+            "Callbacks {{ predicate: fn(u32) -> bool @ {addr:#x}, g: fn(u32) -> u32 @ {g:#x}, \
+             h: fn() @ {h:#x} }}"
+        )
+    );
+
+    let value: deflect::value::Struct = value.try_into()?;
+    let predicate = value.field("predicate")?.expect("missing field").value()?;
+    let predicate: deflect::value::SharedRef = predicate.try_into()?;
+    let function: deflect::value::Function = predicate.deref()?.try_into()?;
+    assert_eq!(function.addr(), addr);
+    let schema = function.schema();
+    let parameters = schema.parameters()?;
+    assert_eq!(parameters.len(), 1);
+    assert!(matches!(parameters[0], deflect::schema::Type::u32(_)));
+    assert!(matches!(
+        schema.return_type()?,
+        Some(deflect::schema::Type::bool(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn dyn_ref() -> Result<(), Box<dyn std::error::Error>> {
+    struct Foo {
+        bar: u8,
+    }
+
+    trait Trait {
+        fn bar(&self) -> u8;
+    }
+
+    impl Trait for Foo {
+        fn bar(&self) -> u8 {
+            self.bar
+        }
+    }
+
+    let context = deflect::default_provider()?;
+
+    let mut data = Foo { bar: 7 };
+    let shared: &dyn Trait = &data;
+    assert_eq!(shared.bar(), 7);
+    let erased: &dyn deflect::Reflect = &shared;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "&Foo { bar: 7 }");
+
+    let value: deflect::value::DynRef = value.try_into()?;
+    let value: deflect::value::Struct = value.downcast_value()?.try_into()?;
+    assert_eq!(value.schema().name()?.to_string_lossy()?, "Foo");
+
+    let unique: &mut dyn Trait = &mut data;
+    let erased: &dyn deflect::Reflect = &unique;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "&mut Foo { bar: 7 }");
+
+    let any: Box<dyn std::any::Any> = Box::new(42u64);
+    let erased: &dyn deflect::Reflect = &any;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "box 42");
+
+    Ok(())
+}
+
+#[test]
+fn closure() -> Result<(), Box<dyn std::error::Error>> {
+    let x = 1u32;
+    let name = "hi";
+    let line = line!() + 1;
+    let callback = move || x + name.len() as u32;
+    assert_eq!(callback(), 3);
+
+    let erased: &dyn deflect::Reflect = &callback;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Struct = value.try_into()?;
+    assert!(value.schema().is_closure()?);
+
+    let captured = value.field("x")?.expect("missing capture").value()?;
+    assert_eq!(u32::try_from(captured)?, 1);
+
+    let rendered = value.to_string();
+    assert!(
+        rendered.starts_with("{closure@tests/reflect.rs:"),
+        "{rendered}"
+    );
+    assert!(
+        rendered.ends_with(&format!(":{line}}} {{ x: 1, name: \"hi\" }}"))
+            || rendered.ends_with(&format!(":{line}}} {{ name: \"hi\", x: 1 }}")),
+        "{rendered}"
+    );
+    Ok(())
+}
+
+mod future {
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    };
+
+    /// A future that is pending on its first poll.
+    struct Yield(bool);
+
+    impl Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if std::mem::replace(&mut self.0, true) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    async fn parked(a: u32) -> u32 {
+        let b = a * 2;
+        Yield(false).await;
+        a + b
+    }
+
+    #[test]
+    fn unresumed() -> Result<(), Box<dyn std::error::Error>> {
+        let future = parked(7);
+        let erased: &dyn deflect::Reflect = &future;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        let value: deflect::value::Enum = value.try_into()?;
+        assert!(value.schema().is_future()?);
+        assert_eq!(value.suspend_point()?, None);
+        assert_eq!(
+            value.to_string(),
+            "{async fn body of reflect::future::parked()}::Unresumed { a: 7 }"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn suspended() -> Result<(), Box<dyn std::error::Error>> {
+        let mut future = Box::pin(parked(7));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+
+        let erased: &dyn deflect::Reflect = &*future;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        let value: deflect::value::Enum = value.try_into()?;
+        assert_eq!(value.suspend_point()?, Some(0));
+
+        let variant = value.variant()?;
+        let b = variant.field("b")?.expect("missing local").value()?;
+        assert_eq!(u32::try_from(b)?, 14);
+        Ok(())
+    }
+
+    #[test]
+    fn async_block() -> Result<(), Box<dyn std::error::Error>> {
+        let a = 3u8;
+        let line = line!() + 1;
+        let mut future = Box::pin(async move {
+            let b = a + 1;
+            Yield(false).await;
+            a + b
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+
+        let erased: &dyn deflect::Reflect = &*future;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        let value: deflect::value::Enum = value.try_into()?;
+        assert!(value.schema().is_coroutine()?);
+        assert!(value.schema().is_future()?);
+        assert_eq!(value.suspend_point()?, Some(0));
+
+        let rendered = value.to_string();
+        assert!(
+            rendered.starts_with(&format!("{{async block@tests/reflect.rs:{line}}}::Suspend0 {{")),
+            "{rendered}"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn vector() -> Result<(), Box<dyn std::error::Error>> {
+    use std::arch::x86_64::{_mm_set_epi32, _mm_set_ps};
+    let context = deflect::default_provider()?;
+
+    let data = unsafe { _mm_set_ps(4.0, 3.0, 2.0, 1.0) };
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Vector = value.try_into()?;
+    assert_eq!(value.schema().lane_count()?, 4);
+    assert_eq!(value.to_string(), "f32x4(1.0, 2.0, 3.0, 4.0)");
+
+    let data = unsafe { _mm_set_epi32(0, 1, 0, 2) };
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "i64x2(2, 1)");
+    Ok(())
+}
+
+#[test]
+fn type_aliases() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    // a C `struct Aliased { count_t count; const volatile limit_t limit;
+    // uint32_t plain; const bool flag; }`, whose `limit_t` aliases `size_t`.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let bool = fixture.base_type(unit, "bool", gimli::DW_ATE_boolean, 1);
+    let wrap = |fixture: &mut Fixture, tag, name: Option<&str>, r#type| {
+        let mut attrs = vec![(gimli::DW_AT_type, AttributeValue::UnitRef(r#type))];
+        if let Some(name) = name {
+            attrs.push((gimli::DW_AT_name, AttributeValue::String(name.into())));
+        }
+        fixture.entry(unit, None, tag, attrs)
+    };
+    let count_t = wrap(&mut fixture, gimli::DW_TAG_typedef, Some("count_t"), u32);
+    let size_t = wrap(&mut fixture, gimli::DW_TAG_typedef, Some("size_t"), u32);
+    let limit_t = wrap(&mut fixture, gimli::DW_TAG_typedef, Some("limit_t"), size_t);
+    let volatile = wrap(&mut fixture, gimli::DW_TAG_volatile_type, None, limit_t);
+    let const_volatile = wrap(&mut fixture, gimli::DW_TAG_const_type, None, volatile);
+    let const_bool = wrap(&mut fixture, gimli::DW_TAG_const_type, None, bool);
+    let aliased = fixture.structure(unit, None, "Aliased", 13);
+    fixture.member(unit, aliased, "count", count_t, 0);
+    fixture.member(unit, aliased, "limit", const_volatile, 4);
+    fixture.member(unit, aliased, "plain", u32, 8);
+    fixture.member(unit, aliased, "flag", const_bool, 12);
+
+    let mut bytes = Aligned([0; 13]);
+    for (index, n) in [5u32, 6, 7].iter().enumerate() {
+        bytes.0[index * 4..][..4].copy_from_slice(&n.to_ne_bytes());
+    }
+    bytes.0[12] = 1;
+    let provider = fixture.provider((unit, aliased));
+    let erased: &dyn deflect::Reflect = &bytes;
+    let value: deflect::value::Struct<_> = erased.reflect(&provider)?.try_into()?;
+    // aliases and qualifiers are transparent.
+    assert_eq!(
+        value.to_string(),
+        "Aliased { count: 5, limit: 6, plain: 7, flag: true }"
+    );
+
+    // the outermost alias of each field, through any qualifiers.
+    let fields = value
+        .schema()
+        .fields()?
+        .iter()?
+        .map(|field| {
+            let field = field?;
+            let alias = match field.type_alias()? {
+                Some(alias) => Some(alias.to_string_lossy()?.into_owned()),
+                None => None,
+            };
+            Ok((field.r#type()?.to_string(), alias))
+        })
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+    let alias = |alias: &str| Some(alias.to_owned());
+    assert_eq!(
+        fields,
+        [
+            ("u32".to_owned(), alias("count_t")),
+            ("u32".to_owned(), alias("limit_t")),
+            ("u32".to_owned(), None),
+            ("bool".to_owned(), None),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn manually_drop() -> Result<(), Box<dyn std::error::Error>> {
+    let data = std::mem::ManuallyDrop::new(5u32);
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::ManuallyDrop = value.try_into()?;
+    assert_eq!(u32::try_from(value.value()?)?, 5);
+    assert_eq!(value.to_string(), "5");
+
+    // shaped and named like a `ManuallyDrop`, but not one.
+    #[allow(dead_code)]
+    struct ManuallyDrop<T> {
+        value: T,
+    }
+
+    let erased: &dyn deflect::Reflect = &ManuallyDrop { value: 5u32 };
+    let value = erased.reflect(&context)?;
+    assert!(matches!(value, deflect::Value::Struct(_)));
+    Ok(())
+}
+
+#[test]
+fn maybe_uninit() -> Result<(), Box<dyn std::error::Error>> {
+    let data = std::mem::MaybeUninit::new(7u16);
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::MaybeUninit = value.try_into()?;
+    assert_eq!(
+        value.to_string(),
+        "MaybeUninit<u16>(<possibly uninitialized>)"
+    );
+    let inner = unsafe { value.assume_init_value()? };
+    assert_eq!(u16::try_from(inner)?, 7);
+
+    // shaped and named like a `MaybeUninit`, but a union, which cannot be
+    // reflected.
+    #[allow(dead_code)]
+    union MaybeUninit<T: Copy> {
+        uninit: (),
+        value: T,
+    }
+
+    let erased: &dyn deflect::Reflect = &MaybeUninit { value: 7u16 };
+    assert!(erased.reflect(&context).is_err());
+    Ok(())
+}
+
+#[test]
+fn matches_derived_debug() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Pair<T>(T, char);
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Event {
+        Quit,
+        Key(char),
+        Move { x: f32, y: f64 },
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Log {
+        pairs: [Pair<u8>; 2],
+        events: [Event; 3],
+        point: (f32, i8, (bool,)),
+        label: Option<&'static str>,
+    }
+
+    let data = Log {
+        pairs: [Pair(1, 'a'), Pair(2, '\n')],
+        events: [Event::Quit, Event::Key('"'), Event::Move { x: 1.0, y: -0.5 }],
+        point: (2.0, -3, (true,)),
+        label: Some("tab\t"),
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), format!("{data:?}"));
+    assert_eq!(value.to_owned_snapshot()?.to_string(), format!("{data:?}"));
+    Ok(())
+}
+
+#[test]
+fn alternate_display() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u16),
+        Empty,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Scene {
+        shapes: [Shape; 3],
+        origin: (i8, i8),
+        empty: [u8; 0],
+    }
+
+    let data = Scene {
+        shapes: [Shape::Circle { r: 3 }, Shape::Square(4), Shape::Empty],
+        origin: (-1, 1),
+        empty: [],
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    assert_eq!(format!("{value:#}"), format!("{data:#?}"));
+    assert_eq!(format!("{:#}", value.to_owned_snapshot()?), format!("{data:#?}"));
+
+    let data = Box::new(Shape::Circle { r: 3 });
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    assert_eq!(format!("{value:#}"), "box Circle {\n    r: 3,\n}");
+    let snapshot = value.to_owned_snapshot()?;
+    assert_eq!(format!("{snapshot:#}"), "box Circle {\n    r: 3,\n}");
+    Ok(())
+}
+
+#[test]
+fn assert_reflect_matches_debug() {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u16),
+    }
+
+    deflect::assert_reflect_matches_debug!([Shape::Circle { r: 3 }, Shape::Square(4)]);
+    deflect::assert_reflect_matches_debug!((Some('x'), -1i64, "str"));
+    deflect::assert_reflect_matches_debug!(Box::new(Shape::Circle { r: 3 }), "box " => "");
+    deflect::assert_reflect_matches_debug(&Box::new(Shape::Square(4)), &[("box ", "")]);
+
+    let mismatch = std::panic::catch_unwind(|| {
+        deflect::assert_reflect_matches_debug!(Box::new(Shape::Square(4)));
+    });
+    assert!(mismatch.is_err());
+}
+
+#[test]
+fn dbg() {
+    #[allow(dead_code)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[allow(dead_code)]
+    struct View<'a> {
+        name: &'a str,
+    }
+
+    let config = Config { retries: 3 };
+    // borrowed values are returned as they were passed.
+    let borrowed: &Config = deflect::dbg!(&config);
+    assert!(std::ptr::eq(borrowed, &config));
+
+    // values need not be `'static`.
+    let name = String::from("local");
+    let view = deflect::dbg!(View { name: &name });
+    assert_eq!(view.name, "local");
+    let erased: &dyn deflect::Reflect = &view;
+    assert_eq!(format!("{erased:?}"), "View { name: \"local\" }");
+
+    let (retries, borrowed, view) = deflect::dbg!(config.retries, &config, view);
+    assert_eq!(retries, 3);
+    assert!(std::ptr::eq(borrowed, &config));
+    assert_eq!(view.name, "local");
+}
+
+#[test]
+fn format_errors() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(C, packed)]
+    struct Packed {
+        a: u8,
+        b: u32,
+    }
+
+    // ensures that `b`, at an offset of one byte, is misaligned.
+    #[repr(align(4))]
+    struct Aligned(Packed);
+
+    let data = Aligned(Packed { a: 1, b: 2 });
+    let erased: &dyn deflect::Reflect = &data.0;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    // the misaligned `b` cannot be read as a `u32`
+    assert_eq!(
+        value.to_string(),
+        "Packed { a: 1, b: <error: primitive is misaligned> }"
+    );
+    let mut rendered = String::new();
+    let err = value.try_format(&mut rendered).unwrap_err();
+    assert_eq!(err.to_string(), "primitive is misaligned");
+
+    let a = value.get_path("a")?.expect("missing field");
+    let mut rendered = String::new();
+    a.try_format(&mut rendered)?;
+    assert_eq!(rendered, "1");
+
+    #[allow(dead_code)]
+    union Raw {
+        bits: u32,
+        float: f32,
+    }
+
+    #[allow(dead_code)]
+    struct Tagged {
+        kind: u8,
+        raw: Raw,
+    }
+
+    let data = Tagged {
+        kind: 1,
+        raw: Raw { bits: 2 },
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    // the placeholder names both the type being read, and why it could not be.
+    let rendered = value.to_string();
+    assert!(rendered.starts_with("Tagged { kind: 1, raw: <error: while reading `"), "{rendered}");
+    assert!(
+        rendered.ends_with(": cannot reflect debuginfo entries of tag DW_TAG_union_type> }"),
+        "{rendered}"
+    );
+    Ok(())
+}
+
+#[test]
+fn schema_error_context() -> Result<(), Box<dyn std::error::Error>> {
+    // unions, other than `MaybeUninit`s, cannot be reflected.
+    #[allow(dead_code)]
+    union Raw {
+        bits: u32,
+    }
+
+    #[allow(dead_code)]
+    struct Holder {
+        raw: Raw,
+    }
+
+    let context = deflect::default_provider()?;
+    let is_unsupported = |err: &deflect::anyhow::Error| {
+        matches!(
+            err.root_cause().downcast_ref::<deflect::ErrorKind>(),
+            Some(deflect::ErrorKind::UnsupportedLayout { .. })
+        )
+    };
+
+    let err = deflect::reflect_type::<Raw, _>(&context).unwrap_err();
+    let context_of_err = err.to_string();
+    assert!(
+        context_of_err.starts_with("while reading `Raw` (DIE 0x"),
+        "{context_of_err}"
+    );
+    assert!(is_unsupported(&err));
+
+    let deflect::schema::Type::Struct(holder) = deflect::reflect_type::<Holder, _>(&context)?
+    else {
+        panic!("expected a struct");
+    };
+    let field = holder.fields()?.iter()?.try_next()?.expect("missing field");
+    let err = field.r#type().unwrap_err();
+    let mut chain = err.chain().map(ToString::to_string);
+    let outer = chain.next().unwrap();
+    assert!(
+        outer.starts_with("while reading field `raw` of `Holder` (DIE 0x"),
+        "{outer}"
+    );
+    let inner = chain.next().unwrap();
+    assert!(inner.starts_with("while reading `Raw` (DIE 0x"), "{inner}");
+    assert!(is_unsupported(&err));
+    Ok(())
+}
+
+#[test]
+fn cycles() -> Result<(), Box<dyn std::error::Error>> {
+    use std::cell::Cell;
+
+    #[allow(dead_code)]
+    struct Node<'a> {
+        id: u8,
+        next: Cell<Option<&'a Node<'a>>>,
+    }
+
+    let a = Node {
+        id: 1,
+        next: Cell::new(None),
+    };
+    let b = Node {
+        id: 2,
+        next: Cell::new(Some(&a)),
+    };
+    a.next.set(Some(&b));
+
+    let erased: &dyn deflect::Reflect = &a;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    // the cycle is closed by `b`'s pointer back to the root.
+    let cell = |inner: &str| format!("Cell {{ value: UnsafeCell {{ value: {inner} }} }}");
+    let cycle = format!("Some(&<cycle -> {:#x}>)", &a as *const Node as usize);
+    let b_once = format!("Some(&Node {{ id: 2, next: {} }})", cell(&cycle));
+    assert_eq!(
+        value.to_string(),
+        format!("Node {{ id: 1, next: {} }}", cell(&b_once))
+    );
+
+    // values that are merely shared, rather than cyclic, are displayed in
+    // full each time.
+    let leaf = 5u8;
+    let data = (&leaf, &leaf);
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "(&5, &5)");
+    Ok(())
+}
+
+#[test]
+fn transparent_newtypes() -> Result<(), Box<dyn std::error::Error>> {
+    struct UserId(#[allow(dead_code)] u64);
+
+    struct Session {
+        #[allow(dead_code)]
+        user: UserId,
+    }
+
+    let data = Session { user: UserId(42) };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "Session { user: UserId(42) }");
+
+    let options = deflect::FormatOptions::new().transparent_newtypes(true);
+    assert_eq!(
+        value.display_with(options).to_string(),
+        "Session { user: 42 }"
+    );
+
+    let value: deflect::value::Struct = value.try_into()?;
+    let user = value.field("user")?.expect("missing field").value()?;
+    let user: deflect::value::Struct = user.try_into()?;
+    assert!(user.schema().transparent_inner()?.is_some());
+    Ok(())
+}
+
+#[test]
+fn format_limits() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Inner {
+        id: u16,
+    }
+
+    #[allow(dead_code)]
+    struct Outer {
+        inner: Inner,
+        samples: [u8; 1000],
+    }
+
+    let data = Outer {
+        inner: Inner { id: 255 },
+        samples: [7; 1000],
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let samples = value.get_path("samples")?.expect("missing field");
+
+    let options = deflect::FormatOptions::new().max_elements(2);
+    assert_eq!(
+        samples.display_with(options).to_string(),
+        "[7, 7, … 998 more]"
+    );
+
+    let options = deflect::FormatOptions::new().max_depth(1).max_elements(0);
+    assert_eq!(
+        value.display_with(options).to_string(),
+        "Outer { inner: Inner { id: … }, samples: [… 1000 more] }"
+    );
+
+    let options = deflect::FormatOptions::new().max_bytes(11);
+    assert_eq!(value.display_with(options).to_string(), "Outer { inn…");
+
+    let options = deflect::FormatOptions::new()
+        .hex_integers(true)
+        .field_types(true);
+    let inner = value.get_path("inner")?.expect("missing field");
+    assert_eq!(
+        inner.display_with(options).to_string(),
+        "Inner { id: u16 = 0xff }"
+    );
+    Ok(())
+}
+
+mod variant {
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u32),
+        Empty,
+    }
+
+    #[test]
+    fn struct_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = &Shape::Circle { r: 3 };
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), "Circle { r: 3 }");
+
+        let value: deflect::value::Enum = value.try_into()?;
+        let variant = value.variant()?;
+        assert_eq!(variant.name()?.to_string_lossy()?, "Circle");
+        let r = variant.field("r")?.expect("missing field").value()?;
+        assert_eq!(u8::try_from(r)?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn tuple_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = &Shape::Square(4);
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), "Square(4)");
+        Ok(())
+    }
+
+    #[test]
+    fn unit_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = &Shape::Empty;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), "Empty");
+        Ok(())
+    }
+
+    #[test]
+    fn prelude_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let data: Result<u32, ()> = Ok(42);
+        let erased: &dyn deflect::Reflect = &data;
+        let context = deflect::default_provider()?;
+        let value = erased.reflect(&context)?;
+        assert_eq!(value.to_string(), "Ok(42)");
+        Ok(())
+    }
+}
+
+#[test]
+fn field_by_name() -> Result<(), Box<dyn std::error::Error>> {
+    struct Config {
+        #[allow(dead_code)]
+        port: u16,
+        #[allow(dead_code)]
+        verbose: bool,
+    }
+
+    let data = Config {
+        port: 8080,
+        verbose: true,
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Struct = value.try_into()?;
+
+    let port = value.field_value("port")?.expect("missing field");
+    assert_eq!(u16::try_from(port)?, 8080);
+    let verbose = value.field_value("verbose")?.expect("missing field");
+    assert!(bool::try_from(verbose)?);
+    assert!(value.field_value("missing")?.is_none());
+
+    let schema = value.schema();
+    let field = schema.field_by_name("verbose")?.expect("missing field");
+    assert_eq!(field.r#type()?.to_string(), "bool");
+    assert!(schema.field_by_name("missing")?.is_none());
+    Ok(())
+}
+
+#[test]
+fn fallible_iteration() -> Result<(), Box<dyn std::error::Error>> {
+    struct Pair {
+        #[allow(dead_code)]
+        a: u8,
+        #[allow(dead_code)]
+        b: u16,
+    }
+
+    let erased: &dyn deflect::Reflect = &Pair { a: 1, b: 2 };
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Struct = value.try_into()?;
+
+    let mut fields = value.fields()?;
+    let names = fields
+        .iter()?
+        .map(|field| Ok(field?.schema().name()?.to_string_lossy()?.into_owned()))
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+    assert_eq!(names, ["a", "b"]);
+
+    let erased: &dyn deflect::Reflect = &Some(1u8);
+    let value = erased.reflect(&context)?;
+    let value: deflect::value::Enum = value.try_into()?;
+    let mut variants = value.schema().variants()?;
+    let variants = variants.iter()?.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(variants.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn get_path() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Server {
+        addr: &'static str,
+        port: u16,
+    }
+
+    #[allow(dead_code)]
+    enum Mode {
+        Primary { backup: Box<Server> },
+        Standby(u8),
+    }
+
+    #[allow(dead_code)]
+    struct Config {
+        servers: [Server; 3],
+        fallback: &'static [(u8, u8)],
+        mode: Mode,
+    }
+
+    let data = Config {
+        servers: [
+            Server { addr: "a", port: 1 },
+            Server { addr: "b", port: 2 },
+            Server { addr: "c", port: 3 },
+        ],
+        fallback: &[(4, 5), (6, 7)],
+        mode: Mode::Primary {
+            backup: Box::new(Server { addr: "d", port: 8 }),
+        },
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+
+    let get = |path| -> Result<Option<String>, Box<dyn std::error::Error>> {
+        Ok(value.get_path(path)?.map(|value| value.to_string()))
+    };
+
+    assert_eq!(get("servers[2].addr")?.as_deref(), Some("\"c\""));
+    assert_eq!(get("fallback[1].0")?.as_deref(), Some("6"));
+    assert_eq!(get("mode::Primary.backup.port")?.as_deref(), Some("8"));
+    assert_eq!(get("mode.backup.*.addr")?.as_deref(), Some("\"d\""));
+    assert_eq!(get("mode::Standby.0")?, None);
+    assert_eq!(get("servers[3]")?, None);
+    assert_eq!(get("missing")?, None);
+
+    assert!(value.get_path("").is_err());
+    assert!(value.get_path("servers[").is_err());
+    assert!(value.get_path("servers[x]").is_err());
+    assert!(value.get_path("mode::").is_err());
+    Ok(())
+}
+
+#[test]
+fn slice_indexing() -> Result<(), Box<dyn std::error::Error>> {
+    let context = deflect::default_provider()?;
+
+    let data: &[u16] = &[10, 20, 30];
+    let erased: &dyn deflect::Reflect = &data;
+    let value: deflect::value::Slice = erased.reflect(&context)?.try_into()?;
+    assert!(!value.is_empty()?);
+    assert_eq!(u16::try_from(value.get(2)?.expect("in bounds"))?, 30);
+    assert!(value.get(3)?.is_none());
+    let mut sum = 0;
+    for elt in &value {
+        sum += u16::try_from(elt?)?;
+    }
+    assert_eq!(sum, 60);
+    assert_eq!(value.into_iter().count(), 3);
+
+    let data: [u8; 0] = [];
+    let erased: &dyn deflect::Reflect = &data;
+    let value: deflect::value::Array = erased.reflect(&context)?.try_into()?;
+    assert!(value.is_empty()?);
+    assert!(value.get(0)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn downcast_ref() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, PartialEq)]
+    struct Meters {
+        value: u32,
+    }
+
+    #[allow(dead_code)]
+    struct Feet {
+        value: u32,
+    }
+
+    #[allow(dead_code)]
+    struct Wrapper {
+        length: Meters,
+        count: i32,
+    }
+
+    let data = Wrapper {
+        length: Meters { value: 42 },
+        count: 7,
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+
+    let length = value.get_path("length")?.expect("missing field");
+    assert_eq!(length.downcast_ref::<Meters>(&context)?, &Meters { value: 42 });
+    assert!(length.downcast_ref::<Feet>(&context).is_err());
+    assert!(length.downcast_ref::<u32>(&context).is_err());
+
+    let count = value.get_path("count")?.expect("missing field");
+    assert_eq!(count.downcast_ref::<i32>(&context)?, &7);
+    assert!(count.downcast_ref::<u32>(&context).is_err());
+
+    assert!(length.is::<Meters>(&context)?);
+    assert!(!length.is::<Feet>(&context)?);
+    let r#type = deflect::reflect_type::<Wrapper, _>(&context)?;
+    assert!(r#type.matches::<Wrapper, _>(&context)?);
+    assert!(!r#type.matches::<Meters, _>(&context)?);
+    Ok(())
+}
+
+#[test]
+fn owned_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u16),
+    }
+
+    #[allow(dead_code)]
+    struct Scene {
+        name: String,
+        shapes: Box<[Shape]>,
+        origin: &'static (i8, i8),
+        hidden: Option<Box<u32>>,
+    }
+
+    let context = deflect::default_provider()?;
+    let (snapshot, expected) = {
+        let data = Scene {
+            name: "scene".to_owned(),
+            shapes: vec![Shape::Circle { r: 3 }, Shape::Square(4)].into_boxed_slice(),
+            origin: &(-1, 1),
+            hidden: Some(Box::new(5)),
+        };
+        let erased: &dyn deflect::Reflect = &data;
+        let value = erased.reflect(&context)?;
+        let origin = value.get_path("origin")?.expect("missing field");
+        (value.to_owned_snapshot()?, origin.to_string())
+    };
+
+    let snapshot = std::thread::spawn(move || snapshot).join().unwrap();
+    let deflect::Snapshot::Struct { name, fields } = &snapshot else {
+        panic!("expected a struct, found {snapshot:?}")
+    };
+    assert!(name.ends_with("Scene"));
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[2].1.to_string(), expected);
+    assert_eq!(
+        fields[1].1.to_string(),
+        "box [Circle { r: 3 }, Square(4)]"
+    );
+    assert_eq!(fields[3].1.to_string(), "Some(box 5)");
+    Ok(())
+}
+
+#[test]
+fn structural_eq() -> Result<(), Box<dyn std::error::Error>> {
+    use std::borrow::Cow;
+
+    // no `PartialEq`; the padding after `tag` may differ between instances.
+    #[allow(dead_code)]
+    struct Record {
+        tag: u8,
+        id: u64,
+        name: &'static str,
+        aliases: Cow<'static, str>,
+        scores: &'static [u16],
+    }
+
+    let context = deflect::default_provider()?;
+    let a = Record {
+        tag: 1,
+        id: 2,
+        name: "a",
+        aliases: Cow::Borrowed("x"),
+        scores: &[3, 4],
+    };
+    let b = Record {
+        tag: 1,
+        id: 2,
+        name: String::from("a").leak(),
+        aliases: Cow::Owned("x".to_owned()),
+        scores: vec![3, 4].leak(),
+    };
+    let c = Record {
+        tag: 1,
+        id: 5,
+        name: "a",
+        aliases: Cow::Borrowed("x"),
+        scores: &[3, 4],
+    };
+
+    let a_erased: &dyn deflect::Reflect = &a;
+    let b_erased: &dyn deflect::Reflect = &b;
+    let c_erased: &dyn deflect::Reflect = &c;
+    let a = a_erased.reflect(&context)?;
+    let b = b_erased.reflect(&context)?;
+    let c = c_erased.reflect(&context)?;
+    assert!(a.structural_eq(&b)?);
+    assert!(b.structural_eq(&a)?);
+    assert!(!a.structural_eq(&c)?);
+
+    let name = a.get_path("name")?.expect("missing field");
+    assert!(!a.structural_eq(&name)?);
+    Ok(())
+}
+
+#[test]
+fn structural_hash() -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    #[allow(dead_code)]
+    struct Sample {
+        flag: bool,
+        reading: f64,
+        label: &'static str,
+        raw: *const u8,
+    }
+
+    let context = deflect::default_provider()?;
+    let hash = |sample: &Sample| -> Result<u64, Box<dyn std::error::Error>> {
+        let erased: &dyn deflect::Reflect = sample;
+        let mut hasher = DefaultHasher::new();
+        erased.reflect(&context)?.structural_hash(&mut hasher)?;
+        Ok(hasher.finish())
+    };
+
+    let a = Sample {
+        flag: true,
+        reading: 0.0,
+        label: "a",
+        raw: &0,
+    };
+    let b = Sample {
+        flag: true,
+        reading: -0.0,
+        label: String::from("a").leak(),
+        raw: std::ptr::null(),
+    };
+    let c = Sample {
+        flag: true,
+        reading: 1.0,
+        label: "a",
+        raw: std::ptr::null(),
+    };
+    assert_eq!(hash(&a)?, hash(&b)?);
+    assert_ne!(hash(&a)?, hash(&c)?);
+    Ok(())
+}
+
+#[test]
+fn diff() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    enum Mode {
+        Active { since: u32 },
+        Idle,
+    }
+
+    #[allow(dead_code)]
+    struct State {
+        port: u16,
+        peers: &'static [(u8, bool)],
+        mode: Mode,
+        backup: Mode,
+    }
+
+    let context = deflect::default_provider()?;
+    let before = State {
+        port: 80,
+        peers: &[(1, true), (2, true)],
+        mode: Mode::Active { since: 10 },
+        backup: Mode::Idle,
+    };
+    let after = State {
+        port: 8080,
+        peers: &[(1, false), (2, true), (3, true)],
+        mode: Mode::Active { since: 20 },
+        backup: Mode::Idle,
+    };
+    let before: &dyn deflect::Reflect = &before;
+    let after: &dyn deflect::Reflect = &after;
+    let before = before.reflect(&context)?;
+    let after = after.reflect(&context)?;
+
+    let diff = deflect::diff(&before, &after)?;
+    let paths: Vec<_> = diff.changes().iter().map(|change| change.path()).collect();
+    assert_eq!(paths, ["port", "peers[0].1", "peers[2]", "mode.since"]);
+    assert_eq!(
+        diff.to_string(),
+        "--- a\n+++ b\n\
+         @@ port @@\n-80\n+8080\n\
+         @@ peers[0].1 @@\n-true\n+false\n\
+         @@ peers[2] @@\n+(3, true)\n\
+         @@ mode.since @@\n-10\n+20\n"
+    );
+    assert!(deflect::diff(&before, &before)?.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn to_json() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::serde_json::json;
+    use deflect::{JsonEnums, JsonOptions};
+
+    #[allow(dead_code)]
+    enum State {
+        Idle,
+        Active { since: u8 },
+    }
+
+    #[allow(dead_code)]
+    struct Point(i8, i8);
+
+    #[allow(dead_code)]
+    struct Record {
+        name: &'static str,
+        point: Point,
+        states: [State; 2],
+        digest: [u8; 4],
+        large: u128,
+        missing: Option<&'static u8>,
+        present: Option<&'static u8>,
+    }
+
+    let context = deflect::default_provider()?;
+    let data = Record {
+        name: "record",
+        point: Point(-1, 2),
+        states: [State::Idle, State::Active { since: 10 }],
+        digest: [0xde, 0xad, 0xbe, 0xef],
+        large: u128::MAX,
+        missing: None,
+        present: Some(&7),
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+
+    assert_eq!(
+        value.to_json()?,
+        json!({
+            "name": "record",
+            "point": [-1, 2],
+            "states": ["Idle", {"Active": {"since": 10}}],
+            "digest": [0xde, 0xad, 0xbe, 0xef],
+            "large": u128::MAX.to_string(),
+            "missing": null,
+            "present": 7,
+        })
+    );
+
+    let options = JsonOptions::new()
+        .enums(JsonEnums::Adjacent)
+        .hex_bytes_above(2)
+        .follow_references(false);
+    let json = value.to_json_with(&options)?;
+    assert_eq!(
+        json["states"],
+        json!([
+            {"variant": "Idle"},
+            {"variant": "Active", "fields": {"since": 10}},
+        ])
+    );
+    assert_eq!(json["digest"], json!("deadbeef"));
+    let address = format!("{:#x}", data.present.unwrap() as *const u8 as usize);
+    assert_eq!(json["present"], json!(address));
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn to_schema_json() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::serde_json::json;
+
+    #[allow(dead_code)]
+    enum Shape {
+        Empty,
+        Square { side: u16 },
+    }
+
+    #[allow(dead_code)]
+    struct Node {
+        shape: Shape,
+        points: [u8; 3],
+        next: Option<Box<Node>>,
+    }
+
+    let context = deflect::default_provider()?;
+    let schema = deflect::reflect_type::<Node, _>(&context)?.to_schema_json()?;
+    let node = "reflect::to_schema_json::Node";
+    let shape = "reflect::to_schema_json::Shape";
+    let boxed = "alloc::boxed::Box<reflect::to_schema_json::Node>";
+    let option = format!("core::option::Option<{boxed}>");
+    assert_eq!(schema["root"], json!(node));
+
+    let types = &schema["types"];
+    assert_eq!(types[node]["kind"], json!("struct"));
+    // `rustc` chooses field offsets, so only names and types are compared.
+    let fields: Vec<_> = types[node]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| (field["name"].clone(), field["type"].clone()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (json!("shape"), json!(shape)),
+            (json!("points"), json!("[u8; 3]")),
+            (json!("next"), json!(option)),
+        ]
+    );
+
+    assert_eq!(types[shape]["kind"], json!("enum"));
+    assert_eq!(types[shape]["variants"][0]["name"], json!("Empty"));
+    assert_eq!(types[shape]["variants"][1]["name"], json!("Square"));
+    assert_eq!(types[shape]["variants"][1]["fields"][0]["type"], json!("u16"));
+    assert_eq!(
+        types["[u8; 3]"],
+        json!({
+            "kind": "array",
+            "name": "[u8; 3]",
+            "element": "u8",
+            "len": 3,
+            "size": 3,
+            "align": 1,
+        })
+    );
+    assert_eq!(types[option.as_str()]["kind"], json!("enum"));
+    // recursive types refer to one another by name.
+    assert_eq!(types[boxed]["pointee"], json!(node));
+    assert_eq!(types["u8"]["kind"], json!("primitive"));
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn to_msgpack() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::rmp::encode;
+
+    #[allow(dead_code)]
+    enum State {
+        Idle,
+        Active { since: u8 },
+    }
+
+    #[allow(dead_code)]
+    struct Record {
+        name: &'static str,
+        point: (i8, i8),
+        states: [State; 2],
+        digest: [u8; 2],
+        present: Option<&'static u8>,
+    }
+
+    let context = deflect::default_provider()?;
+    let data = Record {
+        name: "record",
+        point: (-1, 2),
+        states: [State::Idle, State::Active { since: 10 }],
+        digest: [0xbe, 0xef],
+        present: Some(&7),
+    };
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+
+    let mut expected = vec![];
+    encode::write_map_len(&mut expected, 5)?;
+    encode::write_str(&mut expected, "name")?;
+    encode::write_str(&mut expected, "record")?;
+    encode::write_str(&mut expected, "point")?;
+    encode::write_array_len(&mut expected, 2)?;
+    encode::write_sint(&mut expected, -1)?;
+    encode::write_sint(&mut expected, 2)?;
+    encode::write_str(&mut expected, "states")?;
+    encode::write_array_len(&mut expected, 2)?;
+    encode::write_str(&mut expected, "Idle")?;
+    encode::write_map_len(&mut expected, 1)?;
+    encode::write_str(&mut expected, "Active")?;
+    encode::write_map_len(&mut expected, 1)?;
+    encode::write_str(&mut expected, "since")?;
+    encode::write_uint(&mut expected, 10)?;
+    encode::write_str(&mut expected, "digest")?;
+    encode::write_bin(&mut expected, &[0xbe, 0xef])?;
+    encode::write_str(&mut expected, "present")?;
+    encode::write_uint(&mut expected, 7)?;
+
+    assert_eq!(value.to_msgpack()?, expected);
+    Ok(())
+}
+
+#[test]
+fn visit() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::anyhow::Error;
+    use deflect::value::{Field, Value, Variant, Visit};
+
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u16),
+    }
+
+    #[allow(dead_code)]
+    struct Scene<'a> {
+        shapes: [Shape; 2],
+        origin: &'a (i32, i32),
+    }
+
+    /// Collects the path to, and the display of, each primitive leaf.
+    #[derive(Default)]
+    struct Leaves {
+        path: Vec<String>,
+        leaves: Vec<String>,
+    }
+
+    impl<'value, 'dwarf, P> Visit<'value, 'dwarf, P> for Leaves
+    where
+        P: deflect::DebugInfoProvider,
+    {
+        fn visit_primitive(&mut self, value: &Value<'value, 'dwarf, P>) -> Result<(), Error> {
+            let path = self.path.join(".");
+            self.leaves.push(format!("{path} = {value}"));
+            Ok(())
+        }
+
+        fn visit_variant(&mut self, variant: &Variant<'value, 'dwarf, P>) -> Result<(), Error> {
+            let name = variant.name()?;
+            self.path.push(name.to_string_lossy()?.into_owned());
+            deflect::value::walk_variant(self, variant)?;
+            self.path.pop();
+            Ok(())
+        }
+
+        fn visit_field(&mut self, field: &Field<'value, 'dwarf, P>) -> Result<(), Error> {
+            let name = field.schema().name()?;
+            self.path.push(name.to_string_lossy()?.into_owned());
+            deflect::value::walk_field(self, field)?;
+            self.path.pop();
+            Ok(())
+        }
+
+        fn visit_slice_element(
+            &mut self,
+            index: usize,
+            value: &Value<'value, 'dwarf, P>,
+        ) -> Result<(), Error> {
+            self.path.push(index.to_string());
+            self.visit_value(value)?;
+            self.path.pop();
+            Ok(())
+        }
+    }
+
+    let origin = (-1, 2);
+    let scene = Scene {
+        shapes: [Shape::Circle { r: 3 }, Shape::Square(4)],
+        origin: &origin,
+    };
+    let erased: &dyn deflect::Reflect = &scene;
+    let context = deflect::default_provider()?;
+    let value = erased.reflect(&context)?;
+    let mut visitor = Leaves::default();
+    value.visit(&mut visitor)?;
+    assert_eq!(
+        visitor.leaves,
+        [
+            "shapes.0.Circle.r = 3",
+            "shapes.1.Square.__0 = 4",
+            "origin.__0 = -1",
+            "origin.__1 = 2",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn leaves() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Server<'a> {
+        name: &'a str,
+        port: Option<u16>,
+    }
+
+    #[allow(dead_code)]
+    struct Config<'a> {
+        servers: [Server<'a>; 2],
+        retries: (u8, bool),
+    }
+
+    let config = Config {
+        servers: [
+            Server {
+                name: "a",
+                port: Some(80),
+            },
+            Server {
+                name: "b",
+                port: None,
+            },
+        ],
+        retries: (3, true),
+    };
+    let erased: &dyn deflect::Reflect = &config;
+    let context = deflect::default_provider()?;
+    let leaves = erased
+        .reflect(&context)?
+        .leaves()
+        .map(|leaf| {
+            let (path, value) = leaf?;
+            Ok((path.to_string(), value.to_string()))
+        })
+        .collect::<Result<Vec<_>, deflect::anyhow::Error>>()?;
+    assert_eq!(
+        leaves,
+        [
+            ("servers[0].name", "\"a\""),
+            ("servers[0].port::Some.0", "80"),
+            ("servers[1].name", "\"b\""),
+            ("retries.0", "3"),
+            ("retries.1", "true"),
+        ]
+        .map(|(path, value)| (path.to_owned(), value.to_owned()))
+    );
+
+    // paths are displayed in the syntax accepted by `get_path`.
+    let value = erased.reflect(&context)?;
+    for (path, expected) in leaves {
+        let leaf = value.get_path(&path)?.expect("the path should resolve");
+        assert_eq!(leaf.to_string(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn exhaustive_match() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::Value;
+
+    /// Classifies `value` by matching exhaustively on its kind.
+    fn kind(value: &Value) -> &'static str {
+        match value {
+            Value::bool(_) | Value::char(_) | Value::unit(_) => "primitive",
+            Value::f32(_) | Value::f64(_) => "float",
+            Value::i8(_)
+            | Value::i16(_)
+            | Value::i32(_)
+            | Value::i64(_)
+            | Value::i128(_)
+            | Value::isize(_)
+            | Value::u8(_)
+            | Value::u16(_)
+            | Value::u32(_)
+            | Value::u64(_)
+            | Value::u128(_)
+            | Value::usize(_) => "integer",
+            Value::str(_) => "str",
+            Value::Atomic(_) | Value::NonZero(_) | Value::ManuallyDrop(_) => "wrapper",
+            Value::MaybeUninit(_) => "uninit",
+            Value::Array(_) | Value::Vector(_) => "array",
+            Value::Slice(_) | Value::BoxedSlice(_) => "slice",
+            Value::SharedRef(_)
+            | Value::UniqueRef(_)
+            | Value::Box(_)
+            | Value::BoxedDyn(_)
+            | Value::DynRef(_) => "ref",
+            Value::ConstPtr(_) | Value::MutPtr(_) | Value::NonNull(_) => "ptr",
+            Value::Function(_) => "function",
+            Value::Struct(_) => "struct",
+            Value::Enum(_) => "enum",
+        }
+    }
+
+    let context = deflect::default_provider()?;
+    let data = (1u8, 2.0f32, "three", [4u8], Some(5u8), &6u8);
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    let kinds = ["0", "1", "2", "3", "4", "5"]
+        .map(|field| Ok(kind(&value.get_path(field)?.unwrap())))
+        .into_iter()
+        .collect::<Result<Vec<_>, deflect::anyhow::Error>>()?;
+    assert_eq!(kinds, ["integer", "float", "str", "array", "enum", "ref"]);
+    Ok(())
+}
+
+#[test]
+fn find_type() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Config {
+        retries: u8,
+        verbose: bool,
+    }
+
+    let context = deflect::default_provider()?;
+    // ensure that `Config` has debug info.
+    let _ = deflect::reflect_type::<Config, _>(&context)?;
+
+    let r#type = deflect::find_type(&context, "reflect::find_type::Config")?;
+    let deflect::schema::Type::Struct(r#type) = r#type else {
+        panic!("expected a struct, found {type}");
+    };
+    assert_eq!(r#type.size()?, 2);
+    let fields = {
+        let mut fields = r#type.fields()?;
+        let mut fields = fields.iter()?;
+        let mut names = vec![];
+        while let Some(field) = fields.try_next()? {
+            names.push(field.name()?.to_string_lossy()?.into_owned());
+        }
+        names
+    };
+    assert_eq!(fields, ["retries", "verbose"]);
+
+    // primitives are not namespaced.
+    assert!(matches!(
+        deflect::find_type(&context, "u8")?,
+        deflect::schema::Type::u8(_)
+    ));
+
+    let err = deflect::find_type(&context, "reflect::find_type::Missing").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "could not find a type named `reflect::find_type::Missing`"
+    );
+    Ok(())
+}
+
+#[test]
+fn memoized_types() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Config {
+        retries: u8,
+        name: &'static str,
+    }
+
+    let context = deflect::default_provider()?;
+    let described = |r#type: deflect::schema::Type<'_, _>| -> Result<_, deflect::anyhow::Error> {
+        Ok((r#type.to_string(), r#type.size()?))
+    };
+    let first = described(deflect::reflect_type::<Config, _>(&context)?)?;
+    assert_eq!(first.1, 24);
+    // the second reflection is memoized.
+    let second = described(deflect::reflect_type::<Config, _>(&context)?)?;
+    assert_eq!(first, second);
+
+    // finding types by name reads (and then frees) sections of its own, which
+    // must not be mistaken for those of the provider.
+    let found = deflect::find_type(&context, "reflect::memoized_types::Config")?;
+    assert_eq!(described(found)?, first);
+    let third = described(deflect::reflect_type::<Config, _>(&context)?)?;
+    assert_eq!(third, first);
+
+    // types are memoized per thread.
+    let other = std::thread::spawn(|| -> Result<_, deflect::anyhow::Error> {
+        let context = deflect::default_provider()?;
+        let r#type = deflect::reflect_type::<Config, _>(&context)?;
+        Ok((r#type.to_string(), r#type.size()?))
+    })
+    .join()
+    .unwrap()?;
+    assert_eq!(other, first);
+    Ok(())
+}
+
+#[test]
+fn find_types() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{schema::Type, TypeFilter};
+
+    #[allow(dead_code)]
+    struct Small(u8);
+
+    #[allow(dead_code)]
+    #[repr(align(64))]
+    struct Padded(u8);
+
+    #[allow(dead_code)]
+    enum Choice {
+        A,
+        B(u32),
+    }
+
+    let context = deflect::default_provider()?;
+    // ensure that these types have debug info.
+    let _ = deflect::reflect_type::<Small, _>(&context)?;
+    let _ = deflect::reflect_type::<Padded, _>(&context)?;
+    let _ = deflect::reflect_type::<Choice, _>(&context)?;
+
+    let names = |filter: TypeFilter| -> Result<Vec<String>, deflect::anyhow::Error> {
+        let mut names = vec![];
+        for r#type in deflect::find_types(&context, &filter)? {
+            let name = match &r#type {
+                Type::Struct(r#type) => r#type.name()?.to_string_lossy()?.into_owned(),
+                Type::Enum(r#type) => r#type.name().to_string_lossy()?.into_owned(),
+                r#type => r#type.to_string(),
+            };
+            // skip the closures of this test.
+            if !name.starts_with('{') {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    };
+
+    let all = TypeFilter::new().name("reflect::find_types::*");
+    assert_eq!(names(all.clone())?, ["Choice", "Padded", "Small"]);
+    assert_eq!(names(all.clone().size(4..))?, ["Choice", "Padded"]);
+    assert_eq!(names(all.clone().align(64..))?, ["Padded"]);
+    assert_eq!(names(all.clone().in_crate("core"))?, Vec::<String>::new());
+    assert_eq!(
+        names(TypeFilter::new().in_crate("reflect").name("*::Padded"))?,
+        ["Padded"]
+    );
+    Ok(())
+}
+
+#[test]
+fn layout_report() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Inner {
+        a: u8,
+        b: u32,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Outer {
+        flag: bool,
+        inner: Inner,
+        tail: u16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum Shape {
+        Circle { r: u32 },
+        Empty,
+    }
+
+    let context = deflect::default_provider()?;
+    let layout = deflect::reflect_type::<Outer, _>(&context)?.layout_report()?;
+    assert_eq!((layout.size(), layout.align()), (16, Some(4)));
+    assert_eq!((layout.holes(), layout.padding()), (1, 5));
+    assert_eq!(layout.tail_padding(), 2);
+    let inner = &layout.members()[1];
+    assert_eq!(
+        (inner.name(), inner.offset(), inner.size()),
+        ("inner", 4, 8)
+    );
+    assert_eq!(inner.layout().map(|inner| inner.padding()), Some(3));
+    assert_eq!(
+        layout.to_string(),
+        "struct Outer {
+    /*    0    1 */ flag: bool,
+    /* XXX 3 bytes hole */
+    /*    4    8 */ inner: struct Inner {
+        /*    0    1 */ a: u8,
+        /* XXX 3 bytes hole */
+        /*    4    4 */ b: u32,
+    },
+    /*   12    2 */ tail: u16,
+    /* XXX 2 bytes tail padding */
+} /* size: 16, align: 4, holes: 1, padding: 5 */"
+    );
+
+    let layout = deflect::reflect_type::<Shape, _>(&context)?.layout_report()?;
+    assert_eq!(
+        layout.to_string(),
+        "enum Shape {
+    Circle {
+        /*    0    1 */ <discriminant>: u8,
+        /* XXX 3 bytes hole */
+        /*    4    4 */ r: u32,
+    },
+    Empty {
+        /*    0    1 */ <discriminant>: u8,
+        /* XXX 7 bytes tail padding */
+    },
+} /* size: 8, align: 4 */"
+    );
+    Ok(())
+}
+
+#[test]
+fn field_layout() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Header {
+        tag: u8,
+        len: u32,
+        body: [u16; 3],
+    }
+
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<Header, _>(&context)?;
+    let deflect::schema::Type::Struct(r#type) = r#type else {
+        panic!("expected a struct");
+    };
+    assert_eq!(r#type.size()?, std::mem::size_of::<Header>() as u64);
+    assert_eq!(r#type.align()?, Some(std::mem::align_of::<Header>() as u64));
+
+    let mut layout = vec![];
+    let mut fields = r#type.fields()?;
+    let mut fields = fields.iter()?;
+    while let Some(field) = fields.try_next()? {
+        let name = field.name()?.to_string_lossy()?.into_owned();
+        let offset = field.offset()?.address(0)?;
+        layout.push((name, offset, field.size()?, field.align()?));
+    }
+    assert_eq!(
+        layout,
+        [
+            ("tag".to_owned(), 0, 1, Some(1)),
+            ("len".to_owned(), 4, 4, Some(4)),
+            ("body".to_owned(), 8, 6, Some(2)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn variant_lookup() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { r: u8 },
+        Square(u16),
+        Empty,
+    }
+
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        High = 5,
+    }
+
+    let name = |variant: Option<deflect::schema::Variant<_>>| -> Result<_, deflect::anyhow::Error> {
+        Ok(match variant {
+            Some(variant) => Some(variant.name()?.to_string_lossy()?.into_owned()),
+            None => None,
+        })
+    };
+
+    let context = deflect::default_provider()?;
+    let deflect::schema::Type::Enum(shape) = deflect::reflect_type::<Shape, _>(&context)? else {
+        panic!("expected an enum");
+    };
+    assert_eq!(shape.variant_count()?, 3);
+    assert_eq!(
+        name(shape.variant_by_name("Square")?)?.as_deref(),
+        Some("Square")
+    );
+    assert_eq!(name(shape.variant_by_name("Triangle")?)?, None);
+
+    let deflect::schema::Type::Enum(level) = deflect::reflect_type::<Level, _>(&context)? else {
+        panic!("expected an enum");
+    };
+    assert_eq!(level.variant_count()?, 2);
+    assert_eq!(
+        name(level.variant_for_discriminant(5)?)?.as_deref(),
+        Some("High")
+    );
+    assert_eq!(
+        name(level.variant_for_discriminant(1)?)?.as_deref(),
+        Some("Low")
+    );
+    assert_eq!(name(level.variant_for_discriminant(3)?)?, None);
+
+    // values of fieldless enums resolve to the variant of their discriminant.
+    let erased: &dyn deflect::Reflect = &Level::Low;
+    assert_eq!(erased.reflect(&context)?.to_string(), "Low");
+    Ok(())
+}
+
+#[test]
+fn to_rust_source() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum Kind {
+        A = 1,
+        B = 5,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Header {
+        tag: u8,
+        len: u32,
+        kind: Kind,
+        next: Option<&'static Header>,
+    }
+
+    let context = deflect::default_provider()?;
+    let source = deflect::reflect_type::<Header, _>(&context)?.to_rust_source()?;
+    assert_eq!(
+        source,
+        "\
+/// `reflect::to_rust_source::Header`
+#[repr(C, align(8))]
+pub struct Header {
+    pub tag: u8,
+    _pad0: [u8; 3],
+    pub len: u32,
+    pub kind: Kind,
+    _pad1: [u8; 7],
+    pub next: Option_Header,
+}
+const _: () = assert!(core::mem::size_of::<Header>() == 24);
+const _: () = assert!(core::mem::align_of::<Header>() == 8);
+const _: () = assert!(core::mem::offset_of!(Header, tag) == 0);
+const _: () = assert!(core::mem::offset_of!(Header, len) == 4);
+const _: () = assert!(core::mem::offset_of!(Header, kind) == 8);
+const _: () = assert!(core::mem::offset_of!(Header, next) == 16);
+
+/// `reflect::to_rust_source::Kind`
+#[repr(u8)]
+pub enum Kind {
+    A = 1,
+    B = 5,
+}
+const _: () = assert!(core::mem::size_of::<Kind>() == 1);
+
+/// `core::option::Option<&reflect::to_rust_source::Header>`
+///
+/// An opaque enum of the variants `None`, `Some`.
+#[repr(C, align(8))]
+pub struct Option_Header {
+    _opaque: [u8; 8],
+}
+const _: () = assert!(core::mem::size_of::<Option_Header>() == 8);
+"
+    );
+    Ok(())
+}
+
+#[test]
+fn abi_diff() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{AbiChange, DebugInfo, DebugInfoProvider};
+
+    mod before {
+        #[allow(dead_code)]
+        #[repr(C)]
+        pub struct Header {
+            pub tag: u8,
+            pub len: u16,
+        }
+
+        #[allow(dead_code)]
+        #[repr(u8)]
+        pub enum Kind {
+            A = 1,
+            B = 5,
+        }
+
+        #[allow(dead_code)]
+        pub struct Legacy(pub u8);
+    }
+
+    mod after {
+        #[allow(dead_code)]
+        #[repr(C)]
+        pub struct Header {
+            pub tag: u8,
+            pub len: u32,
+            pub flags: u8,
+        }
+
+        #[allow(dead_code)]
+        #[repr(u8)]
+        pub enum Kind {
+            A = 1,
+            B = 6,
+            C = 7,
+        }
+    }
+
+    /// Presents the types of the module `from` as the types of
+    /// `reflect::abi_diff`, as if they were described by another build.
+    #[derive(Clone)]
+    struct Build<P> {
+        provider: P,
+        from: &'static str,
+    }
+
+    unsafe impl<P: DebugInfoProvider> DebugInfoProvider for Build<P> {
+        type Reader = P::Reader;
+
+        fn info_for(
+            &self,
+            fn_addr: u64,
+        ) -> Result<DebugInfo<'_, Self::Reader>, deflect::anyhow::Error> {
+            self.provider.info_for(fn_addr)
+        }
+
+        fn info_for_types(
+            &self,
+            matches: &mut dyn FnMut(&[u8]) -> bool,
+        ) -> Result<Vec<DebugInfo<'_, Self::Reader>>, deflect::anyhow::Error> {
+            self.provider.info_for_types(&mut |name| {
+                match name.strip_prefix(self.from.as_bytes()) {
+                    Some(name) => matches(&[&b"reflect::abi_diff::"[..], name].concat()),
+                    None => false,
+                }
+            })
+        }
+    }
+
+    let context = deflect::default_provider()?;
+    // ensure that the types have debug info.
+    let _ = deflect::reflect_type::<before::Header, _>(&context)?;
+    let _ = deflect::reflect_type::<before::Kind, _>(&context)?;
+    let _ = deflect::reflect_type::<before::Legacy, _>(&context)?;
+    let _ = deflect::reflect_type::<after::Header, _>(&context)?;
+    let _ = deflect::reflect_type::<after::Kind, _>(&context)?;
+
+    let a = Build {
+        provider: context,
+        from: "reflect::abi_diff::before::",
+    };
+    let b = Build {
+        provider: context,
+        from: "reflect::abi_diff::after::",
+    };
+    assert!(deflect::abi_diff(&a, &a)?.is_empty());
+
+    let diff = deflect::abi_diff(&a, &b)?;
+    assert_eq!(
+        diff.to_string(),
+        "\
+~ reflect::abi_diff::Header: size 4 -> 12
+~ reflect::abi_diff::Header: align 2 -> 4
++ reflect::abi_diff::Header.flags
+~ reflect::abi_diff::Header.len: offset 2 -> 4
+~ reflect::abi_diff::Header.len: type u16 -> u32
+~ reflect::abi_diff::Kind::B: discriminant 5 -> 6
++ reflect::abi_diff::Kind::C
+- reflect::abi_diff::Legacy
+"
+    );
+    assert_eq!(
+        diff.changes().last(),
+        Some(&AbiChange::TypeRemoved {
+            type_name: "reflect::abi_diff::Legacy".to_owned()
+        })
+    );
+
+    // only the types that match the filter are compared.
+    let filter = deflect::TypeFilter::new().name("*::Kind");
+    let diff = deflect::abi_diff_with(&a, &b, &filter)?;
+    assert_eq!(diff.changes().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn display_name() -> Result<(), Box<dyn std::error::Error>> {
+    fn name_of<T, P: deflect::DebugInfoProvider>(
+        _: &T,
+        provider: &P,
+    ) -> Result<String, deflect::anyhow::Error> {
+        deflect::reflect_type::<T, _>(provider)?.display_name()
+    }
+
+    let context = deflect::default_provider()?;
+    // default allocators are elided.
+    assert_eq!(
+        name_of(&Vec::<String>::new(), &context)?,
+        "Vec<alloc::string::String>"
+    );
+    assert_eq!(name_of(&Box::new(1u8), &context)?, "alloc::boxed::Box<u8>");
+    assert_eq!(name_of(&[0u16; 4], &context)?, "[u16; 4]");
+
+    // environments are named like their bodies.
+    let closure = || ();
+    assert_eq!(
+        name_of(&Some(closure), &context)?,
+        "Option<reflect::display_name::{closure#0}>"
+    );
+    Ok(())
+}
+
+#[test]
+fn generic_params() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Pair<K, V> {
+        key: K,
+        value: V,
+    }
+
+    fn params_of<T, P: deflect::DebugInfoProvider>(
+        provider: &P,
+    ) -> Result<Vec<(String, String)>, deflect::anyhow::Error> {
+        let mut params = vec![];
+        for param in deflect::reflect_type::<T, _>(provider)?.generic_params()? {
+            let name = param.name()?.to_string_lossy()?.into_owned();
+            params.push((name, param.r#type()?.display_name()?));
+        }
+        Ok(params)
+    }
+
+    let context = deflect::default_provider()?;
+    let param = |name: &str, r#type: &str| (name.to_owned(), r#type.to_owned());
+    assert_eq!(
+        params_of::<Vec<String>, _>(&context)?,
+        [param("T", "String"), param("A", "Global")]
+    );
+    assert_eq!(params_of::<Option<u8>, _>(&context)?, [param("T", "u8")]);
+    assert_eq!(
+        params_of::<Pair<&str, [u8; 2]>, _>(&context)?,
+        [param("K", "&str"), param("V", "[u8; 2]")]
+    );
+    assert_eq!(params_of::<u8, _>(&context)?, []);
+    assert_eq!(params_of::<Box<u8>, _>(&context)?, []);
+    Ok(())
+}
+
+#[test]
+fn repeated_reflection() -> Result<(), Box<dyn std::error::Error>> {
+    struct Meters(#[allow(dead_code)] u32);
+    struct Feet(#[allow(dead_code)] u32);
+
+    let context = deflect::default_provider()?;
+    // the debuginfo of each type is resolved once, and then reused.
+    for i in 0..3 {
+        let meters: &dyn deflect::Reflect = &Meters(i);
+        let feet: &dyn deflect::Reflect = &Feet(i * 3);
+        let meters = meters.reflect(&context)?.to_string();
+        let feet = feet.reflect(&context)?.to_string();
+        assert_eq!(meters, format!("Meters({i})"));
+        assert_eq!(feet, format!("Feet({})", i * 3));
+    }
+    Ok(())
+}
+
+#[test]
+fn compile_accessor() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Stats {
+        count: u16,
+        latency: [u64; 4],
+    }
+
+    #[allow(dead_code)]
+    struct Sample {
+        id: u32,
+        stats: Stats,
+        pair: (u8, i16),
+        next: Option<Box<Sample>>,
+    }
+
+    let sample = |id, latency| Sample {
+        id,
+        stats: Stats { count: 4, latency },
+        pair: (1, -2),
+        next: None,
+    };
+
+    let context = deflect::default_provider()?;
+    let deflect::schema::Type::Struct(schema) = deflect::reflect_type::<Sample, _>(&context)?
+    else {
+        panic!("expected a struct")
+    };
+    let latency = schema
+        .compile_accessor("stats.latency[2]")?
+        .expect("resolves");
+    let pair = schema.compile_accessor("pair.1")?.expect("resolves");
+    assert_eq!(latency.r#type().display_name()?, "u64");
+
+    for data in [sample(1, [5, 6, 7, 8]), sample(2, [9, 10, 11, 12])] {
+        let erased: &dyn deflect::Reflect = &data;
+        let value: deflect::value::Struct = erased.reflect(&context)?.try_into()?;
+        assert_eq!(u64::try_from(latency.get(&value)?)?, data.stats.latency[2]);
+        assert_eq!(i16::try_from(pair.get(&value)?)?, -2);
+    }
+
+    assert!(schema.compile_accessor("stats.missing")?.is_none());
+    assert!(schema.compile_accessor("stats.latency[4]")?.is_none());
+    assert!(schema.compile_accessor("id.0")?.is_none());
+    assert!(schema.compile_accessor("next.*.id").is_err());
+    assert!(schema.compile_accessor("next::Some.0").is_err());
+
+    // accessors only apply to values of the struct they were compiled for.
+    let erased: &dyn deflect::Reflect = &(1u8, 2i16);
+    let other: deflect::value::Struct = erased.reflect(&context)?.try_into()?;
+    assert!(pair.get(&other).is_err());
+    Ok(())
+}
+
+/// Runs `objcopy` with `args`; fails if it is unavailable (e.g., on macOS),
+/// in which case the tests that edit this test's binary are skipped.
+fn objcopy(args: &[&std::ffi::OsStr]) -> bool {
+    let status = std::process::Command::new("objcopy").args(args).status();
+    let ran = matches!(status, Ok(status) if status.success());
+    if !ran {
+        eprintln!("skipping a test, as `objcopy` is unavailable");
+    }
+    ran
+}
+
+/// Runs the test `name` in `exe`, a copy of this test's binary edited by
+/// `objcopy`, with `DEFLECT_RERUN` set to `mode`; `exe` is then removed.
+fn rerun(exe: &std::path::Path, name: &str, mode: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::process::Command::new(exe)
+        .args(["--exact", name])
+        .env("DEFLECT_RERUN", mode)
+        .output();
+    std::fs::remove_file(exe)?;
+    let output = output?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    Ok(())
+}
+
+/// Types are looked up in the `.debug_pubtypes` index of a binary, rather
+/// than in the units it covers; this test runs again in copies of its binary
+/// with an index that covers every unit, and lists `PubtypesTarget` or not.
+#[test]
+fn name_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::{AttributeValue, EndianSlice, NativeEndian};
+
+    #[allow(dead_code)]
+    struct PubtypesTarget {
+        value: u32,
+    }
+    const NAME: &str = "reflect::name_indexes::PubtypesTarget";
+
+    let context = deflect::default_provider()?;
+    // ensure that `PubtypesTarget` has debug info.
+    let _ = deflect::reflect_type::<PubtypesTarget, _>(&context)?;
+
+    if let Some(mode) = std::env::var_os("DEFLECT_RERUN") {
+        let found = deflect::find_type(&context, NAME);
+        if mode == "listed" {
+            assert_eq!(found?.size()?, 4);
+        } else {
+            // with `rayon`, the types of all units are indexed, too.
+            assert_eq!(found.is_ok(), cfg!(feature = "rayon"));
+        }
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()?;
+    let temp = |name: &str| {
+        let name = format!("deflect-{name}-{}", std::process::id());
+        std::env::temp_dir().join(name)
+    };
+    let (info, abbrev, str) = (temp("info"), temp("abbrev"), temp("str"));
+    let section_file = |name: &str, path: &std::path::Path| format!("{name}={}", path.display());
+    let dumped = objcopy(&[
+        "--dump-section".as_ref(),
+        section_file(".debug_info", &info).as_ref(),
+        "--dump-section".as_ref(),
+        section_file(".debug_abbrev", &abbrev).as_ref(),
+        "--dump-section".as_ref(),
+        section_file(".debug_str", &str).as_ref(),
+        exe.as_ref(),
+        temp("dumped").as_ref(),
+    ]);
+    std::fs::remove_file(temp("dumped")).ok();
+    if !dumped {
+        return Ok(());
+    }
+    let read = |path: &std::path::Path| {
+        let data = std::fs::read(path);
+        std::fs::remove_file(path).ok();
+        data
+    };
+    let (info, abbrev, str) = (read(&info)?, read(&abbrev)?, read(&str)?);
+    let debug_info = gimli::DebugInfo::new(&info, NativeEndian);
+    let debug_abbrev = gimli::DebugAbbrev::new(&abbrev, NativeEndian);
+    let debug_str = gimli::DebugStr::new(&str, NativeEndian);
+
+    // the offset, length and root of each unit, and the unit and offset of
+    // the first definition of `PubtypesTarget`.
+    let mut units = vec![];
+    let mut target = None;
+    let mut headers = debug_info.units();
+    while let Some(header) = headers.next()? {
+        let offset = header.offset().as_debug_info_offset().unwrap();
+        let root = gimli::UnitOffset(header.header_size());
+        units.push((offset, header.length_including_self(), root));
+        let abbreviations = header.abbreviations(&debug_abbrev)?;
+        let mut entries = header.entries(&abbreviations);
+        while let Some((_, entry)) = entries.next_dfs()? {
+            if target.is_some() || entry.tag() != gimli::DW_TAG_structure_type {
+                continue;
+            }
+            let name = match entry.attr_value(gimli::DW_AT_name)? {
+                Some(AttributeValue::DebugStrRef(name)) => debug_str.get_str(name)?,
+                Some(AttributeValue::String(name)) => name,
+                _ => continue,
+            };
+            if name == EndianSlice::new(b"PubtypesTarget", NativeEndian) {
+                target = Some((offset, entry.offset()));
+            }
+        }
+    }
+    let target = target.expect("missing `PubtypesTarget`");
+
+    for mode in ["listed", "unlisted"] {
+        let mut pubtypes = vec![];
+        for &(unit, length, root) in &units {
+            let mut set = vec![];
+            set.extend(2u16.to_ne_bytes());
+            set.extend((unit.0 as u32).to_ne_bytes());
+            set.extend((length as u32).to_ne_bytes());
+            let mut item = |offset: gimli::UnitOffset, name: &str| {
+                set.extend((offset.0 as u32).to_ne_bytes());
+                set.extend(name.as_bytes());
+                set.push(0);
+            };
+            // each unit is covered by the index once it lists any entry.
+            item(root, "<root>");
+            if mode == "listed" && unit == target.0 {
+                item(target.1, NAME);
+            }
+            set.extend(0u32.to_ne_bytes());
+            pubtypes.extend((set.len() as u32).to_ne_bytes());
+            pubtypes.extend(set);
+        }
+        let section = temp(&format!("pubtypes-{mode}"));
+        std::fs::write(&section, pubtypes)?;
+        let indexed = temp(&format!("indexed-{mode}"));
+        let added = objcopy(&[
+            "--add-section".as_ref(),
+            section_file(".debug_pubtypes", &section).as_ref(),
+            exe.as_ref(),
+            indexed.as_ref(),
+        ]);
+        std::fs::remove_file(&section)?;
+        if added {
+            rerun(&indexed, "name_indexes", mode)?;
+        }
+    }
+    Ok(())
+}
+
+/// Types are looked up from several threads at once; with `rayon`, each waits
+/// for the names of all types to be indexed in the background.
+#[test]
+fn concurrent_type_lookups() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct One(u8);
+
+    #[allow(dead_code)]
+    struct Two(u16);
+
+    #[allow(dead_code)]
+    struct Four(u32);
+
+    let context = deflect::default_provider()?;
+    // ensure that these types have debug info.
+    let _ = deflect::reflect_type::<One, _>(&context)?;
+    let _ = deflect::reflect_type::<Two, _>(&context)?;
+    let _ = deflect::reflect_type::<Four, _>(&context)?;
+
+    std::thread::scope(|scope| {
+        let lookups: Vec<_> = (0..4)
+            .map(|_| {
+                scope.spawn(|| -> Result<(), deflect::anyhow::Error> {
+                    let context = deflect::default_provider()?;
+                    for (name, size) in [("One", 1), ("Two", 2), ("Four", 4)] {
+                        let name = format!("reflect::concurrent_type_lookups::{name}");
+                        assert_eq!(deflect::find_type(&context, &name)?.size()?, size);
+                    }
+                    let missing = "reflect::concurrent_type_lookups::Missing";
+                    assert!(deflect::find_type(&context, missing).is_err());
+                    Ok(())
+                })
+            })
+            .collect();
+        for lookup in lookups {
+            lookup.join().unwrap()?;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn index_cache() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Cached {
+        hits: u32,
+    }
+
+    let dir = std::env::temp_dir().join(format!("deflect-index-cache-{}", std::process::id()));
+    std::env::set_var("XDG_CACHE_HOME", &dir);
+
+    let context = deflect::default_provider()?.with_index_cache();
+    let _ = deflect::reflect_type::<Cached, _>(&context)?;
+    let r#type = deflect::find_type(&context, "reflect::index_cache::Cached")?;
+    assert_eq!(r#type.size()?, 4);
+    assert!(deflect::find_type(&context, "reflect::index_cache::Missing").is_err());
+
+    // the index is saved under the build ID of this binary.
+    let saved = std::fs::read_dir(dir.join("deflect"))?.count();
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(saved, 1);
+    Ok(())
+}
+
+#[test]
+fn send() -> Result<(), Box<dyn std::error::Error>> {
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[allow(dead_code)]
+    struct Event {
+        level: u8,
+        message: &'static str,
+    }
+
+    let context = deflect::default_provider()?;
+    let event = Event {
+        level: 3,
+        message: "ready",
+    };
+    let erased: &dyn deflect::Reflect = &event;
+    let value = erased.reflect(&context)?;
+    let r#type = deflect::reflect_type::<Event, _>(&context)?;
+    assert_send(&value);
+    assert_send(&r#type);
+
+    // e.g., as in a logging pipeline that formats values on another thread.
+    let (value, r#type) = std::thread::scope(|scope| {
+        let value = scope.spawn(move || value.to_string());
+        let r#type = scope.spawn(move || r#type.to_string());
+        (value.join(), r#type.join())
+    });
+    assert_eq!(value.unwrap(), r#"Event { level: 3, message: "ready" }"#);
+    assert_eq!(r#type.unwrap(), "Event { level: u8, message: &str }");
+    Ok(())
+}
+
+#[test]
+fn error_kind() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{DebugInfoProvider, ErrorKind};
+
+    let context = deflect::default_provider()?;
+    let Err(err) = context.info_for(1) else {
+        panic!("expected no debuginfo at address 0x1")
+    };
+    assert!(matches!(
+        err.downcast_ref::<ErrorKind>(),
+        Some(ErrorKind::SymbolNotFound { addr: 1 })
+    ));
+    Ok(())
+}
+
+/// Reflecting code without debuginfo fails with `NoDebugInfo`; this test runs
+/// again in a copy of its binary stripped of debuginfo.
+#[test]
+fn missing_debug_info() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::ErrorKind;
+
+    if std::env::var_os("DEFLECT_RERUN").is_some() {
+        let context = deflect::default_provider()?;
+        let erased: &dyn deflect::Reflect = &42u8;
+        let Err(err) = erased.reflect(&context) else {
+            panic!("reflected code without debuginfo")
+        };
+        let Some(ErrorKind::NoDebugInfo { suggestion }) = err.downcast_ref::<ErrorKind>() else {
+            panic!("expected `NoDebugInfo`, found: {err:#}")
+        };
+        assert!(suggestion.contains("`debug = true`"), "{suggestion}");
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()?;
+    let stripped = std::env::temp_dir().join(format!("deflect-stripped-{}", std::process::id()));
+    if objcopy(&["--strip-debug".as_ref(), exe.as_ref(), stripped.as_ref()]) {
+        rerun(&stripped, "missing_debug_info", "stripped")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn malformed_schemas() {
+    use gimli::write::{AttributeValue, Expression, UnitEntryId, UnitId};
+
+    /// Why reflecting and formatting an 8-byte value of the type built by
+    /// `root` fails.
+    fn error(root: impl FnOnce(&mut Fixture, UnitId) -> UnitEntryId) -> String {
+        let mut fixture = Fixture::new();
+        let unit = fixture.unit(8, None);
+        let root = root(&mut fixture, unit);
+        let provider = fixture.provider((unit, root));
+        let erased: &dyn deflect::Reflect = &Aligned([0; 8]);
+        let err = erased
+            .reflect(&provider)
+            .and_then(|value| value.try_format(&mut String::new()))
+            .expect_err("reflected a malformed type");
+        err.root_cause().to_string()
+    }
+
+    /// Adds an enum whose discriminant is of `discriminant`, and whose only
+    /// variant has a field if `field`.
+    fn choice(
+        fixture: &mut Fixture,
+        unit: UnitId,
+        discriminant: UnitEntryId,
+        field: bool,
+    ) -> UnitEntryId {
+        let choice = fixture.structure(unit, None, "Choice", 8);
+        let variant_part = fixture.entry(unit, Some(choice), gimli::DW_TAG_variant_part, vec![]);
+        let discr = fixture.member(unit, variant_part, "__discr", discriminant, 0);
+        fixture
+            .dwarf
+            .units
+            .get_mut(unit)
+            .get_mut(variant_part)
+            .set(gimli::DW_AT_discr, AttributeValue::UnitRef(discr));
+        let attrs = vec![(gimli::DW_AT_discr_value, AttributeValue::Udata(0))];
+        let variant = fixture.entry(unit, Some(variant_part), gimli::DW_TAG_variant, attrs);
+        if field {
+            let only = fixture.structure(unit, Some(choice), "Only", 8);
+            fixture.member(unit, variant, "Only", only, 0);
+        }
+        choice
+    }
+
+    // a field located by an expression that reads memory.
+    let located = error(|fixture, unit| {
+        let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+        let located = fixture.structure(unit, None, "Located", 8);
+        let mut location = Expression::new();
+        location.op(gimli::DW_OP_dup);
+        location.op_deref();
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String("x".into())),
+            (gimli::DW_AT_type, AttributeValue::UnitRef(u32)),
+            (
+                gimli::DW_AT_data_member_location,
+                AttributeValue::Exprloc(location),
+            ),
+        ];
+        fixture.entry(unit, Some(located), gimli::DW_TAG_member, attrs);
+        located
+    });
+    assert_eq!(
+        located,
+        r#"attribute Some("DW_AT_data_member_location") had an unexpected form"#
+    );
+
+    // a field whose offset, within its base, overflows.
+    let overflowing = error(|fixture, unit| {
+        let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+        let base = fixture.structure(unit, None, "Base", 4);
+        fixture.member(unit, base, "x", u32, u64::MAX);
+        let derived = fixture.structure(unit, None, "Derived", 8);
+        let attrs = vec![
+            (gimli::DW_AT_type, AttributeValue::UnitRef(base)),
+            (gimli::DW_AT_data_member_location, AttributeValue::Udata(4)),
+        ];
+        fixture.entry(unit, Some(derived), gimli::DW_TAG_inheritance, attrs);
+        derived
+    });
+    assert_eq!(overflowing, "arithmetic operation overflowed");
+
+    // an enum whose discriminant is not an integer.
+    let float = error(|fixture, unit| {
+        let f32 = fixture.base_type(unit, "f32", gimli::DW_ATE_float, 4);
+        choice(fixture, unit, f32, true)
+    });
+    assert_eq!(float, "type mismatch; expected `an integer`, found `f32`");
+
+    // an enum variant without a field.
+    let empty = error(|fixture, unit| {
+        let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+        choice(fixture, unit, u32, false)
+    });
+    assert_eq!(
+        empty,
+        r#"DIE did not have expected child of tag Some("DW_TAG_member")"#
+    );
+}
+
+#[test]
+fn optimized_layouts() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    // a `Sparse { a: u8, b: u32, c: u8 }` whose debuginfo, as may that of
+    // optimized builds, omits the sizes of `Sparse` and `u32`.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let u8 = fixture.base_type(unit, "u8", gimli::DW_ATE_unsigned, 1);
+    let attrs = vec![
+        (gimli::DW_AT_name, AttributeValue::String("u32".into())),
+        (
+            gimli::DW_AT_encoding,
+            AttributeValue::Encoding(gimli::DW_ATE_unsigned),
+        ),
+    ];
+    let u32 = fixture.entry(unit, None, gimli::DW_TAG_base_type, attrs);
+    let attrs = vec![(gimli::DW_AT_name, AttributeValue::String("Sparse".into()))];
+    let sparse = fixture.entry(unit, None, gimli::DW_TAG_structure_type, attrs);
+    fixture.member(unit, sparse, "a", u8, 0);
+    fixture.member(unit, sparse, "b", u32, 4);
+    fixture.member(unit, sparse, "c", u8, 8);
+
+    let mut bytes = Aligned([1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0]);
+    bytes.0[4..8].copy_from_slice(&2u32.to_ne_bytes());
+    let provider = fixture.provider((unit, sparse));
+    let erased: &dyn deflect::Reflect = &bytes;
+    let value: deflect::value::Struct<_> = erased.reflect(&provider)?.try_into()?;
+    assert_eq!(value.to_string(), "Sparse { a: 1, b: 2, c: 3 }");
+
+    // the size of a struct is the extent of its fields, padded to the
+    // alignment of its most-aligned field.
+    let schema = value.schema();
+    assert_eq!(schema.align()?, Some(4));
+    assert_eq!(schema.size()?, 12);
+    let b = value.field("b")?.expect("missing field");
+    assert_eq!(b.schema().r#type()?.size()?, 4);
+    Ok(())
+}
+
+#[test]
+fn origins() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    // the declaration of a `struct Spec { x: u32, y: u32 }`, which names it,
+    // and the definition that completes it, whose `x` is an instance of the
+    // declared `x`.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let attrs = vec![
+        (gimli::DW_AT_name, AttributeValue::String("Spec".into())),
+        (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+        (gimli::DW_AT_declaration, AttributeValue::Flag(true)),
+    ];
+    let declaration = fixture.entry(unit, None, gimli::DW_TAG_structure_type, attrs);
+    let attrs = vec![
+        (gimli::DW_AT_name, AttributeValue::String("x".into())),
+        (gimli::DW_AT_type, AttributeValue::UnitRef(u32)),
+    ];
+    let declared_x = fixture.entry(unit, Some(declaration), gimli::DW_TAG_member, attrs);
+    let specification = AttributeValue::UnitRef(declaration);
+    let attrs = vec![(gimli::DW_AT_specification, specification)];
+    let definition = fixture.entry(unit, None, gimli::DW_TAG_structure_type, attrs);
+    let origin = AttributeValue::UnitRef(declared_x);
+    let attrs = vec![
+        (gimli::DW_AT_abstract_origin, origin),
+        (gimli::DW_AT_data_member_location, AttributeValue::Udata(0)),
+    ];
+    fixture.entry(unit, Some(definition), gimli::DW_TAG_member, attrs);
+    fixture.member(unit, definition, "y", u32, 4);
+
+    let mut bytes = Aligned([0; 8]);
+    bytes.0[..4].copy_from_slice(&7u32.to_ne_bytes());
+    bytes.0[4..].copy_from_slice(&9u32.to_ne_bytes());
+    let provider = fixture.provider((unit, definition));
+    let erased: &dyn deflect::Reflect = &bytes;
+    let value: deflect::value::Struct<_> = erased.reflect(&provider)?.try_into()?;
+    assert_eq!(value.to_string(), "Spec { x: 7, y: 9 }");
+    assert_eq!(value.schema().size()?, 8);
+
+    // a field that is, malformedly, an instance of itself.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let cyclic = fixture.structure(unit, None, "Cyclic", 4);
+    let field = fixture.entry(unit, Some(cyclic), gimli::DW_TAG_member, vec![]);
+    let origin = AttributeValue::UnitRef(field);
+    let entry = fixture.dwarf.units.get_mut(unit).get_mut(field);
+    entry.set(gimli::DW_AT_abstract_origin, origin);
+    entry.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(0));
+    let provider = fixture.provider((unit, cyclic));
+    let erased: &dyn deflect::Reflect = &Aligned([0; 4]);
+    let value: deflect::value::Struct<_> = erased.reflect(&provider)?.try_into()?;
+    let mut fields = value.schema().fields()?;
+    let field = fields.iter()?.next().expect("missing field")?;
+    let err = field.name().expect_err("named a field without a name");
+    assert_eq!(
+        err.root_cause().to_string(),
+        r#"DIE did not have attribute attr Some("DW_AT_name")"#
+    );
+    let err = field.r#type().expect_err("typed a field without a type");
+    assert_eq!(
+        err.root_cause().to_string(),
+        r#"DIE did not have attribute attr Some("DW_AT_type")"#
+    );
+    Ok(())
+}
+
+#[test]
+fn cross_unit_references() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::{AttributeValue, Reference};
+
+    // a `Linked { local: u8, remote: Remote }`, whose `Remote { value: u32,
+    // back: u8 }` is defined in another unit, and refers back to this one.
+    let mut fixture = Fixture::new();
+    let local = fixture.unit(8, None);
+    let remote = fixture.unit(8, None);
+    let u8 = fixture.base_type(local, "u8", gimli::DW_ATE_unsigned, 1);
+    let u32 = fixture.base_type(remote, "u32", gimli::DW_ATE_unsigned, 4);
+    let reference = |unit, entry| AttributeValue::DebugInfoRef(Reference::Entry(unit, entry));
+    let member = |fixture: &mut Fixture, unit, parent, name: &str, r#type, offset| {
+        let location = AttributeValue::Udata(offset);
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String(name.into())),
+            (gimli::DW_AT_type, r#type),
+            (gimli::DW_AT_data_member_location, location),
+        ];
+        fixture.entry(unit, Some(parent), gimli::DW_TAG_member, attrs);
+    };
+    let remote_struct = fixture.structure(remote, None, "Remote", 8);
+    fixture.member(remote, remote_struct, "value", u32, 0);
+    let back = reference(local, u8);
+    member(&mut fixture, remote, remote_struct, "back", back, 4);
+    let linked = fixture.structure(local, None, "Linked", 12);
+    fixture.member(local, linked, "local", u8, 0);
+    let remote_type = reference(remote, remote_struct);
+    member(&mut fixture, local, linked, "remote", remote_type, 4);
+
+    let mut bytes = Aligned([1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0]);
+    bytes.0[4..8].copy_from_slice(&2u32.to_ne_bytes());
+    let provider = fixture.provider((local, linked));
+    let erased: &dyn deflect::Reflect = &bytes;
+    let value: deflect::value::Struct<_> = erased.reflect(&provider)?.try_into()?;
+    assert_eq!(
+        value.to_string(),
+        "Linked { local: 1, remote: Remote { value: 2, back: 3 } }"
+    );
+
+    /// A provider that may be shared with other threads; `addr2line`'s
+    /// contexts are not `Sync`, for the line tables they parse lazily, but
+    /// only their DWARF sections are read here.
+    #[derive(Clone)]
+    struct Shared(Synthetic);
+
+    unsafe impl Send for Shared {}
+    unsafe impl Sync for Shared {}
+
+    unsafe impl deflect::DebugInfoProvider for Shared {
+        type Reader = SyntheticReader;
+
+        fn info_for(
+            &self,
+            addr: u64,
+        ) -> Result<deflect::DebugInfo<'_, Self::Reader>, deflect::anyhow::Error> {
+            deflect::DebugInfoProvider::info_for(&self.0, addr)
+        }
+    }
+
+    // memos are reachable from the threads that values are sent to; so
+    // references first followed there are followed through them.
+    let shared = Shared(fixture.provider((local, linked)));
+    let value: deflect::value::Struct<_> = erased.reflect(&shared)?.try_into()?;
+    let formatted = std::thread::scope(|scope| scope.spawn(move || value.to_string()).join());
+    assert_eq!(
+        formatted.unwrap(),
+        "Linked { local: 1, remote: Remote { value: 2, back: 3 } }"
+    );
+
+    // references into other units are only followed in memoized sections.
+    let unmemoized = fixture.provider_with((local, linked), |context| Box::leak(Box::new(context)));
+    let value: deflect::value::Struct<_> = erased.reflect(&unmemoized)?.try_into()?;
+    assert_eq!(value.field_value("local")?.unwrap().to_string(), "1");
+    let error = value.field_value("remote").err().unwrap();
+    assert!(format!("{error:#}").contains("not memoized"));
+    Ok(())
+}
+
+#[test]
+fn deep_size_of() -> Result<(), Box<dyn std::error::Error>> {
+    use std::rc::Rc;
+
+    #[allow(dead_code)]
+    struct Node {
+        id: u64,
+        tags: Vec<u32>,
+        name: String,
+        child: Option<Box<[u16; 4]>>,
+        shared: Rc<[u8; 16]>,
+    }
+
+    let shared = Rc::new([0u8; 16]);
+    let mut tags = Vec::with_capacity(8);
+    tags.extend([1, 2, 3]);
+    let node = Node {
+        id: 1,
+        tags,
+        name: String::from("hello"),
+        child: Some(Box::new([0; 4])),
+        shared: shared.clone(),
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &node;
+    let value = erased.reflect(&context)?;
+
+    // the `Rc` allocation holds the strong and weak counts before the value.
+    let rc_size = 2 * std::mem::size_of::<usize>() + 16;
+    let heap = 8 * 4 + 5 + 4 * 2;
+    assert_eq!(
+        deflect::deep_size_of(&value)?,
+        std::mem::size_of::<Node>() + heap + rc_size
+    );
+    let divided =
+        deflect::DeepSizeOptions::new().shared(deflect::SharedPolicy::DivideByStrongCount);
+    assert_eq!(
+        deflect::deep_size_of_with(&value, &divided)?,
+        std::mem::size_of::<Node>() + heap + rc_size / 2
+    );
+    let skipped = deflect::DeepSizeOptions::new().shared(deflect::SharedPolicy::Skip);
+    assert_eq!(
+        deflect::deep_size_of_with(&value, &skipped)?,
+        std::mem::size_of::<Node>() + heap
+    );
+    drop(shared);
+    Ok(())
+}
+
+#[test]
+fn to_dot() -> Result<(), Box<dyn std::error::Error>> {
+    use std::rc::Rc;
+
+    #[allow(dead_code)]
+    struct Leaf {
+        id: u8,
+    }
+
+    #[allow(dead_code)]
+    struct Tree {
+        children: Vec<Option<Box<Leaf>>>,
+        first: Rc<Leaf>,
+        again: Rc<Leaf>,
+    }
+
+    let shared = Rc::new(Leaf { id: 3 });
+    let tree = Tree {
+        children: vec![
+            Some(Box::new(Leaf { id: 1 })),
+            None,
+            Some(Box::new(Leaf { id: 2 })),
+        ],
+        first: shared.clone(),
+        again: shared,
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &tree;
+    let value = erased.reflect(&context)?;
+    let dot = value.to_dot()?;
+
+    assert!(dot.starts_with("digraph {\n"), "{dot}");
+    assert!(dot.contains("n0 [label=\"Tree\\n"), "{dot}");
+    assert!(
+        dot.contains("n0 -> n1 [label=\"children[0]::Some.0\"];"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains("n0 -> n2 [label=\"children[2]::Some.0\"];"),
+        "{dot}"
+    );
+    assert!(dot.contains("[label=\"Leaf\\nLeaf { id: 2 }\"]"), "{dot}");
+    // both `Rc`s lead to the same allocation.
+    assert!(dot.contains("n0 -> n3 [label=\"first\"];"), "{dot}");
+    assert!(dot.contains("n0 -> n3 [label=\"again\"];"), "{dot}");
+    assert_eq!(dot.matches(" -> ").count(), 4, "{dot}");
+    Ok(())
+}
+
+#[test]
+fn hexdump() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Header {
+        flags: u8,
+        id: u32,
+        tag: Option<u16>,
+    }
+
+    let header = Header {
+        flags: 0xab,
+        id: 0x0403_0201,
+        tag: Some(7),
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &header;
+    let value = erased.reflect(&context)?;
+    let hexdump = value.hexdump()?;
+
+    assert_eq!(hexdump.len(), std::mem::size_of::<Header>());
+    assert_eq!(hexdump.path_of(0), Some("flags"));
+    assert_eq!(hexdump.byte(0), Some(0xab));
+    for offset in 1..4 {
+        assert!(hexdump.is_padding(offset));
+        assert_eq!(hexdump.byte(offset), None);
+    }
+    for offset in 4..8 {
+        assert_eq!(hexdump.path_of(offset), Some("id"));
+    }
+    assert_eq!(hexdump.byte(4), Some(0x01));
+    assert_eq!(hexdump.path_of(8), Some("tag.<discriminant>"));
+    assert_eq!(hexdump.path_of(10), Some("tag::Some.0"));
+    assert_eq!(hexdump.byte(10), Some(7));
+
+    let dumped = hexdump.to_string();
+    assert!(dumped.starts_with("Header (12 bytes)\n"), "{dumped}");
+    assert!(dumped.contains("0000  ab"), "{dumped}");
+    assert!(dumped.contains("0001  .. .. .."), "{dumped}");
+    assert!(dumped.contains("0004  01 02 03 04"), "{dumped}");
+    Ok(())
+}
+
+#[test]
+fn portable_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Inventory {
+        name: String,
+        counts: Vec<u32>,
+        owner: Box<Option<&'static str>>,
+        shared: std::rc::Rc<(u8, char)>,
+        again: std::rc::Rc<(u8, char)>,
+        debug: Box<dyn std::fmt::Debug>,
+        aisles: std::collections::HashMap<u8, &'static str>,
+    }
+
+    let shared = std::rc::Rc::new((7, 'x'));
+    let inventory = Inventory {
+        name: "pantry".to_owned(),
+        counts: vec![1, 2, 3],
+        owner: Box::new(Some("ferris")),
+        again: shared.clone(),
+        shared,
+        debug: Box::new(42u16),
+        aisles: [(1, "bread"), (2, "jam")].into(),
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &inventory;
+    let value = erased.reflect(&context)?;
+    let mut saved = vec![];
+    value.save_snapshot(&mut saved)?;
+    let expected = value.to_string();
+    drop(inventory);
+
+    let snapshot = deflect::PortableSnapshot::load(&saved[..])?;
+    let loaded = snapshot.value()?;
+    // the blocks of the snapshot are at other addresses than the originals.
+    let without_addresses = |displayed: String| {
+        displayed
+            .split(' ')
+            .filter(|word| !word.starts_with("0x"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert_eq!(
+        without_addresses(loaded.to_string()),
+        without_addresses(expected)
+    );
+
+    let deflect::Value::Struct(loaded) = loaded else { panic!() };
+    let shared = loaded.field("shared")?.unwrap().value()?;
+    let again = loaded.field("again")?.unwrap().value()?;
+    assert_eq!(shared.to_string(), again.to_string());
+    let owner = loaded.field("owner")?.unwrap().value()?;
+    assert_eq!(owner.to_string(), "box Some(\"ferris\")");
+    // the buckets of `HashMap`s are saved.
+    #[cfg(feature = "serde")]
+    {
+        use serde::Deserialize;
+        let aisles = loaded.field("aisles")?.unwrap().value()?;
+        let aisles = std::collections::HashMap::<u8, String>::deserialize(aisles)?;
+        assert_eq!(aisles, [(1, "bread".to_owned()), (2, "jam".to_owned())].into());
+    }
+
+    assert!(deflect::PortableSnapshot::load(&saved[..saved.len() / 2]).is_err());
+    Ok(())
+}
+
+#[test]
+fn corrupt_snapshots() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    /// A snapshot of a `Holder { one: &u32, many: &[u32] }`, whose `many` has
+    /// `len` elements.
+    fn holder(len: u64, blocks: &[&[u8]], relocations: &[(u32, u64, u32, u64)]) -> Vec<u8> {
+        let mut fixture = Fixture::new();
+        let unit = fixture.unit(8, None);
+        let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+        let usize = fixture.base_type(unit, "usize", gimli::DW_ATE_unsigned, 8);
+        let pointer = |fixture: &mut Fixture, name: Option<&str>| {
+            let mut attrs = vec![
+                (gimli::DW_AT_type, AttributeValue::UnitRef(u32)),
+                (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+            ];
+            if let Some(name) = name {
+                attrs.push((gimli::DW_AT_name, AttributeValue::String(name.into())));
+            }
+            fixture.entry(unit, None, gimli::DW_TAG_pointer_type, attrs)
+        };
+        let reference = pointer(&mut fixture, Some("&u32"));
+        let data_ptr = pointer(&mut fixture, None);
+        let slice = fixture.structure(unit, None, "&[u32]", 16);
+        fixture.member(unit, slice, "data_ptr", data_ptr, 0);
+        fixture.member(unit, slice, "length", usize, 8);
+        let holder = fixture.structure(unit, None, "Holder", 24);
+        fixture.member(unit, holder, "one", reference, 0);
+        fixture.member(unit, holder, "many", slice, 8);
+
+        let mut bytes = [0; 24];
+        bytes[16..].copy_from_slice(&len.to_ne_bytes());
+        let blocks = [&[&bytes[..]], blocks].concat();
+        fixture.snapshot((unit, holder), &blocks, relocations)
+    }
+
+    let one = 5u32.to_ne_bytes();
+    let many: Vec<u8> = [1u32, 2, 3].iter().flat_map(|n| n.to_ne_bytes()).collect();
+    let relocated = [(0, 0, 1, 0), (0, 8, 2, 0)];
+    let snapshot = holder(3, &[&one, &many], &relocated);
+    let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+    assert_eq!(
+        snapshot.value()?.to_string(),
+        "Holder { one: &5, many: &[1, 2, 3] }"
+    );
+
+    let corrupt = [
+        // a reference that was not relocated.
+        (holder(3, &[&one, &many], &relocated[1..]), "a pointer was not relocated"),
+        // a reference to a block too small for its referent.
+        (holder(3, &[&one[..2], &many], &relocated), "a value is out of bounds"),
+        // a slice longer than its block.
+        (holder(4, &[&one, &many], &relocated), "a value is out of bounds"),
+        (holder(u64::MAX, &[&one, &many], &relocated), "a length is out of bounds"),
+        // a relocation out of its block.
+        (holder(3, &[&one, &many], &[(0, 20, 1, 0), (0, 8, 2, 0)]), "a pointer is out of bounds"),
+    ];
+    for (snapshot, reason) in corrupt {
+        let err = deflect::PortableSnapshot::load(&snapshot[..]).err().unwrap();
+        assert_eq!(err.to_string(), format!("invalid snapshot: {reason}"));
+    }
+    Ok(())
+}
+
+#[test]
+fn reloaded_snapshots() -> Result<(), Box<dyn std::error::Error>> {
+    /// A snapshot of a struct named `name`, with a `u8` field of each name of
+    /// `fields`, whose values are `bytes`.
+    fn snapshot(name: &str, fields: &[&str], bytes: &[u8]) -> Vec<u8> {
+        let mut fixture = Fixture::new();
+        let unit = fixture.unit(8, None);
+        // the struct is the first entry of each snapshot, and so is at the
+        // same offset in each.
+        let root = fixture.structure(unit, None, name, bytes.len() as u64);
+        let u8 = fixture.base_type(unit, "u8", gimli::DW_ATE_unsigned, 1);
+        for (offset, field) in fields.iter().enumerate() {
+            fixture.member(unit, root, field, u8, offset as u64);
+        }
+        fixture.snapshot((unit, root), &[bytes], &[])
+    }
+
+    let snapshots = [
+        (snapshot("Pair", &["a", "b"], &[1, 2]), "Pair { a: 1, b: 2 }"),
+        (snapshot("Single", &["only"], &[3]), "Single { only: 3 }"),
+        (snapshot("Triple", &["x", "y", "z"], &[4, 5, 6]), "Triple { x: 4, y: 5, z: 6 }"),
+    ];
+    // the debuginfo of each snapshot is freed once it is dropped, and what was
+    // memoized of it is not mistaken for that of the next, which may be loaded
+    // at the same address.
+    for _ in 0..16 {
+        for (snapshot, expected) in &snapshots {
+            let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+            assert_eq!(snapshot.value()?.to_string(), *expected);
+        }
+    }
+    Ok(())
+}
+
+// the bits of the fixture are laid out for a little-endian target.
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn foreign_address_size() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    // a `Narrow { len: usize, one: &u32, raw: *const u32 }` of a target with
+    // 4-byte addresses, whose pointers cannot point into this program.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(4, None);
+    let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let usize = fixture.base_type(unit, "usize", gimli::DW_ATE_unsigned, 4);
+    let pointer = |fixture: &mut Fixture, name: &str| {
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String(name.into())),
+            (gimli::DW_AT_type, AttributeValue::UnitRef(u32)),
+            (gimli::DW_AT_byte_size, AttributeValue::Udata(4)),
+        ];
+        fixture.entry(unit, None, gimli::DW_TAG_pointer_type, attrs)
+    };
+    let reference = pointer(&mut fixture, "&u32");
+    let raw = pointer(&mut fixture, "*const u32");
+    let narrow = fixture.structure(unit, None, "Narrow", 12);
+    fixture.member(unit, narrow, "len", usize, 0);
+    fixture.member(unit, narrow, "one", reference, 4);
+    fixture.member(unit, narrow, "raw", raw, 8);
+
+    let bytes: Vec<u8> = [7u32, 0x1000, 0xdead_beef]
+        .iter()
+        .flat_map(|n| n.to_ne_bytes())
+        .collect();
+    // the pointers are not relocated, as they are never followed.
+    let snapshot = fixture.snapshot((unit, narrow), &[&bytes], &[]);
+    let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+    let value: deflect::value::Struct<_> = snapshot.value()?.try_into()?;
+
+    let len = value.field("len")?.expect("missing field").value()?;
+    assert_eq!(len.to_string(), "7");
+    let raw = value.field("raw")?.expect("missing field").value()?;
+    let raw: deflect::value::ConstPtr<_> = raw.try_into()?;
+    assert_eq!(raw.addr(), 0xdead_beef);
+    let one = value.field("one")?.expect("missing field").value()?;
+    let one: deflect::value::SharedRef<_> = one.try_into()?;
+    let err = one.deref().err().expect("dereferenced a foreign address");
+    assert_eq!(
+        err.to_string(),
+        "cannot dereference a 4-byte address on a target with 8-byte addresses"
+    );
+    Ok(())
+}
+
+#[test]
+fn inheritance() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::{AttributeValue, Expression};
+
+    /// A snapshot of a C++ `class Derived : Base, Other { uint32_t b; }`, whose
+    /// `Other` is virtual if `virtual`.
+    fn derived(r#virtual: bool) -> Vec<u8> {
+        let mut fixture = Fixture::new();
+        let unit = fixture.unit(8, None);
+        let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+        let base = fixture.structure(unit, None, "Base", 4);
+        fixture.member(unit, base, "a", u32, 0);
+        let other = fixture.structure(unit, None, "Other", 4);
+        fixture.member(unit, other, "c", u32, 0);
+        let attrs = vec![
+            (gimli::DW_AT_name, AttributeValue::String("Derived".into())),
+            (gimli::DW_AT_byte_size, AttributeValue::Udata(12)),
+        ];
+        let derived = fixture.entry(unit, None, gimli::DW_TAG_class_type, attrs);
+        let attrs = vec![
+            (gimli::DW_AT_type, AttributeValue::UnitRef(base)),
+            (gimli::DW_AT_data_member_location, AttributeValue::Udata(4)),
+        ];
+        fixture.entry(unit, Some(derived), gimli::DW_TAG_inheritance, attrs);
+        // the offsets of bases may be given by location expressions; those of
+        // virtual bases read the vtable.
+        let mut location = Expression::new();
+        if r#virtual {
+            location.op(gimli::DW_OP_dup);
+            location.op_deref();
+            location.op(gimli::DW_OP_plus);
+        } else {
+            location.op_plus_uconst(8);
+        }
+        let mut attrs = vec![
+            (gimli::DW_AT_type, AttributeValue::UnitRef(other)),
+            (gimli::DW_AT_data_member_location, AttributeValue::Exprloc(location)),
+        ];
+        if r#virtual {
+            let virtuality = AttributeValue::Virtuality(gimli::DW_VIRTUALITY_virtual);
+            attrs.push((gimli::DW_AT_virtuality, virtuality));
+        }
+        fixture.entry(unit, Some(derived), gimli::DW_TAG_inheritance, attrs);
+        fixture.member(unit, derived, "b", u32, 0);
+
+        let bytes: Vec<u8> = [1u32, 2, 3].iter().flat_map(|n| n.to_ne_bytes()).collect();
+        fixture.snapshot((unit, derived), &[&bytes], &[])
+    }
+
+    // the fields of bases precede those of the derived class, at the offsets
+    // of their bases.
+    let snapshot = deflect::PortableSnapshot::load(&derived(false)[..])?;
+    let value: deflect::value::Struct<_> = snapshot.value()?.try_into()?;
+    assert_eq!(value.to_string(), "Derived { a: 2, c: 3, b: 1 }");
+    let offsets = value
+        .schema()
+        .fields()?
+        .iter()?
+        .map(|field| field?.offset()?.address(0))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(offsets, [4, 8, 0]);
+
+    let err = deflect::PortableSnapshot::load(&derived(true)[..])
+        .err()
+        .expect("flattened a virtual base");
+    assert_eq!(
+        err.root_cause().to_string(),
+        "virtual base classes cannot be reflected; their offsets are only known at run time"
+    );
+    Ok(())
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn bitfields() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let int = fixture.base_type(unit, "i32", gimli::DW_ATE_signed, 4);
+    let unsigned = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let bool = fixture.base_type(unit, "bool", gimli::DW_ATE_boolean, 1);
+    let flags = fixture.structure(unit, None, "Flags", 4);
+    let overrun = fixture.structure(unit, None, "Overrun", 4);
+    let mut bitfield = |parent, name: &str, r#type, attrs: &[(gimli::DwAt, u64)]| {
+        let mut attrs: Vec<_> = attrs
+            .iter()
+            .map(|&(name, value)| (name, AttributeValue::Udata(value)))
+            .collect();
+        attrs.push((gimli::DW_AT_name, AttributeValue::String(name.into())));
+        attrs.push((gimli::DW_AT_type, AttributeValue::UnitRef(r#type)));
+        fixture.entry(unit, Some(parent), gimli::DW_TAG_member, attrs);
+    };
+    let bits = |bit_size, bit_offset| {
+        [(gimli::DW_AT_bit_size, bit_size), (gimli::DW_AT_data_bit_offset, bit_offset)]
+    };
+    bitfield(flags, "signed", int, &bits(3, 0));
+    bitfield(flags, "unsigned", unsigned, &bits(5, 3));
+    bitfield(flags, "empty", int, &bits(0, 8));
+    bitfield(flags, "flag", bool, &bits(1, 8));
+    // DWARF 2 and 3 count bits from the most significant bit of the storage
+    // unit; here, bits 12 to 15.
+    bitfield(
+        flags,
+        "legacy",
+        unsigned,
+        &[
+            (gimli::DW_AT_byte_size, 4),
+            (gimli::DW_AT_bit_size, 4),
+            (gimli::DW_AT_bit_offset, 16),
+            (gimli::DW_AT_data_member_location, 0),
+        ],
+    );
+    bitfield(overrun, "past", unsigned, &bits(8, 32));
+
+    // -3 in 3 bits, then 17 in 5 bits; true in 1 bit, then 9 at bit 12.
+    let bytes = [0b1000_1101, 0b1001_0001, 0, 0];
+    let snapshot = fixture.snapshot((unit, overrun), &[&bytes], &[]);
+    let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+    let deflect::Value::Struct(value) = snapshot.value()? else { panic!() };
+    // bits beyond the struct are not read.
+    assert!(value.field("past")?.unwrap().bits().is_err());
+
+    let snapshot = fixture.snapshot((unit, flags), &[&bytes], &[]);
+    let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+    let deflect::Value::Struct(value) = snapshot.value()? else { panic!() };
+    let bits_of = |name| value.field(name).unwrap().unwrap().bits();
+    assert_eq!(bits_of("signed")?, Some(0b101));
+    assert_eq!(bits_of("unsigned")?, Some(17));
+    assert_eq!(bits_of("empty")?, Some(0));
+    assert_eq!(bits_of("flag")?, Some(1));
+    assert_eq!(bits_of("legacy")?, Some(9));
+    assert!(value.field("signed")?.unwrap().value().is_err());
+    assert_eq!(
+        value.to_string(),
+        "Flags { signed: -3, unsigned: 17, empty: 0, flag: true, legacy: 9 }"
+    );
+    #[cfg(feature = "json")]
+    {
+        let json = deflect::Value::Struct(value).to_json()?;
+        assert_eq!(json["signed"], -3);
+        assert_eq!(json["unsigned"], 17);
+        assert_eq!(json["flag"], true);
+    }
+    Ok(())
+}
+
+#[test]
+fn std_layouts() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    /// A snapshot of a `Vec<u8>` of `[1, 2, 3]` with a capacity of 4, in a unit
+    /// produced by `producer`; in the layout of the standard library since
+    /// 1.84, if `inner`, and otherwise before.
+    fn vec(producer: Option<&str>, inner: bool) -> Vec<u8> {
+        let mut fixture = Fixture::new();
+        let unit = fixture.unit(8, producer);
+        let u8 = fixture.base_type(unit, "u8", gimli::DW_ATE_unsigned, 1);
+        let usize = fixture.base_type(unit, "usize", gimli::DW_ATE_unsigned, 8);
+        let ptr = fixture.entry(
+            unit,
+            None,
+            gimli::DW_TAG_pointer_type,
+            vec![
+                (gimli::DW_AT_name, AttributeValue::String("*const u8".into())),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(u8)),
+                (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+            ],
+        );
+        let raw = fixture.structure(unit, None, "RawVec<u8>", 16);
+        let buffer = match inner {
+            true => fixture.structure(unit, None, "RawVecInner", 16),
+            false => raw,
+        };
+        fixture.member(unit, buffer, "ptr", ptr, 0);
+        fixture.member(unit, buffer, "cap", usize, 8);
+        if inner {
+            fixture.member(unit, raw, "inner", buffer, 0);
+        }
+
+        let namespace = |name: &str| vec![(gimli::DW_AT_name, AttributeValue::String(name.into()))];
+        let alloc = fixture.entry(unit, None, gimli::DW_TAG_namespace, namespace("alloc"));
+        let module = fixture.entry(unit, Some(alloc), gimli::DW_TAG_namespace, namespace("vec"));
+        let vec = fixture.structure(unit, Some(module), "Vec<u8>", 24);
+        fixture.entry(
+            unit,
+            Some(vec),
+            gimli::DW_TAG_template_type_parameter,
+            vec![
+                (gimli::DW_AT_name, AttributeValue::String("T".into())),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(u8)),
+            ],
+        );
+        fixture.member(unit, vec, "buf", raw, 0);
+        fixture.member(unit, vec, "len", usize, 16);
+
+        let mut bytes = [0; 24];
+        bytes[8..16].copy_from_slice(&4u64.to_ne_bytes());
+        bytes[16..].copy_from_slice(&3u64.to_ne_bytes());
+        fixture.snapshot((unit, vec), &[&bytes, &[1, 2, 3, 4]], &[(0, 0, 1, 0)])
+    }
+
+    let deep_size = |snapshot: Vec<u8>| -> Result<usize, deflect::anyhow::Error> {
+        let snapshot = deflect::PortableSnapshot::load(&snapshot[..])?;
+        let size = deflect::deep_size_of(&snapshot.value()?)?;
+        Ok(size)
+    };
+
+    // compilers of unknown versions are assumed to use any known layout.
+    let unknown = [
+        None,
+        Some("GNU C17 13.2.0"),
+        Some("clang LLVM (rustc version 1.x)"),
+        Some("clang LLVM (rustc version)"),
+    ];
+    let before = [
+        "clang LLVM (rustc version 1.80.0 (051478957 2024-07-21))",
+        "clang LLVM (rustc version 1.83.0-beta.5 (8f5a9a9d4 2024-11-20))",
+    ];
+    let since = [
+        "clang LLVM (rustc version 1.84.0 (9fc6b4312 2025-01-07))",
+        "clang LLVM (rustc version 1.86.0-nightly (a9e7b3048 2025-01-31))",
+    ];
+    for producer in unknown.into_iter().chain(before.map(Some)) {
+        assert_eq!(deep_size(vec(producer, false))?, 24 + 4, "{producer:?}");
+    }
+    for producer in unknown.into_iter().chain(since.map(Some)) {
+        assert_eq!(deep_size(vec(producer, true))?, 24 + 4, "{producer:?}");
+    }
+
+    // the layout of a known version is not mistaken for another's.
+    for (producer, inner) in [(before[0], true), (since[1], false)] {
+        let err = deep_size(vec(Some(producer), inner)).unwrap_err();
+        let Some(deflect::ErrorKind::UnsupportedStdLayout {
+            r#type,
+            producer: found,
+        }) = err.root_cause().downcast_ref()
+        else {
+            panic!("unexpected error: {err:#}");
+        };
+        assert_eq!((r#type.as_str(), found.as_str()), ("Vec<u8>", producer));
+    }
+    Ok(())
+}
+
+#[test]
+fn crash_snapshots() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct ServerState {
+        port: u16,
+        peers: &'static [&'static str],
+    }
+
+    static STATE: ServerState = ServerState {
+        port: 8080,
+        peers: &["alpha", "beta"],
+    };
+    let _guard = lock_registry(&["server state", "answer", "peers.alpha", "peers/alpha"]);
+    deflect::register("server state", &STATE)?;
+    deflect::register("answer", &42u64)?;
+    assert!(deflect::unregister("answer"));
+    assert!(!deflect::unregister("answer"));
+    // names that are replaced alike are written to distinct files.
+    deflect::register("peers.alpha", &1u8)?;
+    deflect::register("peers/alpha", &2u8)?;
+
+    let dir = std::env::temp_dir().join(format!("deflect-crash-{}", std::process::id()));
+    let written = deflect::write_snapshots(&dir)?;
+    assert_eq!(
+        written,
+        vec![
+            dir.join("peers_alpha.snapshot"),
+            dir.join("peers_alpha-2.snapshot"),
+            dir.join("server_state.snapshot"),
+        ]
+    );
+    let snapshot = deflect::PortableSnapshot::load(std::fs::File::open(&written[1])?)?;
+    assert_eq!(snapshot.value()?.to_string(), "2");
+
+    let snapshot = deflect::PortableSnapshot::load(std::fs::File::open(&written[2])?)?;
+    let deflect::Value::Struct(state) = snapshot.value()? else { panic!() };
+    assert_eq!(state.field("port")?.unwrap().value()?.to_string(), "8080");
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[cfg(feature = "valuable")]
+#[test]
+fn valuable() -> Result<(), Box<dyn std::error::Error>> {
+    use valuable::Valuable;
+
+    #[allow(dead_code)]
+    enum Role {
+        Admin,
+        Guest { visits: u32 },
+    }
+
+    #[allow(dead_code)]
+    struct Session {
+        user: &'static str,
+        roles: [Role; 2],
+        token: Option<Box<(u8, bool)>>,
+        expires: Option<u64>,
+    }
+
+    let session = Session {
+        user: "ferris",
+        roles: [Role::Admin, Role::Guest { visits: 3 }],
+        token: Some(Box::new((7, true))),
+        expires: None,
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &session;
+    let value = erased.reflect(&context)?;
+
+    assert_eq!(
+        format!("{:?}", value.as_value()),
+        "Session { user: \"ferris\", roles: [Role::Admin, Role::Guest { visits: 3 }], \
+         token: (u8, bool)(7, true), expires: () }"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() -> Result<(), Box<dyn std::error::Error>> {
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    enum Mode {
+        Idle,
+        Active { since: u8 },
+        Paused(u16),
+    }
+
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        port: u16,
+        limits: Vec<u32>,
+        modes: [Mode; 3],
+        owner: Option<&'static str>,
+        internal: bool,
+        scores: std::collections::HashMap<String, u8>,
+        lock: std::sync::Mutex<u16>,
+    }
+
+    /// A stable mirror of `Mode`.
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum ModeV1 {
+        Idle,
+        Active { since: u8 },
+        Paused(u16),
+    }
+
+    /// A stable mirror of `Config`, without its internal fields.
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ConfigV1 {
+        name: String,
+        port: u32,
+        limits: Vec<u64>,
+        modes: Vec<ModeV1>,
+        owner: Option<String>,
+        scores: std::collections::HashMap<String, u32>,
+        lock: u16,
+    }
+
+    let config = Config {
+        name: "primary".to_owned(),
+        port: 8080,
+        limits: vec![1, 2],
+        modes: [Mode::Idle, Mode::Active { since: 7 }, Mode::Paused(3)],
+        owner: Some("ferris"),
+        internal: true,
+        scores: [("alpha".to_owned(), 3), ("beta".to_owned(), 5)].into(),
+        lock: std::sync::Mutex::new(9),
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &config;
+
+    assert_eq!(
+        ConfigV1::deserialize(erased.reflect(&context)?)?,
+        ConfigV1 {
+            name: "primary".to_owned(),
+            port: 8080,
+            limits: vec![1, 2],
+            modes: vec![ModeV1::Idle, ModeV1::Active { since: 7 }, ModeV1::Paused(3)],
+            owner: Some("ferris".to_owned()),
+            scores: [("alpha".to_owned(), 3), ("beta".to_owned(), 5)].into(),
+            lock: 9,
+        }
+    );
+    assert!(u8::deserialize(erased.reflect(&context)?).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn encode() -> Result<(), Box<dyn std::error::Error>> {
+    use serde::Serialize;
+    use std::num::NonZeroU16;
+
+    #[allow(dead_code)]
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle { radius: u16 },
+        Rect(u8, u8),
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Packet {
+        id: u32,
+        shapes: [Shape; 2],
+        offset: (i8, bool),
+        checksum: Option<NonZeroU16>,
+    }
+
+    /// The input of a `Packet`, in the serde data model.
+    #[derive(Serialize)]
+    enum ShapeInput {
+        Circle { radius: u16 },
+        Rect(u8, u8),
+    }
+
+    #[derive(Serialize)]
+    struct PacketInput {
+        id: u8,
+        shapes: [ShapeInput; 2],
+        offset: (i8, bool),
+        checksum: Option<u16>,
+    }
+
+    #[derive(Serialize)]
+    struct Partial {
+        id: u32,
+    }
+
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<Packet, _>(&context)?;
+    let input = PacketInput {
+        id: 7,
+        shapes: [ShapeInput::Circle { radius: 300 }, ShapeInput::Rect(2, 3)],
+        offset: (-1, true),
+        checksum: Some(0xbeef),
+    };
+    let bytes = r#type.encode(&input)?;
+    assert_eq!(bytes.len(), std::mem::size_of::<Packet>());
+    // SAFETY: the bytes were laid out as a `Packet`.
+    let packet = unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const Packet) };
+    assert_eq!(
+        packet,
+        Packet {
+            id: 7,
+            shapes: [Shape::Circle { radius: 300 }, Shape::Rect(2, 3)],
+            offset: (-1, true),
+            checksum: NonZeroU16::new(0xbeef),
+        }
+    );
+
+    let r#type = deflect::reflect_type::<Option<NonZeroU16>, _>(&context)?;
+    assert_eq!(r#type.encode(&None::<u16>)?, [0, 0]);
+    assert!(r#type.encode(&Partial { id: 1 }).is_err());
+    // zero is the niche of `None`.
+    assert!(r#type.encode(&Some(0u16)).is_err());
+    let r#type = deflect::reflect_type::<NonZeroU16, _>(&context)?;
+    assert_eq!(r#type.encode(&0xbeefu16)?, 0xbeefu16.to_ne_bytes());
+    assert!(r#type.encode(&0u16).is_err());
+
+    let r#type = deflect::reflect_type::<std::ptr::NonNull<u8>, _>(&context)?;
+    assert_eq!(r#type.encode(&0x1000usize)?, 0x1000usize.to_ne_bytes());
+    assert!(r#type.encode(&0usize).is_err());
+    let r#type = deflect::reflect_type::<Option<std::ptr::NonNull<u8>>, _>(&context)?;
+    assert_eq!(r#type.encode(&None::<usize>)?, 0usize.to_ne_bytes());
+    assert!(r#type.encode(&Some(0usize)).is_err());
+    {
+        // named, and shaped, like an `Option`; but not one.
+        #[allow(dead_code)]
+        enum Option<T> {
+            None,
+            Some(T),
+        }
+        let r#type = deflect::reflect_type::<Option<u8>, _>(&context)?;
+        assert!(r#type.encode(&None::<u8>).is_err());
+    }
+
+    let r#type = deflect::reflect_type::<Packet, _>(&context)?;
+    assert!(r#type.encode(&Partial { id: 1 }).is_err());
+    assert!(r#type.encode(&u64::MAX).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn encode_signed_discriminants() -> Result<(), Box<dyn std::error::Error>> {
+    use serde::Serialize;
+
+    #[allow(dead_code)]
+    #[derive(Debug, PartialEq)]
+    #[repr(i8)]
+    enum Small {
+        Low = -2,
+        High = 1,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, PartialEq)]
+    #[repr(i32)]
+    enum Large {
+        Low = -70_000,
+        High = 70_000,
+    }
+
+    /// The input of a `Small` or `Large`, in the serde data model.
+    #[derive(Serialize)]
+    enum Input {
+        Low,
+        High,
+    }
+
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<Small, _>(&context)?;
+    assert_eq!(r#type.encode(&Input::Low)?, (-2i8).to_ne_bytes());
+    assert_eq!(r#type.encode(&Input::High)?, 1i8.to_ne_bytes());
+    let r#type = deflect::reflect_type::<Large, _>(&context)?;
+    let bytes = r#type.encode(&Input::Low)?;
+    assert_eq!(bytes, (-70_000i32).to_ne_bytes());
+    // SAFETY: the bytes were laid out as a `Large`.
+    let large = unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const Large) };
+    assert_eq!(large, Large::Low);
+    assert_eq!(r#type.encode(&Input::High)?, 70_000i32.to_ne_bytes());
+    Ok(())
+}
+
+#[cfg(all(feature = "erased-serde", feature = "json"))]
+#[test]
+fn erased_serialize() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::serde_json::json;
+
+    #[allow(dead_code)]
+    enum Level {
+        Info,
+        Warn { code: u16 },
+    }
+
+    #[allow(dead_code)]
+    struct Event {
+        message: String,
+        levels: Vec<Level>,
+        span: Option<(u8, u8)>,
+    }
+
+    let event = Event {
+        message: "started".to_owned(),
+        levels: vec![Level::Info, Level::Warn { code: 3 }],
+        span: Some((1, 2)),
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &event;
+    let value = erased.reflect(&context)?;
+
+    // e.g., as a field of a logging pipeline that records type-erased values.
+    let fields: Vec<(&str, &dyn deflect::erased_serde::Serialize)> = vec![("event", &value)];
+    let (_, field) = fields[0];
+    assert_eq!(
+        deflect::serde_json::to_value(field)?,
+        json!({
+            "message": "started",
+            "levels": ["Info", {"Warn": {"code": 3}}],
+            "span": [1, 2],
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn to_metrics() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::AtomicU32;
+
+    #[allow(dead_code)]
+    struct Stats {
+        requests: AtomicU32,
+        healthy: bool,
+        latency: (f32, f64),
+        name: &'static str,
+        shards: [Option<u8>; 2],
+    }
+
+    let stats = Stats {
+        requests: AtomicU32::new(42),
+        healthy: true,
+        latency: (0.5, f64::INFINITY),
+        name: "primary",
+        shards: [Some(3), None],
+    };
+    let context = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &stats;
+    let value = erased.reflect(&context)?;
+
+    assert_eq!(
+        value.to_metrics("server")?,
+        "server_requests 42\n\
+         server_healthy 1\n\
+         server_latency_0 0.5\n\
+         server_latency_1 +Inf\n\
+         server_shards_0_Some_0 3\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn registry() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::Arc;
+
+    #[allow(dead_code)]
+    struct Limits {
+        max: u32,
+    }
+
+    static LIMITS: Limits = Limits { max: 10 };
+
+    let _guard = lock_registry(&["limits", "name", "guarded"]);
+    let registry = deflect::registry();
+    registry.insert("name", Arc::new("primary"))?;
+    registry.insert("limits", &LIMITS)?;
+    // objects are listed and dumped in the order of their names.
+    assert_eq!(registry.names(), ["limits", "name"]);
+    assert_eq!(
+        registry.dump()?,
+        [
+            ("limits".to_owned(), "Limits {\n    max: 10,\n}".to_owned()),
+            ("name".to_owned(), "\"primary\"".to_owned()),
+        ]
+    );
+    #[cfg(feature = "json")]
+    assert_eq!(
+        registry.dump_json()?,
+        deflect::serde_json::json!({ "limits": { "max": 10 }, "name": "primary" })
+    );
+
+    // objects are replaced by name.
+    registry.insert("name", Arc::new("secondary"))?;
+    assert_eq!(registry.dump()?[1].1, "\"secondary\"");
+
+    assert!(registry.remove("name"));
+    assert!(!registry.remove("name"));
+    assert_eq!(registry.names(), ["limits"]);
+
+    // objects that hold data guarded by a lock, which is not taken when they
+    // are read, are refused.
+    #[allow(dead_code)]
+    struct Guarded {
+        hits: std::sync::Mutex<u32>,
+    }
+
+    static GUARDED: Guarded = Guarded {
+        hits: std::sync::Mutex::new(0),
+    };
+    let err = registry.insert("guarded", &GUARDED).unwrap_err();
+    assert!(format!("{err:#}").contains("UnsafeCell<u32>"), "{err:#}");
+    assert!(registry
+        .insert("guarded", Arc::new(vec![std::sync::RwLock::new(1u8)]))
+        .is_err());
+    // atomics are read atomically.
+    registry.insert("guarded", Arc::new(std::sync::atomic::AtomicU32::new(1)))?;
+    assert!(registry.remove("guarded"));
+    Ok(())
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+    use http::{Request, StatusCode};
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    struct Config {
+        limits: Vec<u32>,
+        mode: Option<&'static str>,
+        ports: [u16; 2],
+    }
+
+    let config = std::sync::Arc::new(Config {
+        limits: vec![10, 20],
+        mode: Some("strict"),
+        ports: [80, 443],
+    });
+    let _guard = lock_registry(&["http config", "http raw"]);
+    deflect::registry().insert("http config", config)?;
+
+    // unions, other than `MaybeUninit`s, cannot be reflected.
+    union Raw {
+        _bits: u32,
+        _float: f32,
+    }
+    deflect::registry().insert("http raw", std::sync::Arc::new(Raw { _bits: 1 }))?;
+
+    let get = |uri: &str| {
+        let response = deflect::http::handle(&Request::get(uri).body(()).unwrap());
+        let body: serde_json::Value = serde_json::from_str(response.body()).unwrap();
+        (response.status(), body)
+    };
+
+    let (status, list) = get("/debug/values");
+    assert_eq!(status, StatusCode::OK);
+    let entries = list.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], json!({ "name": "http config", "type": "Config" }));
+    // objects that cannot be reflected are still listed.
+    assert_eq!(entries[1]["name"], "http raw");
+    let listed = entries[1]["type"].as_str().unwrap();
+    assert!(listed.starts_with("<error: "), "{listed}");
+
+    let (status, mode) = get("/debug/values/http%20config?path=mode");
+    assert_eq!((status, mode), (StatusCode::OK, json!("strict")));
+
+    let (status, mode) = get("/debug/values/http%20config?path=mode%3A%3ASome.0");
+    assert_eq!((status, mode), (StatusCode::OK, json!("strict")));
+
+    // `+` encodes a space in queries.
+    let (status, port) = get("/debug/values/http%20config?path=ports%5B+1+%5D");
+    assert_eq!((status, port), (StatusCode::OK, json!(443)));
+
+    let (status, _) = get("/debug/values/http%20config?path=nonexistent");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get("/debug/values/missing");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get("/debug/values/http%20config?path=%5B");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // escapes are of exactly two hexadecimal digits, without a sign.
+    let (status, _) = get("/debug/values/http%+1config");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let post = Request::post("/debug/values").body(()).unwrap();
+    let response = deflect::http::handle(&post);
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    assert!(deflect::registry().remove("http config"));
+    assert!(deflect::registry().remove("http raw"));
+    Ok(())
+}
+
+#[test]
+fn set_field() -> Result<(), Box<dyn std::error::Error>> {
+    struct Flags {
+        verbose: bool,
+        level: u32,
+    }
+
+    let mut flags = Flags {
+        verbose: false,
+        level: 1,
+    };
+    let context = deflect::default_provider()?;
+    let erased: &mut dyn deflect::Reflect = &mut flags;
+    let mut place = erased.reflect_mut(&context)?;
+
+    unsafe { place.set_field("verbose", &true)? };
+    unsafe { place.write_field_unchecked("level", &7u32.to_ne_bytes())? };
+    assert!(unsafe { place.write_field_unchecked("level", &[0; 2]) }.is_err());
+    assert!(unsafe { place.set_field("level", &7u64) }.is_err());
+    assert!(unsafe { place.set_field("missing", &7u32) }.is_err());
+    assert_eq!(
+        place.value()?.to_string(),
+        "Flags { verbose: true, level: 7 }"
+    );
+
+    assert!(flags.verbose);
+    assert_eq!(flags.level, 7);
+    Ok(())
+}
+
+/// Writes through a place, on debuginfo that is read without file I/O; run
+/// under Miri to check that writes have the provenance to write.
+#[test]
+fn place_writes() -> Result<(), Box<dyn std::error::Error>> {
+    // a `Pair { low: u8, high: u32 }`.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(std::mem::size_of::<usize>() as u8, None);
+    let u8 = fixture.base_type(unit, "u8", gimli::DW_ATE_unsigned, 1);
+    let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let pair = fixture.structure(unit, None, "Pair", 8);
+    fixture.member(unit, pair, "low", u8, 0);
+    fixture.member(unit, pair, "high", u32, 4);
+    let provider = fixture.provider((unit, pair));
+
+    let mut bytes = Aligned([1, 0, 0, 0, 2, 0, 0, 0]);
+    bytes.0[4..].copy_from_slice(&2u32.to_ne_bytes());
+    let erased: &mut dyn deflect::Reflect = &mut bytes;
+    let mut place = erased.reflect_mut(&provider)?;
+    assert_eq!(place.value()?.to_string(), "Pair { low: 1, high: 2 }");
+    unsafe { place.write_field_unchecked("high", &9u32.to_ne_bytes())? };
+    unsafe { place.write_field_unchecked("low", &[3])? };
+    assert_eq!(place.value()?.to_string(), "Pair { low: 3, high: 9 }");
+    assert_eq!(bytes.0[0], 3);
+    assert_eq!(bytes.0[4..], 9u32.to_ne_bytes());
+    Ok(())
+}
+
+#[quickcheck_macros::quickcheck]
+fn generate_bytes(seed: u64) -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Shape {
+        Empty,
+        Circle { r: u16 },
+        Tagged(char, bool),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Sample {
+        flag: bool,
+        initial: char,
+        maybe: Option<bool>,
+        level: Option<std::num::NonZeroU8>,
+        ordering: Option<std::cmp::Ordering>,
+        shape: Shape,
+        shapes: [Option<Shape>; 2],
+        counts: [i16; 3],
+    }
+
+    // xorshift64*, which never produces zero from a non-zero state.
+    let mut state = seed | 1;
+    let mut next_u64 = || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    };
+
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<Sample, _>(&context)?;
+    let bytes = r#type.generate_bytes(&mut next_u64)?;
+    assert_eq!(bytes.len(), std::mem::size_of::<Sample>());
+    let mut sample = std::mem::MaybeUninit::<Sample>::uninit();
+    let sample = unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), sample.as_mut_ptr().cast(), bytes.len());
+        sample.assume_init()
+    };
+
+    let erased: &dyn deflect::Reflect = &sample;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), format!("{sample:?}"));
+
+    let boxed = deflect::reflect_type::<Box<u8>, _>(&context)?;
+    assert!(boxed.generate_bytes(&mut next_u64).is_err());
+    Ok(())
+}
+
+/// The fuzzing entry points return errors, rather than panicking, for
+/// well-formed DWARF and for each corruption of a byte of it.
+#[test]
+#[cfg(feature = "fuzzing")]
+fn fuzzing() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::fuzz::Sections;
+    use gimli::write::AttributeValue;
+
+    // a `Fuzzed { flag: bool, letter: char, many: &[u32] }`.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let bool = fixture.base_type(unit, "bool", gimli::DW_ATE_boolean, 1);
+    let char = fixture.base_type(unit, "char", gimli::DW_ATE_UTF, 4);
+    let u32 = fixture.base_type(unit, "u32", gimli::DW_ATE_unsigned, 4);
+    let usize = fixture.base_type(unit, "usize", gimli::DW_ATE_unsigned, 8);
+    let attrs = vec![
+        (gimli::DW_AT_type, AttributeValue::UnitRef(u32)),
+        (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+    ];
+    let data_ptr = fixture.entry(unit, None, gimli::DW_TAG_pointer_type, attrs);
+    let slice = fixture.structure(unit, None, "&[u32]", 16);
+    fixture.member(unit, slice, "data_ptr", data_ptr, 0);
+    fixture.member(unit, slice, "length", usize, 8);
+    let fuzzed = fixture.structure(unit, None, "Fuzzed", 24);
+    fixture.member(unit, fuzzed, "flag", bool, 0);
+    fixture.member(unit, fuzzed, "letter", char, 4);
+    fixture.member(unit, fuzzed, "many", slice, 8);
+    let (sections, _) = fixture.sections();
+
+    let debug_abbrev = sections.debug_abbrev.slice();
+    let debug_str = sections.debug_str.slice();
+    let mut bytes = vec![1, 0, 0, 0];
+    bytes.extend(u32::from('a').to_ne_bytes());
+    bytes.extend([0; 8]);
+    bytes.extend(2u64.to_ne_bytes());
+    let fuzz = |debug_info: &[u8]| -> Result<(), deflect::anyhow::Error> {
+        let sections = Sections {
+            little_endian: cfg!(target_endian = "little"),
+            debug_info,
+            debug_abbrev,
+            debug_str,
+            ..Sections::default()
+        };
+        deflect::fuzz::schemas(sections)?;
+        deflect::fuzz::values(sections, &bytes)
+    };
+
+    let debug_info = sections.debug_info.slice();
+    fuzz(debug_info)?;
+    let mut corrupt = debug_info.to_vec();
+    for index in 0..corrupt.len() {
+        for flip in [0x01, 0x80, 0xff] {
+            corrupt[index] ^= flip;
+            let _ = fuzz(&corrupt);
+            corrupt[index] ^= flip;
+        }
+        let _ = fuzz(&debug_info[..index]);
+    }
+    Ok(())
+}
+
+#[test]
+fn benchmarked_paths() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Tree {
+        id: u64,
+        children: Vec<Tree>,
+    }
+
+    // the caches of the default provider are thread-local, so this is a cold
+    // reflection, as in `benches/reflect.rs`.
+    let displayed = std::thread::spawn(|| -> Result<_, deflect::anyhow::Error> {
+        let context = deflect::default_provider()?;
+        let tree = Tree {
+            id: 1,
+            children: vec![Tree {
+                id: 2,
+                children: vec![],
+            }],
+        };
+        let erased: &dyn deflect::Reflect = &tree;
+        let value = erased.reflect(&context)?;
+        Ok((value.to_string(), format!("{value:#}")))
+    })
+    .join()
+    .unwrap()?;
+    let (compact, pretty) = displayed;
+    assert!(compact.contains("id: 1"));
+    assert_eq!(compact.lines().count(), 1);
+    assert!(pretty.contains("    id: 1,\n"));
+    assert!(pretty.lines().count() > 1);
+
+    let context = deflect::default_provider()?;
+    let data: Vec<u32> = (0..4096).collect();
+    let slice = data.as_slice();
+    let erased: &dyn deflect::Reflect = &slice;
+    let value: deflect::value::Slice = erased.reflect(&context)?.try_into()?;
+    let mut sum = 0;
+    for elt in value.iter()? {
+        sum += u64::from(u32::try_from(elt?)?);
+    }
+    assert_eq!(sum, 4095 * 4096 / 2);
+    assert_eq!(u32::try_from(value.get(4095)?.expect("in bounds"))?, 4095);
+    Ok(())
+}
+
+#[test]
+fn address_sized_types() -> Result<(), Box<dyn std::error::Error>> {
+    let context = deflect::default_provider()?;
+    let word = std::mem::size_of::<usize>() as u64;
+    assert_eq!(deflect::reflect_type::<&u8, _>(&context)?.size()?, word);
+    assert_eq!(deflect::reflect_type::<*const u8, _>(&context)?.size()?, word);
+    assert_eq!(deflect::reflect_type::<Box<u8>, _>(&context)?.size()?, word);
+    assert_eq!(deflect::reflect_type::<usize, _>(&context)?.size()?, word);
+
+    let data: &[u16] = &[1, 2, 3];
+    let erased: &dyn deflect::Reflect = &data;
+    let value: deflect::value::Slice = erased.reflect(&context)?.try_into()?;
+    assert_eq!(value.length()?, 3);
+    assert_eq!(value.to_string(), "&[1, 2, 3]");
+    Ok(())
+}
+
+#[test]
+fn numeric_conversions() -> Result<(), Box<dyn std::error::Error>> {
+    let context = deflect::default_provider()?;
+
+    let erased: &dyn deflect::Reflect = &200u8;
+    let value = erased.reflect(&context)?;
+    assert_eq!(<&u8>::try_from(&value)?, &200);
+    assert_eq!(u64::try_from(&value)?, 200);
+    assert_eq!(i64::try_from(&value)?, 200);
+    assert_eq!(f64::try_from(value)?, 200.0);
+
+    let erased: &dyn deflect::Reflect = &-3i16;
+    let value = erased.reflect(&context)?;
+    assert!(u64::try_from(&value).is_err());
+    assert_eq!(i64::try_from(value)?, -3);
+
+    let erased: &dyn deflect::Reflect = &u64::MAX;
+    let value = erased.reflect(&context)?;
+    assert_eq!(u64::try_from(&value)?, u64::MAX);
+    assert!(i64::try_from(&value).is_err());
+    assert!(f64::try_from(&value).is_err());
+
+    let erased: &dyn deflect::Reflect = &1.5f32;
+    let value = erased.reflect(&context)?;
+    assert_eq!(f64::try_from(&value)?, 1.5);
+    assert!(u64::try_from(value).is_err());
+
+    let erased: &dyn deflect::Reflect = &();
+    let value = erased.reflect(&context)?;
+    <&()>::try_from(&value)?;
+    Ok(())
+}
+
+#[test]
+fn dynamic_numeric_accessors() -> Result<(), Box<dyn std::error::Error>> {
+    let context = deflect::default_provider()?;
+
+    let erased: &dyn deflect::Reflect = &-7i8;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.as_i128(), Some(-7));
+    assert_eq!(value.as_u128(), None);
+    assert_eq!(value.as_f64(), Some(-7.0));
+
+    let erased: &dyn deflect::Reflect = &u128::MAX;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.as_u128(), Some(u128::MAX));
+    assert_eq!(value.as_i128(), None);
+    assert_eq!(value.as_f64(), Some(u128::MAX as f64));
+
+    let erased: &dyn deflect::Reflect = &std::num::NonZeroU16::new(9).unwrap();
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.as_u128(), Some(9));
+    assert_eq!(value.as_i128(), Some(9));
+
+    let erased: &dyn deflect::Reflect = &2.5f32;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.as_f64(), Some(2.5));
+    assert_eq!(value.as_i128(), None);
+
+    let erased: &dyn deflect::Reflect = &true;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.as_f64(), None);
+    Ok(())
+}
+
+#[test]
+fn bit_pattern_validation() -> Result<(), Box<dyn std::error::Error>> {
+    fn reflect_as<T, P: deflect::DebugInfoProvider>(
+        context: &P,
+        data: &std::mem::MaybeUninit<T>,
+    ) -> Result<(), deflect::anyhow::Error> {
+        let erased: &dyn deflect::Reflect = data;
+        let value: deflect::value::MaybeUninit<'_, '_, P> = erased.reflect(context)?.try_into()?;
+        unsafe { value.assume_init_value() }.map(drop)
+    }
+
+    let context = deflect::default_provider()?.with_validation();
+
+    let data: std::mem::MaybeUninit<bool> = unsafe { std::mem::transmute(7u8) };
+    let err = reflect_as(&context, &data).unwrap_err();
+    let kind = err.downcast_ref::<deflect::ErrorKind>();
+    assert!(matches!(
+        kind,
+        Some(deflect::ErrorKind::InvalidBitPattern { r#type: "bool", bytes }) if bytes == &[7]
+    ));
+
+    let data: std::mem::MaybeUninit<char> = unsafe { std::mem::transmute(0xd800u32) };
+    let err = reflect_as(&context, &data).unwrap_err();
+    let kind = err.downcast_ref::<deflect::ErrorKind>();
+    assert!(matches!(
+        kind,
+        Some(deflect::ErrorKind::InvalidBitPattern { r#type: "char", .. })
+    ));
+
+    reflect_as(&context, &std::mem::MaybeUninit::new(true))?;
+    reflect_as(&context, &std::mem::MaybeUninit::new('🦀'))?;
+    Ok(())
+}
+
+#[test]
+fn invalid_utf8() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes: &[u8] = b"ab\xffc";
+    let data: std::mem::MaybeUninit<&str> = unsafe { std::mem::transmute(bytes) };
+    let erased: &dyn deflect::Reflect = &data;
+    let context = deflect::default_provider()?;
+    let value: deflect::value::MaybeUninit = erased.reflect(&context)?.try_into()?;
+    let value = unsafe { value.assume_init_value()? };
+
+    assert!(<&str>::try_from(&value).is_err());
+    let mut output = String::new();
+    let err = value.try_format(&mut output).unwrap_err();
+    assert!(err.to_string().contains("invalid utf-8"));
+
+    let options = deflect::FormatOptions::new().lossy_strings(true);
+    assert_eq!(value.display_with(options).to_string(), "\"ab\u{fffd}c\"");
+
+    let deflect::Value::str(value) = value else {
+        panic!("expected a `&str`");
+    };
+    assert_eq!(value.as_bytes(), bytes);
+    assert_eq!(value.to_string_lossy(), "ab\u{fffd}c");
+    Ok(())
+}
+
+#[test]
+fn byte_formats() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{ByteFormat, FormatOptions};
+
+    let context = deflect::default_provider()?;
+    let data: &[u8] = b"\x01\x02hi\"";
+    let erased: &dyn deflect::Reflect = &data;
+    let value = erased.reflect(&context)?;
+    let slice: &deflect::value::Slice = (&value).try_into()?;
+    assert_eq!(slice.as_bytes()?, data);
+
+    let display = |options: FormatOptions| value.display_with(options).to_string();
+    assert_eq!(display(FormatOptions::new()), "&[1, 2, 104, 105, 34]");
+    let escaped = FormatOptions::new().bytes(ByteFormat::Escaped);
+    assert_eq!(display(escaped), "&b\"\\x01\\x02hi\\\"\"");
+    let hex = FormatOptions::new().bytes(ByteFormat::Hex);
+    assert_eq!(display(hex.max_elements(2)), "&hex:0102… 3 more");
+    let base64 = FormatOptions::new().bytes(ByteFormat::Base64);
+    assert_eq!(display(base64), "&base64:AQJoaSI=");
+
+    let data: &[u16] = &[1, 2];
+    let erased: &dyn deflect::Reflect = &data;
+    let slice: deflect::value::Slice = erased.reflect(&context)?.try_into()?;
+    assert!(slice.as_bytes().is_err());
+    let base64 = FormatOptions::new().bytes(ByteFormat::Base64);
+    assert_eq!(deflect::Value::from(slice).display_with(base64).to_string(), "&[1, 2]");
+    Ok(())
+}
+
+#[test]
+fn std_adapters() -> Result<(), Box<dyn std::error::Error>> {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::time::{Duration, SystemTime};
+
+    fn display<T>(value: &T, options: deflect::FormatOptions) -> String {
+        let context = deflect::default_provider().unwrap();
+        let erased: &dyn deflect::Reflect = value;
+        let value = erased.reflect(&context).unwrap();
+        value.display_with(options).to_string()
+    }
+    let options = deflect::FormatOptions::new();
+
+    assert_eq!(display(&Duration::from_millis(1500), options.clone()), "1.5s");
+    let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 500_000_000);
+    assert_eq!(display(&time, options.clone()), "2023-11-14T22:13:20.5Z");
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+    assert_eq!(display(&ip, options.clone()), "127.0.0.1");
+    assert_eq!(display(&Ipv6Addr::LOCALHOST, options.clone()), "::1");
+    let socket: SocketAddr = "[::1]:8080".parse()?;
+    assert_eq!(display(&socket, options.clone()), "[::1]:8080");
+    let socket: SocketAddr = "10.0.0.2:443".parse()?;
+    assert_eq!(display(&Some(socket), options.clone()), "Some(10.0.0.2:443)");
+
+    let raw = display(&Duration::from_millis(1500), options.std_adapters(false));
+    assert!(raw.starts_with("Duration { secs: 1, nanos: "), "{raw}");
+    Ok(())
+}
+
+#[test]
+fn os_strings() -> Result<(), Box<dyn std::error::Error>> {
+    use std::ffi::{CString, OsString};
+    use std::path::PathBuf;
+
+    let context = deflect::default_provider()?;
+
+    let path = PathBuf::from("/etc/hosts");
+    let erased: &dyn deflect::Reflect = &path;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "\"/etc/hosts\"");
+    assert_eq!(value.string_bytes()?, Some(&b"/etc/hosts"[..]));
+
+    let os_string = OsString::from("tab\there");
+    let erased: &dyn deflect::Reflect = &os_string;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "\"tab\\there\"");
+
+    let c_string = CString::new("hi")?;
+    let erased: &dyn deflect::Reflect = &c_string;
+    let value = erased.reflect(&context)?;
+    assert_eq!(value.to_string(), "\"hi\"");
+    assert_eq!(value.string_bytes()?, Some(&b"hi"[..]));
+
+    let erased: &dyn deflect::Reflect = &7u8;
+    assert_eq!(erased.reflect(&context)?.string_bytes()?, None);
+    Ok(())
+}
+
+#[test]
+fn boxed_errors() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct ConfigError {
+        path: &'static str,
+        source: Option<Box<dyn std::error::Error>>,
+    }
+
+    impl std::fmt::Display for ConfigError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "could not read {}", self.path)
+        }
+    }
+
+    impl std::error::Error for ConfigError {}
+
+    #[derive(Debug)]
+    struct ParseError(u32);
+
+    impl std::fmt::Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "syntax error on line {}", self.0)
+        }
+    }
+
+    impl std::error::Error for ParseError {}
+
+    let error: Box<dyn std::error::Error> = Box::new(ConfigError {
+        path: "/etc/app.toml",
+        source: Some(Box::new(ParseError(3))),
+    });
+    let erased: &dyn deflect::Reflect = &error;
+    let context = deflect::default_provider()?;
+    let value: deflect::value::BoxedDyn = erased.reflect(&context)?.try_into()?;
+
+    let chain = value.error_chain()?;
+    assert_eq!(chain.len(), 2);
+    assert_eq!(chain[1].to_string(), "ParseError(3)");
+
+    let display = deflect::Value::from(value).to_string();
+    assert!(display.starts_with("box dyn Error(reflect::"), "{display}");
+    assert!(display.contains("ConfigError { path: \"/etc/app.toml\""), "{display}");
+    assert!(display.contains("Some(box dyn Error(reflect::"), "{display}");
+    Ok(())
+}
+
+#[test]
+fn container_adapters() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{Adapted, ContainerAdapter, Elements};
+
+    /// A vector whose elements are inline until it has more than two.
+    #[allow(dead_code)]
+    struct Small {
+        inline: [u16; 2],
+        heap: *const u16,
+        len: usize,
+    }
+
+    /// A handle to an object in a pool.
+    #[allow(dead_code)]
+    struct Handle {
+        slot: std::ptr::NonNull<u64>,
+    }
+
+    struct Adapter;
+
+    unsafe impl ContainerAdapter for Adapter {
+        fn matches(&self, type_name: &str) -> bool {
+            type_name.ends_with("::Small") || type_name.ends_with("::Handle")
+        }
+
+        fn elements(&self, type_name: &str, read: &dyn Fn(&str) -> Option<u128>) -> Option<Elements> {
+            if type_name.ends_with("::Handle") {
+                return Some(Elements::Deref { pointer: "slot".to_owned() });
+            }
+            let len = read("len")?.try_into().ok()?;
+            Some(match len {
+                0..=2 => Elements::Inline { array: "inline".to_owned(), len },
+                _ => Elements::Pointer { data: "heap".to_owned(), len },
+            })
+        }
+    }
+
+    let context = deflect::default_provider()?.with_container_adapter(&Adapter);
+
+    let inline = Small { inline: [7, 8], heap: std::ptr::null(), len: 1 };
+    let erased: &dyn deflect::Reflect = &inline;
+    assert_eq!(erased.reflect(&context)?.to_string(), "[7]");
+
+    let spilled = [1u16, 2, 3];
+    let heap = Small { inline: [0, 0], heap: spilled.as_ptr(), len: 3 };
+    let erased: &dyn deflect::Reflect = &heap;
+    let value: deflect::value::Struct<_> = erased.reflect(&context)?.try_into()?;
+    let Some(Adapted::Elements(elements)) = value.adapted()? else {
+        panic!("expected elements");
+    };
+    assert_eq!(elements.len(), 3);
+    assert_eq!(deflect::Value::from(value).to_string(), "[1, 2, 3]");
+
+    let mut object = 42u64;
+    let handle = Handle { slot: std::ptr::NonNull::from(&mut object) };
+    let erased: &dyn deflect::Reflect = &handle;
+    assert_eq!(erased.reflect(&context)?.to_string(), "42");
+
+    // without the adapter, containers are displayed by their fields.
+    let erased: &dyn deflect::Reflect = &inline;
+    let plain = deflect::default_provider()?;
+    assert!(erased.reflect(&plain)?.to_string().starts_with("Small { inline: [7, 8]"));
+    Ok(())
+}
+
+#[test]
+fn format_overrides() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Uuid([u8; 4]);
+
+    #[allow(dead_code)]
+    struct Session {
+        id: Uuid,
+        hits: u8,
+    }
+
+    let context = unsafe {
+        deflect::default_provider()?.with_format_override(std::any::type_name::<Uuid>(), |bytes, f| {
+            bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+        })
+    };
+
+    let before = Session { id: Uuid([0xde, 0xad, 0xbe, 0xef]), hits: 1 };
+    let after = Session { id: Uuid([0xca, 0xfe, 0xba, 0xbe]), hits: 1 };
+    let before: &dyn deflect::Reflect = &before;
+    let after: &dyn deflect::Reflect = &after;
+    let before = before.reflect(&context)?;
+    let after = after.reflect(&context)?;
+    assert_eq!(before.to_string(), "Session { id: deadbeef, hits: 1 }");
+
+    // overridden values are leaves of diffs, and opaque in snapshots.
+    let diff = deflect::diff(&before, &after)?;
+    assert_eq!(diff.to_string(), "--- a\n+++ b\n@@ id @@\n-deadbeef\n+cafebabe\n");
+
+    #[cfg(feature = "json")]
+    assert_eq!(before.to_json()?, deflect::serde_json::json!({ "id": "deadbeef", "hits": 1 }));
+
+    // without the override, values are displayed by their fields.
+    let plain = deflect::default_provider()?;
+    let erased: &dyn deflect::Reflect = &Uuid([0; 4]);
+    assert_eq!(erased.reflect(&plain)?.to_string(), "Uuid([0, 0, 0, 0])");
+    Ok(())
+}
+
+#[test]
+fn redaction() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::Redaction;
+
+    #[allow(dead_code)]
+    struct Token(u64);
+
+    #[allow(dead_code)]
+    struct Config {
+        user: &'static str,
+        password: &'static str,
+        api_token: u32,
+        session: Token,
+    }
+
+    let redaction = Redaction::new()
+        .fields(["password", "*_token"])
+        .type_name(std::any::type_name::<Token>());
+    let context = deflect::default_provider()?.with_redaction(redaction);
+
+    let before = Config { user: "root", password: "hunter2", api_token: 1, session: Token(7) };
+    let after = Config { user: "root", password: "hunter3", api_token: 1, session: Token(7) };
+    let before: &dyn deflect::Reflect = &before;
+    let after: &dyn deflect::Reflect = &after;
+    let before = before.reflect(&context)?;
+    let after = after.reflect(&context)?;
+    assert_eq!(
+        before.to_string(),
+        r#"Config { user: "root", password: <redacted>, api_token: <redacted>, session: <redacted> }"#
+    );
+
+    // redacted values are still diffed, but not displayed.
+    let diff = deflect::diff(&before, &after)?;
+    assert_eq!(diff.to_string(), "--- a\n+++ b\n@@ password @@\n-<redacted>\n+<redacted>\n");
+    assert!(!before.to_owned_snapshot()?.to_string().contains("hunter2"));
+
+    #[cfg(feature = "json")]
+    assert_eq!(
+        before.to_json()?,
+        deflect::serde_json::json!({
+            "user": "root",
+            "password": "<redacted>",
+            "api_token": "<redacted>",
+            "session": "<redacted>",
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn static_providers() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::Redaction;
+
+    #[allow(dead_code)]
+    struct Login {
+        user: &'static str,
+        password: &'static str,
+    }
+
+    fn reflect<'value, P: deflect::DebugInfoProvider>(
+        value: &'value Login,
+        context: &'static P,
+    ) -> Result<deflect::Value<'value, 'static, P>, deflect::anyhow::Error> {
+        let erased: &dyn deflect::Reflect = value;
+        erased.reflect(context)
+    }
+
+    let login = Login { user: "root", password: "hunter2" };
+    let plain = deflect::default_provider()?;
+    assert!(std::ptr::eq(plain.clone().into_static(), plain.clone().into_static()));
+    assert!(std::ptr::eq(plain.clone().into_static(), deflect::default_provider()?.into_static()));
+    assert_eq!(
+        reflect(&login, plain.clone().into_static())?.to_string(),
+        r#"Login { user: "root", password: "hunter2" }"#
+    );
+
+    // configured providers are leaked once, rather than once per call.
+    let redacted = plain.clone().with_redaction(Redaction::new().field("password"));
+    let context = redacted.clone().into_static();
+    assert!(std::ptr::eq(context, redacted.clone().into_static()));
+    assert!(!std::ptr::eq(context, plain.into_static()));
+    let validated = redacted.with_validation();
+    assert!(!std::ptr::eq(context, validated.clone().into_static()));
+    assert!(std::ptr::eq(validated.clone().into_static(), validated.into_static()));
+
+    // as are providers with format overrides, whose clones share them.
+    let overridden = unsafe {
+        deflect::default_provider()?
+            .with_format_override("reflect::Login", |_, f| f.write_str("<login>"))
+    };
+    assert!(std::ptr::eq(overridden.clone().into_static(), overridden.into_static()));
+    assert_eq!(
+        reflect(&login, context)?.to_string(),
+        r#"Login { user: "root", password: <redacted> }"#
+    );
+    Ok(())
+}
+
+#[test]
+fn address_formats() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::{AddressFormat, FormatOptions};
+
+    #[allow(dead_code)]
+    struct Pointers {
+        a: *const i32,
+        b: *const i32,
+        c: *const i32,
+        null: *const i32,
+    }
+
+    let context = deflect::default_provider()?;
+    let (x, y) = (1, 2);
+    let before = Pointers { a: &x, b: &y, c: &x, null: std::ptr::null() };
+    let after = Pointers { a: &x, b: &x, c: &x, null: std::ptr::null() };
+    let before: &dyn deflect::Reflect = &before;
+    let after: &dyn deflect::Reflect = &after;
+    let before = before.reflect(&context)?;
+    let after = after.reflect(&context)?;
+
+    let hidden = FormatOptions::new().addresses(AddressFormat::Hidden);
+    assert_eq!(
+        before.display_with(hidden).to_string(),
+        "Pointers { a: <ptr> as *const i32, b: <ptr> as *const i32, \
+         c: <ptr> as *const i32, null: 0x0 as *const i32 }"
+    );
+
+    let stable = FormatOptions::new().addresses(AddressFormat::Stable);
+    assert_eq!(
+        before.display_with(stable.clone()).to_string(),
+        "Pointers { a: <ptr#1> as *const i32, b: <ptr#2> as *const i32, \
+         c: <ptr#1> as *const i32, null: 0x0 as *const i32 }"
+    );
+
+    // ids are shared by all of the values rendered within a scope; only the
+    // changed pointers are rendered.
+    let diff = stable.scope(|| deflect::diff(&before, &after))?;
+    assert_eq!(
+        diff.to_string(),
+        "--- a\n+++ b\n@@ b @@\n-<ptr#1> as *const i32\n+<ptr#2> as *const i32\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn decl_locations() -> Result<(), Box<dyn std::error::Error>> {
+    use gimli::write::AttributeValue;
+
+    // a `Located { near: u8, far: u8, unknown: u8 }`, declared within the
+    // directory it was compiled in; its fields, outside of it, or nowhere.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let files = fixture.files(unit, "/src", &["/src/located.rs", "/vendor/far.rs"]);
+    let u8 = fixture.base_type(unit, "u8", gimli::DW_ATE_unsigned, 1);
+    let located = fixture.structure(unit, None, "Located", 3);
+    let near = fixture.member(unit, located, "near", u8, 0);
+    let far = fixture.member(unit, located, "far", u8, 1);
+    fixture.member(unit, located, "unknown", u8, 2);
+    let declarations = [
+        (located, files[0], 3),
+        (near, files[0], 4),
+        (far, files[1], 7),
+    ];
+    for (entry, file, line) in declarations {
+        let file = AttributeValue::FileIndex(Some(file));
+        let entry = fixture.dwarf.units.get_mut(unit).get_mut(entry);
+        entry.set(gimli::DW_AT_decl_file, file);
+        entry.set(gimli::DW_AT_decl_line, AttributeValue::Udata(line));
+    }
+    let provider = fixture.provider((unit, located));
+    let r#type = deflect::reflect_type::<Aligned<3>, _>(&provider)?;
+    let location = r#type.decl_location()?.expect("declared");
+    assert_eq!(location.file(), "located.rs");
+    assert_eq!(location.line(), Some(3));
+    assert_eq!(location.to_string(), "located.rs:3");
+
+    let deflect::schema::Type::Struct(schema) = r#type else { panic!("expected a struct") };
+    let mut fields = schema.fields()?;
+    let mut fields = fields.iter()?;
+    let mut locations = vec![];
+    while let Some(field) = fields.try_next()? {
+        let location = field.decl_location()?;
+        locations.push(location.map(|location| (location.file().to_owned(), location.line())));
+    }
+    assert_eq!(
+        locations,
+        [
+            Some(("located.rs".to_owned(), Some(4))),
+            Some(("/vendor/far.rs".to_owned(), Some(7))),
+            None,
+        ]
+    );
+
+    // a type of a unit without a line program has no declaration location.
+    let mut fixture = Fixture::new();
+    let unit = fixture.unit(8, None);
+    let located = fixture.structure(unit, None, "Unlocated", 0);
+    let provider = fixture.provider((unit, located));
+    let r#type = deflect::reflect_type::<Aligned<0>, _>(&provider)?;
+    assert_eq!(r#type.decl_location()?, None);
+
+    // nor does a primitive.
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<u8, _>(&context)?;
+    assert_eq!(r#type.decl_location()?, None);
+    Ok(())
+}
+
+#[test]
+fn provenance() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    struct Local(u8);
+
+    let context = deflect::default_provider()?;
+    let local = deflect::reflect_type::<Local, _>(&context)?;
+    assert_eq!(local.crate_name()?.as_deref(), Some("reflect"));
+    let unit = local.compilation_unit()?;
+    assert!(unit.producer().map_or(false, |producer| producer.contains("rustc")));
+
+    let vec = deflect::reflect_type::<Vec<Local>, _>(&context)?;
+    assert_eq!(vec.crate_name()?.as_deref(), Some("alloc"));
+
+    for r#type in [
+        deflect::reflect_type::<u64, _>(&context)?,
+        deflect::reflect_type::<&[u8], _>(&context)?,
+        deflect::reflect_type::<(u8, u16), _>(&context)?,
+    ] {
+        assert_eq!(r#type.crate_name()?, None, "{}", r#type);
+    }
+    Ok(())
+}
+
+#[test]
+fn field_at_offset() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Inner {
+        flag: u8,
+        count: u32,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Outer {
+        id: u16,
+        inners: [Inner; 2],
+    }
+
+    let context = deflect::default_provider()?;
+    let r#type = deflect::reflect_type::<Outer, _>(&context)?;
+    let at = |offset| -> Result<_, deflect::anyhow::Error> {
+        let at = r#type.field_at_offset(offset)?.expect("in bounds");
+        Ok((at.path().to_string(), at.offset(), at.is_padding()))
+    };
+
+    assert_eq!(at(1)?, ("id".to_owned(), 1, false));
+    assert_eq!(at(2)?, ("".to_owned(), 2, true));
+    assert_eq!(at(4)?, ("inners[0].flag".to_owned(), 0, false));
+    assert_eq!(at(6)?, ("inners[0]".to_owned(), 2, true));
+    assert_eq!(at(18)?, ("inners[1].count".to_owned(), 2, false));
+    assert!(r#type.field_at_offset(20)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn find_references() -> Result<(), Box<dyn std::error::Error>> {
+    use std::rc::Rc;
+
+    #[allow(dead_code)]
+    struct Owner {
+        name: String,
+        shared: Rc<u64>,
+        boxed: Box<[u16; 4]>,
+        raw: *const u16,
+    }
+
+    let context = deflect::default_provider()?;
+    let boxed = Box::new([1, 2, 3, 4]);
+    let raw = &boxed[2] as *const u16;
+    let owner = Owner { name: "leak".to_owned(), shared: Rc::new(7), boxed, raw };
+    let erased: &dyn deflect::Reflect = &owner;
+    let value = erased.reflect(&context)?;
+
+    let find = |addr: usize| -> Result<Vec<(String, usize)>, deflect::anyhow::Error> {
+        let found = deflect::find_references(&value, addr)?;
+        Ok(found.iter().map(|found| (found.path().to_string(), found.offset())).collect())
+    };
+
+    let name = owner.name.as_ptr() as usize;
+    assert_eq!(find(name + 1)?, [("name.vec".to_owned(), 1)]);
+    let shared = &*owner.shared as *const u64 as usize;
+    assert_eq!(find(shared)?.len(), 1);
+    assert_eq!(find(raw as usize)?, [("boxed".to_owned(), 4), ("raw".to_owned(), 0)]);
+    assert!(find(0x10)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn pretty_printers() -> Result<(), Box<dyn std::error::Error>> {
+    use deflect::Debugger;
+
+    #[allow(dead_code)]
+    enum Slot {
+        Empty,
+        Full(&'static u8),
+    }
+
+    #[allow(dead_code)]
+    struct Table {
+        slots: [Slot; 2],
+        len: u8,
+    }
+
+    let context = deflect::default_provider()?;
+    let types = [deflect::reflect_type::<Table, _>(&context)?];
+    let table = std::any::type_name::<Table>();
+    let slot = std::any::type_name::<Slot>();
+
+    let gdb = deflect::pretty_printers(&types, Debugger::Gdb)?;
+    assert!(gdb.contains(&format!("\"{table}\": {{\n        \"kind\": \"struct\",")));
+    // the layouts of types reachable through elements are included.
+    assert!(gdb.contains(&format!("\"{slot}\": {{\n        \"kind\": \"enum\",")));
+    // `Empty` is niche-encoded as a zero, and `Full` is the dataful variant.
+    assert!(gdb.contains("(\"Empty\", 0, []),"));
+    assert!(gdb.contains("(\"Full\", None, [(\"__0\", 0, "));
+    assert!(gdb.contains("gdb.pretty_printers.append(_lookup)"));
+
+    let lldb = deflect::pretty_printers(&types, Debugger::Lldb)?;
+    assert!(lldb.contains("def __lldb_init_module(debugger, internal_dict):"));
+    Ok(())
+}